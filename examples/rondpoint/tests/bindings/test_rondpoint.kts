@@ -170,6 +170,9 @@ assert(op.sinonI32Hex() == 0x7fffffff)
 assert(op.sinonU32Hex() == 0xffffffff.toUInt())
 assert(op.sinonI64Hex() == 0x7fffffffffffffffL)
 assert(op.sinonU64Hex() == 0xffffffffffffffffuL)
+// The largest unsigned values from Rust keep their magnitude, rather than turning negative.
+assert(op.sinonU32Hex().toLong() == 4294967295L)
+assert(op.sinonU64Hex().toString() == "18446744073709551615")
 
 // octal integers
 assert(op.sinonU32Oct() == 493u) // 0o755
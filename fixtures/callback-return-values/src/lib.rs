@@ -0,0 +1,67 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    latitude: f64,
+    longitude: f64,
+    name: Option<String>,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LookupError {
+    #[error("Unknown place: {place}")]
+    NotFound { place: String },
+}
+
+trait Geocoder {
+    fn locate(&self, place: String) -> Location;
+    fn try_locate(&self, place: String) -> Result<Location, LookupError>;
+    fn forget(&self, place: String);
+}
+
+#[derive(Debug, Default)]
+pub struct Navigator;
+
+impl Navigator {
+    fn new() -> Self {
+        Navigator
+    }
+
+    fn locate(&self, geocoder: Box<dyn Geocoder>, place: String) -> Location {
+        geocoder.locate(place)
+    }
+
+    // Formats the record on the Rust side, to check that Rust lifted every field correctly.
+    fn describe(&self, geocoder: Box<dyn Geocoder>, place: String) -> String {
+        let location = geocoder.locate(place.clone());
+        format!(
+            "{} is at {:.4}, {:.4}",
+            location.name.unwrap_or(place),
+            location.latitude,
+            location.longitude
+        )
+    }
+
+    fn try_locate(
+        &self,
+        geocoder: Box<dyn Geocoder>,
+        place: String,
+    ) -> Result<Location, LookupError> {
+        geocoder.try_locate(place)
+    }
+
+    fn forget_all(&self, geocoder: Box<dyn Geocoder>, places: Vec<String>) -> u32 {
+        let count = places.len() as u32;
+        for place in places {
+            geocoder.forget(place);
+        }
+        count
+    }
+}
+
+include!(concat!(
+    env!("OUT_DIR"),
+    "/callback_return_values.uniffi.rs"
+));
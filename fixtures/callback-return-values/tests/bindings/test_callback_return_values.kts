@@ -0,0 +1,51 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+import uniffi.callback_return_values.*
+
+class KotlinGeocoder : Geocoder {
+    val forgotten = mutableListOf<String>()
+
+    override fun locate(place: String): Location = when (place) {
+        "Paris" -> Location(48.8566, 2.3522, "Paris")
+        else -> Location(-33.8688, 151.2093, null)
+    }
+
+    override fun tryLocate(place: String): Location {
+        if (place == "Atlantis") {
+            throw LookupException.NotFound(place)
+        }
+        return locate(place)
+    }
+
+    override fun forget(place: String) {
+        forgotten.add(place)
+    }
+}
+
+val geocoder = KotlinGeocoder()
+val navigator = Navigator()
+
+// A record returned from Kotlin is lifted by Rust, and then sent back to Kotlin unchanged.
+assert(navigator.locate(geocoder, "Paris") == Location(48.8566, 2.3522, "Paris"))
+assert(navigator.locate(geocoder, "Sydney") == Location(-33.8688, 151.2093, null))
+
+// Rust can see all the fields of the returned record.
+assert(navigator.describe(geocoder, "Paris") == "Paris is at 48.8566, 2.3522")
+assert(navigator.describe(geocoder, "Sydney") == "Sydney is at -33.8688, 151.2093")
+
+// Methods that can throw either return the record, or the error.
+assert(navigator.tryLocate(geocoder, "Paris") == Location(48.8566, 2.3522, "Paris"))
+try {
+    navigator.tryLocate(geocoder, "Atlantis")
+    throw RuntimeException("Should have thrown a LookupException")
+} catch (e: LookupException.NotFound) {
+    assert(e.place == "Atlantis")
+}
+
+// Methods that don't return anything send back an empty buffer.
+assert(navigator.forgetAll(geocoder, listOf("Paris", "Sydney")) == 2u)
+assert(geocoder.forgotten == listOf("Paris", "Sydney"))
+
+navigator.destroy()
@@ -0,0 +1,4 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/callback_return_values.udl",
+    ["tests/bindings/test_callback_return_values.kts",]
+);
@@ -2,6 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 
@@ -125,6 +126,45 @@ fn get_num_alive() -> u64 {
 type Result<T, E = CoverallError> = std::result::Result<T, E>;
 type ComplexResult<T, E = ComplexError> = std::result::Result<T, E>;
 
+pub struct CachedRecord {
+    key: String,
+    cached: String,
+}
+
+fn identity_cached_record(value: CachedRecord) -> CachedRecord {
+    value
+}
+
+fn get_max_u8() -> u8 {
+    u8::MAX
+}
+
+fn get_min_i8() -> i8 {
+    i8::MIN
+}
+
+fn count_by_length(words: Vec<String>) -> HashMap<u32, u64> {
+    let mut counts = HashMap::new();
+    for word in words {
+        *counts.entry(word.len() as u32).or_insert(0) += 1;
+    }
+    counts
+}
+
+pub struct EmptyRecord;
+
+fn identity_empty_record(value: EmptyRecord) -> EmptyRecord {
+    value
+}
+
+fn do_thing(should_fail: bool) -> Result<()> {
+    if should_fail {
+        Err(CoverallError::TooManyHoles)
+    } else {
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 pub struct Coveralls {
     name: String,
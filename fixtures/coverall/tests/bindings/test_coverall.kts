@@ -103,6 +103,35 @@ Coveralls("test_return_objects").use { coveralls ->
 // Destroying `coveralls` will kill both.
 assert(getNumAlive() == 0UL);
 
+// A field marked `[ExcludeFromEquality]` doesn't affect equality or the hash code, but is
+// still lowered and lifted along with the rest of the record.
+val cached = CachedRecord("key", "cached")
+val recached = CachedRecord("key", "recached")
+assert(cached == recached)
+assert(cached.hashCode() == recached.hashCode())
+assert(cached != CachedRecord("other", "cached"))
+val returned = identityCachedRecord(recached)
+assert(returned == cached)
+assert(returned.cached == "recached")
+
+assert(countByLength(listOf("a", "bb", "cc")) == mapOf(1u to 1UL, 2u to 2UL))
+
+// Single bytes are lifted straight from the `Byte` that the FFI returns, keeping their sign.
+assert(getMaxU8() == 255.toUByte())
+assert(getMinI8() == (-128).toByte())
+
+// A record with no fields is an `object`, and there's nothing to pass across the FFI.
+assert(identityEmptyRecord(EmptyRecord) == EmptyRecord)
+
+// A function returning `Result<(), CoverallError>` returns `Unit` or throws.
+assert(doThing(false) == Unit)
+try {
+    doThing(true)
+    throw RuntimeException("Expected function to throw exception")
+} catch(e: CoverallException.TooManyHoles) {
+    // Expected result
+}
+
 Coveralls("test_simple_errors").use { coveralls ->
     try {
         coveralls.maybeThrow(true)
@@ -59,6 +59,10 @@ class TestCoverall(unittest.TestCase):
         self.assertIsNone(d.maybe_float64)
         self.assertIsNone(d.coveralls)
 
+    def test_bytes(self):
+        self.assertEqual(get_max_u8(), 255)
+        self.assertEqual(get_min_i8(), -128)
+
     def test_constructors(self):
         self.assertEqual(get_num_alive(), 0)
         # must work.
@@ -180,5 +184,8 @@ class TestCoverall(unittest.TestCase):
         with self.assertRaisesRegex(TypeError, "Coveralls.*Patch"):
             coveralls.take_other(patch)
 
+    def test_maps_with_non_string_keys(self):
+        self.assertEqual(count_by_length(["a", "bb", "cc"]), {1: 1, 2: 2})
+
 if __name__=='__main__':
     unittest.main()
@@ -0,0 +1,17 @@
+import uniffi.regression_test_enum_repr.*;
+
+// The payload is serialized after the single-byte opcode, so it will only survive
+// the round-trip if both sides agree on how wide the opcode is.
+for (opcode in Opcode.values()) {
+    assert(roundTrip(Message(opcode, 0xdeadbeefu)) == Message(opcode, 0xdeadbeefu))
+    assert(makeMessage(opcode, 42u) == Message(opcode, 42u))
+}
+
+// The last registers have discriminants with the top bit of their byte set, so they're only read
+// back as the right register if the byte is treated as unsigned.
+for (register in Register.values()) {
+    assert(roundTripInstruction(Instruction(register, 0xdeadbeefu)) == Instruction(register, 0xdeadbeefu))
+}
+val instruction = roundTripInstruction(Instruction(Register.R130, 0xcafeu))
+assert(instruction.register == Register.R130)
+assert(instruction.operand == 0xcafeu)
@@ -0,0 +1,15 @@
+from regression_test_enum_repr import *
+
+# The payload is serialized after the single-byte opcode, so it will only survive
+# the round-trip if both sides agree on how wide the opcode is.
+for opcode in Opcode:
+    assert round_trip(Message(opcode, 0xdeadbeef)) == Message(opcode, 0xdeadbeef)
+    assert make_message(opcode, 42) == Message(opcode, 42)
+
+# The last registers have discriminants with the top bit of their byte set, so they're only read
+# back as the right register if the byte is treated as unsigned.
+for register in Register:
+    assert round_trip_instruction(Instruction(register, 0xdeadbeef)) == Instruction(register, 0xdeadbeef)
+instruction = round_trip_instruction(Instruction(Register.R130, 0xcafe))
+assert instruction.register == Register.R130
+assert instruction.operand == 0xcafe
@@ -0,0 +1,17 @@
+import regression_test_enum_repr
+
+// The payload is serialized after the single-byte opcode, so it will only survive
+// the round-trip if both sides agree on how wide the opcode is.
+for opcode in [Opcode.ping, .pong, .close] {
+    assert(roundTrip(message: Message(opcode: opcode, payload: 0xdeadbeef)) == Message(opcode: opcode, payload: 0xdeadbeef))
+    assert(makeMessage(opcode: opcode, payload: 42) == Message(opcode: opcode, payload: 42))
+}
+
+// The last registers have discriminants with the top bit of their byte set, so they're only read
+// back as the right register if the byte is treated as unsigned.
+for register in [Register.r1, .r127, .r128, .r129, .r130] {
+    assert(roundTripInstruction(instruction: Instruction(register: register, operand: 0xdeadbeef)) == Instruction(register: register, operand: 0xdeadbeef))
+}
+let instruction = roundTripInstruction(instruction: Instruction(register: .r130, operand: 0xcafe))
+assert(instruction.register == .r130)
+assert(instruction.operand == 0xcafe)
@@ -0,0 +1,8 @@
+uniffi_macros::build_foreign_language_testcases!(
+    "src/test.udl",
+    [
+        "tests/bindings/test.py",
+        "tests/bindings/test.kts",
+        "tests/bindings/test.swift",
+    ]
+);
@@ -0,0 +1,27 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+pub type MaybeInt = Option<i32>;
+
+impl UniffiCustomTypeWrapper for MaybeInt {
+    type Wrapped = Option<i32>;
+
+    fn wrap(val: Self::Wrapped) -> uniffi::Result<Self> {
+        Ok(val)
+    }
+
+    fn unwrap(obj: Self) -> Self::Wrapped {
+        obj
+    }
+}
+
+pub fn round_trip(value: Option<MaybeInt>) -> Option<MaybeInt> {
+    value
+}
+
+pub fn some_none() -> Option<MaybeInt> {
+    Some(None)
+}
+
+include!(concat!(env!("OUT_DIR"), "/test.uniffi.rs"));
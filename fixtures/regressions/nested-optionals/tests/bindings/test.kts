@@ -0,0 +1,9 @@
+import uniffi.regression_test_nested_optionals.*;
+
+// Each of the three states of an `Option<Option<i32>>` must survive a round-trip.
+assert(roundTrip(null) == null)
+assert(roundTrip(Some(null)) == Some(null))
+assert(roundTrip(Some(42)) == Some(42))
+
+// Including when it comes from the Rust side.
+assert(someNone() == Some(null))
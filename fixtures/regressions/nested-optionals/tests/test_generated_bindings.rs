@@ -0,0 +1 @@
+uniffi_macros::build_foreign_language_testcases!("src/test.udl", ["tests/bindings/test.kts",]);
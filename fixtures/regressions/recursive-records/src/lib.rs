@@ -0,0 +1,41 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+pub struct Tree {
+    value: u32,
+    left: Option<Box<Tree>>,
+    right: Option<Box<Tree>>,
+}
+
+// Make a complete binary tree with the given number of levels, numbering the nodes
+// breadth-first from 1 at the root.
+fn make_subtree(value: u32, depth: u32) -> Option<Box<Tree>> {
+    if depth == 0 {
+        return None;
+    }
+    Some(Box::new(Tree {
+        value,
+        left: make_subtree(value * 2, depth - 1),
+        right: make_subtree(value * 2 + 1, depth - 1),
+    }))
+}
+
+pub fn make_tree(depth: u32) -> Tree {
+    *make_subtree(1, depth.max(1)).unwrap()
+}
+
+pub fn round_trip(tree: Tree) -> Tree {
+    tree
+}
+
+pub fn depth(tree: Tree) -> u32 {
+    fn subtree_depth(tree: &Option<Box<Tree>>) -> u32 {
+        tree.as_ref().map_or(0, |t| {
+            1 + subtree_depth(&t.left).max(subtree_depth(&t.right))
+        })
+    }
+    1 + subtree_depth(&tree.left).max(subtree_depth(&tree.right))
+}
+
+include!(concat!(env!("OUT_DIR"), "/test.uniffi.rs"));
@@ -0,0 +1,13 @@
+import uniffi.regression_test_recursive_records.*;
+
+// A three-level tree has to survive a round-trip in both directions.
+val tree = makeTree(3u)
+assert(tree.value == 1u)
+assert(tree.left!!.left!!.value == 4u)
+assert(tree.right!!.right!!.value == 7u)
+assert(tree.left!!.left!!.left == null)
+assert(roundTrip(tree) == tree)
+
+val lopsided = Tree(1u, Tree(2u, Tree(3u, null, null), null), null)
+assert(depth(lopsided) == 3u)
+assert(roundTrip(lopsided) == lopsided)
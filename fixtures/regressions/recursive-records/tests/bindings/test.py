@@ -0,0 +1,13 @@
+from regression_test_recursive_records import *
+
+# A three-level tree has to survive a round-trip in both directions.
+tree = make_tree(3)
+assert tree.value == 1
+assert tree.left.left.value == 4
+assert tree.right.right.value == 7
+assert tree.left.left.left is None
+assert round_trip(tree) == tree
+
+lopsided = Tree(1, Tree(2, Tree(3, None, None), None), None)
+assert depth(lopsided) == 3
+assert round_trip(lopsided) == lopsided
@@ -26,6 +26,10 @@ fn now() -> SystemTime {
     SystemTime::now()
 }
 
+fn before_epoch(seconds: u64, nanoseconds: u32) -> SystemTime {
+    SystemTime::UNIX_EPOCH - Duration::new(seconds, nanoseconds)
+}
+
 fn equal(a: SystemTime, b: SystemTime) -> bool {
     a == b
 }
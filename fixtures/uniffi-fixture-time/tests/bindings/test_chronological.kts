@@ -15,6 +15,18 @@ assert(diff(Instant.ofEpochSecond(101, 101), Instant.ofEpochSecond(100, 100))
 assert(add(Instant.parse("1955-11-05T00:06:00.283000001Z"), Duration.ofSeconds(1, 1))
         .equals(Instant.parse("1955-11-05T00:06:01.283000002Z")))
 
+// Test reading pre-epoch timestamps from Rust, whose nanoseconds count back from the epoch too
+assert(beforeEpoch(1UL, 999_999_999u).equals(Instant.ofEpochSecond(-2, 1)))
+assert(beforeEpoch(-Instant.MIN.epochSecond.toULong(), 0u).equals(Instant.MIN))
+
+// Test reading a pre-epoch timestamp that's out of range for an Instant
+try {
+        beforeEpoch(Long.MAX_VALUE.toULong(), 0u)
+        throw RuntimeException("Should have thrown a DateTimeException exception!")
+} catch (e: DateTimeException) {
+        // It's okay!
+}
+
 // Test exceptions are propagated
 try {
         diff(Instant.ofEpochSecond(100), Instant.ofEpochSecond(101))
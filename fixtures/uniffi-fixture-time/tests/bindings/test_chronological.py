@@ -11,6 +11,9 @@ assert diff(datetime.fromtimestamp(101.000002, timezone.utc), datetime.fromtimes
 # Test pre-epoch timestamps
 assert add(datetime.fromisoformat('1955-11-05T00:06:00.283001+00:00'), timedelta(seconds=1, microseconds=1)) == datetime.fromisoformat('1955-11-05T00:06:01.283002+00:00')
 
+# Test reading pre-epoch timestamps from Rust
+assert before_epoch(1, 500_000_000) == datetime.fromtimestamp(-1.5, timezone.utc)
+
 # Test exceptions are propagated
 try:
     diff(datetime.fromtimestamp(100, timezone.utc), datetime.fromtimestamp(101, timezone.utc))
@@ -112,9 +112,16 @@
 //! `ForeignCallback`. The caller of `ForeignCallback`, the `KeychainProxy` unpacks the returned buffer into the correct
 //! type and then returns to client code.
 //!
+//! Each live proxy is also recorded in a map from its address to its handle, so that when Rust passes a
+//! `Box<dyn Keychain>` back to the foreign language, the proxy can be recognised and lowered into its handle.
+//! The foreign language can then return the very same object that it originally passed to Rust, rather than
+//! a new wrapper around it.
+//!
 
 use super::RustBuffer;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
 
 /// ForeignCallback is the Rust representation of a foreign language function.
 /// It is the basis for all callbacks interfaces. It is registered exactly once per callback interface,
@@ -132,6 +139,16 @@ pub type ForeignCallback =
 /// and it can be deleted from the handle map.
 pub const IDX_CALLBACK_FREE: u32 = 0;
 
+/// Status codes written at the start of the `RustBuffer` returned by a callback method that can fail.
+///
+/// Methods that can fail return a buffer containing one of these codes as an `i8`, followed by the
+/// serialized return value on success, the serialized error if the foreign code threw one of the
+/// declared errors, or a string describing the problem if it threw anything else. Methods that
+/// can't fail just return the serialized return value.
+pub const CALLBACK_SUCCESS: i8 = 0;
+pub const CALLBACK_ERROR: i8 = 1;
+pub const CALLBACK_UNEXPECTED_ERROR: i8 = 2;
+
 // Overly-paranoid sanity checking to ensure that these types are
 // convertible between each-other. `transmute` actually should check this for
 // us too, but this helps document the invariants we rely on in this code.
@@ -142,6 +159,12 @@ pub const IDX_CALLBACK_FREE: u32 = 0;
 static_assertions::assert_eq_size!(usize, ForeignCallback);
 static_assertions::assert_eq_size!(usize, Option<ForeignCallback>);
 
+lazy_static::lazy_static! {
+    // The handles of the live proxies for every callback interface, keyed by the proxy's address.
+    // Addresses are unique among live allocations, so one map can hold the proxies of them all.
+    static ref LIVE_PROXIES: Mutex<HashMap<usize, u64>> = Mutex::new(HashMap::new());
+}
+
 /// Struct to hold a foreign callback.
 pub struct ForeignCallbackInternals {
     callback_ptr: AtomicUsize,
@@ -181,4 +204,34 @@ impl ForeignCallbackInternals {
         let ptr_value = self.callback_ptr.load(Ordering::SeqCst);
         unsafe { std::mem::transmute::<usize, Option<ForeignCallback>>(ptr_value) }
     }
+
+    /// Record that the proxy at `addr` calls the foreign object with the given handle.
+    pub fn register_proxy(&self, addr: usize, handle: u64) {
+        LIVE_PROXIES.lock().unwrap().insert(addr, handle);
+    }
+
+    /// Forget about the proxy at `addr`, returning its handle if it was a live proxy.
+    ///
+    /// This is called when a proxy is dropped, which should then tell the foreign language to free
+    /// its handle, and when it's passed back to the foreign language, which takes over the handle.
+    /// Either way only the first call gets the handle back, so the handle is only freed once.
+    pub fn unregister_proxy(&self, addr: usize) -> Option<u64> {
+        LIVE_PROXIES.lock().unwrap().remove(&addr)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_proxies_can_only_be_unregistered_once() {
+        static INTERNALS: ForeignCallbackInternals = ForeignCallbackInternals::new();
+        let proxy = Box::new(42u64);
+        let addr = &*proxy as *const u64 as usize;
+        assert_eq!(INTERNALS.unregister_proxy(addr), None);
+        INTERNALS.register_proxy(addr, 7);
+        assert_eq!(INTERNALS.unregister_proxy(addr), Some(7));
+        assert_eq!(INTERNALS.unregister_proxy(addr), None);
+    }
 }
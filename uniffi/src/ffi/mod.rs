@@ -7,9 +7,13 @@ pub mod foreignbytes;
 pub mod foreigncallbacks;
 pub mod rustbuffer;
 pub mod rustcalls;
+pub mod rustfuture;
+pub mod stream;
 
 use ffidefault::FfiDefault;
 pub use foreignbytes::*;
 pub use foreigncallbacks::*;
 pub use rustbuffer::*;
 pub use rustcalls::*;
+pub use rustfuture::*;
+pub use stream::*;
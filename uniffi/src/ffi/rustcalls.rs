@@ -13,6 +13,7 @@
 use super::FfiDefault;
 use crate::{FfiConverter, RustBuffer, RustBufferFfiConverter};
 use anyhow::Result;
+use std::any::Any;
 use std::mem::MaybeUninit;
 use std::panic;
 
@@ -106,14 +107,7 @@ where
             // Try to coerce the cause into a RustBuffer containing a String.  Since this code can
             // panic, we need to use a second catch_unwind().
             let message_result = panic::catch_unwind(panic::AssertUnwindSafe(move || {
-                // The documentation suggests that it will *usually* be a str or String.
-                let message = if let Some(s) = cause.downcast_ref::<&'static str>() {
-                    (*s).to_string()
-                } else if let Some(s) = cause.downcast_ref::<String>() {
-                    s.clone()
-                } else {
-                    "Unknown panic!".to_string()
-                };
+                let message = panic_message(&*cause);
                 log::error!("Caught a panic calling rust code: {:?}", message);
                 String::lower(message)
             }));
@@ -132,6 +126,18 @@ where
     }
 }
 
+// Get a description of the cause of a panic, as caught by `catch_unwind()`.
+pub(crate) fn panic_message(cause: &(dyn Any + Send)) -> String {
+    // The documentation suggests that it will *usually* be a str or String.
+    if let Some(s) = cause.downcast_ref::<&'static str>() {
+        (*s).to_string()
+    } else if let Some(s) = cause.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic!".to_string()
+    }
+}
+
 /// Wrap a rust function call and return the result directly
 ///
 /// `callback` is responsible for making the call to the Rust function.  It must convert any return
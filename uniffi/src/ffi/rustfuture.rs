@@ -0,0 +1,163 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Low-level support for calling async rust functions
+//!
+//! Functions declared as `[Async]` in the UDL are implemented as an `async fn` in Rust.
+//! The FFI can't wait for a future, so their scaffolding function returns immediately
+//! and the result is delivered later on, like this:
+//!
+//!   - The foreign language code passes a `FutureCallback` and an opaque `u64` when
+//!     calling the scaffolding function, along with the usual arguments.
+//!   - The scaffolding calls the Rust function to get a future, and hands it to [`run_future`].
+//!   - Once the future has completed, its output is serialized into a `RustBuffer` and
+//!     passed to the `FutureCallback` along with the `u64`, which the foreign language
+//!     code can use to find and resume whatever was waiting for the result.
+//!
+//! The buffer starts with one of the `CALLBACK_*` status codes used for callback interface
+//! methods that can fail, followed by the serialized return value, the serialized error, or
+//! (if the future panicked) a string describing what went wrong.
+//!
+//! There's no async runtime here, so each future is driven on its own thread. That's fine
+//! for functions that spend most of their time waiting on I/O or on other threads, but
+//! it does mean the futures must be `Send + 'static`. Cancellation isn't supported yet;
+//! once started, each future will run until it completes.
+
+use super::{panic_message, RustBuffer, CALLBACK_UNEXPECTED_ERROR};
+use crate::FfiConverter;
+use bytes::BufMut;
+use std::future::Future;
+use std::panic;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+/// FutureCallback is the Rust representation of a foreign language function that receives the
+/// result of an async function call.
+///
+/// The `callback_data` is the value that the foreign language code passed in when making the call,
+/// and the `result` buffer holds the serialized result as described above. The foreign language
+/// code is responsible for freeing the buffer.
+pub type FutureCallback = extern "C" fn(callback_data: u64, result: RustBuffer);
+
+/// Drive a future to completion in the background, and pass its result to a `FutureCallback`.
+///
+/// The `write_output` function is responsible for serializing the output of the future,
+/// including the leading status code. If either the future or `write_output` panics, the
+/// callback will receive `CALLBACK_UNEXPECTED_ERROR` along with the panic message.
+pub fn run_future<F, W>(future: F, write_output: W, callback: FutureCallback, callback_data: u64)
+where
+    F: Future + Send + 'static,
+    W: FnOnce(F::Output, &mut Vec<u8>) + Send + 'static,
+{
+    thread::spawn(move || {
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(move || {
+            crate::panichook::ensure_setup();
+            let mut buf = Vec::new();
+            write_output(block_on(future), &mut buf);
+            buf
+        }));
+        let buf = match result {
+            Ok(buf) => buf,
+            Err(cause) => {
+                let message = panic_message(&*cause);
+                log::error!("Caught a panic running rust future: {:?}", message);
+                let mut buf = Vec::new();
+                buf.put_i8(CALLBACK_UNEXPECTED_ERROR);
+                <String as FfiConverter>::write(message, &mut buf);
+                buf
+            }
+        };
+        callback(callback_data, RustBuffer::from_vec(buf));
+    });
+}
+
+// Wakes a future by unparking the thread that is blocked on it.
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+// Poll a future on the current thread, parking it while the future is pending.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker: Waker = Arc::new(ThreadWaker(thread::current())).into();
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::CALLBACK_SUCCESS;
+    use std::pin::Pin;
+    use std::sync::{mpsc, Mutex};
+
+    lazy_static::lazy_static! {
+        static ref RESULTS: Mutex<Option<mpsc::Sender<(u64, Vec<u8>)>>> = Mutex::new(None);
+    }
+
+    extern "C" fn callback(callback_data: u64, result: RustBuffer) {
+        let sender = RESULTS.lock().unwrap().clone().unwrap();
+        sender
+            .send((callback_data, result.destroy_into_vec()))
+            .unwrap();
+    }
+
+    // A future that is pending the first time it's polled, to check that we get woken up again.
+    struct YieldOnce(bool);
+
+    impl Future for YieldOnce {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            if self.0 {
+                Poll::Ready(42)
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_future() {
+        let (sender, receiver) = mpsc::channel();
+        *RESULTS.lock().unwrap() = Some(sender);
+
+        run_future(
+            YieldOnce(false),
+            |output, buf| {
+                buf.put_i8(CALLBACK_SUCCESS);
+                <u32 as FfiConverter>::write(output, buf);
+            },
+            callback,
+            1,
+        );
+        let (callback_data, result) = receiver.recv().unwrap();
+        assert_eq!(callback_data, 1);
+        assert_eq!(result, vec![0, 0, 0, 0, 42]);
+
+        run_future(
+            async { panic!("Oh no!") },
+            |_output: (), _buf| unreachable!(),
+            callback,
+            2,
+        );
+        let (callback_data, result) = receiver.recv().unwrap();
+        assert_eq!(callback_data, 2);
+        assert_eq!(result[0] as i8, CALLBACK_UNEXPECTED_ERROR);
+        let mut rest = &result[1..];
+        assert_eq!(String::try_read(&mut rest).unwrap(), "Oh no!");
+    }
+}
@@ -0,0 +1,128 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Low-level support for returning streams of values
+//!
+//! Types declared as `[Stream] typedef T Name` in the UDL are implemented as a [`Stream`]
+//! in Rust, which wraps an iterator over values of type `T`. Rather than serializing all
+//! of the values at once, the stream is handed to the foreign language code which pulls
+//! the values out of it one at a time, like this:
+//!
+//!   - The Rust function returns a `Stream`, which is boxed and lowered into an opaque `u64`
+//!     handle, in much the same way that objects are lowered into a pointer.
+//!   - The foreign language code repeatedly passes the handle to the `next` FFI function for
+//!     that stream type, which calls [`stream_next`] and gets back a serialized `Option<T>`.
+//!   - Once it receives `None`, or if it stops reading early, the foreign language code
+//!     passes the handle to the `free` FFI function, which calls [`stream_free`].
+//!
+//! Streams can only be returned from Rust, not passed into it.
+
+use super::RustBuffer;
+use crate::FfiConverter;
+use anyhow::{bail, Result};
+use bytes::BufMut;
+use std::sync::Mutex;
+
+/// A stream of values returned from Rust, which the foreign language code reads one at a time.
+///
+/// The two type parameters of `FfiConverter` are conflated here in the same way as for
+/// `Option<T>` and `Vec<T>`: the generated scaffolding names the type as `Stream<T>`, where `T`
+/// is the `FfiConverter` for the item type, but Rust code constructs it from the item type itself.
+pub struct Stream<T>(Mutex<Box<dyn Iterator<Item = T> + Send>>);
+
+impl<T> Stream<T> {
+    /// Create a stream that yields each of the values produced by the given iterator.
+    ///
+    /// The iterator is only advanced when the foreign language code asks for the next value,
+    /// on whichever thread it happens to ask from.
+    pub fn new<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: Send + 'static,
+    {
+        Stream(Mutex::new(Box::new(iter.into_iter())))
+    }
+}
+
+/// Support for returning streams via the FFI.
+///
+/// The stream is boxed and passed as an opaque handle, which the foreign language code
+/// is responsible for freeing by calling the `free` FFI function for that stream type.
+unsafe impl<T: FfiConverter> FfiConverter for Stream<T> {
+    type RustType = Stream<T::RustType>;
+    type FfiType = u64;
+
+    fn lower(obj: Self::RustType) -> Self::FfiType {
+        Box::into_raw(Box::new(obj)) as usize as u64
+    }
+
+    fn try_lift(_v: Self::FfiType) -> Result<Self::RustType> {
+        bail!("Streams can't be passed into Rust")
+    }
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        buf.put_u64(Self::lower(obj));
+    }
+
+    fn try_read(_buf: &mut &[u8]) -> Result<Self::RustType> {
+        bail!("Streams can't be passed into Rust")
+    }
+}
+
+/// Read the next value from a stream, serialized as an `Option` that is `None` once
+/// the stream has been exhausted.
+///
+/// # Safety
+///
+/// The handle must have been obtained by lowering a `Stream<T>`, and not yet passed
+/// to [`stream_free`].
+pub unsafe fn stream_next<T: FfiConverter>(handle: u64) -> RustBuffer {
+    let stream = &*(handle as usize as *const Stream<T::RustType>);
+    // If a previous call panicked while holding the lock, the iterator may be in an
+    // inconsistent state, so it's safest to treat the stream as exhausted.
+    let next = match stream.0.lock() {
+        Ok(mut iter) => iter.next(),
+        Err(_) => None,
+    };
+    <Option<T> as FfiConverter>::lower(next)
+}
+
+/// Free a stream, whether or not all of its values have been read.
+///
+/// # Safety
+///
+/// The handle must have been obtained by lowering a `Stream<T>`, and must not be used
+/// again after calling this function.
+pub unsafe fn stream_free<T: FfiConverter>(handle: u64) {
+    drop(Box::from_raw(handle as usize as *mut Stream<T::RustType>));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stream_next() {
+        let handle = <Stream<u32> as FfiConverter>::lower(Stream::new(vec![1, 2]));
+        unsafe {
+            assert_eq!(
+                stream_next::<u32>(handle).destroy_into_vec(),
+                vec![1, 0, 0, 0, 1]
+            );
+            assert_eq!(
+                stream_next::<u32>(handle).destroy_into_vec(),
+                vec![1, 0, 0, 0, 2]
+            );
+            assert_eq!(stream_next::<u32>(handle).destroy_into_vec(), vec![0]);
+            // Reading past the end keeps returning `None`, since the vec's iterator is fused.
+            assert_eq!(stream_next::<u32>(handle).destroy_into_vec(), vec![0]);
+            stream_free::<u32>(handle);
+        }
+    }
+
+    #[test]
+    fn test_streams_cannot_be_lifted() {
+        assert!(<Stream<u32> as FfiConverter>::try_lift(0).is_err());
+    }
+}
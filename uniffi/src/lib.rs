@@ -34,6 +34,7 @@ use paste::paste;
 use std::{
     collections::HashMap,
     convert::TryFrom,
+    hash::Hash,
     time::{Duration, SystemTime},
 };
 
@@ -300,6 +301,36 @@ unsafe impl FfiConverter for bool {
     }
 }
 
+/// Support for passing char values via the FFI.
+///
+/// Chars are passed as a `u32` holding their Unicode code point. Not every `u32` is
+/// a valid code point (for example, the surrogates used in UTF-16 aren't), so lifting
+/// can fail if the foreign language code passes in something that isn't.
+unsafe impl FfiConverter for char {
+    type RustType = Self;
+    type FfiType = u32;
+
+    fn lower(obj: Self::RustType) -> Self::FfiType {
+        obj as u32
+    }
+
+    fn try_lift(v: Self::FfiType) -> Result<Self::RustType> {
+        match std::char::from_u32(v) {
+            Some(c) => Ok(c),
+            None => bail!("invalid code point for char: {:#x}", v),
+        }
+    }
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        buf.put_u32(<char as FfiConverter>::lower(obj));
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+        check_remaining(buf, 4)?;
+        <char as FfiConverter>::try_lift(buf.get_u32())
+    }
+}
+
 /// Support for passing Strings via the FFI.
 ///
 /// Unlike many other implementations of `FfiConverter`, this passes a struct containing
@@ -396,6 +427,35 @@ unsafe impl<T: RustBufferFfiConverter> FfiConverter for T {
     }
 }
 
+/// Support for passing 128-bit integers via the FFI.
+///
+/// There's no portable way to pass 128-bit integers as a C-compatible numeric type,
+/// so they are always passed by serializing to a buffer, as 16 bytes in big-endian order.
+macro_rules! impl_via_rust_buffer_for_128_bit_primitive {
+    ($($T:ty),*) => {
+            $(
+                paste! {
+                    impl RustBufferFfiConverter for $T {
+                        type RustType = Self;
+
+                        fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+                            buf.[<put_ $T>](obj);
+                        }
+
+                        fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+                            check_remaining(buf, 16)?;
+                            Ok(buf.[<get_ $T>]())
+                        }
+                    }
+                }
+            )*
+    };
+}
+
+impl_via_rust_buffer_for_128_bit_primitive! {
+    i128, u128
+}
+
 /// Support for passing timestamp values via the FFI.
 ///
 /// Timestamps values are currently always passed by serializing to a buffer.
@@ -466,6 +526,23 @@ impl RustBufferFfiConverter for Duration {
     }
 }
 
+/// Support for passing boxed values via the FFI.
+///
+/// Boxes are transparent, and serialized in exactly the same way as the value they contain.
+/// They're used for record fields that refer back to the record itself, like the children of
+/// a node in a tree, since Rust can't otherwise know how big the record is.
+impl<T: FfiConverter> RustBufferFfiConverter for Box<T> {
+    type RustType = Box<T::RustType>;
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        <T as FfiConverter>::write(*obj, buf);
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+        Ok(Box::new(<T as FfiConverter>::try_read(buf)?))
+    }
+}
+
 /// Support for passing optional values via the FFI.
 ///
 /// Optional values are currently always passed by serializing to a buffer.
@@ -530,23 +607,55 @@ impl<T: FfiConverter> RustBufferFfiConverter for Vec<T> {
     }
 }
 
+/// Support for passing `bytes` via the FFI.
+///
+/// These are serialized in the same way as a `Vec<u8>`, as an `i32` length followed by
+/// the bytes themselves, but are copied in and out of the buffer in one go rather than
+/// one item at a time. It's a separate UDL type from `sequence<u8>` so that the foreign
+/// language bindings can represent it using their native type for a blob of bytes.
+pub struct FfiConverterBytes;
+
+impl RustBufferFfiConverter for FfiConverterBytes {
+    type RustType = Vec<u8>;
+
+    fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+        // TODO: would be nice not to panic here :-/
+        let len = i32::try_from(obj.len()).unwrap();
+        buf.put_i32(len); // We limit byte strings to i32::MAX bytes
+        buf.put(obj.as_slice());
+    }
+
+    fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+        check_remaining(buf, 4)?;
+        let len = usize::try_from(buf.get_i32())?;
+        check_remaining(buf, len)?;
+        let bytes = buf.chunk()[..len].to_vec();
+        buf.advance(len);
+        Ok(bytes)
+    }
+}
+
 /// Support for associative arrays via the FFI.
-/// Note that because of webidl limitations,
-/// the key must always be of the String type.
+/// The key can be any type that can be hashed and compared for equality.
 ///
 /// HashMaps are currently always passed by serializing to a buffer.
-/// We write a `i32` entries count followed by each entry (string
-/// key followed by the value) in turn.
+/// We write a `i32` entries count followed by each entry (key
+/// followed by the value) in turn.
 /// (It's a signed type due to limits of the JVM).
-impl<V: FfiConverter> RustBufferFfiConverter for HashMap<String, V> {
-    type RustType = HashMap<String, V::RustType>;
+impl<K, V> RustBufferFfiConverter for HashMap<K, V>
+where
+    K: FfiConverter,
+    K::RustType: Hash + Eq,
+    V: FfiConverter,
+{
+    type RustType = HashMap<K::RustType, V::RustType>;
 
     fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
         // TODO: would be nice not to panic here :-/
         let len = i32::try_from(obj.len()).unwrap();
         buf.put_i32(len); // We limit HashMaps to i32::MAX entries
         for (key, value) in obj.into_iter() {
-            <String as FfiConverter>::write(key, buf);
+            <K as FfiConverter>::write(key, buf);
             <V as FfiConverter>::write(value, buf);
         }
     }
@@ -556,7 +665,7 @@ impl<V: FfiConverter> RustBufferFfiConverter for HashMap<String, V> {
         let len = usize::try_from(buf.get_i32())?;
         let mut map = HashMap::with_capacity(len);
         for _ in 0..len {
-            let key = String::try_read(buf)?;
+            let key = <K as FfiConverter>::try_read(buf)?;
             let value = <V as FfiConverter>::try_read(buf)?;
             map.insert(key, value);
         }
@@ -564,6 +673,33 @@ impl<V: FfiConverter> RustBufferFfiConverter for HashMap<String, V> {
     }
 }
 
+/// Support for passing tuples via the FFI.
+///
+/// Tuples are currently always passed by serializing to a buffer.
+/// We write each element in turn, with no length prefix since the number
+/// of elements is known from the type.
+macro_rules! impl_rust_buffer_ffi_converter_for_tuple {
+    ($($T:ident $idx:tt),+) => {
+        impl<$($T: FfiConverter),+> RustBufferFfiConverter for ($($T,)+) {
+            type RustType = ($(<$T as FfiConverter>::RustType,)+);
+
+            fn write(obj: Self::RustType, buf: &mut Vec<u8>) {
+                $(<$T as FfiConverter>::write(obj.$idx, buf);)+
+            }
+
+            fn try_read(buf: &mut &[u8]) -> Result<Self::RustType> {
+                Ok(($(<$T as FfiConverter>::try_read(buf)?,)+))
+            }
+        }
+    };
+}
+
+impl_rust_buffer_ffi_converter_for_tuple!(A 0, B 1);
+impl_rust_buffer_ffi_converter_for_tuple!(A 0, B 1, C 2);
+impl_rust_buffer_ffi_converter_for_tuple!(A 0, B 1, C 2, D 3);
+impl_rust_buffer_ffi_converter_for_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_rust_buffer_ffi_converter_for_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+
 /// Support for passing reference-counted shared objects via the FFI.
 ///
 /// To avoid dealing with complex lifetime semantics over the FFI, any data passed
@@ -651,4 +787,71 @@ mod test {
             "Expected results after lowering and lifting to be equal"
         )
     }
+
+    #[test]
+    fn u128_roundtrip() {
+        let expected = u128::MAX - 1;
+        let result = u128::try_lift(u128::lower(expected)).expect("Failed to lift!");
+        assert_eq!(expected, result)
+    }
+
+    #[test]
+    fn bytes_are_serialized_like_a_vec_of_u8() {
+        let mut bytes = Vec::new();
+        <FfiConverterBytes as FfiConverter>::write(vec![1, 2, 3], &mut bytes);
+        let mut items = Vec::new();
+        <Vec<u8> as FfiConverter>::write(vec![1, 2, 3], &mut items);
+        assert_eq!(bytes, items);
+        let result = <FfiConverterBytes as FfiConverter>::try_lift(RustBuffer::from_vec(bytes))
+            .expect("Failed to lift!");
+        assert_eq!(result, vec![1, 2, 3]);
+        // The length is checked before the bytes are copied.
+        assert!(<FfiConverterBytes as FfiConverter>::try_read(&mut &[0, 0, 0, 4, 1][..]).is_err());
+    }
+
+    #[test]
+    fn char_roundtrip() {
+        let result = char::try_lift(char::lower('🦀')).expect("Failed to lift!");
+        assert_eq!(result, '🦀');
+        let mut buf = Vec::new();
+        <char as FfiConverter>::write('a', &mut buf);
+        assert_eq!(buf, vec![0, 0, 0, 0x61]);
+    }
+
+    #[test]
+    fn char_rejects_surrogates() {
+        assert!(char::try_lift(0xD800).is_err());
+        assert!(char::try_lift(0x110000).is_err());
+    }
+
+    #[test]
+    fn tuple_roundtrip() {
+        type Converter = (i32, Option<String>, (bool, u8));
+        let expected = (-1, Some("hello".to_string()), (true, 7));
+        let lowered = <Converter as FfiConverter>::lower(expected.clone());
+        let result = <Converter as FfiConverter>::try_lift(lowered).expect("Failed to lift!");
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn boxes_are_serialized_like_their_contents() {
+        let mut boxed = Vec::new();
+        <Option<Box<u32>> as FfiConverter>::write(Some(Box::new(7)), &mut boxed);
+        let mut unboxed = Vec::new();
+        <Option<u32> as FfiConverter>::write(Some(7), &mut unboxed);
+        assert_eq!(boxed, unboxed);
+        let result = <Option<Box<u32>> as FfiConverter>::try_read(&mut boxed.as_slice()).unwrap();
+        assert_eq!(result, Some(Box::new(7)));
+    }
+
+    #[test]
+    fn i128_is_written_big_endian() {
+        let mut buf = Vec::new();
+        <i128 as FfiConverter>::write(-2, &mut buf);
+        assert_eq!(buf.len(), 16);
+        assert_eq!(buf[0], 0xFF);
+        assert_eq!(buf[15], 0xFE);
+        let result = <i128 as FfiConverter>::try_read(&mut buf.as_slice()).unwrap();
+        assert_eq!(result, -2);
+    }
 }
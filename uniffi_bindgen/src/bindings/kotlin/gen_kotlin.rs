@@ -2,13 +2,18 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use anyhow::Result;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
 use askama::Template;
 use heck::{CamelCase, MixedCase, ShoutySnakeCase};
 use serde::{Deserialize, Serialize};
 
+use crate::interface::types::{IterTypes, TypeIterator, MAX_TUPLE_ELEMENTS};
 use crate::interface::*;
-use crate::MergeWith;
+use crate::{BindgenError, MergeWith};
 
 // Some config options for it the caller wants to customize the generated Kotlin.
 // Note that this can only be used to control details of the Kotlin *that do not affect the underlying component*,
@@ -17,6 +22,146 @@ use crate::MergeWith;
 pub struct Config {
     package_name: Option<String>,
     cdylib_name: Option<String>,
+    generate_docstrings: Option<bool>,
+    exception_suffix_rules: Option<Vec<(String, String)>>,
+    value_class_records: Option<bool>,
+    external_packages: Option<HashMap<String, String>>,
+    rust_buffer_initial_capacity: Option<u32>,
+    debug_tostring: Option<bool>,
+    function_renames: Option<HashMap<String, String>>,
+    type_renames: Option<HashMap<String, String>>,
+    generate_interfaces: Option<bool>,
+    sequence_type: Option<SequenceType>,
+    custom_types: Option<HashMap<String, CustomTypeConfig>>,
+    use_unsigned_types: Option<bool>,
+    custom_header: Option<String>,
+    generate_builders: Option<bool>,
+    builder_field_threshold: Option<usize>,
+    ffi_namespace: Option<String>,
+    split_files: Option<bool>,
+    generate_all_cases: Option<bool>,
+    generate_record_interfaces: Option<bool>,
+    include_only: Option<Vec<String>>,
+    android_parcelable: Option<bool>,
+    visibility: Option<Visibility>,
+    immutable_collections: Option<bool>,
+    object_init_hook: Option<String>,
+    call_interceptor: Option<String>,
+    preserve_object_identity: Option<bool>,
+    jvm_overloads: Option<bool>,
+    android_keep: Option<bool>,
+    strict_utf8: Option<bool>,
+    target: Option<Target>,
+    group_literal_digits: Option<bool>,
+    omit_unused_helpers: Option<bool>,
+    subpackages: Option<HashMap<String, String>>,
+    // These come from the `[Alias] typedef`s in the UDL, but can be replaced from code.
+    #[serde(skip)]
+    type_aliases: Option<BTreeMap<String, Type>>,
+    // This can only be set from code, via `Config::with_naming_convention`.
+    #[serde(skip)]
+    naming_convention: Option<Arc<dyn NamingConvention>>,
+}
+
+/// The casing rules for turning names from the UDL into Kotlin identifiers.
+///
+/// The default methods give idiomatic Kotlin names. Projects with their own style guide can
+/// override some of them, and use [`Config::with_naming_convention`] to generate bindings with
+/// the result. The `type_renames` and `function_renames` config options still take precedence,
+/// as do the `exception_suffix_rules`, and names that collide with a Kotlin keyword are still
+/// escaped afterwards.
+pub trait NamingConvention: fmt::Debug + Send + Sync {
+    /// The name of a class (for enums, records, objects, etc).
+    fn class_name(&self, nm: &str) -> String {
+        nm.to_camel_case()
+    }
+
+    /// The name of a function or method.
+    fn fn_name(&self, nm: &str) -> String {
+        nm.to_mixed_case()
+    }
+
+    /// The name of a variable, argument or field.
+    fn var_name(&self, nm: &str) -> String {
+        nm.to_mixed_case()
+    }
+
+    /// The name of a variant of an `enum class`.
+    fn enum_variant(&self, nm: &str) -> String {
+        nm.to_shouty_snake_case()
+    }
+
+    /// The name of an exception class, which by default replaces a trailing "Error" with "Exception".
+    ///
+    /// Rust code typically uses "Error" for any type of error but in the Java world, "Error" means
+    /// a non-recoverable error and is distinguished from an "Exception".
+    fn exception_name(&self, nm: &str) -> String {
+        match nm.strip_suffix("Error") {
+            None => nm.to_string(),
+            Some(stripped) => format!("{}Exception", stripped),
+        }
+    }
+}
+
+/// The idiomatic Kotlin [`NamingConvention`], which is used unless another one is configured.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultNamingConvention;
+
+impl NamingConvention for DefaultNamingConvention {}
+
+/// The Kotlin types that can be used to represent a `sequence<T>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SequenceType {
+    List,
+    Array,
+}
+
+/// The visibility modifier to give the generated declarations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    Public,
+    Internal,
+}
+
+/// The kind of Kotlin project to generate bindings for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Target {
+    /// A JVM or Android module, with all of the bindings in the one source set.
+    Jvm,
+    /// A Kotlin Multiplatform project, with `expect` declarations of the public API in the
+    /// common source set and their `actual` implementations in the JVM one.
+    Kmp,
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Visibility::Public => "public",
+            Visibility::Internal => "internal",
+        })
+    }
+}
+
+/// How to represent a custom type in Kotlin, as configured via the `custom_types` config option.
+///
+/// The `converter` names a Kotlin object that implements the generated `UniffiCustomTypeConverter`
+/// interface, to convert between the `type_name` and the builtin type that it's passed over the FFI as.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomTypeConfig {
+    type_name: String,
+    converter: String,
+}
+
+impl CustomTypeConfig {
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    pub fn converter(&self) -> &str {
+        &self.converter
+    }
 }
 
 impl Config {
@@ -35,6 +180,400 @@ impl Config {
             "uniffi".into()
         }
     }
+
+    /// The prefix to use for the names of FFI functions, if it should differ from the namespace.
+    ///
+    /// Unlike the other options here, this also affects the generated scaffolding, since it has
+    /// to export the FFI functions under the same names. See [`ComponentInterface::set_ffi_namespace`].
+    pub fn ffi_namespace(&self) -> Option<&str> {
+        self.ffi_namespace.as_deref()
+    }
+
+    /// Whether to write each enum, record, object and callback interface to a file of its own.
+    ///
+    /// This is opt-in via the `split_files` config option, since one big file is simpler to
+    /// work with for small components. Everything else stays in the main file for the component.
+    pub fn split_files(&self) -> bool {
+        self.split_files.unwrap_or(false)
+    }
+
+    /// The subpackage of `package_name` that a type is generated into, if any.
+    ///
+    /// This is keyed on the name of the type in the UDL, as set by the `subpackages` config
+    /// option, and the subpackage can itself be a dotted path like `network.http`.
+    pub fn subpackage(&self, type_name: &str) -> Option<&str> {
+        self.subpackages
+            .as_ref()
+            .and_then(|subpackages| subpackages.get(type_name))
+            .map(String::as_str)
+    }
+
+    /// The wildcard `import`s that a file in the given subpackage, or in the main package for
+    /// `None`, needs in order to refer to everything that's declared in the other packages.
+    pub fn package_imports(&self, subpackage: Option<&str>) -> Vec<String> {
+        let subpackages = match &self.subpackages {
+            Some(subpackages) if !subpackages.is_empty() => subpackages,
+            _ => return vec![],
+        };
+        let mut imports = BTreeSet::new();
+        if subpackage.is_some() {
+            imports.insert(format!("{}.*", self.package_name()));
+        }
+        for other in subpackages.values() {
+            if Some(other.as_str()) != subpackage {
+                imports.insert(format!("{}.{}.*", self.package_name(), other));
+            }
+        }
+        imports.into_iter().collect()
+    }
+
+    /// Whether to give the companion object of each flat enum an `allCases` list of its variants.
+    ///
+    /// This is opt-in via the `generate_all_cases` config option. The companion objects all
+    /// implement a common `CaseIterable` interface, so code can be generic over which enum it
+    /// lists, e.g. when building a UI picker. Enums with associated data can't be enumerated.
+    pub fn generate_all_cases(&self) -> bool {
+        self.generate_all_cases.unwrap_or(false)
+    }
+
+    /// Whether to refer to records by a generated `FooData` interface rather than the data class.
+    ///
+    /// This is opt-in via the `generate_record_interfaces` config option, so that a component can
+    /// accept records from other Kotlin code that has its own class of the same shape. Like with
+    /// `generate_interfaces`, only the concrete data class can be lowered into Rust.
+    pub fn generate_record_interfaces(&self) -> bool {
+        self.generate_record_interfaces.unwrap_or(false)
+    }
+
+    /// The names of the functions, constants and types to generate bindings for, if not all of them.
+    ///
+    /// This is set via the `include_only` config option, for consumers of a large component that
+    /// only use a small part of it. Anything that the named items depend on is included too.
+    pub fn include_only(&self) -> Option<&[String]> {
+        self.include_only.as_deref()
+    }
+
+    /// Whether to make records `Parcelable` using `@Parcelize`, so they can be sent in an Intent.
+    ///
+    /// This is opt-in via the `android_parcelable` config option, since it only works on Android
+    /// with the `kotlin-parcelize` plugin applied. Records that contain objects can't be parcelled.
+    pub fn android_parcelable(&self) -> bool {
+        self.android_parcelable.unwrap_or(false)
+    }
+
+    /// The visibility of the generated classes, functions and constants, `public` by default.
+    ///
+    /// Setting the `visibility` config option to `internal` keeps the bindings out of the public
+    /// API of the Kotlin module that they're compiled into, for components that are wrapped by
+    /// a hand-written API. Declarations that are already an implementation detail stay `internal`.
+    pub fn visibility(&self) -> Visibility {
+        self.visibility.unwrap_or(Visibility::Public)
+    }
+
+    /// Whether to stop code that holds a record from changing the collections in its fields.
+    ///
+    /// This is opt-in via the `immutable_collections` config option. Fields holding a sequence or
+    /// a map are declared with `val` rather than `var`, and the lists and maps that we lift from
+    /// Rust are read-only views, so a record and its `copy()` can't change collections they share.
+    pub fn immutable_collections(&self) -> bool {
+        self.immutable_collections.unwrap_or(false)
+    }
+
+    /// Kotlin code to run in the `init` block of every generated object class, if any.
+    ///
+    /// The templates are compiled into `uniffi-bindgen`, so they can't be overridden to add
+    /// things like logging when an object is created. This option is a hook for that instead,
+    /// and the code can refer to the newly-created object as `this`.
+    pub fn object_init_hook(&self) -> Option<&str> {
+        self.object_init_hook.as_deref()
+    }
+
+    /// The Kotlin object to wrap each call into Rust with, for instrumenting them.
+    ///
+    /// This is set via the `call_interceptor` config option, and names an object implementing the
+    /// generated `UniffiCallInterceptor` interface. Calls to functions, constructors and methods
+    /// are wrapped, but calls that only start an async function aren't, and nor are the internal
+    /// calls for managing buffers and freeing objects.
+    pub fn call_interceptor(&self) -> Option<&str> {
+        self.call_interceptor.as_deref()
+    }
+
+    /// Whether to give back the same Kotlin instance each time Rust returns the same object.
+    ///
+    /// This is opt-in via the `preserve_object_identity` config option, so that methods which
+    /// return `self` can be chained without creating a new instance for every call. Since the
+    /// instances are shared, destroying one destroys it for all of the code that holds it.
+    pub fn preserve_object_identity(&self) -> bool {
+        self.preserve_object_identity.unwrap_or(false)
+    }
+
+    /// Whether to annotate functions and constructors that end in default arguments with
+    /// `@JvmOverloads`, so that Java callers can leave those arguments out too.
+    ///
+    /// Methods never get the annotation, because their defaults are declared on the
+    /// object's interface, where Kotlin doesn't allow it.
+    pub fn jvm_overloads(&self) -> bool {
+        self.jvm_overloads.unwrap_or(false)
+    }
+
+    /// Whether to annotate the classes that JNA uses via reflection with `@Keep`, so that R8
+    /// doesn't strip or rename them in Android release builds.
+    ///
+    /// This is opt-in via the `android_keep` config option, since the annotation comes from
+    /// `androidx.annotation`, which isn't available outside of Android.
+    pub fn android_keep(&self) -> bool {
+        self.android_keep.unwrap_or(false)
+    }
+
+    /// Whether to throw an `InternalException` when a string from Rust isn't valid UTF-8.
+    ///
+    /// By default, Kotlin's decoder silently replaces invalid bytes with U+FFFD. That should
+    /// never happen, since Rust strings are always valid UTF-8, so the `strict_utf8` config
+    /// option turns it into an error instead, to catch serialization bugs in testing.
+    pub fn strict_utf8(&self) -> bool {
+        self.strict_utf8.unwrap_or(false)
+    }
+
+    /// The kind of Kotlin project to generate bindings for, as set by the `target` config option.
+    pub fn target(&self) -> Target {
+        self.target.unwrap_or(Target::Jvm)
+    }
+
+    /// Whether to write large decimal literals with an underscore between each group of three
+    /// digits, like `1_000_000`, so that they're easier to read.
+    ///
+    /// This is opt-in via the `group_literal_digits` config option, and only applies to literals
+    /// with more than four digits. Literals written in hex in the UDL are always hex in Kotlin.
+    pub fn group_literal_digits(&self) -> bool {
+        self.group_literal_digits.unwrap_or(false)
+    }
+
+    /// Whether to leave out the shared helper code that nothing in the component uses.
+    ///
+    /// The helpers for each type are only ever generated for the types that the component uses,
+    /// but some of the shared ones are always generated, such as `ForeignBytes` and the helpers
+    /// for lifting and lowering compound values. This is opt-in via the `omit_unused_helpers`
+    /// config option, since code outside the bindings in the same package could be using them.
+    pub fn omit_unused_helpers(&self) -> bool {
+        self.omit_unused_helpers.unwrap_or(false)
+    }
+
+    /// Use the given `typealias`es in place of the types that they stand for, rather than the
+    /// ones declared with `[Alias] typedef` in the UDL.
+    pub fn with_type_aliases(mut self, aliases: impl IntoIterator<Item = (String, Type)>) -> Self {
+        self.type_aliases = Some(aliases.into_iter().collect());
+        self
+    }
+
+    /// The `typealias`es to declare, with the types that they stand for, in order of name.
+    pub fn type_aliases(&self) -> Vec<(String, Type)> {
+        match &self.type_aliases {
+            Some(aliases) => aliases
+                .iter()
+                .map(|(name, type_)| (name.clone(), type_.clone()))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// The `typealias` to use in place of a type, if it has one.
+    pub fn type_alias(&self, type_: &Type) -> Option<&str> {
+        self.type_aliases
+            .as_ref()?
+            .iter()
+            .find(|(_, t)| *t == type_)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Whether the bindings are split into `expect` and `actual` declarations for a Kotlin
+    /// Multiplatform project. Only the JVM `actual` declarations are implemented so far.
+    pub fn multiplatform(&self) -> bool {
+        self.target() == Target::Kmp
+    }
+
+    /// Whether to wrap the lists that we lift from Rust in a read-only view.
+    ///
+    /// Arrays are always mutable, so this only applies when sequences are represented as a `List`.
+    pub fn read_only_lists(&self) -> bool {
+        self.immutable_collections() && self.sequence_type() == SequenceType::List
+    }
+
+    /// Whether to declare a field of a record with `val` rather than `var`.
+    pub fn is_read_only_field(&self, rec: &Record, field: &Field) -> bool {
+        fn is_collection(type_: &Type) -> bool {
+            match type_ {
+                Type::Sequence(_) | Type::Map(_, _) => true,
+                Type::Optional(inner) => is_collection(inner),
+                _ => false,
+            }
+        }
+        self.render_as_value_class(rec)
+            || (self.immutable_collections() && is_collection(&field.type_()))
+    }
+
+    /// Use a different [`NamingConvention`] for the generated code.
+    pub fn with_naming_convention(mut self, convention: impl NamingConvention + 'static) -> Self {
+        self.naming_convention = Some(Arc::new(convention));
+        self
+    }
+
+    /// The [`NamingConvention`] to use for the generated code.
+    pub fn naming_convention(&self) -> &dyn NamingConvention {
+        match &self.naming_convention {
+            Some(convention) => convention.as_ref(),
+            None => &DefaultNamingConvention,
+        }
+    }
+
+    /// Whether to render the docstrings from the UDL as KDoc comments.
+    pub fn generate_docstrings(&self) -> bool {
+        self.generate_docstrings.unwrap_or(true)
+    }
+
+    /// Rules for renaming error types into exceptions, as `(suffix, replacement)` pairs.
+    ///
+    /// These are tried in order, and the first one whose suffix matches the name of the error
+    /// is used. If none of them match then we fall back to replacing any "Error" suffix.
+    pub fn exception_suffix_rules(&self) -> &[(String, String)] {
+        self.exception_suffix_rules.as_deref().unwrap_or(&[])
+    }
+
+    /// Whether to render a record as a Kotlin `value class` rather than a `data class`.
+    ///
+    /// This is opt-in via the `value_class_records` config option, and is only possible for
+    /// records that have exactly one field. We don't do it when that field is optional, since
+    /// a value class wrapping a nullable type can't itself be used where nullability is needed,
+    /// or when it's excluded from equality, since value classes can't override `equals`.
+    ///
+    /// Only the Kotlin declaration changes. The record is still lowered into a `RustBuffer` and
+    /// lifted from one, since the scaffolding that reads and writes it is shared by all bindings.
+    pub fn render_as_value_class(&self, rec: &Record) -> bool {
+        self.value_class_records.unwrap_or(false)
+            && !rec.has_fields_excluded_from_equality()
+            && matches!(rec.fields().as_slice(), [field] if !matches!(field.type_(), Type::Optional(_)))
+    }
+
+    /// Whether to generate a nested `Builder` class for a record, for constructing it field by field.
+    ///
+    /// This is opt-in via the `generate_builders` config option, and only applies to records with
+    /// more fields than the `builder_field_threshold` config option (4 by default), since smaller
+    /// records are easy enough to construct by calling the constructor directly.
+    pub fn render_builder(&self, rec: &Record) -> bool {
+        self.generate_builders.unwrap_or(false)
+            && rec.fields().len() > self.builder_field_threshold.unwrap_or(4)
+    }
+
+    /// The Kotlin package from which to import an external type.
+    ///
+    /// This can be set per type name via the `external_packages` config option, and otherwise
+    /// assumes that the crate defining the type uses the default package name for its namespace.
+    pub fn external_package(&self, name: &str, crate_name: &str) -> String {
+        match self.external_packages.as_ref().and_then(|p| p.get(name)) {
+            Some(package) => package.clone(),
+            None => format!("uniffi.{}", crate_name.replace('-', "_")),
+        }
+    }
+
+    /// The number of bytes to allocate up-front when serializing a value into a `RustBuffer`.
+    ///
+    /// The buffer grows on demand, but each time it does so we have to call into Rust and create
+    /// a new `ByteBuffer` view of its memory. Components that routinely pass large records or
+    /// sequences can avoid much of that churn by starting with a larger buffer.
+    pub fn rust_buffer_initial_capacity(&self) -> u32 {
+        // Kotlin sizes buffers using a signed `Int`.
+        self.rust_buffer_initial_capacity
+            .unwrap_or(16)
+            .min(i32::MAX as u32)
+    }
+
+    /// Whether to give objects a `toString()` that shows their underlying pointer, for debugging.
+    pub fn debug_tostring(&self) -> bool {
+        self.debug_tostring.unwrap_or(false)
+    }
+
+    /// Whether to refer to objects by their generated `FooInterface` rather than the concrete `Foo`.
+    ///
+    /// This is opt-in via the `generate_interfaces` config option, and makes it possible for
+    /// consumers to pass in a mock implementation of an object when testing their own code.
+    /// Only the concrete class holds a pointer to the Rust object, so that's what we lower.
+    pub fn generate_interfaces(&self) -> bool {
+        self.generate_interfaces.unwrap_or(false)
+    }
+
+    /// Whether to represent sequences as a `List<T>` (the default) or as an array.
+    ///
+    /// Arrays avoid some overhead for large sequences, particularly for primitive element types
+    /// which use specialized arrays like `IntArray` to avoid boxing. But note that arrays compare
+    /// by identity rather than by contents, including in the generated `equals()` for records.
+    pub fn sequence_type(&self) -> SequenceType {
+        self.sequence_type.unwrap_or(SequenceType::List)
+    }
+
+    /// Whether to represent unsigned integers with Kotlin's unsigned types like `UInt`.
+    ///
+    /// This is on by default. Turning it off represents each unsigned integer by the signed type
+    /// of the same size, holding the same bits, which can be easier to use from Java and avoids
+    /// the need to opt in to `@ExperimentalUnsignedTypes` on older versions of Kotlin.
+    pub fn use_unsigned_types(&self) -> bool {
+        self.use_unsigned_types.unwrap_or(true)
+    }
+
+    /// The part of the `custom_header` config option that goes at the very start of the file.
+    ///
+    /// File-level annotations like `@file:Suppress(...)` are only valid before the `package`
+    /// statement, so this is everything up to and including the last of them.
+    pub fn custom_header_preamble(&self) -> &str {
+        self.split_custom_header().0.trim_end()
+    }
+
+    /// The rest of the `custom_header` config option, which goes after our own imports.
+    pub fn custom_header_body(&self) -> &str {
+        self.split_custom_header().1.trim()
+    }
+
+    fn split_custom_header(&self) -> (&str, &str) {
+        let header = self.custom_header.as_deref().unwrap_or_default();
+        let mut split = 0;
+        let mut offset = 0;
+        for line in header.split_inclusive('\n') {
+            offset += line.len();
+            if line.trim_start().starts_with("@file:") {
+                split = offset;
+            }
+        }
+        header.split_at(split)
+    }
+
+    /// How to represent a custom type in Kotlin, if it has been configured.
+    ///
+    /// This is keyed on the name of the type in the UDL. Custom types that aren't configured
+    /// are represented by the builtin type that they wrap.
+    pub fn custom_type(&self, name: &str) -> Option<&CustomTypeConfig> {
+        self.custom_types
+            .as_ref()
+            .and_then(|custom_types| custom_types.get(name))
+    }
+
+    /// The Kotlin name to use for a function or method, if it has been explicitly renamed.
+    ///
+    /// This is keyed on the name of the function in the UDL, or on the name of the type and the
+    /// method joined by a `.` for methods and constructors, and the new name is used as-is.
+    pub fn function_rename(&self, name: &str) -> Option<&str> {
+        self.function_renames
+            .as_ref()
+            .and_then(|renames| renames.get(name))
+            .map(String::as_str)
+    }
+
+    /// The Kotlin name to use for a type, if it has been explicitly renamed.
+    ///
+    /// This is keyed on the name of the type in the UDL, and the new name is used as-is.
+    pub fn type_rename(&self, name: &str) -> Option<&str> {
+        self.type_renames
+            .as_ref()
+            .and_then(|renames| renames.get(name))
+            .map(String::as_str)
+    }
 }
 
 impl From<&ComponentInterface> for Config {
@@ -42,6 +581,41 @@ impl From<&ComponentInterface> for Config {
         Config {
             package_name: Some(format!("uniffi.{}", ci.namespace())),
             cdylib_name: Some(format!("uniffi_{}", ci.namespace())),
+            generate_docstrings: None,
+            exception_suffix_rules: None,
+            value_class_records: None,
+            external_packages: None,
+            rust_buffer_initial_capacity: None,
+            debug_tostring: None,
+            function_renames: None,
+            type_renames: None,
+            generate_interfaces: None,
+            sequence_type: None,
+            custom_types: None,
+            use_unsigned_types: None,
+            custom_header: None,
+            generate_builders: None,
+            builder_field_threshold: None,
+            ffi_namespace: None,
+            split_files: None,
+            generate_all_cases: None,
+            generate_record_interfaces: None,
+            include_only: None,
+            android_parcelable: None,
+            visibility: None,
+            immutable_collections: None,
+            object_init_hook: None,
+            call_interceptor: None,
+            preserve_object_identity: None,
+            jvm_overloads: None,
+            android_keep: None,
+            strict_utf8: None,
+            target: None,
+            group_literal_digits: None,
+            omit_unused_helpers: None,
+            subpackages: None,
+            type_aliases: Some(ci.iter_type_aliases().into_iter().collect()),
+            naming_convention: None,
         }
     }
 }
@@ -51,6 +625,69 @@ impl MergeWith for Config {
         Config {
             package_name: self.package_name.merge_with(&other.package_name),
             cdylib_name: self.cdylib_name.merge_with(&other.cdylib_name),
+            generate_docstrings: self
+                .generate_docstrings
+                .merge_with(&other.generate_docstrings),
+            exception_suffix_rules: self
+                .exception_suffix_rules
+                .merge_with(&other.exception_suffix_rules),
+            value_class_records: self
+                .value_class_records
+                .merge_with(&other.value_class_records),
+            external_packages: self.external_packages.merge_with(&other.external_packages),
+            rust_buffer_initial_capacity: self
+                .rust_buffer_initial_capacity
+                .merge_with(&other.rust_buffer_initial_capacity),
+            debug_tostring: self.debug_tostring.merge_with(&other.debug_tostring),
+            function_renames: self.function_renames.merge_with(&other.function_renames),
+            type_renames: self.type_renames.merge_with(&other.type_renames),
+            generate_interfaces: self
+                .generate_interfaces
+                .merge_with(&other.generate_interfaces),
+            sequence_type: self.sequence_type.merge_with(&other.sequence_type),
+            custom_types: self.custom_types.merge_with(&other.custom_types),
+            use_unsigned_types: self
+                .use_unsigned_types
+                .merge_with(&other.use_unsigned_types),
+            custom_header: self.custom_header.merge_with(&other.custom_header),
+            generate_builders: self.generate_builders.merge_with(&other.generate_builders),
+            builder_field_threshold: self
+                .builder_field_threshold
+                .merge_with(&other.builder_field_threshold),
+            ffi_namespace: self.ffi_namespace.merge_with(&other.ffi_namespace),
+            split_files: self.split_files.merge_with(&other.split_files),
+            generate_all_cases: self
+                .generate_all_cases
+                .merge_with(&other.generate_all_cases),
+            generate_record_interfaces: self
+                .generate_record_interfaces
+                .merge_with(&other.generate_record_interfaces),
+            include_only: self.include_only.merge_with(&other.include_only),
+            android_parcelable: self
+                .android_parcelable
+                .merge_with(&other.android_parcelable),
+            visibility: self.visibility.merge_with(&other.visibility),
+            immutable_collections: self
+                .immutable_collections
+                .merge_with(&other.immutable_collections),
+            object_init_hook: self.object_init_hook.merge_with(&other.object_init_hook),
+            call_interceptor: self.call_interceptor.merge_with(&other.call_interceptor),
+            preserve_object_identity: self
+                .preserve_object_identity
+                .merge_with(&other.preserve_object_identity),
+            jvm_overloads: self.jvm_overloads.merge_with(&other.jvm_overloads),
+            android_keep: self.android_keep.merge_with(&other.android_keep),
+            strict_utf8: self.strict_utf8.merge_with(&other.strict_utf8),
+            target: self.target.merge_with(&other.target),
+            group_literal_digits: self
+                .group_literal_digits
+                .merge_with(&other.group_literal_digits),
+            omit_unused_helpers: self
+                .omit_unused_helpers
+                .merge_with(&other.omit_unused_helpers),
+            subpackages: self.subpackages.merge_with(&other.subpackages),
+            type_aliases: self.type_aliases.merge_with(&other.type_aliases),
+            naming_convention: self.naming_convention.merge_with(&other.naming_convention),
         }
     }
 }
@@ -63,165 +700,1389 @@ pub struct KotlinWrapper<'a> {
 }
 impl<'a> KotlinWrapper<'a> {
     pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        // Fill in anything that wasn't explicitly configured with the defaults for this
+        // component, so that e.g. we don't try to load a library named plain "uniffi".
+        let config = config.merge_with(&ci.into());
         Self { config, ci }
     }
-}
-
-mod filters {
-    use super::*;
-    use std::fmt;
 
-    /// Get the Kotlin syntax for representing a given api-level `Type`.
-    pub fn type_kt(type_: &Type) -> Result<String, askama::Error> {
-        Ok(match type_ {
-            // These native Kotlin types map nicely to the FFI without conversion.
-            Type::UInt8 => "UByte".to_string(),
-            Type::UInt16 => "UShort".to_string(),
-            Type::UInt32 => "UInt".to_string(),
-            Type::UInt64 => "ULong".to_string(),
-            Type::Int8 => "Byte".to_string(),
-            Type::Int16 => "Short".to_string(),
-            Type::Int32 => "Int".to_string(),
-            Type::Int64 => "Long".to_string(),
-            Type::Float32 => "Float".to_string(),
-            Type::Float64 => "Double".to_string(),
-            // These types need conversion, and special handling for lifting/lowering.
-            Type::Boolean => "Boolean".to_string(),
-            Type::String => "String".to_string(),
-            Type::Timestamp => "java.time.Instant".to_string(),
-            Type::Duration => "java.time.Duration".to_string(),
-            Type::Enum(name)
-            | Type::Record(name)
-            | Type::Object(name)
-            | Type::Error(name)
-            | Type::CallbackInterface(name) => class_name_kt(name)?,
-            Type::Optional(t) => format!("{}?", type_kt(t)?),
-            Type::Sequence(t) => format!("List<{}>", type_kt(t)?),
-            Type::Map(t) => format!("Map<String, {}>", type_kt(t)?),
-            Type::External { .. } => panic!("no support for external types yet"),
-            Type::Wrapped { .. } => panic!("no support for wrapped types yet"),
-        })
+    /// Whether any optional types in the interface need to wrap their value in `Some`.
+    pub fn contains_nested_optionals(&self) -> bool {
+        contains_nested_optionals(self.ci, &self.config)
     }
 
-    /// Get the Kotlin syntax for representing a given low-level `FFIType`.
-    pub fn type_ffi(type_: &FFIType) -> Result<String, askama::Error> {
-        Ok(match type_ {
-            // Note that unsigned integers in Kotlin are currently experimental, but java.nio.ByteBuffer does not
-            // support them yet. Thus, we use the signed variants to represent both signed and unsigned
-            // types from the component API.
-            FFIType::Int8 | FFIType::UInt8 => "Byte".to_string(),
-            FFIType::Int16 | FFIType::UInt16 => "Short".to_string(),
-            FFIType::Int32 | FFIType::UInt32 => "Int".to_string(),
-            FFIType::Int64 | FFIType::UInt64 => "Long".to_string(),
-            FFIType::Float32 => "Float".to_string(),
-            FFIType::Float64 => "Double".to_string(),
-            FFIType::RustArcPtr => "Pointer".to_string(),
-            FFIType::RustBuffer => "RustBuffer.ByValue".to_string(),
-            FFIType::ForeignBytes => "ForeignBytes.ByValue".to_string(),
-            FFIType::ForeignCallback => "ForeignCallback".to_string(),
-        })
+    /// Whether the bindings use Kotlin's unsigned types, which need opting in to.
+    pub fn uses_unsigned_types(&self) -> bool {
+        self.config.use_unsigned_types() && self.ci.contains_unsigned_types()
     }
 
-    pub fn literal_kt(literal: &Literal) -> Result<String, askama::Error> {
-        fn typed_number(type_: &Type, num_str: String) -> Result<String, askama::Error> {
-            Ok(match type_ {
-                // Bytes, Shorts and Ints can all be inferred from the type.
-                Type::Int8 | Type::Int16 | Type::Int32 => num_str,
-                Type::Int64 => format!("{}L", num_str),
+    /// Whether to generate `ForeignBytes`, which is only used to pass `[ByRef] bytes` arguments.
+    ///
+    /// The `rustbuffer_from_bytes` FFI function also takes it, but we never call that from Kotlin,
+    /// so without `ForeignBytes` we leave it out of the JNA library instead.
+    pub fn needs_foreign_bytes(&self) -> bool {
+        let from_bytes = self.ci.ffi_rustbuffer_from_bytes();
+        !self.config.omit_unused_helpers()
+            || self.ci.iter_ffi_function_definitions().iter().any(|func| {
+                func.name() != from_bytes.name()
+                    && func
+                        .arguments()
+                        .iter()
+                        .any(|arg| arg.type_() == FFIType::ForeignBytes)
+            })
+    }
 
-                Type::UInt8 | Type::UInt16 | Type::UInt32 => format!("{}u", num_str),
-                Type::UInt64 => format!("{}uL", num_str),
+    /// The FFI functions to declare in the JNA library.
+    pub fn ffi_functions(&self) -> Vec<FFIFunction> {
+        let from_bytes = self.ci.ffi_rustbuffer_from_bytes();
+        self.ci
+            .iter_ffi_function_definitions()
+            .into_iter()
+            .filter(|func| self.needs_foreign_bytes() || func.name() != from_bytes.name())
+            .collect()
+    }
 
-                Type::Float32 => format!("{}f", num_str),
-                Type::Float64 => num_str,
-                _ => panic!("Unexpected literal: {} is not a number", num_str),
+    /// Whether to generate the helpers for lifting and lowering values that are serialized into
+    /// a `RustBuffer`. Strings don't need them, since they're passed as their bytes alone.
+    pub fn needs_compound_helpers(&self) -> bool {
+        !self.config.omit_unused_helpers()
+            || self.ci.contains_async_functions()
+            || self.ci.iter_types().iter().any(|t| match t {
+                Type::String => false,
+                Type::Stream(_) => true,
+                _ => FFIType::from(t) == FFIType::RustBuffer,
             })
-        }
+    }
+}
 
-        Ok(match literal {
-            Literal::Boolean(v) => format!("{}", v),
-            Literal::String(s) => format!("\"{}\"", s),
-            Literal::Null => "null".into(),
-            Literal::EmptySequence => "listOf()".into(),
-            Literal::EmptyMap => "mapOf".into(),
-            Literal::Enum(v, type_) => format!("{}.{}", type_kt(type_)?, enum_variant_kt(v)?),
-            Literal::Int(i, radix, type_) => typed_number(
-                type_,
-                match radix {
-                    Radix::Octal => format!("{:#x}", i),
-                    Radix::Decimal => format!("{}", i),
-                    Radix::Hexadecimal => format!("{:#x}", i),
-                },
-            )?,
-            Literal::UInt(i, radix, type_) => typed_number(
-                type_,
-                match radix {
-                    Radix::Octal => format!("{:#x}", i),
-                    Radix::Decimal => format!("{}", i),
-                    Radix::Hexadecimal => format!("{:#x}", i),
-                },
-            )?,
-            Literal::Float(string, type_) => typed_number(type_, string.clone())?,
-        })
+/// The common source set's half of the bindings for a Kotlin Multiplatform project.
+///
+/// This holds an `expect` declaration for each of the functions, objects, records and enums,
+/// whose `actual` declarations are rendered by [`KotlinWrapper`] for the JVM source set, along
+/// with the plain Kotlin declarations that don't depend on the FFI, like the constants.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "CommonWrapper.kt")]
+pub struct KotlinCommonWrapper<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+}
+impl<'a> KotlinCommonWrapper<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface) -> Self {
+        let config = config.merge_with(&ci.into());
+        Self { config, ci }
     }
 
-    /// Get the idiomatic Kotlin rendering of a class name (for enums, records, errors, etc).
-    pub fn class_name_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
-        Ok(nm.to_string().to_camel_case())
+    /// Whether any optional types in the interface need to wrap their value in `Some`.
+    pub fn contains_nested_optionals(&self) -> bool {
+        contains_nested_optionals(self.ci, &self.config)
     }
 
-    /// Get the idiomatic Kotlin rendering of a function name.
-    pub fn fn_name_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
-        Ok(nm.to_string().to_mixed_case())
+    /// Whether the declarations use Kotlin's unsigned types, which need opting in to.
+    pub fn uses_unsigned_types(&self) -> bool {
+        self.config.use_unsigned_types() && self.ci.contains_unsigned_types()
     }
+}
 
-    /// Get the idiomatic Kotlin rendering of a variable name.
-    pub fn var_name_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
-        Ok(nm.to_string().to_mixed_case())
+fn contains_nested_optionals(ci: &ComponentInterface, config: &Config) -> bool {
+    ci.iter_types().iter().any(|t| match t {
+        Type::Optional(inner) => filters::is_nullable_kt(inner, config).unwrap_or(false),
+        _ => false,
+    })
+}
+
+/// A top-level declaration that gets a file of its own when the `split_files` option is enabled.
+#[derive(Debug, Clone)]
+pub enum KotlinDeclaration {
+    Enum(Enum),
+    Record(Record),
+    Object(Object),
+    CallbackInterface(CallbackInterface),
+}
+
+impl KotlinDeclaration {
+    /// List the declarations from a `ComponentInterface` that can be split out into their own files.
+    pub fn iter_declarations(ci: &ComponentInterface) -> Vec<KotlinDeclaration> {
+        let enums = ci.iter_enum_definitions().into_iter().map(Self::Enum);
+        let records = ci.iter_record_definitions().into_iter().map(Self::Record);
+        let objects = ci.iter_object_definitions().into_iter().map(Self::Object);
+        let callback_interfaces = ci
+            .iter_callback_interface_definitions()
+            .into_iter()
+            .map(Self::CallbackInterface);
+        enums
+            .chain(records)
+            .chain(objects)
+            .chain(callback_interfaces)
+            .collect()
     }
 
-    /// Get the idiomatic Kotlin rendering of an individual enum variant.
-    pub fn enum_variant_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
-        Ok(nm.to_string().to_shouty_snake_case())
+    /// The name of the declaration in the UDL.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Enum(e) => e.name(),
+            Self::Record(rec) => rec.name(),
+            Self::Object(obj) => obj.name(),
+            Self::CallbackInterface(cbi) => cbi.name(),
+        }
+    }
+
+    /// The name of the file that the declaration is written to, named after its Kotlin class.
+    ///
+    /// Declarations in a subpackage go in the matching directory below the main package's.
+    pub fn file_name(&self, config: &Config) -> String {
+        let file_name = format!(
+            "{}.kt",
+            filters::class_name_kt(&self.name(), config).unwrap()
+        );
+        match config.subpackage(self.name()) {
+            Some(subpackage) => format!("{}/{}", subpackage.replace('.', "/"), file_name),
+            None => file_name,
+        }
+    }
+
+    /// The Kotlin package that the declaration is generated into.
+    pub fn package_name(&self, config: &Config) -> String {
+        match config.subpackage(self.name()) {
+            Some(subpackage) => format!("{}.{}", config.package_name(), subpackage),
+            None => config.package_name(),
+        }
+    }
+}
+
+impl IterTypes for KotlinDeclaration {
+    fn iter_types(&self) -> TypeIterator<'_> {
+        match self {
+            Self::Enum(e) => e.iter_types(),
+            Self::Record(rec) => rec.iter_types(),
+            Self::Object(obj) => obj.iter_types(),
+            Self::CallbackInterface(cbi) => cbi.iter_types(),
+        }
+    }
+}
+
+/// A file holding a single [`KotlinDeclaration`], for use with the `split_files` config option.
+///
+/// The helpers that the declaration depends on are all `internal`, so they can live in the
+/// main file rendered by [`KotlinWrapper`] as long as both files are in the same module.
+#[derive(Template)]
+#[template(syntax = "kt", escape = "none", path = "DeclarationFile.kt")]
+pub struct KotlinDeclarationFile<'a> {
+    config: Config,
+    ci: &'a ComponentInterface,
+    decl: KotlinDeclaration,
+}
+impl<'a> KotlinDeclarationFile<'a> {
+    pub fn new(config: Config, ci: &'a ComponentInterface, decl: KotlinDeclaration) -> Self {
+        let config = config.merge_with(&ci.into());
+        Self { config, ci, decl }
+    }
+
+    /// Whether the declaration uses Kotlin's unsigned types, which need opting in to.
+    pub fn uses_unsigned_types(&self) -> bool {
+        self.config.use_unsigned_types() && self.ci.item_contains_unsigned_types(&self.decl)
+    }
+}
+
+/// Check that Kotlin bindings can be generated for a `ComponentInterface`, without generating them.
+///
+/// Types that aren't defined are already rejected when the `ComponentInterface` is built, so this
+/// runs the generator's own checks: it resolves every type to its Kotlin representation, checks
+/// that errors used as values can have a sealed class, and checks that no two types get helpers
+/// with the same name. The problems are reported in a single error, like this:
+///
+/// ```text
+/// can't generate Kotlin bindings:
+///   * Error "Failure" can only be thrown, because it's declared as a flat enum
+///   * The Kotlin helpers for record "FooBar" and record "Foo_Bar" would both be named "TypeFooBar"
+/// ```
+pub fn validate(ci: &ComponentInterface) -> Result<()> {
+    let config = Config::from(ci);
+    let mut problems = ci
+        .iter_types()
+        .into_iter()
+        .filter_map(|type_| {
+            filters::type_kt(&type_, &config)
+                .err()
+                .map(|e| format!("{:?}: {}", type_, e))
+        })
+        .collect::<Vec<_>>();
+    problems.extend(
+        check_errors_used_as_values(&config, ci)
+            .err()
+            .map(|e| e.to_string()),
+    );
+    problems.extend(check_helper_names(ci).err().map(|e| e.to_string()));
+    if !problems.is_empty() {
+        bail!(
+            "can't generate Kotlin bindings:\n{}",
+            problems
+                .iter()
+                .map(|p| format!("  * {}", p))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+/// Check that the errors which are used as values can have a sealed class alongside their exception.
+///
+/// Only the message of a flat error is sent from Rust, so they can only be thrown. The sealed
+/// class for a rich error is named after the error in the UDL, so it mustn't clash with the name
+/// of the exception class.
+pub(super) fn check_errors_used_as_values(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    for e in ci.iter_error_definitions() {
+        if !ci.is_error_used_as_value(e.name()) {
+            continue;
+        }
+        if e.is_flat() {
+            return Err(BindgenError::UnsupportedType {
+                name: e.name().to_string(),
+                message: format!(
+                    "Error {:?} can only be thrown, because it's declared as a flat enum",
+                    e.name()
+                ),
+            }
+            .into());
+        }
+        let class_name = filters::class_name_kt(&e.name(), config)?;
+        if filters::exception_name_kt(&e.name(), config)? == class_name {
+            return Err(BindgenError::NameCollision {
+                message: format!(
+                    "Error {:?} is used as a value, so its exception can't also be called {:?}",
+                    e.name(),
+                    class_name
+                ),
+                name: class_name,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Check that no two types get Kotlin helpers with the same name.
+///
+/// The helpers for each type, like `liftOptionalTypeFoo`, are named after its canonical name
+/// in camel case, which can make distinct types collide, like records named `FooBar` and
+/// `Foo_Bar`. The second type's helpers would then silently replace the first's.
+pub(super) fn check_helper_names(ci: &ComponentInterface) -> Result<()> {
+    fn describe(type_: &Type) -> String {
+        match type_ {
+            Type::Record(name) => format!("record {:?}", name),
+            Type::Enum(name) => format!("enum {:?}", name),
+            Type::Error(name) => format!("error {:?}", name),
+            Type::Object(name) => format!("object {:?}", name),
+            Type::CallbackInterface(name) => format!("callback interface {:?}", name),
+            Type::External { name, .. } | Type::Wrapped { name, .. } => {
+                format!("type {:?}", name)
+            }
+            _ => format!("type {:?}", type_.canonical_name()),
+        }
+    }
+    let mut seen: HashMap<String, Type> = HashMap::new();
+    for type_ in ci.iter_types() {
+        let name = filters::ffi_converter_name(&type_)?;
+        if let Some(other) = seen.get(&name) {
+            return Err(BindgenError::NameCollision {
+                message: format!(
+                    "The Kotlin helpers for {} and {} would both be named {:?}",
+                    describe(other),
+                    describe(&type_),
+                    name
+                ),
+                name,
+            }
+            .into());
+        }
+        seen.insert(name, type_);
+    }
+    Ok(())
+}
+
+/// Check that the `subpackages` config option only puts declarations that get a file of their
+/// own into subpackages, since each Kotlin file can only be in one package.
+pub(super) fn check_subpackages(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    let subpackages = match &config.subpackages {
+        Some(subpackages) if !subpackages.is_empty() => subpackages,
+        _ => return Ok(()),
+    };
+    if !config.split_files() {
+        bail!("`subpackages` can only be used with `split_files`, since each Kotlin file can only be in one package");
+    }
+    let declarations = KotlinDeclaration::iter_declarations(ci);
+    // Sort the names so that we always report the same one first.
+    for (name, subpackage) in subpackages.iter().collect::<BTreeMap<_, _>>() {
+        if !declarations.iter().any(|decl| decl.name() == name) {
+            match ci.get_type(name) {
+                Some(_) => bail!("Can't put {:?} in a subpackage, because only enums, records, objects and callback interfaces can be", name),
+                None => bail!("Can't put unknown type {:?} in a subpackage", name),
+            }
+        }
+        if subpackage.split('.').any(|part| part.is_empty()) {
+            bail!("Invalid subpackage {:?} for {:?}", subpackage, name);
+        }
+    }
+    Ok(())
+}
+
+/// Check that everything in the interface can be declared in the common source set of a Kotlin
+/// Multiplatform project, if that's what we're generating bindings for.
+///
+/// This is a first version, so some declarations and config options that would need nested or
+/// more involved `expect` declarations aren't supported yet, and neither are the types that we
+/// represent using classes from the JVM, which don't exist in common code.
+pub(super) fn check_multiplatform_support(config: &Config, ci: &ComponentInterface) -> Result<()> {
+    if !config.multiplatform() {
+        return Ok(());
+    }
+    let options = [
+        ("split_files", config.split_files()),
+        ("android_parcelable", config.android_parcelable()),
+        (
+            "generate_record_interfaces",
+            config.generate_record_interfaces(),
+        ),
+        (
+            "value_class_records",
+            config.value_class_records.unwrap_or(false),
+        ),
+    ];
+    if let Some((option, _)) = options.iter().find(|(_, enabled)| *enabled) {
+        bail!("`{}` isn't supported with `target = \"kmp\"` yet", option);
+    }
+    let unsupported = |name: &str, message: String| -> Result<()> {
+        Err(BindgenError::UnsupportedType {
+            name: name.to_string(),
+            message,
+        }
+        .into())
+    };
+    if let Some(e) = ci.iter_error_definitions().first() {
+        return unsupported(
+            e.name(),
+            format!(
+                "Error {:?} isn't supported with `target = \"kmp\"` yet",
+                e.name()
+            ),
+        );
+    }
+    if let Some(e) = ci.iter_enum_definitions().iter().find(|e| !e.is_flat()) {
+        return unsupported(
+            e.name(),
+            format!(
+                "Enum {:?} has associated data, which isn't supported with `target = \"kmp\"` yet",
+                e.name()
+            ),
+        );
+    }
+    if let Some(cbi) = ci.iter_callback_interface_definitions().first() {
+        return unsupported(
+            cbi.name(),
+            format!(
+                "Callback interface {:?} isn't supported with `target = \"kmp\"` yet",
+                cbi.name()
+            ),
+        );
+    }
+    for type_ in ci.iter_types() {
+        if matches!(
+            type_,
+            Type::Timestamp | Type::Duration | Type::Int128 | Type::UInt128
+        ) {
+            return unsupported(
+                &type_.canonical_name(),
+                format!(
+                    "{} can't be used with `target = \"kmp\"`, because it's represented by {}, which is only available on the JVM",
+                    type_.canonical_name(),
+                    filters::type_kt(&type_, config)?
+                ),
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Something that the generated Kotlin bindings handle in a lossy way.
+///
+/// These don't stop the bindings from being generated, but are reported alongside them so that
+/// users can find out about e.g. a value that will look different on the Kotlin side.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    /// The item the warning is about, like "function `get_count`" or "config".
+    pub item: String,
+    /// A description of what is lossy about it.
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.item, self.message)
+    }
+}
+
+/// Collect warnings about anything that the Kotlin bindings for a `ComponentInterface` would
+/// handle in a lossy way with the given config.
+///
+/// Each declaration is only checked for the types that it uses directly, so that e.g. a record
+/// with an unsigned field yields a single warning, rather than one for every function using it.
+pub fn collect_warnings(config: &Config, ci: &ComponentInterface) -> Vec<Warning> {
+    let config = config.merge_with(&ci.into());
+    let mut warnings = Vec::new();
+    if let Some(capacity) = config.rust_buffer_initial_capacity {
+        if capacity > i32::MAX as u32 {
+            warnings.push(Warning {
+                item: "config".into(),
+                message: format!(
+                    "`rust_buffer_initial_capacity` of {} is larger than Kotlin can allocate, so {} will be used instead",
+                    capacity,
+                    config.rust_buffer_initial_capacity()
+                ),
+            });
+        }
+    }
+    if !config.use_unsigned_types() {
+        for f in ci.iter_function_definitions() {
+            warnings.extend(unsigned_types_warning(
+                format!("function `{}`", f.name()),
+                f.iter_types(),
+                &config,
+            ));
+        }
+        for obj in ci.iter_object_definitions() {
+            for cons in obj.constructors() {
+                warnings.extend(unsigned_types_warning(
+                    format!("constructor `{}.{}`", obj.name(), cons.name()),
+                    cons.iter_types(),
+                    &config,
+                ));
+            }
+            for meth in obj.methods() {
+                warnings.extend(unsigned_types_warning(
+                    format!("method `{}.{}`", obj.name(), meth.name()),
+                    meth.iter_types(),
+                    &config,
+                ));
+            }
+        }
+        for rec in ci.iter_record_definitions() {
+            warnings.extend(unsigned_types_warning(
+                format!("record `{}`", rec.name()),
+                rec.iter_types(),
+                &config,
+            ));
+        }
+        for e in ci.iter_enum_definitions() {
+            warnings.extend(unsigned_types_warning(
+                format!("enum `{}`", e.name()),
+                e.iter_types(),
+                &config,
+            ));
+        }
+        for e in ci.iter_error_definitions() {
+            warnings.extend(unsigned_types_warning(
+                format!("error `{}`", e.name()),
+                e.iter_types(),
+                &config,
+            ));
+        }
+        for cbi in ci.iter_callback_interface_definitions() {
+            warnings.extend(unsigned_types_warning(
+                format!("callback interface `{}`", cbi.name()),
+                cbi.iter_types(),
+                &config,
+            ));
+        }
+        for c in ci.iter_constant_definitions() {
+            warnings.extend(unsigned_types_warning(
+                format!("constant `{}`", c.name()),
+                c.iter_types(),
+                &config,
+            ));
+        }
+    }
+    warnings
+}
+
+// Warn about any unsigned types that are represented by signed Kotlin types.
+fn unsigned_types_warning(
+    item: String,
+    types: TypeIterator<'_>,
+    config: &Config,
+) -> Option<Warning> {
+    let unsigned: BTreeSet<_> = types
+        .filter_map(|t| filters::signed_kt(t, config).map(|_| t.canonical_name()))
+        .collect();
+    if unsigned.is_empty() {
+        return None;
+    }
+    Some(Warning {
+        item,
+        message: format!(
+            "unsigned types ({}) are represented by signed Kotlin types because `use_unsigned_types` is off, so large values will appear negative",
+            unsigned.into_iter().collect::<Vec<_>>().join(", ")
+        ),
+    })
+}
+
+/// The hard keywords of the Kotlin language, which cannot be used as identifiers without escaping.
+///
+/// Soft and modifier keywords (like `value` or `data`) are fine to use as identifiers, so they
+/// don't need to be included here.
+pub const KOTLIN_HARD_KEYWORDS: &[&str] = &[
+    "as",
+    "break",
+    "class",
+    "continue",
+    "do",
+    "else",
+    "false",
+    "for",
+    "fun",
+    "if",
+    "in",
+    "interface",
+    "is",
+    "null",
+    "object",
+    "package",
+    "return",
+    "super",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typealias",
+    "typeof",
+    "val",
+    "var",
+    "when",
+    "while",
+];
+
+/// Wrap an identifier in backticks if it would otherwise collide with a Kotlin keyword.
+fn escape_keyword(nm: String) -> String {
+    if KOTLIN_HARD_KEYWORDS.contains(&nm.as_str()) {
+        format!("`{}`", nm)
+    } else {
+        nm
+    }
+}
+
+/// The askama filters used by the Kotlin templates.
+///
+/// Filters that depend on how the bindings are configured take the [`Config`] as an explicit
+/// argument, as in `{{ type_|type_kt(config) }}`. Each template merges its config with the
+/// defaults for the component once, when it's constructed, and then every filter call during
+/// the render borrows that same instance, so new options can be read by any filter without
+/// constructing anything per call.
+mod filters {
+    use super::*;
+    use std::fmt;
+
+    /// Get the Kotlin syntax for representing a given api-level `Type`.
+    pub fn type_kt(type_: &Type, config: &Config) -> Result<String, askama::Error> {
+        match config.type_alias(type_) {
+            Some(alias) => class_name_kt(&alias, config),
+            None => aliased_type_kt(type_, config),
+        }
+    }
+
+    /// Get the Kotlin type that stands for a given `Type`, spelled out even if it has a
+    /// `typealias`, as in the declaration of the alias itself.
+    ///
+    /// The types nested inside it still use their own aliases.
+    pub fn aliased_type_kt(type_: &Type, config: &Config) -> Result<String, askama::Error> {
+        if let Some((signed_type, _, _)) = signed_kt(type_, config) {
+            return Ok(signed_type.to_string());
+        }
+        Ok(match type_ {
+            // These native Kotlin types map nicely to the FFI without conversion.
+            Type::UInt8 => "UByte".to_string(),
+            Type::UInt16 => "UShort".to_string(),
+            Type::UInt32 => "UInt".to_string(),
+            Type::UInt64 => "ULong".to_string(),
+            Type::Int8 => "Byte".to_string(),
+            Type::Int16 => "Short".to_string(),
+            Type::Int32 => "Int".to_string(),
+            Type::Int64 => "Long".to_string(),
+            // Kotlin doesn't have a native 128-bit integer type, so we represent both
+            // the signed and unsigned variants using `BigInteger`.
+            Type::Int128 | Type::UInt128 => "java.math.BigInteger".to_string(),
+            Type::Float32 => "Float".to_string(),
+            Type::Float64 => "Double".to_string(),
+            // These types need conversion, and special handling for lifting/lowering.
+            Type::Boolean => "Boolean".to_string(),
+            Type::Char => "Char".to_string(),
+            Type::String => "String".to_string(),
+            Type::Bytes => "ByteArray".to_string(),
+            Type::Timestamp => "java.time.Instant".to_string(),
+            Type::Duration => "java.time.Duration".to_string(),
+            Type::Object(name) if config.generate_interfaces() => {
+                format!("{}Interface", class_name_kt(name, config)?)
+            }
+            Type::Record(name) if config.generate_record_interfaces() => {
+                format!("{}Data", class_name_kt(name, config)?)
+            }
+            Type::Enum(name)
+            | Type::Record(name)
+            | Type::Object(name)
+            | Type::Error(name)
+            | Type::CallbackInterface(name) => class_name_kt(name, config)?,
+            // External types are imported from the package of the component that defines them,
+            // and use the same `lift`/`lower`/`read`/`write` helpers as our own records and enums.
+            Type::External { name, .. } => class_name_kt(name, config)?,
+            // Custom types get a `typealias` for their configured Kotlin type, or for the builtin
+            // type that they wrap, so that we can refer to them by name.
+            Type::Wrapped { name, .. } => class_name_kt(name, config)?,
+            // Kotlin's nullable types don't nest, so if the inner type is already nullable
+            // then we need to wrap it for `Some(None)` to be distinct from `None`.
+            Type::Optional(t) if is_nullable_kt(t, config)? => {
+                format!("Some<{}>?", type_kt(t, config)?)
+            }
+            Type::Optional(t) => format!("{}?", type_kt(t, config)?),
+            Type::Sequence(t) => match config.sequence_type() {
+                SequenceType::List => format!("List<{}>", type_kt(t, config)?),
+                SequenceType::Array => match primitive_array_kt(t, config) {
+                    Some((array_type, _)) => array_type.to_string(),
+                    None => format!("Array<{}>", type_kt(t, config)?),
+                },
+            },
+            Type::Map(k, v) => format!("Map<{}, {}>", type_kt(k, config)?, type_kt(v, config)?),
+            // Fully qualified, so that the bindings only depend on kotlinx-coroutines if they use streams.
+            Type::Stream(t) => format!("kotlinx.coroutines.flow.Flow<{}>", type_kt(t, config)?),
+            Type::Tuple(ts) => format!(
+                "{}<{}>",
+                tuple_class_kt(&ts.len())?,
+                ts.iter()
+                    .map(|t| type_kt(t, config))
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+        })
+    }
+
+    /// Get the name of the Kotlin class used for tuples with the given number of elements.
+    ///
+    /// The standard library only has `Pair` and `Triple`, so for larger tuples we generate
+    /// generic data classes of our own, with fields named in the same style.
+    pub fn tuple_class_kt(arity: &usize) -> Result<String, askama::Error> {
+        Ok(match arity {
+            2 => "Pair".to_string(),
+            3 => "Triple".to_string(),
+            n => format!("Tuple{}", n),
+        })
+    }
+
+    /// Get the names of the fields of a tuple class with the given number of elements.
+    pub fn tuple_fields_kt(arity: &usize) -> Result<Vec<&'static str>, askama::Error> {
+        const NAMES: [&str; MAX_TUPLE_ELEMENTS] =
+            ["first", "second", "third", "fourth", "fifth", "sixth"];
+        Ok(NAMES[..*arity].to_vec())
+    }
+
+    /// Get the supertypes of the class generated for a record, like `: Disposable `, if any.
+    pub fn record_supertypes_kt(
+        rec: &Record,
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let mut supertypes = Vec::new();
+        if config.generate_record_interfaces() {
+            // The interface is `Disposable` itself, if needs be.
+            supertypes.push(format!("{}Data", class_name_kt(&rec.name(), config)?));
+        } else if ci.item_contains_object_references(rec) {
+            supertypes.push("Disposable".to_string());
+        }
+        if config.android_parcelable() {
+            supertypes.push("Parcelable".to_string());
+        }
+        Ok(if supertypes.is_empty() {
+            String::new()
+        } else {
+            format!(": {} ", supertypes.join(", "))
+        })
+    }
+
+    /// Get the sizes of the tuples in the interface that need a tuple class generating for them.
+    pub fn generated_tuple_arities_kt(
+        ci: &ComponentInterface,
+    ) -> Result<Vec<usize>, askama::Error> {
+        let arities: BTreeSet<usize> = ci
+            .iter_types()
+            .into_iter()
+            .filter_map(|t| match t {
+                Type::Tuple(ts) if ts.len() > 3 => Some(ts.len()),
+                _ => None,
+            })
+            .collect();
+        Ok(arities.into_iter().collect())
+    }
+
+    // Kotlin's specialized array types, which store primitive elements without boxing them,
+    // along with an expression for an empty array of each type.
+    fn primitive_array_kt(type_: &Type, config: &Config) -> Option<(&'static str, &'static str)> {
+        let unsigned = config.use_unsigned_types();
+        Some(match type_ {
+            Type::UInt8 if unsigned => ("UByteArray", "ubyteArrayOf()"),
+            Type::Int8 | Type::UInt8 => ("ByteArray", "byteArrayOf()"),
+            Type::UInt16 if unsigned => ("UShortArray", "ushortArrayOf()"),
+            Type::Int16 | Type::UInt16 => ("ShortArray", "shortArrayOf()"),
+            Type::UInt32 if unsigned => ("UIntArray", "uintArrayOf()"),
+            Type::Int32 | Type::UInt32 => ("IntArray", "intArrayOf()"),
+            Type::UInt64 if unsigned => ("ULongArray", "ulongArrayOf()"),
+            Type::Int64 | Type::UInt64 => ("LongArray", "longArrayOf()"),
+            Type::Float32 => ("FloatArray", "floatArrayOf()"),
+            Type::Float64 => ("DoubleArray", "doubleArrayOf()"),
+            Type::Boolean => ("BooleanArray", "booleanArrayOf()"),
+            Type::Char => ("CharArray", "charArrayOf()"),
+            _ => return None,
+        })
+    }
+
+    // When the `use_unsigned_types` config option is turned off, unsigned integers are represented
+    // by the signed Kotlin type of the same size. That's also how they're passed over the FFI, so
+    // we don't need any helpers to lift or lower them, only the methods to read and write them.
+    pub(super) fn signed_kt(
+        type_: &Type,
+        config: &Config,
+    ) -> Option<(&'static str, &'static str, &'static str)> {
+        if config.use_unsigned_types() {
+            return None;
+        }
+        Some(match type_ {
+            Type::UInt8 => ("Byte", "get", "putByte"),
+            Type::UInt16 => ("Short", "getShort", "putShort"),
+            Type::UInt32 => ("Int", "getInt", "putInt"),
+            Type::UInt64 => ("Long", "getLong", "putLong"),
+            _ => return None,
+        })
+    }
+
+    /// Get the Kotlin syntax for the default value of a field or argument of the given type.
+    ///
+    /// This is the same as `literal_kt`, except that an empty sequence needs to know which
+    /// type of sequence it's creating.
+    pub fn default_kt(
+        literal: &Literal,
+        type_: &Type,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(match (literal, type_) {
+            (Literal::EmptySequence, Type::Optional(t)) => default_kt(literal, t, config)?,
+            (Literal::EmptySequence, Type::Sequence(t)) => match config.sequence_type() {
+                SequenceType::List => "listOf()".to_string(),
+                SequenceType::Array => primitive_array_kt(t, config)
+                    .map_or("arrayOf()", |(_, empty)| empty)
+                    .to_string(),
+            },
+            _ => literal_kt(literal, config)?,
+        })
+    }
+
+    /// Get the Kotlin syntax for representing a given low-level `FFIType`.
+    pub fn type_ffi(type_: &FFIType) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            // Note that unsigned integers in Kotlin are currently experimental, but java.nio.ByteBuffer does not
+            // support them yet. Thus, we use the signed variants to represent both signed and unsigned
+            // types from the component API.
+            FFIType::Int8 | FFIType::UInt8 => "Byte".to_string(),
+            FFIType::Int16 | FFIType::UInt16 => "Short".to_string(),
+            FFIType::Int32 | FFIType::UInt32 => "Int".to_string(),
+            FFIType::Int64 | FFIType::UInt64 => "Long".to_string(),
+            FFIType::Float32 => "Float".to_string(),
+            FFIType::Float64 => "Double".to_string(),
+            FFIType::RustArcPtr => "Pointer".to_string(),
+            FFIType::RustBuffer => "RustBuffer.ByValue".to_string(),
+            FFIType::ForeignBytes => "ForeignBytes.ByValue".to_string(),
+            FFIType::ForeignCallback => "ForeignCallback".to_string(),
+            FFIType::FutureCallback => "FutureCallback".to_string(),
+        })
+    }
+
+    /// Get a Kotlin string literal for the given text, e.g. for a deprecation message.
+    ///
+    /// Kotlin string literals treat `$` as the start of a template expression,
+    /// so it needs escaping along with the usual suspects.
+    pub fn string_literal_kt(s: &str) -> Result<String, askama::Error> {
+        let mut escaped = String::with_capacity(s.len() + 2);
+        escaped.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '$' => escaped.push_str("\\$"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                _ => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        Ok(escaped)
+    }
+
+    pub fn literal_kt(literal: &Literal, config: &Config) -> Result<String, askama::Error> {
+        fn typed_number(type_: &Type, num_str: String) -> Result<String, askama::Error> {
+            Ok(match type_ {
+                // Bytes, Shorts and Ints can all be inferred from the type.
+                Type::Int8 | Type::Int16 | Type::Int32 => num_str,
+                Type::Int64 => format!("{}L", num_str),
+
+                Type::UInt8 | Type::UInt16 | Type::UInt32 => format!("{}u", num_str),
+                Type::UInt64 => format!("{}uL", num_str),
+
+                Type::Float32 => format!("{}f", num_str),
+                Type::Float64 => num_str,
+                _ => panic!("Unexpected literal: {} is not a number", num_str),
+            })
+        }
+
+        // Kotlin allows underscores anywhere between the digits of a literal.
+        fn decimal(num: impl fmt::Display, group: bool) -> String {
+            let num_str = num.to_string();
+            let digits = num_str.trim_start_matches('-');
+            if !group || digits.len() <= 4 {
+                return num_str;
+            }
+            let sign = &num_str[..num_str.len() - digits.len()];
+            format!("{}{}", sign, group_digits(digits))
+        }
+
+        // Splits the digits into threes from the right, so `1234567` becomes `1_234_567`.
+        fn group_digits(digits: &str) -> String {
+            let groups: Vec<&str> = digits
+                .as_bytes()
+                .rchunks(3)
+                .rev()
+                .map(|group| std::str::from_utf8(group).expect("digits are ASCII"))
+                .collect();
+            groups.join("_")
+        }
+
+        // Kotlin doesn't have negative hex literals, so we negate a positive one instead.
+        fn signed_number(
+            i: i128,
+            radix: Radix,
+            type_: &Type,
+            group: bool,
+        ) -> Result<String, askama::Error> {
+            // The magnitude of the most negative `Long` doesn't fit in a `Long` literal.
+            if *type_ == Type::Int64 && i == i64::MIN as i128 {
+                return Ok("Long.MIN_VALUE".into());
+            }
+            let sign = if i < 0 { "-" } else { "" };
+            let num_str = match radix {
+                Radix::Decimal => decimal(i, group),
+                Radix::Octal | Radix::Hexadecimal => format!("{}{:#x}", sign, i.unsigned_abs()),
+            };
+            typed_number(type_, num_str)
+        }
+
+        // WebIDL allows some spellings of floats that Kotlin doesn't, like `1.` or `Infinity`.
+        fn float_number(string: &str, type_: &Type) -> Result<String, askama::Error> {
+            let class = if *type_ == Type::Float32 {
+                "Float"
+            } else {
+                "Double"
+            };
+            Ok(match string {
+                "Infinity" => format!("{}.POSITIVE_INFINITY", class),
+                "-Infinity" => format!("{}.NEGATIVE_INFINITY", class),
+                "NaN" => format!("{}.NaN", class),
+                _ => {
+                    let mut num_str = String::with_capacity(string.len() + 1);
+                    let mut chars = string.chars().peekable();
+                    while let Some(c) = chars.next() {
+                        num_str.push(c);
+                        if c == '.' && !matches!(chars.peek(), Some(d) if d.is_ascii_digit()) {
+                            num_str.push('0');
+                        }
+                    }
+                    typed_number(type_, num_str)?
+                }
+            })
+        }
+
+        let group = config.group_literal_digits();
+        Ok(match literal {
+            Literal::Boolean(v) => format!("{}", v),
+            Literal::String(s) => string_literal_kt(s)?,
+            Literal::Null => "null".into(),
+            Literal::EmptySequence => "listOf()".into(),
+            Literal::EmptyMap => "mapOf()".into(),
+            // The `ComponentInterface` rejects literals for enums with associated data,
+            // which are rendered as a `sealed class`, so this always names an `enum class` entry.
+            Literal::Enum(v, type_ @ Type::Enum(_)) => {
+                format!(
+                    "{}.{}",
+                    type_kt(type_, config)?,
+                    enum_variant_kt(v, config)?
+                )
+            }
+            Literal::Enum(..) => unreachable!("Enum literals must have an enum type"),
+            Literal::Int(i, _, Type::Int128) => format!("java.math.BigInteger(\"{}\")", i),
+            Literal::UInt(i, _, Type::UInt128) => format!("java.math.BigInteger(\"{}\")", i),
+            // Unsigned integers that are represented by signed types need a value with the same bits.
+            Literal::UInt(i, _, Type::UInt8) if !config.use_unsigned_types() => {
+                format!("{}", *i as u8 as i8)
+            }
+            Literal::UInt(i, _, Type::UInt16) if !config.use_unsigned_types() => {
+                decimal(*i as u16 as i16, group)
+            }
+            Literal::UInt(i, _, Type::UInt32) if !config.use_unsigned_types() => {
+                decimal(*i as u32 as i32, group)
+            }
+            Literal::UInt(i, _, Type::UInt64) if !config.use_unsigned_types() => signed_number(
+                *i as u64 as i64 as i128,
+                Radix::Decimal,
+                &Type::Int64,
+                group,
+            )?,
+            Literal::Int(i, radix, type_) => signed_number(*i, *radix, type_, group)?,
+            Literal::UInt(i, radix, type_) => typed_number(
+                type_,
+                match radix {
+                    Radix::Octal => format!("{:#x}", i),
+                    Radix::Decimal => decimal(i, group),
+                    Radix::Hexadecimal => format!("{:#x}", i),
+                },
+            )?,
+            Literal::Float(string, type_) => float_number(string, type_)?,
+        })
+    }
+
+    /// Get the idiomatic Kotlin rendering of a class name (for enums, records, errors, etc).
+    ///
+    /// Individual types can be given a different name via the `type_renames` config option.
+    pub fn class_name_kt(nm: &dyn fmt::Display, config: &Config) -> Result<String, askama::Error> {
+        let nm = nm.to_string();
+        Ok(match config.type_rename(&nm) {
+            Some(renamed) => renamed.to_string(),
+            None => config.naming_convention().class_name(&nm),
+        })
+    }
+
+    /// Get the idiomatic Kotlin rendering of the subclass for a variant of an enum with fields.
+    ///
+    /// Unlike [`class_name_kt`], this ignores the `type_renames` config option, which only applies
+    /// to the types themselves.
+    pub fn variant_class_name_kt(
+        nm: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(config.naming_convention().class_name(&nm.to_string()))
+    }
+
+    /// Get the idiomatic Kotlin rendering of a top-level function name.
+    ///
+    /// Individual functions can be given a different name via the `function_renames` config option.
+    pub fn fn_name_kt(nm: &dyn fmt::Display, config: &Config) -> Result<String, askama::Error> {
+        let nm = nm.to_string();
+        Ok(escape_keyword(match config.function_rename(&nm) {
+            Some(renamed) => renamed.to_string(),
+            None => config.naming_convention().fn_name(&nm),
+        }))
+    }
+
+    /// Get the idiomatic Kotlin rendering of the name of a method or constructor of `owner`.
+    ///
+    /// Methods are renamed via the `function_renames` config option by their qualified name, like
+    /// `"Counter.new"`, so that renaming a top-level function doesn't rename methods that share
+    /// its name.
+    pub fn method_name_kt(
+        nm: &dyn fmt::Display,
+        owner: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let nm = nm.to_string();
+        Ok(escape_keyword(
+            match config.function_rename(&format!("{}.{}", owner, nm)) {
+                Some(renamed) => renamed.to_string(),
+                None => config.naming_convention().fn_name(&nm),
+            },
+        ))
+    }
+
+    /// Get the Kotlin name of an internal helper function, which is never renamed.
+    pub fn helper_fn_name_kt(
+        nm: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(escape_keyword(
+            config.naming_convention().fn_name(&nm.to_string()),
+        ))
+    }
+
+    /// Get the Kotlin `import` target for a type defined in another component.
+    ///
+    /// If the type has been renamed for use in this component, we import it under an alias.
+    pub fn external_import_kt(
+        nm: &dyn fmt::Display,
+        crate_name: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let nm = nm.to_string();
+        let remote_name = config.naming_convention().class_name(&nm);
+        let local_name = class_name_kt(&nm, config)?;
+        let package = config.external_package(&nm, &crate_name.to_string());
+        Ok(if local_name == remote_name {
+            format!("{}.{}", package, remote_name)
+        } else {
+            format!("{}.{} as {}", package, remote_name, local_name)
+        })
+    }
+
+    /// Get the Kotlin `import` targets for all of the external types used in a component.
+    ///
+    /// These are sorted by the import target rather than by type name, which gives the usual
+    /// ordering for imports even when some of the types come from configured packages.
+    pub fn external_imports_kt(
+        ci: &ComponentInterface,
+        config: &Config,
+    ) -> Result<Vec<String>, askama::Error> {
+        let mut imports = ci
+            .iter_external_types()
+            .iter()
+            .map(|(name, crate_name)| external_import_kt(name, crate_name, config))
+            .collect::<Result<Vec<_>, _>>()?;
+        imports.sort();
+        imports.extend(config.package_imports(None));
+        Ok(imports)
+    }
+
+    /// Get the Kotlin `import` targets for just the external types used by a single declaration.
+    pub fn declaration_imports_kt(
+        decl: &KotlinDeclaration,
+        config: &Config,
+    ) -> Result<Vec<String>, askama::Error> {
+        let mut imports = decl
+            .iter_types()
+            .filter_map(|t| match t {
+                Type::External { name, crate_name } => {
+                    Some(external_import_kt(name, crate_name, config))
+                }
+                _ => None,
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        imports.sort();
+        imports.dedup();
+        imports.extend(config.package_imports(config.subpackage(decl.name())));
+        Ok(imports)
+    }
+
+    /// Get the Kotlin package that a single declaration is generated into.
+    pub fn declaration_package_kt(
+        decl: &KotlinDeclaration,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(decl.package_name(config))
+    }
+
+    /// Get the idiomatic Kotlin rendering of a variable name.
+    pub fn var_name_kt(nm: &dyn fmt::Display, config: &Config) -> Result<String, askama::Error> {
+        Ok(escape_keyword(
+            config.naming_convention().var_name(&nm.to_string()),
+        ))
+    }
+
+    /// Get the idiomatic Kotlin rendering of an individual enum variant.
+    pub fn enum_variant_kt(
+        nm: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        Ok(config.naming_convention().enum_variant(&nm.to_string()))
+    }
+
+    /// Get the idiomatic Kotlin rendering of a constant name.
+    pub fn const_name_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+        Ok(nm.to_string().to_shouty_snake_case())
     }
 
     /// Get the idiomatic Kotlin rendering of an exception name
     ///
-    /// This replaces "Error" at the end of the name with "Exception".  Rust code typically uses
-    /// "Error" for any type of error but in the Java world, "Error" means a non-recoverable error
-    /// and is distinguished from an "Exception".
-    pub fn exception_name_kt(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
+    /// This replaces "Error" at the end of the name with "Exception", as described in
+    /// [`NamingConvention::exception_name`].
+    ///
+    /// Components that use other naming schemes for their errors can customize this via the
+    /// `exception_suffix_rules` config option.
+    pub fn exception_name_kt(
+        nm: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let name = nm.to_string();
+        match config.type_rename(&name) {
+            Some(renamed) => Ok(renamed.to_string()),
+            None => variant_exception_name_kt(&name, config),
+        }
+    }
+
+    /// Get the idiomatic Kotlin rendering of the exception for a variant of an error.
+    ///
+    /// This follows the same `exception_suffix_rules` as [`exception_name_kt`], but ignores the
+    /// `type_renames` config option, which only applies to the errors themselves.
+    pub fn variant_exception_name_kt(
+        nm: &dyn fmt::Display,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
         let name = nm.to_string();
-        match name.strip_suffix("Error") {
-            None => Ok(name),
-            Some(stripped) => {
-                let mut kt_exc_name = stripped.to_owned();
-                kt_exc_name.push_str("Exception");
-                Ok(kt_exc_name)
+        for (suffix, replacement) in config.exception_suffix_rules() {
+            if let Some(stripped) = name.strip_suffix(suffix.as_str()) {
+                return Ok(format!("{}{}", stripped, replacement));
             }
         }
+        Ok(config.naming_convention().exception_name(&name))
     }
 
-    /// Get a Kotlin expression for lowering a value into something we can pass over the FFI.
+    /// Get a KDoc comment containing the given docstring.
     ///
-    /// Where possible, this delegates to a `lower()` method on the type itself, but special
-    /// handling is required for some compound data types.
-    pub fn lower_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
-        let nm = var_name_kt(nm)?;
+    /// Each line after the first is prefixed with `indent`, and the comment ends with a newline
+    /// and `indent` so that it can be placed directly before the item it documents. Kotlin block
+    /// comments nest, so we escape anything in the text that would open or close a comment.
+    pub fn docstring_kt(docstring: &str, indent: &str) -> Result<String, askama::Error> {
+        let mut kdoc = String::from("/**\n");
+        for line in docstring.lines() {
+            kdoc.push_str(indent);
+            kdoc.push_str(" *");
+            if !line.is_empty() {
+                kdoc.push(' ');
+                kdoc.push_str(&line.replace("*/", "*&#47;").replace("/*", "/&#42;"));
+            }
+            kdoc.push('\n');
+        }
+        kdoc.push_str(indent);
+        kdoc.push_str(" */\n");
+        kdoc.push_str(indent);
+        Ok(kdoc)
+    }
+
+    /// Get a KDoc comment for a function or method, with `@param` and `@return` tags.
+    ///
+    /// We generate a `@param` tag for each argument in the signature, and a `@return` tag
+    /// unless the function returns `Unit`. Any of these tags that are already in the docstring
+    /// are moved into place, so that the description written in the UDL is kept.
+    pub fn function_docstring_kt(
+        docstring: &str,
+        arguments: &[&Argument],
+        has_return: &bool,
+        indent: &str,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let mut body: Vec<&str> = Vec::new();
+        let mut params: Vec<(&str, Vec<&str>)> = Vec::new();
+        let mut returns: Option<Vec<&str>> = None;
+        // Lines following a tag are part of its description, up to a blank line or another tag.
+        let mut in_param = false;
+        let mut in_return = false;
+        for line in docstring.lines() {
+            let trimmed = line.trim();
+            if let Some(rest) = trimmed.strip_prefix("@param ") {
+                let mut parts = rest.trim_start().splitn(2, ' ');
+                let name = parts.next().unwrap_or_default();
+                params.push((name, parts.next().into_iter().map(str::trim).collect()));
+                in_param = true;
+                in_return = false;
+            } else if trimmed == "@return" || trimmed.starts_with("@return ") {
+                let description = trimmed["@return".len()..].trim();
+                returns = Some(if description.is_empty() {
+                    vec![]
+                } else {
+                    vec![description]
+                });
+                in_param = false;
+                in_return = true;
+            } else if trimmed.is_empty() || trimmed.starts_with('@') {
+                body.push(line);
+                in_param = false;
+                in_return = false;
+            } else if in_param {
+                params.last_mut().unwrap().1.push(trimmed);
+            } else if in_return {
+                returns.as_mut().unwrap().push(trimmed);
+            } else {
+                body.push(line);
+            }
+        }
+        while matches!(body.last(), Some(line) if line.trim().is_empty()) {
+            body.pop();
+        }
+
+        let mut tags = Vec::new();
+        for arg in arguments {
+            let kt_name = var_name_kt(&arg.name(), config)?;
+            let position = params
+                .iter()
+                .position(|(name, _)| *name == arg.name() || *name == kt_name);
+            let description = position.map(|i| params.remove(i).1).unwrap_or_default();
+            tags.push(format!("@param {} {}", kt_name, description.join(" ")));
+        }
+        for (name, description) in params {
+            tags.push(format!("@param {} {}", name, description.join(" ")));
+        }
+        if *has_return || returns.is_some() {
+            tags.push(format!("@return {}", returns.unwrap_or_default().join(" ")));
+        }
+
+        let mut lines: Vec<String> = body.into_iter().map(String::from).collect();
+        if !lines.is_empty() && !tags.is_empty() {
+            lines.push(String::new());
+        }
+        lines.extend(tags.into_iter().map(|tag| tag.trim_end().to_string()));
+        docstring_kt(&lines.join("\n"), indent)
+    }
+
+    /// Get the `require(...)` checks for the constraints on a record field, from its `[Validate]`
+    /// attributes, which go in the record's `init` block.
+    pub fn requirements_kt(field: &Field, config: &Config) -> Result<Vec<String>, askama::Error> {
+        let name = var_name_kt(&field.name(), config)?;
+        // The bound as it was written in the UDL, without any Kotlin type suffix.
+        fn bound_text(literal: &Literal) -> String {
+            match literal {
+                Literal::Int(i, ..) => i.to_string(),
+                Literal::UInt(i, ..) => i.to_string(),
+                Literal::Float(s, _) => s.clone(),
+                _ => unreachable!("Constraint bounds are always numbers"),
+            }
+        }
+        field
+            .constraints()
+            .iter()
+            .map(|constraint| {
+                let (check, message) = match constraint {
+                    Constraint::NonEmpty => (
+                        format!("{}.isNotEmpty()", name),
+                        format!("{} must not be empty", name),
+                    ),
+                    Constraint::Min(bound) => (
+                        format!("{} >= {}", name, literal_kt(bound, config)?),
+                        format!("{} must be at least {}", name, bound_text(bound)),
+                    ),
+                    Constraint::Max(bound) => (
+                        format!("{} <= {}", name, literal_kt(bound, config)?),
+                        format!("{} must be at most {}", name, bound_text(bound)),
+                    ),
+                };
+                // The constraints only apply to an optional field when it has a value.
+                let check = if field.is_optional() {
+                    format!("{} == null || {}", name, check)
+                } else {
+                    check
+                };
+                Ok(format!(
+                    "require({}) {{ {} }}",
+                    check,
+                    string_literal_kt(&message)?
+                ))
+            })
+            .collect()
+    }
+
+    /// Whether values of the given type can be `null` in Kotlin.
+    pub fn is_nullable_kt(type_: &Type, config: &Config) -> Result<bool, askama::Error> {
+        Ok(matches!(passed_as(type_, config), Type::Optional(_)))
+    }
+
+    /// Get the Kotlin syntax for the low-level type that a given api-level `Type` is lowered into.
+    pub fn ffi_type_kt(type_: &Type) -> Result<String, askama::Error> {
+        type_ffi(&FFIType::from(type_))
+    }
+
+    /// Get the name that identifies the Kotlin helpers for passing a type over the FFI.
+    ///
+    /// This is derived from the type's canonical name, so it's unique for each type and can
+    /// be used to name e.g. the `lift{}` and `lower{}` helper functions for compound types.
+    pub fn ffi_converter_name(type_: &Type) -> Result<String, askama::Error> {
+        // Note that this deliberately ignores any `type_renames`, since it only names internal helpers.
+        Ok(type_.canonical_name().to_camel_case())
+    }
+
+    /// The different shapes of Kotlin code that we generate for passing a type over the FFI.
+    enum Converter {
+        /// Static methods on an `{ffi_converter_name}Internals` object.
+        Internals(String),
+        /// Top-level `lift{ffi_converter_name}()` etc functions.
+        Functions(String),
+        /// Methods on the type itself, and on its companion object.
+        Methods,
+    }
+
+    fn converter(type_: &Type) -> Result<Converter, askama::Error> {
         Ok(match type_ {
-            Type::CallbackInterface(_) => format!(
-                "{}Internals.lower({})",
-                class_name_kt(&type_.canonical_name())?,
-                nm,
-            ),
+            Type::CallbackInterface(_) => Converter::Internals(ffi_converter_name(type_)?),
             Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_)
+            | Type::Map(_, _)
+            | Type::Stream(_)
+            | Type::Tuple(_)
+            | Type::Bytes
             | Type::Timestamp
-            | Type::Duration => {
-                format!("lower{}({})", class_name_kt(&type_.canonical_name())?, nm,)
+            | Type::Duration
+            | Type::Int128
+            | Type::UInt128
+            | Type::Wrapped { .. } => Converter::Functions(ffi_converter_name(type_)?),
+            _ => Converter::Methods,
+        })
+    }
+
+    /// Get the type whose helpers we use for passing a given type over the FFI.
+    ///
+    /// Custom types without a configured converter are just their builtin type in Kotlin,
+    /// so they're passed over the FFI in exactly the same way.
+    fn passed_as<'a>(type_: &'a Type, config: &Config) -> &'a Type {
+        match type_ {
+            Type::Wrapped { name, prim } if config.custom_type(name).is_none() => {
+                passed_as(prim, config)
+            }
+            _ => type_,
+        }
+    }
+
+    /// Get the name of the Kotlin class that implements the `lift`/`lower`/`read`/`write` methods for a type.
+    ///
+    /// This is the same as `type_kt`, except for objects and records that are referred to by
+    /// their interface.
+    fn class_kt(type_: &Type, config: &Config) -> Result<String, askama::Error> {
+        match type_ {
+            Type::Object(name) | Type::Record(name) => class_name_kt(name, config),
+            _ => type_kt(type_, config),
+        }
+    }
+
+    /// Get a Kotlin expression for a value that we can call the `lower`/`write` methods on.
+    ///
+    /// Objects and records that are referred to by their interface have to be cast to the concrete
+    /// class, which will fail if someone tries to pass another implementation over the FFI.
+    fn concrete_kt(nm: &str, type_: &Type, config: &Config) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::Object(_) if config.generate_interfaces() => {
+                format!("({} as {})", nm, class_kt(type_, config)?)
+            }
+            Type::Record(_) if config.generate_record_interfaces() => {
+                format!("({} as {})", nm, class_kt(type_, config)?)
             }
-            _ => format!("{}.lower()", nm),
+            _ => nm.to_string(),
+        })
+    }
+
+    /// Get a Kotlin expression for lowering a value into something we can pass over the FFI.
+    ///
+    /// Where possible, this delegates to a `lower()` method on the type itself, but special
+    /// handling is required for some compound data types.
+    pub fn lower_kt(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        let nm = var_name_kt(nm, config)?;
+        let type_ = passed_as(type_, config);
+        if signed_kt(type_, config).is_some() {
+            return Ok(nm);
+        }
+        Ok(match converter(type_)? {
+            Converter::Internals(name) => format!("{}Internals.lower({})", name, nm),
+            Converter::Functions(name) => format!("lower{}({})", name, nm),
+            Converter::Methods => format!("{}.lower()", concrete_kt(&nm, type_, config)?),
         })
     }
 
@@ -233,26 +2094,19 @@ mod filters {
         nm: &dyn fmt::Display,
         target: &dyn fmt::Display,
         type_: &Type,
+        config: &Config,
     ) -> Result<String, askama::Error> {
-        let nm = var_name_kt(nm)?;
-        Ok(match type_ {
-            Type::CallbackInterface(_) => format!(
-                "{}Internals.write({}, {})",
-                class_name_kt(&type_.canonical_name())?,
-                nm,
-                target,
-            ),
-            Type::Optional(_)
-            | Type::Sequence(_)
-            | Type::Map(_)
-            | Type::Timestamp
-            | Type::Duration => format!(
-                "write{}({}, {})",
-                class_name_kt(&type_.canonical_name())?,
-                nm,
-                target,
-            ),
-            _ => format!("{}.write({})", nm, target),
+        // Note that `nm` is a Kotlin expression here rather than a variable name, so it's the
+        // caller's responsibility to have passed any identifiers in it through `var_name_kt`.
+        let nm = nm.to_string();
+        let type_ = passed_as(type_, config);
+        if let Some((_, _, put)) = signed_kt(type_, config) {
+            return Ok(format!("{}.{}({})", target, put, nm));
+        }
+        Ok(match converter(type_)? {
+            Converter::Internals(name) => format!("{}Internals.write({}, {})", name, nm, target),
+            Converter::Functions(name) => format!("write{}({}, {})", name, nm, target),
+            Converter::Methods => format!("{}.write({})", concrete_kt(&nm, type_, config)?, target),
         })
     }
 
@@ -260,20 +2114,20 @@ mod filters {
     ///
     /// Where possible, this delegates to a `lift()` method on the type itself, but special
     /// handling is required for some compound data types.
-    pub fn lift_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+    pub fn lift_kt(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
         let nm = nm.to_string();
-        Ok(match type_ {
-            Type::CallbackInterface(_) => format!(
-                "{}Internals.lift({})",
-                class_name_kt(&type_.canonical_name())?,
-                nm,
-            ),
-            Type::Optional(_)
-            | Type::Sequence(_)
-            | Type::Map(_)
-            | Type::Timestamp
-            | Type::Duration => format!("lift{}({})", class_name_kt(&type_.canonical_name())?, nm),
-            _ => format!("{}.lift({})", type_kt(type_)?, nm),
+        let type_ = passed_as(type_, config);
+        if signed_kt(type_, config).is_some() {
+            return Ok(nm);
+        }
+        Ok(match converter(type_)? {
+            Converter::Internals(name) => format!("{}Internals.lift({})", name, nm),
+            Converter::Functions(name) => format!("lift{}({})", name, nm),
+            Converter::Methods => format!("{}.lift({})", class_kt(type_, config)?, nm),
         })
     }
 
@@ -281,20 +2135,834 @@ mod filters {
     ///
     /// Where possible, this delegates to a `read()` method on the type itself, but special
     /// handling is required for some compound data types.
-    pub fn read_kt(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
+    pub fn read_kt(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
         let nm = nm.to_string();
-        Ok(match type_ {
-            Type::CallbackInterface(_) => format!(
-                "{}Internals.read({})",
-                class_name_kt(&type_.canonical_name())?,
-                nm,
-            ),
-            Type::Optional(_)
-            | Type::Sequence(_)
-            | Type::Map(_)
-            | Type::Timestamp
-            | Type::Duration => format!("read{}({})", class_name_kt(&type_.canonical_name())?, nm),
-            _ => format!("{}.read({})", type_kt(type_)?, nm),
+        let type_ = passed_as(type_, config);
+        if let Some((_, get, _)) = signed_kt(type_, config) {
+            return Ok(format!("{}.{}()", nm, get));
+        }
+        Ok(match converter(type_)? {
+            Converter::Internals(name) => format!("{}Internals.read({})", name, nm),
+            Converter::Functions(name) => format!("read{}({})", name, nm),
+            Converter::Methods => format!("{}.read({})", class_kt(type_, config)?, nm),
         })
     }
+
+    /// Get a Kotlin expression for reading an object that Rust has lent to a callback
+    /// interface method, rather than transferring a reference to it.
+    pub fn read_borrowed_kt(
+        nm: &dyn fmt::Display,
+        type_: &Type,
+        config: &Config,
+    ) -> Result<String, askama::Error> {
+        match type_ {
+            Type::Object(_) => Ok(format!("{}.readBorrowed({})", class_kt(type_, config)?, nm)),
+            _ => unreachable!("Only objects can be lent to callbacks"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::filters::*;
+    use super::*;
+
+    #[test]
+    fn test_wrapper_uses_component_defaults_for_missing_config() {
+        let ci = ComponentInterface::from_webidl("namespace example {};").unwrap();
+        let wrapper = KotlinWrapper::new(Config::default(), &ci);
+        assert_eq!(wrapper.config.cdylib_name(), "uniffi_example");
+        assert_eq!(wrapper.config.package_name(), "uniffi.example");
+        let config = Config {
+            cdylib_name: Some("custom".into()),
+            ..Config::default()
+        };
+        let wrapper = KotlinWrapper::new(config, &ci);
+        assert_eq!(wrapper.config.cdylib_name(), "custom");
+        assert_eq!(wrapper.config.package_name(), "uniffi.example");
+    }
+
+    #[test]
+    fn test_records_containing_objects_cannot_be_parcelable() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test { Holder hold(); };
+                dictionary Holder { sequence<Resource> resources; };
+                interface Resource {};
+            "#,
+        )
+        .unwrap();
+        let config: Config = toml::from_str("android_parcelable = true").unwrap();
+        let err = crate::bindings::generate_kotlin_bindings(&config, &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Record \"Holder\" can't be Parcelable, because it contains an object"
+        );
+    }
+
+    #[test]
+    fn test_lossy_conversions_are_reported_as_warnings() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test {
+                    u64 total(sequence<u32> values);
+                    string describe(Point p);
+                };
+                dictionary Point { u8 x; i32 y; };
+            "#,
+        )
+        .unwrap();
+        let (_, warnings) =
+            crate::bindings::generate_kotlin_bindings(&Config::default(), &ci).unwrap();
+        assert!(warnings.is_empty());
+
+        let config: Config =
+            toml::from_str("use_unsigned_types = false\nrust_buffer_initial_capacity = 4294967295")
+                .unwrap();
+        let (_, warnings) = crate::bindings::generate_kotlin_bindings(&config, &ci).unwrap();
+        let items: Vec<_> = warnings.iter().map(|w| w.item.as_str()).collect();
+        // `describe` only uses unsigned types via the record, which gets a warning of its own.
+        assert_eq!(items, vec!["config", "function `total`", "record `Point`"]);
+        assert!(warnings[1].message.contains("(u32, u64)"));
+        assert!(warnings[0]
+            .to_string()
+            .starts_with("config: `rust_buffer_initial_capacity`"));
+    }
+
+    #[test]
+    fn test_value_class_records_only_wrap_a_single_non_optional_field() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test {};
+                dictionary UserId { u64 id; };
+                dictionary MaybeUserId { u64? id; };
+                dictionary Point { u32 x; u32 y; };
+            "#,
+        )
+        .unwrap();
+        let config = Config {
+            value_class_records: Some(true),
+            ..Config::from(&ci)
+        };
+        let user_id = ci.get_record_definition("UserId").unwrap();
+        assert!(config.render_as_value_class(user_id));
+        assert!(!config.render_as_value_class(ci.get_record_definition("MaybeUserId").unwrap()));
+        assert!(!config.render_as_value_class(ci.get_record_definition("Point").unwrap()));
+        assert!(!Config::from(&ci).render_as_value_class(user_id));
+    }
+
+    #[test]
+    fn test_errors_used_as_values_must_be_rich_and_have_a_distinct_exception_name() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test { sequence<FlatError> failures(); };
+                [Error]
+                enum FlatError { "Oops" };
+            "#,
+        )
+        .unwrap();
+        let err = crate::bindings::generate_kotlin_bindings(&Config::default(), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error \"FlatError\" can only be thrown, because it's declared as a flat enum"
+        );
+
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test { Failure? last_failure(); };
+                [Error]
+                interface Failure { Timeout(); };
+            "#,
+        )
+        .unwrap();
+        let err = crate::bindings::generate_kotlin_bindings(&Config::default(), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error \"Failure\" is used as a value, so its exception can't also be called \"Failure\""
+        );
+    }
+
+    #[test]
+    fn test_types_whose_helpers_would_share_a_name_are_rejected() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test { FooBar first(); Foo_Bar second(); };
+                dictionary FooBar { u32 a; };
+                dictionary Foo_Bar { u32 b; };
+            "#,
+        )
+        .unwrap();
+        let err = crate::bindings::generate_kotlin_bindings(&Config::default(), &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The Kotlin helpers for record \"FooBar\" and record \"Foo_Bar\" would both be named \"TypeFooBar\""
+        );
+        assert!(matches!(
+            err.downcast_ref::<BindgenError>(),
+            Some(BindgenError::NameCollision { name, .. }) if name == "TypeFooBar"
+        ));
+    }
+
+    #[test]
+    fn test_split_files_must_have_distinct_names() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test {};
+                dictionary Test { u32 value; };
+            "#,
+        )
+        .unwrap();
+        let config: Config = toml::from_str("split_files = true").unwrap();
+        let err = crate::bindings::kotlin::generate_binding_files(&config, &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Can't split bindings into files: Test.kt would be generated twice"
+        );
+        assert!(matches!(
+            err.downcast_ref::<BindgenError>(),
+            Some(BindgenError::NameCollision { name, .. }) if name == "Test.kt"
+        ));
+    }
+
+    #[test]
+    fn test_kmp_rejects_what_it_cant_declare_in_common_code_yet() {
+        let config: Config = toml::from_str("target = \"kmp\"").unwrap();
+        let generate = |udl: &str| {
+            let ci = ComponentInterface::from_webidl(udl).unwrap();
+            crate::bindings::kotlin::generate_binding_files(&config, &ci)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            generate(
+                r#"
+                    namespace test { [Throws=Oops] void fail(); };
+                    [Error] enum Oops { "Bad" };
+                "#
+            ),
+            "Error \"Oops\" isn't supported with `target = \"kmp\"` yet"
+        );
+        assert_eq!(
+            generate(
+                r#"
+                    namespace test {};
+                    [Enum] interface Shape { Square(double side); };
+                "#
+            ),
+            "Enum \"Shape\" has associated data, which isn't supported with `target = \"kmp\"` yet"
+        );
+        assert_eq!(
+            generate("namespace test { timestamp now(); };"),
+            "Timestamp can't be used with `target = \"kmp\"`, because it's represented by java.time.Instant, which is only available on the JVM"
+        );
+
+        let ci = ComponentInterface::from_webidl("namespace test {};").unwrap();
+        let config: Config = toml::from_str("target = \"kmp\"\nsplit_files = true").unwrap();
+        let err = crate::bindings::kotlin::generate_binding_files(&config, &ci).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`split_files` isn't supported with `target = \"kmp\"` yet"
+        );
+    }
+
+    #[test]
+    fn test_validate_accepts_custom_types() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test { Handle make_handle(Token token); };
+                [Wrapped]
+                typedef u64 Handle;
+                [Custom]
+                typedef string Token;
+            "#,
+        )
+        .unwrap();
+        assert!(validate(&ci).is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_every_problem() {
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test { Failure? last_failure(); FooBar first(); Foo_Bar second(); };
+                [Error]
+                enum Failure { "Timeout" };
+                dictionary FooBar { u32 a; };
+                dictionary Foo_Bar { u32 b; };
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            validate(&ci).unwrap_err().to_string(),
+            "can't generate Kotlin bindings:\n  \
+             * Error \"Failure\" can only be thrown, because it's declared as a flat enum\n  \
+             * The Kotlin helpers for record \"FooBar\" and record \"Foo_Bar\" would both be named \"TypeFooBar\""
+        );
+    }
+
+    #[test]
+    fn test_exception_name_kt_applies_suffix_rules_in_order() {
+        let config: Config = toml::from_str(
+            r#"
+                exception_suffix_rules = [["Failure", "Exception"], ["Problem", "Exception"], ["Error", "Err"]]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            exception_name_kt(&"FooFailure", &config).unwrap(),
+            "FooException"
+        );
+        assert_eq!(
+            exception_name_kt(&"BarProblem", &config).unwrap(),
+            "BarException"
+        );
+        assert_eq!(exception_name_kt(&"BazError", &config).unwrap(), "BazErr");
+        assert_eq!(exception_name_kt(&"Qux", &config).unwrap(), "Qux");
+        // Without any rules, we use the default behaviour.
+        let config = Config::default();
+        assert_eq!(
+            exception_name_kt(&"FooFailure", &config).unwrap(),
+            "FooFailure"
+        );
+        assert_eq!(
+            exception_name_kt(&"BazError", &config).unwrap(),
+            "BazException"
+        );
+    }
+
+    #[test]
+    fn test_renames_only_apply_to_what_they_name() {
+        let config: Config = toml::from_str(
+            r#"
+                type_renames = { Circle = "Round", ParseError = "BadInput" }
+                function_renames = { new = "create", "Counter.new" = "make" }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(class_name_kt(&"Circle", &config).unwrap(), "Round");
+        assert_eq!(variant_class_name_kt(&"Circle", &config).unwrap(), "Circle");
+        assert_eq!(
+            exception_name_kt(&"ParseError", &config).unwrap(),
+            "BadInput"
+        );
+        assert_eq!(
+            variant_exception_name_kt(&"ParseError", &config).unwrap(),
+            "ParseException"
+        );
+        assert_eq!(fn_name_kt(&"new", &config).unwrap(), "create");
+        assert_eq!(method_name_kt(&"new", &"Counter", &config).unwrap(), "make");
+        assert_eq!(method_name_kt(&"new", &"Gauge", &config).unwrap(), "new");
+    }
+
+    #[test]
+    fn test_ffi_converter_names_are_used_consistently() {
+        let config = Config::default();
+        let type_ = Type::Optional(Box::new(Type::Record("Point".into())));
+        let name = ffi_converter_name(&type_).unwrap();
+        assert_eq!(
+            lower_kt(&"v", &type_, &config).unwrap(),
+            format!("lower{}(v)", name)
+        );
+        assert_eq!(
+            lift_kt(&"v", &type_, &config).unwrap(),
+            format!("lift{}(v)", name)
+        );
+        let type_ = Type::CallbackInterface("Logger".into());
+        let name = ffi_converter_name(&type_).unwrap();
+        assert_eq!(
+            write_kt(&"v", &"buf", &type_, &config).unwrap(),
+            format!("{}Internals.write(v, buf)", name)
+        );
+        let type_ = Type::Record("Point".into());
+        assert_eq!(read_kt(&"buf", &type_, &config).unwrap(), "Point.read(buf)");
+    }
+
+    #[test]
+    fn test_literal_kt_renders_non_numeric_literals() {
+        let config = Config::default();
+        assert_eq!(
+            literal_kt(&Literal::Boolean(true), &config).unwrap(),
+            "true"
+        );
+        assert_eq!(literal_kt(&Literal::Null, &config).unwrap(), "null");
+        assert_eq!(
+            literal_kt(&Literal::EmptySequence, &config).unwrap(),
+            "listOf()"
+        );
+        assert_eq!(literal_kt(&Literal::EmptyMap, &config).unwrap(), "mapOf()");
+        assert_eq!(
+            literal_kt(
+                &Literal::Enum("second_choice".into(), Type::Enum("choice".into())),
+                &config
+            )
+            .unwrap(),
+            "Choice.SECOND_CHOICE"
+        );
+        assert_eq!(
+            literal_kt(&Literal::UInt(0o17, Radix::Octal, Type::UInt32), &config).unwrap(),
+            "0xfu"
+        );
+    }
+
+    #[test]
+    fn test_literal_kt_escapes_strings() {
+        let config = Config::default();
+        assert_eq!(
+            literal_kt(&Literal::String("hello".into()), &config).unwrap(),
+            "\"hello\""
+        );
+        assert_eq!(
+            literal_kt(&Literal::String("say \"${name}\"\n".into()), &config).unwrap(),
+            "\"say \\\"\\${name}\\\"\\n\""
+        );
+        assert_eq!(
+            literal_kt(&Literal::String("C:\\path".into()), &config).unwrap(),
+            "\"C:\\\\path\""
+        );
+    }
+
+    #[test]
+    fn test_literal_kt_128_bit_integers() {
+        let config = Config::default();
+        assert_eq!(
+            literal_kt(
+                &Literal::UInt(std::u128::MAX, Radix::Hexadecimal, Type::UInt128),
+                &config
+            )
+            .unwrap(),
+            "java.math.BigInteger(\"340282366920938463463374607431768211455\")"
+        );
+        assert_eq!(
+            literal_kt(&Literal::Int(-1, Radix::Decimal, Type::Int128), &config).unwrap(),
+            "java.math.BigInteger(\"-1\")"
+        );
+    }
+
+    #[test]
+    fn test_literal_kt_keeps_hex_and_can_group_decimal_digits() {
+        let config = Config::default();
+        assert_eq!(
+            literal_kt(
+                &Literal::UInt(0xFF00, Radix::Hexadecimal, Type::UInt32),
+                &config
+            )
+            .unwrap(),
+            "0xff00u"
+        );
+        assert_eq!(
+            literal_kt(&Literal::Int(1000000, Radix::Decimal, Type::Int32), &config).unwrap(),
+            "1000000"
+        );
+
+        let config: Config = toml::from_str("group_literal_digits = true").unwrap();
+        let int = |i: i128, type_: Type| {
+            literal_kt(&Literal::Int(i, Radix::Decimal, type_), &config).unwrap()
+        };
+        assert_eq!(int(1000, Type::Int32), "1000");
+        assert_eq!(int(10000, Type::Int32), "10_000");
+        assert_eq!(int(-1234567, Type::Int32), "-1_234_567");
+        assert_eq!(
+            int(i64::MAX as i128, Type::Int64),
+            "9_223_372_036_854_775_807L"
+        );
+        assert_eq!(
+            literal_kt(
+                &Literal::UInt(4294967295, Radix::Decimal, Type::UInt32),
+                &config
+            )
+            .unwrap(),
+            "4_294_967_295u"
+        );
+        // Hex literals are left as they are, as are the strings for 128-bit integers.
+        assert_eq!(
+            literal_kt(
+                &Literal::UInt(0xFFFF0000, Radix::Hexadecimal, Type::UInt32),
+                &config
+            )
+            .unwrap(),
+            "0xffff0000u"
+        );
+        assert_eq!(
+            literal_kt(
+                &Literal::Int(-100000, Radix::Decimal, Type::Int128),
+                &config
+            )
+            .unwrap(),
+            "java.math.BigInteger(\"-100000\")"
+        );
+    }
+
+    #[test]
+    fn test_literal_kt_special_floats() {
+        let config = Config::default();
+        let float = |v: &str, type_: Type| {
+            literal_kt(&Literal::Float(v.to_string(), type_), &config).unwrap()
+        };
+        assert_eq!(float("NaN", Type::Float64), "Double.NaN");
+        assert_eq!(float("Infinity", Type::Float64), "Double.POSITIVE_INFINITY");
+        assert_eq!(
+            float("-Infinity", Type::Float64),
+            "Double.NEGATIVE_INFINITY"
+        );
+        assert_eq!(float("NaN", Type::Float32), "Float.NaN");
+        assert_eq!(float("Infinity", Type::Float32), "Float.POSITIVE_INFINITY");
+        assert_eq!(float("-Infinity", Type::Float32), "Float.NEGATIVE_INFINITY");
+        // Finite values are plain literals, with a suffix to make them a `Float` if needed.
+        assert_eq!(float("1.5", Type::Float64), "1.5");
+        assert_eq!(float("1.5", Type::Float32), "1.5f");
+    }
+
+    #[test]
+    fn test_docstring_kt_indents_multiline_docstrings() {
+        assert_eq!(docstring_kt("Hello.", "").unwrap(), "/**\n * Hello.\n */\n");
+        assert_eq!(
+            docstring_kt("First line.\n\n  Indented.", "    ").unwrap(),
+            "/**\n     * First line.\n     *\n     *   Indented.\n     */\n    "
+        );
+    }
+
+    #[test]
+    fn test_function_docstring_kt_generates_param_and_return_tags() {
+        let config = Config::default();
+        let ci = ComponentInterface::from_webidl(
+            r#"
+                namespace test {
+                    /// Look up a user.
+                    ///
+                    /// @return the user's name, if they exist.
+                    /// @see lookupAll
+                    /// @param user_id the unique ID
+                    ///     of the user.
+                    string? lookup(u32 user_id, boolean include_deleted);
+                    /// Forget everything.
+                    void reset();
+                };
+            "#,
+        )
+        .unwrap();
+        let lookup = ci.get_function_definition("lookup").unwrap();
+        assert_eq!(
+            function_docstring_kt(
+                lookup.docstring().unwrap(),
+                &lookup.arguments(),
+                &true,
+                "",
+                &config
+            )
+            .unwrap(),
+            "/**\n * Look up a user.\n *\n * @see lookupAll\n *\n \
+             * @param userId the unique ID of the user.\n * @param includeDeleted\n \
+             * @return the user's name, if they exist.\n */\n"
+        );
+        let reset = ci.get_function_definition("reset").unwrap();
+        assert_eq!(
+            function_docstring_kt(
+                reset.docstring().unwrap(),
+                &reset.arguments(),
+                &false,
+                "",
+                &config
+            )
+            .unwrap(),
+            "/**\n * Forget everything.\n */\n"
+        );
+    }
+
+    #[test]
+    fn test_docstring_kt_escapes_comment_delimiters() {
+        assert_eq!(
+            docstring_kt("Matches /* and */ in text.", "").unwrap(),
+            "/**\n * Matches /&#42; and *&#47; in text.\n */\n"
+        );
+    }
+
+    // Property tests for `literal_kt`, which check that the Kotlin it generates for arbitrary
+    // literals is valid Kotlin syntax with the same type and value as the original literal.
+    // We don't have a Kotlin compiler to hand, so `eval_kt` understands just enough of the
+    // language to evaluate the expressions that `literal_kt` can produce.
+    mod literal_kt_properties {
+        use super::*;
+        use proptest::prelude::*;
+
+        /// The value of a Kotlin expression, along with enough detail about its type to check it.
+        #[derive(Debug, PartialEq)]
+        enum KtValue {
+            Boolean(bool),
+            String(String),
+            /// An integer, along with the type suffix of its literal, like `L` or `uL`.
+            Integer(i128, &'static str),
+            BigInteger(String),
+            /// The bits of a `Double`, or of a `Float` converted to a `Double`.
+            Float(u64, &'static str),
+            Enum(String, String),
+        }
+
+        fn float_value(v: f64, class: &'static str) -> KtValue {
+            // All NaNs are equal for our purposes.
+            KtValue::Float(if v.is_nan() { f64::NAN } else { v }.to_bits(), class)
+        }
+
+        fn eval_kt(expr: &str) -> KtValue {
+            match expr {
+                "true" => return KtValue::Boolean(true),
+                "false" => return KtValue::Boolean(false),
+                "Long.MIN_VALUE" => return KtValue::Integer(i64::MIN as i128, "L"),
+                _ => (),
+            }
+            for &class in &["Float", "Double"] {
+                if let Some(constant) = expr.strip_prefix(class).and_then(|e| e.strip_prefix('.')) {
+                    let value = match constant {
+                        "NEGATIVE_INFINITY" => f64::NEG_INFINITY,
+                        "POSITIVE_INFINITY" => f64::INFINITY,
+                        "NaN" => f64::NAN,
+                        _ => panic!("Unknown constant {}", expr),
+                    };
+                    return float_value(value, class);
+                }
+            }
+            if let Some(s) = expr.strip_prefix('"') {
+                return KtValue::String(eval_string_kt(s));
+            }
+            if let Some(s) = expr.strip_prefix("java.math.BigInteger(\"") {
+                let digits = s.strip_suffix("\")").expect("unterminated BigInteger");
+                assert!(digits.parse::<i128>().is_ok() || digits.parse::<u128>().is_ok());
+                return KtValue::BigInteger(digits.to_string());
+            }
+            if expr.starts_with(|c: char| c.is_ascii_uppercase()) {
+                let (class, variant) = expr.split_at(expr.find('.').expect("not an enum entry"));
+                return KtValue::Enum(class.to_string(), variant[1..].to_string());
+            }
+            eval_number_kt(expr)
+        }
+
+        fn eval_string_kt(s: &str) -> String {
+            let mut result = String::new();
+            let mut chars = s.chars();
+            loop {
+                match chars.next().expect("unterminated string") {
+                    '"' => break,
+                    '\\' => match chars.next().expect("unterminated escape") {
+                        c @ '"' | c @ '\\' | c @ '$' => result.push(c),
+                        'n' => result.push('\n'),
+                        'r' => result.push('\r'),
+                        't' => result.push('\t'),
+                        'u' => {
+                            let hex: String = chars.by_ref().take(4).collect();
+                            let code = u32::from_str_radix(&hex, 16).expect("invalid \\u escape");
+                            result.push(std::char::from_u32(code).expect("invalid code point"));
+                        }
+                        c => panic!("invalid escape \\{}", c),
+                    },
+                    c @ '$' | c @ '\n' | c @ '\r' => panic!("unescaped {:?} in string", c),
+                    c => {
+                        assert!(!c.is_control(), "unescaped control character {:?}", c);
+                        result.push(c)
+                    }
+                }
+            }
+            assert!(chars.next().is_none(), "trailing text after string");
+            result
+        }
+
+        fn eval_number_kt(expr: &str) -> KtValue {
+            let (negative, unsigned_expr) = match expr.strip_prefix('-') {
+                Some(rest) => (true, rest),
+                None => (false, expr),
+            };
+            // Hex digits include `f`, so we have to check for hex before looking for a suffix.
+            if let Some(hex) = unsigned_expr.strip_prefix("0x") {
+                let (digits, suffix) = split_int_suffix(hex);
+                assert!(digits.chars().all(|c| c.is_ascii_hexdigit()), "{}", expr);
+                let value = i128::from_str_radix(digits, 16).unwrap();
+                return KtValue::Integer(if negative { -value } else { value }, suffix);
+            }
+            if let Some(digits) = unsigned_expr.strip_suffix('f') {
+                assert_valid_float_kt(digits);
+                let value: f32 = expr[..expr.len() - 1].parse().unwrap();
+                return float_value(value as f64, "Float");
+            }
+            let (digits, suffix) = split_int_suffix(unsigned_expr);
+            if digits.chars().all(|c| c.is_ascii_digit()) {
+                assert!(digits == "0" || !digits.starts_with('0'), "{}", expr);
+                let value: i128 = digits.parse().unwrap();
+                return KtValue::Integer(if negative { -value } else { value }, suffix);
+            }
+            assert_eq!(suffix, "", "{}", expr);
+            assert_valid_float_kt(digits);
+            float_value(expr.parse().unwrap(), "Double")
+        }
+
+        fn split_int_suffix(s: &str) -> (&str, &'static str) {
+            for &suffix in &["uL", "u", "L"] {
+                if let Some(digits) = s.strip_suffix(suffix) {
+                    return (digits, suffix);
+                }
+            }
+            (s, "")
+        }
+
+        // Kotlin floats need digits after the point, unlike Rust and WebIDL.
+        fn assert_valid_float_kt(s: &str) {
+            let (mantissa, exponent) = match s.find(|c: char| c == 'e' || c == 'E') {
+                Some(i) => (&s[..i], Some(&s[i + 1..])),
+                None => (s, None),
+            };
+            let valid_mantissa = match mantissa.find('.') {
+                Some(i) => {
+                    let (whole, fraction) = (&mantissa[..i], &mantissa[i + 1..]);
+                    whole.chars().all(|c| c.is_ascii_digit())
+                        && !fraction.is_empty()
+                        && fraction.chars().all(|c| c.is_ascii_digit())
+                }
+                None => {
+                    exponent.is_some()
+                        && !mantissa.is_empty()
+                        && mantissa.chars().all(|c| c.is_ascii_digit())
+                }
+            };
+            let valid_exponent = exponent.map_or(true, |e| {
+                let e = e.strip_prefix(|c: char| c == '+' || c == '-').unwrap_or(e);
+                !e.is_empty() && e.chars().all(|c| c.is_ascii_digit())
+            });
+            assert!(
+                valid_mantissa && valid_exponent,
+                "invalid Kotlin float {}",
+                s
+            );
+        }
+
+        fn radix() -> impl Strategy<Value = Radix> {
+            prop_oneof![
+                Just(Radix::Decimal),
+                Just(Radix::Octal),
+                Just(Radix::Hexadecimal)
+            ]
+        }
+
+        fn signed_integer() -> impl Strategy<Value = (i128, Type, &'static str)> {
+            prop_oneof![
+                any::<i8>().prop_map(|v| (v as i128, Type::Int8, "")),
+                any::<i16>().prop_map(|v| (v as i128, Type::Int16, "")),
+                any::<i32>().prop_map(|v| (v as i128, Type::Int32, "")),
+                any::<i64>().prop_map(|v| (v as i128, Type::Int64, "L")),
+            ]
+        }
+
+        fn unsigned_integer() -> impl Strategy<Value = (u128, Type)> {
+            prop_oneof![
+                any::<u8>().prop_map(|v| (v as u128, Type::UInt8)),
+                any::<u16>().prop_map(|v| (v as u128, Type::UInt16)),
+                any::<u32>().prop_map(|v| (v as u128, Type::UInt32)),
+                any::<u64>().prop_map(|v| (v as u128, Type::UInt64)),
+            ]
+        }
+
+        // How WebIDL might spell a float, which is what ends up in a `Literal::Float`.
+        fn webidl_float(v: f64) -> String {
+            if v.is_nan() {
+                "NaN".into()
+            } else if v.is_infinite() {
+                if v > 0.0 { "Infinity" } else { "-Infinity" }.into()
+            } else {
+                format!("{:?}", v)
+            }
+        }
+
+        proptest! {
+            #[test]
+            fn booleans_round_trip(v in any::<bool>()) {
+                let kt = literal_kt(&Literal::Boolean(v), &Config::default()).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::Boolean(v));
+            }
+
+            #[test]
+            fn strings_round_trip(s in any::<String>()) {
+                let kt = literal_kt(&Literal::String(s.clone()), &Config::default()).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::String(s));
+            }
+
+            #[test]
+            fn strings_with_quotes_and_backslashes_round_trip(s in r#"[a-z"\\$\n{}]*"#) {
+                let kt = literal_kt(&Literal::String(s.clone()), &Config::default()).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::String(s));
+            }
+
+            #[test]
+            fn signed_integers_round_trip((v, type_, suffix) in signed_integer(), radix in radix()) {
+                let kt = literal_kt(&Literal::Int(v, radix, type_), &Config::default()).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::Integer(v, suffix));
+            }
+
+            #[test]
+            fn unsigned_integers_round_trip((v, type_) in unsigned_integer(), radix in radix()) {
+                let literal = Literal::UInt(v, radix, type_.clone());
+                let kt = literal_kt(&literal, &Config::default()).unwrap();
+                let suffix = if type_ == Type::UInt64 { "uL" } else { "u" };
+                prop_assert_eq!(eval_kt(&kt), KtValue::Integer(v as i128, suffix));
+
+                // When represented as signed types, the value has the same bits instead.
+                let config = Config {
+                    use_unsigned_types: Some(false),
+                    ..Config::default()
+                };
+                let (signed, suffix) = match type_ {
+                    Type::UInt8 => (v as u8 as i8 as i128, ""),
+                    Type::UInt16 => (v as u16 as i16 as i128, ""),
+                    Type::UInt32 => (v as u32 as i32 as i128, ""),
+                    _ => (v as u64 as i64 as i128, "L"),
+                };
+                let kt = literal_kt(&literal, &config).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::Integer(signed, suffix));
+            }
+
+            #[test]
+            fn big_integers_round_trip(i in any::<i128>(), u in any::<u128>(), radix in radix()) {
+                let config = Config::default();
+                let kt = literal_kt(&Literal::Int(i, radix, Type::Int128), &config).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::BigInteger(i.to_string()));
+                let kt = literal_kt(&Literal::UInt(u, radix, Type::UInt128), &config).unwrap();
+                prop_assert_eq!(eval_kt(&kt), KtValue::BigInteger(u.to_string()));
+            }
+
+            #[test]
+            fn doubles_round_trip(v in proptest::num::f64::ANY) {
+                let literal = Literal::Float(webidl_float(v), Type::Float64);
+                let kt = literal_kt(&literal, &Config::default()).unwrap();
+                prop_assert_eq!(eval_kt(&kt), float_value(v, "Double"));
+            }
+
+            #[test]
+            fn floats_round_trip(v in proptest::num::f32::ANY) {
+                let literal = Literal::Float(webidl_float(v as f64), Type::Float32);
+                let kt = literal_kt(&literal, &Config::default()).unwrap();
+                prop_assert_eq!(eval_kt(&kt), float_value(v as f64, "Float"));
+            }
+
+            #[test]
+            fn enums_round_trip(variant in "[a-z][a-z0-9]{0,8}(_[a-z0-9]{1,8}){0,3}") {
+                let literal = Literal::Enum(variant.clone(), Type::Enum("choice".into()));
+                let kt = literal_kt(&literal, &Config::default()).unwrap();
+                prop_assert_eq!(
+                    eval_kt(&kt),
+                    KtValue::Enum("Choice".into(), variant.to_shouty_snake_case())
+                );
+            }
+        }
+
+        #[test]
+        fn test_webidl_only_float_spellings_are_valid_kotlin() {
+            let config = Config::default();
+            for (webidl, expected) in
+                &[("1.", 1.0), ("-2.e3", -2000.0), (".5", 0.5), ("1E2", 100.0)]
+            {
+                let kt = literal_kt(&Literal::Float(webidl.to_string(), Type::Float64), &config)
+                    .unwrap();
+                assert_eq!(eval_kt(&kt), float_value(*expected, "Double"), "{}", webidl);
+            }
+        }
+    }
 }
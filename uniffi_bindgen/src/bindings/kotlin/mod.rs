@@ -13,64 +13,209 @@ use std::{
 };
 
 pub mod gen_kotlin;
-pub use gen_kotlin::{Config, KotlinWrapper};
+pub use gen_kotlin::{
+    collect_warnings, validate, Config, DefaultNamingConvention, KotlinCommonWrapper,
+    KotlinDeclaration, KotlinDeclarationFile, KotlinWrapper, NamingConvention, Target, Visibility,
+    Warning,
+};
 
 use super::super::interface::ComponentInterface;
+use crate::{BindgenError, MergeWith};
 
+/// Write the kotlin bindings for the given ComponentInterface into `out_dir`.
+///
+/// Anything that the bindings handle in a lossy way is returned as a list of warnings,
+/// for the caller to report however suits it.
 pub fn write_bindings(
     config: &Config,
     ci: &ComponentInterface,
     out_dir: &Path,
     try_format_code: bool,
-) -> Result<()> {
-    let mut kt_file = full_bindings_path(config, out_dir)?;
-    std::fs::create_dir_all(&kt_file)?;
-    kt_file.push(format!("{}.kt", ci.namespace()));
-    let mut f = File::create(&kt_file).context("Failed to create .kt file for bindings")?;
-    write!(f, "{}", generate_bindings(config, ci)?)?;
-    if try_format_code {
-        if let Err(e) = Command::new("ktlint")
-            .arg("-F")
-            .arg(kt_file.to_str().unwrap())
-            .output()
-        {
-            println!(
-                "Warning: Unable to auto-format {} using ktlint: {:?}",
-                kt_file.file_name().unwrap().to_str().unwrap(),
-                e
-            )
+) -> Result<Vec<Warning>> {
+    let subset = subset(config, ci)?;
+    let ci = subset.as_ref().unwrap_or(ci);
+    for (file_name, contents) in render_binding_files(config, ci)? {
+        let kt_file = full_bindings_path(config, ci, out_dir, &file_name)?;
+        std::fs::create_dir_all(kt_file.parent().unwrap())?;
+        let mut f = File::create(&kt_file).context("Failed to create .kt file for bindings")?;
+        write!(f, "{}", contents)?;
+        if try_format_code {
+            if let Err(e) = Command::new("ktlint")
+                .arg("-F")
+                .arg(kt_file.to_str().unwrap())
+                .output()
+            {
+                println!(
+                    "Warning: Unable to auto-format {} using ktlint: {:?}",
+                    kt_file.file_name().unwrap().to_str().unwrap(),
+                    e
+                )
+            }
         }
     }
-    Ok(())
+    Ok(collect_warnings(config, ci))
 }
 
-fn full_bindings_path(config: &Config, out_dir: &Path) -> Result<PathBuf> {
+// The file names for multiplatform bindings start with the source set that they belong to,
+// which goes above the package directory, in the same layout as a Gradle project's `src`.
+// Otherwise they're relative to the package directory, including for declarations in subpackages.
+fn full_bindings_path(
+    config: &Config,
+    ci: &ComponentInterface,
+    out_dir: &Path,
+    file_name: &str,
+) -> Result<PathBuf> {
+    let config = config.merge_with(&ci.into());
     let package_path: PathBuf = config.package_name().split('.').collect();
-    Ok(PathBuf::from(out_dir).join(package_path))
+    Ok(match file_name.split_once('/') {
+        Some((source_set, file_name)) if config.multiplatform() => out_dir
+            .join(source_set)
+            .join("kotlin")
+            .join(package_path)
+            .join(file_name),
+        _ => out_dir.join(package_path).join(file_name),
+    })
+}
+
+// Narrow the interface down to the items listed in the `include_only` config option, if it's set.
+fn subset(config: &Config, ci: &ComponentInterface) -> Result<Option<ComponentInterface>> {
+    config
+        .include_only()
+        .map(|names| ci.subset(names))
+        .transpose()
+        .context("Failed to select the items listed in `include_only`")
 }
 
-// Generate kotlin bindings for the given ComponentInterface, as a string.
-pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
+/// Generate kotlin bindings for the given ComponentInterface, as a string.
+///
+/// This doesn't touch the filesystem, so callers are free to post-process the
+/// generated source or write it out using their own file layout. Anything that the
+/// bindings handle in a lossy way is reported in the accompanying list of warnings.
+///
+/// With `target = "kmp"` this is only the JVM half of the bindings, so callers should use
+/// [`generate_binding_files`] to get the common half too.
+pub fn generate_bindings(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<(String, Vec<Warning>)> {
+    let subset = subset(config, ci)?;
+    let ci = subset.as_ref().unwrap_or(ci);
+    Ok((render_bindings(config, ci)?, collect_warnings(config, ci)))
+}
+
+fn render_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
+    if config.android_parcelable() {
+        // Objects are passed around as a pointer that only means anything in the current process.
+        if let Some(rec) = ci
+            .iter_record_definitions()
+            .into_iter()
+            .find(|rec| ci.item_contains_object_references(rec))
+        {
+            return Err(BindgenError::UnsupportedType {
+                name: rec.name().to_string(),
+                message: format!(
+                    "Record {:?} can't be Parcelable, because it contains an object",
+                    rec.name()
+                ),
+            }
+            .into());
+        }
+    }
+    gen_kotlin::check_errors_used_as_values(config, ci)?;
+    gen_kotlin::check_helper_names(ci)?;
+    gen_kotlin::check_multiplatform_support(config, ci)?;
+    gen_kotlin::check_subpackages(config, ci)?;
     KotlinWrapper::new(config.clone(), ci)
         .render()
-        .map_err(|_| anyhow::anyhow!("failed to render kotlin bindings"))
+        .context("failed to render kotlin bindings")
+}
+
+/// Generate kotlin bindings for the given ComponentInterface, as a list of file names and contents.
+///
+/// This is a single `{namespace}.kt` file unless the `split_files` config option is enabled, in
+/// which case each enum, record, object and callback interface gets a file of its own alongside it.
+///
+/// With `target = "kmp"`, there's a `commonMain/{namespace}.kt` file with the `expect` declarations
+/// and a `jvmMain/{namespace}.kt` file with their `actual` implementations instead.
+pub fn generate_binding_files(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(String, String)>> {
+    let subset = subset(config, ci)?;
+    render_binding_files(config, subset.as_ref().unwrap_or(ci))
+}
+
+fn render_binding_files(config: &Config, ci: &ComponentInterface) -> Result<Vec<(String, String)>> {
+    use askama::Template;
+    if config.multiplatform() {
+        let jvm = render_bindings(config, ci)?;
+        let common = KotlinCommonWrapper::new(config.clone(), ci)
+            .render()
+            .context("failed to render kotlin bindings")?;
+        return Ok(vec![
+            (format!("commonMain/{}.kt", ci.namespace()), common),
+            (format!("jvmMain/{}.kt", ci.namespace()), jvm),
+        ]);
+    }
+    let mut files = vec![(
+        format!("{}.kt", ci.namespace()),
+        render_bindings(config, ci)?,
+    )];
+    if config.split_files() {
+        for decl in KotlinDeclaration::iter_declarations(ci) {
+            let file_name = decl.file_name(config);
+            // Filesystems that ignore case would silently merge files whose names only differ
+            // by case, so refuse to generate them at all.
+            if files
+                .iter()
+                .any(|(existing, _)| existing.eq_ignore_ascii_case(&file_name))
+            {
+                return Err(BindgenError::NameCollision {
+                    message: format!(
+                        "Can't split bindings into files: {} would be generated twice",
+                        file_name
+                    ),
+                    name: file_name,
+                }
+                .into());
+            }
+            let contents = KotlinDeclarationFile::new(config.clone(), ci, decl)
+                .render()
+                .context("failed to render kotlin bindings")?;
+            files.push((file_name, contents));
+        }
+    }
+    Ok(files)
 }
 
 /// Generate kotlin bindings for the given namespace, then use the kotlin
 /// command-line tools to compile them into a .jar file.
 pub fn compile_bindings(config: &Config, ci: &ComponentInterface, out_dir: &Path) -> Result<()> {
-    let mut kt_file = full_bindings_path(config, out_dir)?;
-    kt_file.push(format!("{}.kt", ci.namespace()));
+    let mut kt_files = Vec::new();
+    let mut common_files = Vec::new();
+    for (file_name, _) in generate_binding_files(config, ci)? {
+        let kt_file = full_bindings_path(config, ci, out_dir, &file_name)?;
+        if file_name.starts_with("commonMain/") {
+            common_files.push(kt_file.to_str().unwrap().to_string());
+        }
+        kt_files.push(kt_file);
+    }
     let mut jar_file = PathBuf::from(out_dir);
     jar_file.push(format!("{}.jar", ci.namespace()));
-    let status = Command::new("kotlinc")
+    let mut cmd = Command::new("kotlinc");
+    if !common_files.is_empty() {
+        // The `expect` declarations have to be compiled along with their `actual` ones.
+        cmd.arg("-Xmulti-platform")
+            .arg(format!("-Xcommon-sources={}", common_files.join(",")));
+    }
+    let status = cmd
         // Our generated bindings should not produce any warnings; fail tests if they do.
         .arg("-Werror")
         // Reflect $CLASSPATH from the environment, to help find `jna.jar`.
         .arg("-classpath")
         .arg(env::var("CLASSPATH").unwrap_or_else(|_| "".to_string()))
-        .arg(&kt_file)
+        .args(kt_files)
         .arg("-d")
         .arg(jar_file)
         .spawn()
@@ -1,26 +1,28 @@
-{% let type_name = cbi.name()|class_name_kt %}
-public interface {{ type_name }} {
+{% let type_name = cbi.name()|class_name_kt(config) %}
+{% call kt::docstring(cbi, "") -%}
+{{ config.visibility() }} interface {{ type_name }} {
     {% for meth in cbi.methods() -%}
-    fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %})
+    {% call kt::function_decorations(meth, "    ") -%}
+    fun {{ meth.name()|method_name_kt(cbi.name(), config) }}({% call kt::arg_list_decl(meth) %})
     {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %}: {{ return_type|type_kt -}}
+    {%- when Some with (return_type) %}: {{ return_type|type_kt(config) -}}
     {%- else -%}
     {%- endmatch %}
     {% endfor %}
 }
 
-{% let canonical_type_name = cbi.type_().canonical_name()|class_name_kt %}
+{% let canonical_type_name = cbi.type_()|ffi_converter_name %}
 {% let callback_internals = format!("{}Internals", canonical_type_name) -%}
 {% let callback_interface_impl = format!("{}FFI", canonical_type_name) -%}
 
-internal class {{ callback_interface_impl }} : ForeignCallback {
+{% call kt::keep_annotation() %}internal class {{ callback_interface_impl }} : ForeignCallback {
     @Suppress("TooGenericExceptionCaught")
     override fun invoke(handle: Long, method: Int, args: RustBuffer.ByValue): RustBuffer.ByValue {
         return {{ callback_internals }}.handleMap.callWithResult(handle) { cb -> 
             when (method) {
                 IDX_CALLBACK_FREE -> {{ callback_internals }}.drop(handle)
                 {% for meth in cbi.methods() -%}
-                {% let method_name = format!("invoke_{}", meth.name())|fn_name_kt -%}
+                {% let method_name = format!("invoke_{}", meth.name())|helper_fn_name_kt(config) -%}
                 {{ loop.index }} -> this.{{ method_name }}(cb, args)
                 {% endfor %}
                 // This should never happen, because an out of bounds method index won't
@@ -32,21 +34,66 @@ internal class {{ callback_interface_impl }} : ForeignCallback {
     }
 
     {% for meth in cbi.methods() -%}
-    {% let method_name = format!("invoke_{}", meth.name())|fn_name_kt %}
+    {% let method_name = format!("invoke_{}", meth.name())|helper_fn_name_kt(config) %}
+    {%- match meth.throws() %}
+    {%- when Some with (e) %}
+    @Suppress("TooGenericExceptionCaught")
+    private fun {{ method_name }}(kotlinCallbackInterface: {{ type_name }}, args: RustBuffer.ByValue): RustBuffer.ByValue {
+        try {
+            {% if meth.return_type().is_some() %}val rval = {% endif %}try {
+            {#- Unpacking args from the RustBuffer #}
+                {%- if meth.arguments().len() != 0 %}
+                val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a Uniffi bug")
+                kotlinCallbackInterface.{{ meth.name()|method_name_kt(cbi.name(), config) }}(
+                    {% for arg in meth.arguments() -%}
+                    {% call kt::read_callback_arg(arg) %}
+                    {%- if !loop.last %}, {% endif %}
+                    {% endfor -%}
+                )
+                {%- else %}
+                kotlinCallbackInterface.{{ meth.name()|method_name_kt(cbi.name(), config) }}()
+                {%- endif %}
+            } catch (e: {{ e|exception_name_kt(config) }}) {
+                // Errors declared in the UDL are sent back to Rust, to be returned as an `Err`.
+                val rbuf = RustBufferBuilder()
+                rbuf.putByte(CALLBACK_ERROR)
+                e.write(rbuf)
+                return rbuf.finalize()
+            } catch (e: Throwable) {
+                // Anything else can't be represented in Rust, so we send back a description
+                // of what went wrong and the Rust side will panic.
+                val rbuf = RustBufferBuilder()
+                rbuf.putByte(CALLBACK_UNEXPECTED_ERROR)
+                e.toString().write(rbuf)
+                return rbuf.finalize()
+            }
+            val rbuf = RustBufferBuilder()
+            rbuf.putByte(CALLBACK_SUCCESS)
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            {{ "rval"|write_kt("rbuf", return_type, config) }}
+            {%- else %}
+            {%- endmatch %}
+            return rbuf.finalize()
+        } finally {
+            RustBuffer.free(args)
+        }
+    }
+    {%- else %}
     private fun {{ method_name }}(kotlinCallbackInterface: {{ type_name }}, args: RustBuffer.ByValue): RustBuffer.ByValue =
         try {
         {#- Unpacking args from the RustBuffer #}
             {%- if meth.arguments().len() != 0 -%}
             {#- Calling the concrete callback object #}
             val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a Uniffi bug")
-            kotlinCallbackInterface.{{ meth.name()|fn_name_kt }}(
+            kotlinCallbackInterface.{{ meth.name()|method_name_kt(cbi.name(), config) }}(
                     {% for arg in meth.arguments() -%}
-                    {{ "buf"|read_kt(arg.type_()) }}
+                    {% call kt::read_callback_arg(arg) %}
                     {%- if !loop.last %}, {% endif %}
                     {% endfor -%}
                 )
             {% else %}
-            kotlinCallbackInterface.{{ meth.name()|fn_name_kt }}()
+            kotlinCallbackInterface.{{ meth.name()|method_name_kt(cbi.name(), config) }}()
             {% endif -%}
 
         {#- Packing up the return value into a RustBuffer #}
@@ -54,7 +101,7 @@ internal class {{ callback_interface_impl }} : ForeignCallback {
                 {%- when Some with (return_type) -%}
                 .let { rval -> 
                     val rbuf = RustBufferBuilder()
-                    {{ "rval"|write_kt("rbuf", return_type) }} 
+                    {{ "rval"|write_kt("rbuf", return_type, config) }} 
                     rbuf.finalize()
                 }
                 {%- else -%}
@@ -65,6 +112,7 @@ internal class {{ callback_interface_impl }} : ForeignCallback {
         } finally {
             RustBuffer.free(args)
         }
+    {%- endmatch %}
 
     {% endfor %}
 }
@@ -0,0 +1,115 @@
+{% let external_imports = ci|external_imports_kt(config) -%}
+{% if !config.custom_header_preamble().is_empty() -%}
+{{ config.custom_header_preamble() }}
+{% endif -%}
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+//
+// These are the declarations for the common source set of a Kotlin Multiplatform project.
+// Anything that calls into Rust is declared with `expect` here, and its `actual` declaration
+// is in the bindings for each platform's source set.
+
+@file:Suppress("NAME_SHADOWING")
+{%- if self.uses_unsigned_types() %}
+@file:OptIn(ExperimentalUnsignedTypes::class)
+{%- endif %}
+
+package {{ config.package_name() }};
+{% for target in external_imports %}
+import {{ target }}
+{%- endfor %}
+{%- if !config.custom_header_body().is_empty() %}
+{{ config.custom_header_body() }}
+{%- endif %}
+
+{% include "PublicHelpers.kt" %}
+
+// Public interface members begin here.
+// Custom types
+{% for (name, prim) in ci.iter_wrapped_types() %}
+{% include "CustomTypeTemplate.kt" %}
+{%- endfor %}
+
+// Type aliases
+{% for (name, type_) in config.type_aliases() %}
+{% include "TypeAliasTemplate.kt" %}
+{%- endfor %}
+
+// Constants
+{% for c in ci.iter_constant_definitions() %}
+{% include "ConstantTemplate.kt" %}
+{%- endfor %}
+
+// Public facing enums
+{% for e in ci.iter_enum_definitions() %}
+{% call kt::docstring(e, "") -%}
+{% call kt::deprecated_annotation(e) %}
+{{ config.visibility() }} expect enum class {{ e.name()|class_name_kt(config) }} {
+    {% for variant in e.variants() -%}
+    {% call kt::docstring(variant, "    ") -%}
+    {% call kt::deprecated_annotation(variant) %}
+    {{ variant.foreign_name()|enum_variant_kt(config) }}{% if loop.last %};{% else %},{% endif %}
+    {%- endfor %}
+}
+{% endfor %}
+
+// Public facing records
+{%- for rec in ci.iter_record_definitions() %}
+
+{% call kt::docstring(rec, "") -%}
+{% call kt::unsigned_types_annotation(rec) %}
+{%- if rec.fields().is_empty() %}
+{{ config.visibility() }} expect object {{ rec.name()|class_name_kt(config) }} {{ rec|record_supertypes_kt(ci, config) }}
+{%- else %}
+{{ config.visibility() }} expect class {{ rec.name()|class_name_kt(config) }}(
+    {%- for field in rec.fields() %}
+    {{ field.name()|var_name_kt(config) }}: {{ field.type_()|type_kt(config) -}}
+    {%- match field.default_value() %}
+        {%- when Some with(literal) %} = {{ literal|default_kt(field.type_(), config) }}
+        {%- else %}
+    {%- endmatch -%}
+    {% if !loop.last %}{{ ", " }}{% endif %}
+    {%- endfor %}
+) {{ rec|record_supertypes_kt(ci, config) }}{
+    {%- for field in rec.fields() %}
+    {% if config.is_read_only_field(rec, field) %}val{% else %}var{% endif %} {{ field.name()|var_name_kt(config) }}: {{ field.type_()|type_kt(config) }}
+    {%- endfor %}
+}
+{%- endif %}
+{% endfor %}
+
+// Namespace functions
+{% for func in ci.iter_function_definitions() %}
+{% call kt::function_docstring(func, "") -%}
+{% call kt::unsigned_types_annotation(func) %}
+{% call kt::function_annotations(func, "") -%}
+{{ config.visibility() }} expect{% if func.is_async() %} suspend{% endif %} fun {{ func.name()|fn_name_kt(config) }}({%- call kt::arg_list_decl(func) -%}){% match func.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_kt(config) }}{% else %}{% endmatch %}
+{% endfor %}
+
+// Objects
+{% for obj in ci.iter_object_definitions() %}
+{% include "ObjectInterfaceTemplate.kt" %}
+
+{% call kt::docstring(obj, "") -%}
+{% call kt::unsigned_types_annotation(obj) %}
+{% call kt::deprecated_annotation(obj) %}
+{{ config.visibility() }} expect class {{ obj.name()|class_name_kt(config) }} : {{ obj.name()|class_name_kt(config) }}Interface, Disposable {
+    {%- match obj.primary_constructor() %}
+    {%- when Some with (cons) %}
+    {% call kt::function_annotations(cons, "    ") -%}
+    constructor({% call kt::arg_list_decl(cons) -%})
+    {%- when None %}
+    {%- endmatch %}
+    {%- if !obj.alternate_constructors().is_empty() %}
+
+    companion object {
+        {%- for cons in obj.alternate_constructors() %}
+        {% call kt::function_annotations(cons, "        ") -%}
+        fun {{ cons.name()|method_name_kt(obj.name(), config) }}({% call kt::arg_list_decl(cons) %}): {{ obj.name()|class_name_kt(config) }}
+        {%- endfor %}
+    }
+    {%- endif %}
+}
+{% endfor %}
+
+{% import "macros.kt" as kt %}
@@ -0,0 +1,11 @@
+{#
+// Kotlin only allows `const val` for primitives, so the 128-bit integer types
+// (which are represented as a `BigInteger`) have to be a plain `val` instead.
+#}
+{% call kt::docstring(c, "") -%}
+{% call kt::unsigned_types_annotation(c) %}
+{{ config.visibility() }} {% match c.type_() -%}
+{%- when Type::Int128 %}val
+{%- when Type::UInt128 %}val
+{%- else %}const val
+{%- endmatch %} {{ c.name()|const_name_kt }}: {{ c.type_()|type_kt(config) }} = {{ c.value().clone()|literal_kt(config) }}
@@ -0,0 +1,2 @@
+{% call kt::unsigned_types_annotation(prim) %}
+{{ config.visibility() }} typealias {{ name|class_name_kt(config) }} = {% match config.custom_type(name) %}{% when Some with (custom) %}{{ custom.type_name() }}{% when None %}{{ prim|type_kt(config) }}{% endmatch %}
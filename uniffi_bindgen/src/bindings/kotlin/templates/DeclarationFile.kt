@@ -0,0 +1,16 @@
+{% let external_imports = decl|declaration_imports_kt(config) -%}
+{% let package_name = decl|declaration_package_kt(config) -%}
+{% include "Header.kt" %}
+
+{% match decl %}
+{%- when KotlinDeclaration::Enum with (e) %}
+{% include "EnumTemplate.kt" %}
+{%- when KotlinDeclaration::Record with (rec) %}
+{% include "RecordTemplate.kt" %}
+{%- when KotlinDeclaration::Object with (obj) %}
+{% include "ObjectTemplate.kt" %}
+{%- when KotlinDeclaration::CallbackInterface with (cbi) %}
+{% include "CallbackInterfaceTemplate.kt" %}
+{%- endmatch %}
+
+{% import "macros.kt" as kt %}
@@ -7,21 +7,33 @@
 
 {% if e.is_flat() %}
 
-enum class {{ e.name()|class_name_kt }} {
+{% call kt::docstring(e, "") -%}
+{% call kt::deprecated_annotation(e) %}
+{{ config.visibility() }} {% call kt::actual() %}enum class {{ e.name()|class_name_kt(config) }} {
     {% for variant in e.variants() -%}
-    {{ variant.name()|enum_variant_kt }}{% if loop.last %};{% else %},{% endif %}
+    {% call kt::docstring(variant, "    ") -%}
+    {% call kt::deprecated_annotation(variant) %}
+    {{ variant.foreign_name()|enum_variant_kt(config) }}{% if loop.last %};{% else %},{% endif %}
     {%- endfor %}
 
-    companion object {
-        internal fun lift(rbuf: RustBuffer.ByValue): {{ e.name()|class_name_kt }} {
-            return liftFromRustBuffer(rbuf) { buf -> {{ e.name()|class_name_kt }}.read(buf) }
+    companion object{% if config.generate_all_cases() %} : CaseIterable<{{ e.name()|class_name_kt(config) }}>{% endif %} {
+        {%- if config.generate_all_cases() %}
+        override val allCases: List<{{ e.name()|class_name_kt(config) }}> = values().toList()
+        {% endif %}
+        internal fun lift(rbuf: RustBuffer.ByValue): {{ e.name()|class_name_kt(config) }} {
+            return liftFromRustBuffer(rbuf) { buf -> {{ e.name()|class_name_kt(config) }}.read(buf) }
         }
 
-        internal fun read(buf: ByteBuffer) =
-            try { values()[buf.getInt() - 1] }
-            catch (e: IndexOutOfBoundsException) {
-                throw RuntimeException("invalid enum value, something is very wrong!!", e)
+        internal fun read(buf: ByteBuffer): {{ e.name()|class_name_kt(config) }} {
+            return readChecked("{{ e.name()|class_name_kt(config) }}") {
+                return when({% call kt::read_discriminant(e) %}) {
+                    {%- for variant in e.variants() %}
+                    {{ variant.discriminant() }} -> {{ variant.foreign_name()|enum_variant_kt(config) }}
+                    {%- endfor %}
+                    else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+                }
             }
+        }
     }
 
     internal fun lower(): RustBuffer.ByValue {
@@ -29,41 +41,53 @@ enum class {{ e.name()|class_name_kt }} {
     }
 
     internal fun write(buf: RustBufferBuilder) {
-        buf.putInt(this.ordinal + 1)
+        // Variants can have explicit discriminants, so we can't rely on `ordinal` here.
+        val discriminant = when(this) {
+            {%- for variant in e.variants() %}
+            {{ variant.foreign_name()|enum_variant_kt(config) }} -> {{ variant.discriminant() }}
+            {%- endfor %}
+        }
+        {% call kt::write_discriminant(e, "discriminant") %}
     }
 }
 
 {% else %}
 
+{% call kt::docstring(e, "") -%}
 {% call kt::unsigned_types_annotation(e) %}
-sealed class {{ e.name()|class_name_kt }}{% if ci.item_contains_object_references(e) %}: Disposable {% endif %} {
+{% call kt::deprecated_annotation(e) %}
+{{ config.visibility() }} sealed class {{ e.name()|class_name_kt(config) }}{% if ci.item_contains_object_references(e) %}: Disposable{% endif %} {
     {% for variant in e.variants() -%}
+    {% call kt::docstring(variant, "    ") -%}
+    {% call kt::deprecated_annotation(variant) %}
     {% if !variant.has_fields() -%}
-    object {{ variant.name()|class_name_kt }} : {{ e.name()|class_name_kt }}()
+    object {{ variant.foreign_name()|variant_class_name_kt(config) }} : {{ e.name()|class_name_kt(config) }}()
     {% else -%}
-    data class {{ variant.name()|class_name_kt }}(
+    data class {{ variant.foreign_name()|variant_class_name_kt(config) }}(
         {% for field in variant.fields() -%}
-        val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt}}{% if loop.last %}{% else %}, {% endif %}
+        val {{ field.name()|var_name_kt(config) }}: {{ field.type_()|type_kt(config)}}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
-    ) : {{ e.name()|class_name_kt }}()
+    ) : {{ e.name()|class_name_kt(config) }}()
     {%- endif %}
     {% endfor %}
 
     companion object {
-        internal fun lift(rbuf: RustBuffer.ByValue): {{ e.name()|class_name_kt }} {
-            return liftFromRustBuffer(rbuf) { buf -> {{ e.name()|class_name_kt }}.read(buf) }
+        internal fun lift(rbuf: RustBuffer.ByValue): {{ e.name()|class_name_kt(config) }} {
+            return liftFromRustBuffer(rbuf) { buf -> {{ e.name()|class_name_kt(config) }}.read(buf) }
         }
 
-        internal fun read(buf: ByteBuffer): {{ e.name()|class_name_kt }} {
-            return when(buf.getInt()) {
-                {%- for variant in e.variants() %}
-                {{ loop.index }} -> {{ e.name()|class_name_kt }}.{{ variant.name()|class_name_kt }}{% if variant.has_fields() %}(
-                    {% for field in variant.fields() -%}
-                    {{ "buf"|read_kt(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
-                    {% endfor -%}
-                ){%- endif -%}
-                {%- endfor %}
-                else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+        internal fun read(buf: ByteBuffer): {{ e.name()|class_name_kt(config) }} {
+            return readChecked("{{ e.name()|class_name_kt(config) }}") {
+                return when(buf.getInt()) {
+                    {%- for variant in e.variants() %}
+                    {{ variant.discriminant() }} -> {{ e.name()|class_name_kt(config) }}.{{ variant.foreign_name()|variant_class_name_kt(config) }}{% if variant.has_fields() %}(
+                        {% for field in variant.fields() -%}
+                        {{ "buf"|read_kt(field.type_(), config) }}{% if loop.last %}{% else %},{% endif %}
+                        {% endfor -%}
+                    ){%- endif -%}
+                    {%- endfor %}
+                    else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+                }
             }
         }
     }
@@ -75,10 +99,11 @@ sealed class {{ e.name()|class_name_kt }}{% if ci.item_contains_object_reference
     internal fun write(buf: RustBufferBuilder) {
         when(this) {
             {%- for variant in e.variants() %}
-            is {{ e.name()|class_name_kt }}.{{ variant.name()|class_name_kt }} -> {
-                buf.putInt({{ loop.index }})
+            is {{ e.name()|class_name_kt(config) }}.{{ variant.foreign_name()|variant_class_name_kt(config) }} -> {
+                buf.putInt({{ variant.discriminant() }})
                 {% for field in variant.fields() -%}
-                {{ "(this.{})"|format(field.name())|write_kt("buf", field.type_()) }}
+                {% let field_name = field.name()|var_name_kt(config) -%}
+                {{ "(this.{})"|format(field_name)|write_kt("buf", field.type_(), config) }}
                 {% endfor %}
             }
             {%- endfor %}
@@ -90,10 +115,10 @@ sealed class {{ e.name()|class_name_kt }}{% if ci.item_contains_object_reference
     override fun destroy() {
         when(this) {
             {%- for variant in e.variants() %}
-            is {{ e.name()|class_name_kt }}.{{ variant.name()|class_name_kt }} -> {
+            is {{ e.name()|class_name_kt(config) }}.{{ variant.foreign_name()|variant_class_name_kt(config) }} -> {
                 {% for field in variant.fields() -%}
                     {%- if ci.item_contains_object_references(field) -%}
-                    this.{{ field.name() }}?.destroy()
+                    this.{{ field.name()|var_name_kt(config) }}?.destroy()
                     {% endif -%}
                 {%- endfor %}
             }
@@ -1,5 +1,5 @@
 @Structure.FieldOrder("code", "error_buf")
-internal open class RustCallStatus : Structure() {
+{% call kt::keep_annotation() %}internal open class RustCallStatus : Structure() {
     @JvmField var code: Int = 0
     @JvmField var error_buf: RustBuffer.ByValue = RustBuffer.ByValue()
 
@@ -16,38 +16,48 @@ internal open class RustCallStatus : Structure() {
     }
 }
 
-class InternalException(message: String) : Exception(message)
+{{ config.visibility() }} class InternalException(message: String) : Exception(message)
 
 // Each top-level error class has a companion object that can lift the error from the call status's rust buffer
-interface CallStatusErrorHandler<E> {
+{{ config.visibility() }} interface CallStatusErrorHandler<E> {
     fun lift(error_buf: RustBuffer.ByValue): E;
 }
 
 {%- for e in ci.iter_error_definitions() %}
 
 // Error {{ e.name() }}
-{%- let toplevel_name=e.name()|exception_name_kt %}
+{%- let toplevel_name=e.name()|exception_name_kt(config) %}
+{% call kt::docstring(e, "") -%}
+{% call kt::deprecated_annotation(e) %}
 {% if e.is_flat() %}
-sealed class {{ toplevel_name }}(message: String): Exception(message){% if ci.item_contains_object_references(e) %}, Disposable {% endif %} {
+{{ config.visibility() }} sealed class {{ toplevel_name }}(message: String): Exception(message){% if ci.item_contains_object_references(e) %}, Disposable{% endif %} {
         // Each variant is a nested class
         // Flat enums carries a string error message, so no special implementation is necessary.
         {% for variant in e.variants() -%}
-        class {{ variant.name()|exception_name_kt }}(message: String) : {{ toplevel_name }}(message)
+        {% call kt::docstring(variant, "        ") -%}
+        {% call kt::deprecated_annotation(variant) %}
+        class {{ variant.foreign_name()|variant_exception_name_kt(config) }}(message: String) : {{ toplevel_name }}(message)
         {% endfor %}
 
 {%- else %}
-sealed class {{ toplevel_name }}(): Exception(){% if ci.item_contains_object_references(e) %}, Disposable {% endif %} {
+{{ config.visibility() }} sealed class {{ toplevel_name }}(): Exception(){% if ci.item_contains_object_references(e) %}, Disposable{% endif %} {
 
     // Each variant is a nested class
     {% for variant in e.variants() -%}
+    {% call kt::docstring(variant, "    ") -%}
+    {% call kt::deprecated_annotation(variant) %}
     {% if !variant.has_fields() -%}
-    class {{ variant.name()|exception_name_kt }} : {{ toplevel_name }}()
+    class {{ variant.foreign_name()|variant_exception_name_kt(config) }} : {{ toplevel_name }}()
     {% else %}
-    class {{ variant.name()|exception_name_kt }}(
+    class {{ variant.foreign_name()|variant_exception_name_kt(config) }}(
         {% for field in variant.fields() -%}
-        val {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt}}{% if loop.last %}{% else %}, {% endif %}
+        val {{ field.name()|var_name_kt(config) }}: {{ field.type_()|type_kt(config)}}{% if loop.last %}{% else %}, {% endif %}
         {% endfor -%}
-    ) : {{ toplevel_name }}()
+    ) : {{ toplevel_name }}() {
+        // Rich errors don't carry a message from Rust, so describe the variant by its fields instead.
+        override val message
+            get() = "{% for field in variant.fields() %}{{ field.name() }}=${ {{ field.name()|var_name_kt(config) }} }{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %}"
+    }
     {%- endif %}
     {% endfor %}
 
@@ -59,38 +69,57 @@ sealed class {{ toplevel_name }}(): Exception(){% if ci.item_contains_object_ref
         }
 
         fun read(error_buf: ByteBuffer): {{ toplevel_name }} {
-            {% if e.is_flat() %}
+            return readChecked("{{ toplevel_name }}") {
+                {% if e.is_flat() %}
+                    return when(error_buf.getInt()) {
+                    {%- for variant in e.variants() %}
+                    {{ variant.discriminant() }} -> {{ toplevel_name }}.{{ variant.foreign_name()|variant_exception_name_kt(config) }}(String.read(error_buf))
+                    {%- endfor %}
+                    else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                }
+                {% else %}
+
                 return when(error_buf.getInt()) {
-                {%- for variant in e.variants() %}
-                {{ loop.index }} -> {{ toplevel_name }}.{{ variant.name()|exception_name_kt }}(String.read(error_buf))
-                {%- endfor %}
-                else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                    {%- for variant in e.variants() %}
+                    {{ variant.discriminant() }} -> {{ toplevel_name }}.{{ variant.foreign_name()|variant_exception_name_kt(config) }}({% if variant.has_fields() %}
+                        {% for field in variant.fields() -%}
+                        {{ "error_buf"|read_kt(field.type_(), config) }}{% if loop.last %}{% else %},{% endif %}
+                        {% endfor -%}
+                    {%- endif -%})
+                    {%- endfor %}
+                    else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                }
+                {%- endif %}
             }
-            {% else %}
-
-            return when(error_buf.getInt()) {
-                {%- for variant in e.variants() %}
-                {{ loop.index }} -> {{ toplevel_name }}.{{ variant.name()|exception_name_kt }}({% if variant.has_fields() %}
-                    {% for field in variant.fields() -%}
-                    {{ "error_buf"|read_kt(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
-                    {% endfor -%}
-                {%- endif -%})
-                {%- endfor %}
-                else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
-            }
-            {%- endif %}
         }
     }
+    {%- if !e.is_flat() %}
+
+    // This is used to send errors thrown by callback interface implementations back to Rust.
+    internal fun write(buf: RustBufferBuilder) {
+        when(this) {
+            {%- for variant in e.variants() %}
+            is {{ toplevel_name }}.{{ variant.foreign_name()|variant_exception_name_kt(config) }} -> {
+                buf.putInt({{ variant.discriminant() }})
+                {% for field in variant.fields() -%}
+                {% let field_name = field.name()|var_name_kt(config) -%}
+                {{ "(this.{})"|format(field_name)|write_kt("buf", field.type_(), config) }}
+                {% endfor %}
+            }
+            {%- endfor %}
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+    {%- endif %}
 
     {% if ci.item_contains_object_references(e) %}
     @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
     override fun destroy() {
         when(this) {
             {%- for variant in e.variants() %}
-            is {{ e.name()|class_name_kt }}.{{ variant.name()|class_name_kt }} -> {
+            is {{ toplevel_name }}.{{ variant.foreign_name()|variant_exception_name_kt(config) }} -> {
                 {% for field in variant.fields() -%}
                     {%- if ci.item_contains_object_references(field) -%}
-                    this.{{ field.name() }}?.destroy()
+                    this.{{ field.name()|var_name_kt(config) }}?.destroy()
                     {% endif -%}
                 {%- endfor %}
             }
@@ -106,7 +135,7 @@ sealed class {{ toplevel_name }}(): Exception(){% if ci.item_contains_object_ref
 // synchronize itself
 
 // Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
-private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+internal inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
     var status = RustCallStatus();
     val return_value = callback(status)
     if (status.isSuccess()) {
@@ -128,7 +157,7 @@ private inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusE
 }
 
 // CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
-object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+{{ config.visibility() }} object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
     override fun lift(error_buf: RustBuffer.ByValue): InternalException {
         RustBuffer.free(error_buf)
         return InternalException("Unexpected CALL_ERROR")
@@ -136,6 +165,6 @@ object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
 }
 
 // Call a rust function that returns a plain value
-private inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+internal inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
     return rustCallWithError(NullCallStatusErrorHandler, callback);
 }
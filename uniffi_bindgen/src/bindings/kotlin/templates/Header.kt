@@ -0,0 +1,40 @@
+{% if !config.custom_header_preamble().is_empty() -%}
+{{ config.custom_header_preamble() }}{{ "\n" }}
+{%- endif -%}
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+{%- if self.uses_unsigned_types() %}
+@file:OptIn(ExperimentalUnsignedTypes::class)
+{%- endif %}
+
+package {{ package_name }};
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import java.util.concurrent.atomic.AtomicLong
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicReference
+import java.util.concurrent.locks.ReentrantLock
+import kotlin.concurrent.withLock
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.resume
+import kotlin.coroutines.suspendCoroutine
+{%- if config.android_keep() %}
+import androidx.annotation.Keep
+{%- endif %}
+{%- if config.android_parcelable() %}
+import android.os.Parcelable
+import kotlinx.parcelize.Parcelize
+{%- endif %}
+{%- for target in external_imports %}
+import {{ target }}
+{%- endfor %}
+{%- if !config.custom_header_body().is_empty() %}
+{{ config.custom_header_body() }}
+{%- endif %}
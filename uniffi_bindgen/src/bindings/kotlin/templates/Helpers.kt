@@ -1,31 +1,11 @@
 // A handful of classes and functions to support the generated data structures.
 // This would be a good candidate for isolating in its own ffi-support lib.
 
-{% if ci.iter_object_definitions().len() > 0 %}
-
-// Interface implemented by anything that can contain an object reference.
-//
-// Such types expose a `destroy()` method that must be called to cleanly
-// dispose of the contained objects. Failure to call this method may result
-// in memory leaks.
-//
-// The easiest way to ensure this method is called is to use the `.use`
-// helper method to execute a block and destroy the object at the end.
-interface Disposable {
-    fun destroy()
-}
+{% if !config.multiplatform() -%}
+{% include "PublicHelpers.kt" %}
+{% endif -%}
 
-inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
-    try {
-        block(this)
-    } finally {
-        try {
-            // N.B. our implementation is on the nullable type `Disposable?`.
-            this?.destroy()
-        } catch (e: Throwable) {
-            // swallow
-        }
-    }
+{% if ci.iter_object_definitions().len() > 0 %}
 
 // The base class for all UniFFI Object types.
 //
@@ -108,13 +88,17 @@ inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
 //
 // [1] https://stackoverflow.com/questions/24376768/can-java-finalize-an-object-when-it-is-still-in-scope/24380219
 //
-abstract class FFIObject(
+{{ config.visibility() }} abstract class FFIObject(
     protected val pointer: Pointer
 ): Disposable, AutoCloseable {
 
     val wasDestroyed = AtomicBoolean(false)
     val callCounter = AtomicLong(1)
 
+    // Objects that Rust lends to a callback interface method for the duration of the call
+    // are still owned by Rust, so their pointer must never be freed from Kotlin.
+    internal val isBorrowed = AtomicBoolean(false)
+
     open protected fun freeRustArcPtr() {
         // To be overridden in subclasses.
     }
@@ -124,7 +108,7 @@ abstract class FFIObject(
         // TODO: maybe we should log a warning if called more than once?
         if (this.wasDestroyed.compareAndSet(false, true)) {
             // This decrement always matches the initial count of 1 given at creation time.
-            if (this.callCounter.decrementAndGet() == 0L) {
+            if (this.callCounter.decrementAndGet() == 0L && !this.isBorrowed.get()) {
                 this.freeRustArcPtr()
             }
         }
@@ -152,7 +136,7 @@ abstract class FFIObject(
             return block(this.pointer)
         } finally {
             // This decrement aways matches the increment we performed above.
-            if (this.callCounter.decrementAndGet() == 0L) {
+            if (this.callCounter.decrementAndGet() == 0L && !this.isBorrowed.get()) {
                 this.freeRustArcPtr()
             }
         }
@@ -187,6 +171,18 @@ internal class ConcurrentHandleMap<T>(
             fn.invoke(obj)
         }
 
+    // Unlike `insert`, this always hands out a new handle, even if the object is already in the map.
+    fun insertUnique(obj: T): Handle =
+        lock.withLock {
+            currentHandle.getAndAdd(stride)
+                .also { handle ->
+                    leftMap[handle] = obj
+                    if (!rightMap.containsKey(obj)) {
+                        rightMap[obj] = handle
+                    }
+                }
+            }
+
     fun get(handle: Handle) = lock.withLock {
         leftMap[handle]
     }
@@ -198,13 +194,15 @@ internal class ConcurrentHandleMap<T>(
     fun remove(handle: Handle): T? =
         lock.withLock {
             leftMap.remove(handle)?.let { obj ->
-                rightMap.remove(obj)
+                if (rightMap[obj] == handle) {
+                    rightMap.remove(obj)
+                }
                 obj
             }
         }
 }
 
-interface ForeignCallback : com.sun.jna.Callback {
+{% call kt::keep_annotation() %}{{ config.visibility() }} interface ForeignCallback : com.sun.jna.Callback {
     public fun invoke(handle: Long, method: Int, args: RustBuffer.ByValue): RustBuffer.ByValue
 }
 
@@ -225,7 +223,10 @@ internal abstract class CallbackInternals<CallbackInterface>(
         return handleMap.remove(handle).let { RustBuffer.ByValue() }
     }
 
-    fun lift(n: Long) = handleMap.get(n)
+    // Rust only passes a callback back to us if we gave it to Rust in the first place, and it hands
+    // its handle back to us when it does, so this returns the original object and frees the handle.
+    fun lift(n: Long): CallbackInterface =
+        handleMap.remove(n) ?: throw InternalException("Callback handle not found; this is a Uniffi bug")
 
     fun read(buf: ByteBuffer) = lift(buf.getLong())
 
@@ -236,5 +237,69 @@ internal abstract class CallbackInternals<CallbackInterface>(
 
     fun write(v: CallbackInterface, buf: RustBufferBuilder) =
         buf.putLong(lower(v))
+
+    // Lends a callback to Rust for a single call, for `[ByRef]` arguments. It gets a handle of
+    // its own, so that a re-entrant call lending the same object can't unregister it early, and
+    // the handle is invalid as soon as the call returns.
+    inline fun <R> lend(v: CallbackInterface, call: (Handle) -> R): R {
+        val handle = handleMap.insertUnique(v)
+        try {
+            return call(handle)
+        } finally {
+            handleMap.remove(handle)
+        }
+    }
 }
-{% endif %}
\ No newline at end of file
+{% endif %}
+
+{% if ci.iter_callback_interface_definitions().len() > 0 || ci.contains_async_functions() %}
+// Status codes at the start of the buffer returned by a callback method that can fail, or passed
+// to the callback for an async function, which must match the ones in the `uniffi` crate.
+internal const val CALLBACK_SUCCESS: Byte = 0
+internal const val CALLBACK_ERROR: Byte = 1
+internal const val CALLBACK_UNEXPECTED_ERROR: Byte = 2
+{% endif %}
+
+{% if ci.contains_async_functions() %}
+{% call kt::keep_annotation() %}{{ config.visibility() }} interface FutureCallback : com.sun.jna.Callback {
+    public fun invoke(callbackData: Long, result: RustBuffer.ByValue)
+}
+
+// Keeps track of the coroutines that are suspended waiting for an async Rust function to complete,
+// and resumes them when Rust calls back with the result.
+//
+// This is a singleton so that JNA (which only keeps a weak reference to callback objects) can't
+// garbage-collect it while there are calls in flight.
+{% call kt::keep_annotation() %}internal object FutureContinuations : FutureCallback {
+    private val continuations = java.util.concurrent.ConcurrentHashMap<Long, Continuation<RustBuffer.ByValue>>()
+    private val nextHandle = AtomicLong(0L)
+
+    fun insert(continuation: Continuation<RustBuffer.ByValue>): Long =
+        nextHandle.getAndIncrement().also { handle ->
+            continuations[handle] = continuation
+        }
+
+    fun remove(handle: Long) {
+        continuations.remove(handle)
+    }
+
+    override fun invoke(callbackData: Long, result: RustBuffer.ByValue) {
+        val continuation = continuations.remove(callbackData)
+        if (continuation != null) {
+            continuation.resume(result)
+        } else {
+            RustBuffer.free(result)
+        }
+    }
+}
+{% endif %}
+
+{% if ci.iter_wrapped_types().len() > 0 %}
+// Implemented by the consumer of these bindings to convert between a custom type and the builtin
+// type that it's passed over the FFI as. Each custom type that should be converted needs an
+// entry in the `custom_types` config option, naming an object that implements this interface.
+{{ config.visibility() }} interface UniffiCustomTypeConverter<CustomType, BuiltinType> {
+    fun lift(value: BuiltinType): CustomType
+    fun lower(value: CustomType): BuiltinType
+}
+{% endif %}
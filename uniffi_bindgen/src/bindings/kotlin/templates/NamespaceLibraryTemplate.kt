@@ -1,5 +1,5 @@
 @Synchronized
-fun findLibraryName(componentName: String): String {
+{{ config.visibility() }} fun findLibraryName(componentName: String): String {
     val libOverride = System.getProperty("uniffi.component.${componentName}.libraryOverride")
     if (libOverride != null) {
         return libOverride
@@ -7,7 +7,7 @@ fun findLibraryName(componentName: String): String {
     return "{{ config.cdylib_name() }}"
 }
 
-inline fun <reified Lib : Library> loadIndirect(
+{{ config.visibility() }} inline fun <reified Lib : Library> loadIndirect(
     componentName: String
 ): Lib {
     return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
@@ -16,7 +16,7 @@ inline fun <reified Lib : Library> loadIndirect(
 // A JNA Library to expose the extern-C FFI definitions.
 // This is an implementation detail which will be called internally by the public API.
 
-internal interface _UniFFILib : Library {
+{% call kt::keep_annotation() %}internal interface _UniFFILib : Library {
     companion object {
         internal val INSTANCE: _UniFFILib by lazy { 
             loadIndirect<_UniFFILib>(componentName = "{{ ci.namespace() }}")
@@ -24,14 +24,14 @@ internal interface _UniFFILib : Library {
             {%- if !callback_interfaces.is_empty() -%}
             .also { lib: _UniFFILib ->
                 {% for cb in callback_interfaces -%}
-                CallbackInterface{{ cb.name()|class_name_kt }}Internals.register(lib)
+                {{ cb.type_()|ffi_converter_name }}Internals.register(lib)
                 {% endfor -%}
             }
             {% endif %}
         }
     }
 
-    {% for func in ci.iter_ffi_function_definitions() -%}
+    {% for func in self.ffi_functions() -%}
     fun {{ func.name() }}(
         {%- call kt::arg_list_ffi_decl(func) %}
     ){%- match func.return_type() -%}{%- when Some with (type_) %}: {{ type_|type_ffi }}{% when None %}: Unit{% endmatch %}
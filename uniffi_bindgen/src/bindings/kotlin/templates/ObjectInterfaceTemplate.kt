@@ -0,0 +1,17 @@
+{% call kt::docstring(obj, "") -%}
+{% call kt::unsigned_types_annotation(obj) %}
+{% call kt::deprecated_annotation(obj) %}
+{{ config.visibility() }} interface {{ obj.name()|class_name_kt(config) }}Interface {
+    {% for meth in obj.methods() -%}
+    {% call kt::function_decorations(meth, "    ") -%}
+    {% if meth.is_property() -%}
+    val {{ meth.property_name()|var_name_kt(config) }}
+    {%- else -%}
+    fun {{ meth.name()|method_name_kt(obj.name(), config) }}({% call kt::arg_list_decl(meth) %})
+    {%- endif -%}
+    {%- match meth.return_type() -%}
+    {%- when Some with (return_type) %}: {{ return_type|type_kt(config) -}}
+    {%- else -%}
+    {%- endmatch %}
+    {% endfor %}
+}
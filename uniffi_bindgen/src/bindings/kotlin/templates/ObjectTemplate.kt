@@ -1,25 +1,29 @@
+{% if !config.multiplatform() -%}
+{% include "ObjectInterfaceTemplate.kt" %}
+{% endif %}
+{% call kt::docstring(obj, "") -%}
 {% call kt::unsigned_types_annotation(obj) %}
-public interface {{ obj.name()|class_name_kt }}Interface {
-    {% for meth in obj.methods() -%}
-    fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_decl(meth) %})
-    {%- match meth.return_type() -%}
-    {%- when Some with (return_type) %}: {{ return_type|type_kt -}}
-    {%- else -%}
-    {%- endmatch %}
-    {% endfor %}
-}
-
-{% call kt::unsigned_types_annotation(obj) %}
-class {{ obj.name()|class_name_kt }}(
+{% call kt::deprecated_annotation(obj) %}
+{{ config.visibility() }} {% call kt::actual() %}class {{ obj.name()|class_name_kt(config) }}(
     pointer: Pointer
-) : FFIObject(pointer), {{ obj.name()|class_name_kt }}Interface {
+) : FFIObject(pointer), {{ obj.name()|class_name_kt(config) }}Interface {
 
     {%- match obj.primary_constructor() %}
     {%- when Some with (cons) %}
-    constructor({% call kt::arg_list_decl(cons) -%}) :
+    {% call kt::function_annotations(cons, "    ") -%}
+    {% call kt::jvm_overloads_annotation(cons) %}
+    {% call kt::actual() %}constructor({% call kt::arg_list_actual(cons) -%}) :
         this({% call kt::to_ffi_call(cons) %})
     {%- when None %}
     {%- endmatch %}
+    {%- match config.object_init_hook() %}
+    {%- when Some with (hook) %}
+
+    init {
+        {{ hook }}
+    }
+    {%- when None %}
+    {%- endmatch %}
 
     /**
      * Disconnect the object from the underlying Rust object.
@@ -30,12 +34,30 @@ class {{ obj.name()|class_name_kt }}(
      * Clients **must** call this method once done with the object, or cause a memory leak.
      */
     override protected fun freeRustArcPtr() {
+        {%- if config.preserve_object_identity() %}
+        synchronized(instances) {
+            val address = Pointer.nativeValue(this.pointer)
+            if (instances[address]?.get() === this) {
+                instances.remove(address)
+            }
+        }
+        {%- endif %}
         rustCall() { status ->
             _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(this.pointer, status)
         }
     }
 
     internal fun lower(): Pointer = callWithPointer { it }
+    {%- if config.debug_tostring() %}
+
+    override fun toString(): String {
+        // This must not call into Rust or dereference the pointer, so that it's safe
+        // to use for logging from anywhere, including after the object is destroyed.
+        val address = java.lang.Long.toHexString(Pointer.nativeValue(this.pointer))
+        val destroyed = if (this.wasDestroyed.get()) ", destroyed" else ""
+        return "{{ obj.name()|class_name_kt(config) }}(pointer=0x$address$destroyed)"
+    }
+    {%- endif %}
 
     internal fun write(buf: RustBufferBuilder) {
         // The Rust code always expects pointers written as 8 bytes,
@@ -47,35 +69,99 @@ class {{ obj.name()|class_name_kt }}(
     {%- match meth.return_type() -%}
 
     {%- when Some with (return_type) -%}
-    override fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_protocol(meth) %}): {{ return_type|type_kt }} =
+    {% call kt::function_annotations(meth, "    ") -%}
+    {% if meth.is_property() -%}
+    override val {{ meth.property_name()|var_name_kt(config) }}: {{ return_type|type_kt(config) }}
+        get() = callWithPointer {
+            {%- call kt::to_ffi_call_with_prefix("it", meth) %}
+        }.let {
+            {{ "it"|lift_kt(return_type, config) }}
+        }
+    {%- else -%}
+    override fun {{ meth.name()|method_name_kt(obj.name(), config) }}({% call kt::arg_list_protocol(meth) %}): {{ return_type|type_kt(config) }} =
         callWithPointer {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }.let {
-            {{ "it"|lift_kt(return_type) }}
+            {{ "it"|lift_kt(return_type, config) }}
         }
+    {%- endif %}
 
     {%- when None -%}
-    override fun {{ meth.name()|fn_name_kt }}({% call kt::arg_list_protocol(meth) %}) =
+    {% call kt::function_annotations(meth, "    ") -%}
+    override fun {{ meth.name()|method_name_kt(obj.name(), config) }}({% call kt::arg_list_protocol(meth) %}) {
         callWithPointer {
             {%- call kt::to_ffi_call_with_prefix("it", meth) %}
         }
+    }
     {% endmatch %}
     {% endfor %}
 
-    companion object {
-        internal fun lift(ptr: Pointer): {{ obj.name()|class_name_kt }} {
-            return {{ obj.name()|class_name_kt }}(ptr)
+    {% if !obj.alternate_constructors().is_empty() %}{% call kt::actual() %}{% endif %}companion object {
+        {%- if config.preserve_object_identity() %}
+        // The live instances, by the address of the Rust object that they point to, so
+        // that we can hand out the same instance whenever Rust returns the same object.
+        private val instances = HashMap<Long, java.lang.ref.WeakReference<{{ obj.name()|class_name_kt(config) }}>>()
+
+        internal fun lift(ptr: Pointer?, reuse: Boolean = true): {{ obj.name()|class_name_kt(config) }} {
+        {%- else %}
+        internal fun lift(ptr: Pointer?): {{ obj.name()|class_name_kt(config) }} {
+        {%- endif %}
+            // Rust should never hand us a null pointer, but if a bug means that it does, we'd
+            // rather throw than crash the whole JVM when the pointer is first used.
+            if (ptr == null || Pointer.nativeValue(ptr) == 0L) {
+                throw InternalException("Received a null pointer for a {{ obj.name()|class_name_kt(config) }} from Rust")
+            }
+            {%- if config.preserve_object_identity() %}
+            if (!reuse) {
+                return {{ obj.name()|class_name_kt(config) }}(ptr)
+            }
+            val address = Pointer.nativeValue(ptr)
+            synchronized(instances) {
+                val existing = instances[address]?.get()
+                if (existing != null && !existing.wasDestroyed.get()) {
+                    // Rust added a reference to the object for us, but the existing instance
+                    // already holds one of its own, so we give this one straight back.
+                    rustCall() { status ->
+                        _UniFFILib.INSTANCE.{{ obj.ffi_object_free().name() }}(ptr, status)
+                    }
+                    return existing
+                }
+                return {{ obj.name()|class_name_kt(config) }}(ptr).also {
+                    instances[address] = java.lang.ref.WeakReference(it)
+                }
+            }
+            {%- else %}
+            return {{ obj.name()|class_name_kt(config) }}(ptr)
+            {%- endif %}
+        }
+
+        internal fun read(buf: ByteBuffer): {{ obj.name()|class_name_kt(config) }} {
+            return readChecked("{{ obj.name()|class_name_kt(config) }}") {
+                // The Rust code always writes pointers as 8 bytes, and will
+                // fail to compile if they don't fit.
+                return {{ obj.name()|class_name_kt(config) }}.lift(Pointer(buf.getLong()))
+            }
         }
 
-        internal fun read(buf: ByteBuffer): {{ obj.name()|class_name_kt }} {
-            // The Rust code always writes pointers as 8 bytes, and will
-            // fail to compile if they don't fit.
-            return {{ obj.name()|class_name_kt }}.lift(Pointer(buf.getLong()))
+        internal fun readBorrowed(buf: ByteBuffer): {{ obj.name()|class_name_kt(config) }} {
+            // Rust only lends us the pointer for the duration of a callback, without
+            // adding a reference for us, so we must never free it.
+            {%- if config.preserve_object_identity() %}
+            // That also means it mustn't share an instance that does hold a reference.
+            val obj = {{ obj.name()|class_name_kt(config) }}.lift(Pointer(buf.getLong()), reuse = false)
+            {%- else %}
+            val obj = {{ obj.name()|class_name_kt(config) }}.read(buf)
+            {%- endif %}
+            obj.isBorrowed.set(true)
+            return obj
         }
 
         {% for cons in obj.alternate_constructors() -%}
-        fun {{ cons.name()|fn_name_kt }}({% call kt::arg_list_decl(cons) %}): {{ obj.name()|class_name_kt }} =
-            {{ obj.name()|class_name_kt }}({% call kt::to_ffi_call(cons) %})
+        {% call kt::function_annotations(cons, "        ") -%}
+        {% call kt::jvm_overloads_annotation(cons) %}
+        @JvmStatic
+        {% call kt::actual() %}fun {{ cons.name()|method_name_kt(obj.name(), config) }}({% call kt::arg_list_actual(cons) %}): {{ obj.name()|class_name_kt(config) }} =
+            {{ obj.name()|class_name_kt(config) }}({% call kt::to_ffi_call(cons) %})
         {% endfor %}
     }
 }
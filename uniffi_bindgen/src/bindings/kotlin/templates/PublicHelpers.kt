@@ -0,0 +1,66 @@
+{#
+// The helpers that are part of the public API, which go in the common source set along with
+// the `expect` declarations when generating multiplatform bindings.
+#}
+{% if config.call_interceptor().is_some() %}
+// Implemented by the consumer of these bindings to wrap every call into Rust, for example to
+// measure how long each one takes. The `call_interceptor` config option names an object that
+// implements this interface, and `name` is the name of the FFI function being called.
+{{ config.visibility() }} interface UniffiCallInterceptor {
+    fun <T> intercept(name: String, call: () -> T): T
+}
+{% endif %}
+
+{% for arity in ci|generated_tuple_arities_kt %}
+{% let fields = arity|tuple_fields_kt %}
+// Kotlin only has `Pair` and `Triple`, so we represent larger tuples with a class of the same
+// style. It's a data class, so it can be destructured in the same way as `Pair` and `Triple`.
+{{ config.visibility() }} data class Tuple{{ arity }}<{% for field in fields %}out T{{ loop.index }}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %}>(
+    {%- for field in fields %}
+    val {{ field }}: T{{ loop.index }}{% if !loop.last %},{% endif %}
+    {%- endfor %}
+) {
+    override fun toString(): String = "({% for field in fields %}${{ field }}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %})"
+}
+{% endfor %}
+
+{% if config.generate_all_cases() %}
+// Interface implemented by the companion object of each enum without associated data,
+// listing all of its variants in the order they're declared.
+{{ config.visibility() }} interface CaseIterable<T> {
+    val allCases: List<T>
+}
+{% endif %}
+
+{% if ci.iter_object_definitions().len() > 0 %}
+
+// Interface implemented by anything that can contain an object reference.
+//
+// Such types expose a `destroy()` method that must be called to cleanly
+// dispose of the contained objects. Failure to call this method may result
+// in memory leaks.
+//
+// The easiest way to ensure this method is called is to use the `.use`
+// helper method to execute a block and destroy the object at the end.
+{{ config.visibility() }} interface Disposable {
+    fun destroy()
+}
+
+{{ config.visibility() }} inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
+    try {
+        block(this)
+    } finally {
+        try {
+            // N.B. our implementation is on the nullable type `Disposable?`.
+            this?.destroy()
+        } catch (e: Throwable) {
+            // swallow
+        }
+    }
+{% endif %}
+
+{% if self.contains_nested_optionals() %}
+// Kotlin's nullable types don't nest, so an optional value whose type is also nullable gets
+// wrapped in `Some`. This lets us tell `Some(None)` apart from `None`, which is plain `null`.
+{{ config.visibility() }} data class Some<T>(val value: T)
+{% endif %}
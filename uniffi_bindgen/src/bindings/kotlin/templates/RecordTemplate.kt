@@ -1,25 +1,91 @@
+{%- if config.generate_record_interfaces() %}
 {% call kt::unsigned_types_annotation(rec) %}
-data class {{ rec.name()|class_name_kt }} (
+{{ config.visibility() }} interface {{ rec.name()|class_name_kt(config) }}Data{% if ci.item_contains_object_references(rec) %} : Disposable{% endif %} {
     {%- for field in rec.fields() %}
-    var {{ field.name()|var_name_kt }}: {{ field.type_()|type_kt -}}
+    val {{ field.name()|var_name_kt(config) }}: {{ field.type_()|type_kt(config) }}
+    {%- endfor %}
+}
+
+{% endif -%}
+{% call kt::docstring(rec, "") -%}
+{% call kt::unsigned_types_annotation(rec) %}
+{%- if config.android_parcelable() %}
+@Parcelize
+{%- endif %}
+{%- if rec.fields().is_empty() %}
+{#- A `data class` has to have at least one field, and every instance would be equal anyway. #}
+{{ config.visibility() }} {% call kt::actual() %}object {{ rec.name()|class_name_kt(config) }} {{ rec|record_supertypes_kt(ci, config) }}{
+    internal fun lift(rbuf: RustBuffer.ByValue): {{ rec.name()|class_name_kt(config) }} {
+        return liftFromRustBuffer(rbuf) { buf -> {{ rec.name()|class_name_kt(config) }}.read(buf) }
+    }
+
+    // There are no fields, so nothing is read or written.
+    @Suppress("UNUSED_PARAMETER")
+    internal fun read(buf: ByteBuffer): {{ rec.name()|class_name_kt(config) }} = this
+
+    internal fun lower(): RustBuffer.ByValue {
+        return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
+    }
+
+    @Suppress("UNUSED_PARAMETER")
+    internal fun write(buf: RustBufferBuilder) {}
+
+    override fun toString(): String = "{{ rec.name()|class_name_kt(config) }}"
+}
+{%- else %}
+{%- if config.render_as_value_class(rec) %}
+@JvmInline
+{{ config.visibility() }} value class {{ rec.name()|class_name_kt(config) }} (
+{%- else %}
+{{ config.visibility() }} {% call kt::actual() %}data class {{ rec.name()|class_name_kt(config) }} {% if config.multiplatform() %}actual constructor{% endif %}(
+{%- endif %}
+    {%- for field in rec.fields() %}
+    {% call kt::actual() %}{% if config.generate_record_interfaces() %}{{ "override " }}{% endif %}{% if config.is_read_only_field(rec, field) %}val{% else %}var{% endif %} {{ field.name()|var_name_kt(config) }}: {{ field.type_()|type_kt(config) -}}
+    {%- if !config.multiplatform() %}
     {%- match field.default_value() %}
-        {%- when Some with(literal) %} = {{ literal|literal_kt }}
+        {%- when Some with(literal) %} = {{ literal|default_kt(field.type_(), config) }}
         {%- else %}
     {%- endmatch -%}
+    {%- endif -%}
     {% if !loop.last %}, {% endif %}
     {%- endfor %}
-) {% if ci.item_contains_object_references(rec) %}: Disposable {% endif %}{
+) {{ rec|record_supertypes_kt(ci, config) }}{
+    {%- if rec.has_constrained_fields() %}
+    init {
+        {%- for field in rec.fields() %}
+        {%- for requirement in field|requirements_kt(config) %}
+        {{ requirement }}
+        {%- endfor %}
+        {%- endfor %}
+    }
+{% endif %}
     companion object {
-        internal fun lift(rbuf: RustBuffer.ByValue): {{ rec.name()|class_name_kt }} {
-            return liftFromRustBuffer(rbuf) { buf -> {{ rec.name()|class_name_kt }}.read(buf) }
+        internal fun lift(rbuf: RustBuffer.ByValue): {{ rec.name()|class_name_kt(config) }} {
+            return liftFromRustBuffer(rbuf) { buf -> {{ rec.name()|class_name_kt(config) }}.read(buf) }
         }
 
-        internal fun read(buf: ByteBuffer): {{ rec.name()|class_name_kt }} {
-            return {{ rec.name()|class_name_kt }}(
-            {%- for field in rec.fields() %}
-            {{ "buf"|read_kt(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
-            {%- endfor %}
-            )
+        internal fun read(buf: ByteBuffer): {{ rec.name()|class_name_kt(config) }} {
+            return readChecked("{{ rec.name()|class_name_kt(config) }}") {
+                {%- if rec.has_constrained_fields() %}
+                // Rust doesn't check the `[Validate]` constraints, so a record from Rust that
+                // breaks them is a bug in the component rather than in the calling code.
+                try {
+                    return {{ rec.name()|class_name_kt(config) }}(
+                    {%- for field in rec.fields() %}
+                    {{ "buf"|read_kt(field.type_(), config) }}{% if loop.last %}{% else %},{% endif %}
+                    {%- endfor %}
+                    )
+                } catch (e: IllegalArgumentException) {
+                    throw InternalException("invalid {{ rec.name()|class_name_kt(config) }} from Rust: ${e.message}")
+                }
+                {%- else %}
+                return {{ rec.name()|class_name_kt(config) }}(
+                {%- for field in rec.fields() %}
+                {{ "buf"|read_kt(field.type_(), config) }}{% if loop.last %}{% else %},{% endif %}
+                {%- endfor %}
+                )
+                {%- endif %}
+            }
         }
     }
 
@@ -29,18 +95,80 @@ data class {{ rec.name()|class_name_kt }} (
 
     internal fun write(buf: RustBufferBuilder) {
         {%- for field in rec.fields() %}
-            {{ "(this.{})"|format(field.name())|write_kt("buf", field.type_()) }}
+            {% let field_name = field.name()|var_name_kt(config) -%}
+            {{ "(this.{})"|format(field_name)|write_kt("buf", field.type_(), config) }}
         {% endfor %}
     }
 
+    {% if config.render_builder(rec) -%}
+    // Fields with a default value, or of an optional type, can be left unset and take their
+    // default (or `null`) when the record is built. All the other fields have to be set.
+    class Builder {
+        {%- for field in rec.fields() %}
+        {%- let field_name = field.name()|var_name_kt(config) %}
+        {%- match field.default_value() %}
+        {%- when Some with(literal) %}
+        private var {{ field_name }}: {{ field.type_()|type_kt(config) }} = {{ literal|default_kt(field.type_(), config) }}
+        {%- else %}
+        {%- if field.is_optional() %}
+        private var {{ field_name }}: {{ field.type_()|type_kt(config) }} = null
+        {%- else %}
+        private var {{ field_name }}: {{ field.type_()|type_kt(config) }}? = null
+        {%- endif %}
+        {%- endmatch %}
+        {%- endfor %}
+        {% for field in rec.fields() %}
+        fun {{ field.name()|var_name_kt(config) }}(value: {{ field.type_()|type_kt(config) }}) = apply { this.{{ field.name()|var_name_kt(config) }} = value }
+        {%- endfor %}
+
+        fun build(): {{ rec.name()|class_name_kt(config) }} {
+            return {{ rec.name()|class_name_kt(config) }}(
+            {%- for field in rec.fields() %}
+            {%- let field_name = field.name()|var_name_kt(config) %}
+            {%- if field.default_value().is_none() && !field.is_optional() %}
+                requireNotNull(this.{{ field_name }}) { "Required field {{ field.name() }} was not set" }{% if !loop.last %},{% endif %}
+            {%- else %}
+                this.{{ field_name }}{% if !loop.last %},{% endif %}
+            {%- endif %}
+            {%- endfor %}
+            )
+        }
+    }
+
+    {% endif -%}
+    {% if rec.has_fields_excluded_from_equality() -%}
+    // These are defined explicitly so that fields marked `[ExcludeFromEquality]` aren't compared.
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (other !is {{ rec.name()|class_name_kt(config) }}) return false
+        {%- for field in rec.fields() %}
+        {%- if !field.excluded_from_equality() %}
+        if (this.{{ field.name()|var_name_kt(config) }} != other.{{ field.name()|var_name_kt(config) }}) return false
+        {%- endif %}
+        {%- endfor %}
+        return true
+    }
+
+    override fun hashCode(): Int {
+        return java.util.Objects.hash(
+        {%- for field in rec.fields() %}
+        {%- if !field.excluded_from_equality() %}
+            this.{{ field.name()|var_name_kt(config) }},
+        {%- endif %}
+        {%- endfor %}
+        )
+    }
+    {%- endif %}
+
     {% if ci.item_contains_object_references(rec) %}
     @Suppress("UNNECESSARY_SAFE_CALL") // codegen is much simpler if we unconditionally emit safe calls here
     override fun destroy() {
         {% for field in rec.fields() %}
             {%- if ci.item_contains_object_references(field) -%}
-            this.{{ field.name() }}?.destroy()
+            this.{{ field.name()|var_name_kt(config) }}?.destroy()
             {% endif -%}
         {%- endfor %}
     }
     {% endif %}
 }
+{%- endif %}
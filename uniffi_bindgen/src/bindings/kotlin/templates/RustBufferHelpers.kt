@@ -1,5 +1,5 @@
 // Helpers for reading primitive data types from a bytebuffer.
-
+{% if self.needs_compound_helpers() %}
 internal fun<T> liftFromRustBuffer(rbuf: RustBuffer.ByValue, readItem: (ByteBuffer) -> T): T {
     val buf = rbuf.asByteBuffer()!!
     try {
@@ -12,7 +12,24 @@ internal fun<T> liftFromRustBuffer(rbuf: RustBuffer.ByValue, readItem: (ByteBuff
         RustBuffer.free(rbuf)
     }
 }
+{% endif %}
+
+// The `read` helpers for compound values wrap their bodies in this, so that if the buffer from
+// Rust is shorter than we expect (say because the Rust code was built from a different version
+// of the component), we report which type we were reading rather than a bare NIO exception.
+// Nested values are checked too, so the innermost type that ran off the end gets named.
+internal inline fun<T> readChecked(typeName: String, read: () -> T): T {
+    try {
+        return read()
+    } catch (e: java.nio.BufferUnderflowException) {
+        throw InternalException("buffer overrun reading $typeName")
+    }
+}
 
+{% if self.needs_compound_helpers() %}
+// This is the only place that compound values allocate a `RustBuffer` when they're lowered.
+// Their `write` helpers write any nested values straight into the same builder, so lowering
+// a deeply nested record still allocates just the one buffer (growing it as needed).
 internal fun<T> lowerIntoRustBuffer(v: T, writeItem: (T, RustBufferBuilder) -> Unit): RustBuffer.ByValue {
     // TODO: maybe we can calculate some sort of initial size hint?
     val buf = RustBufferBuilder()
@@ -24,13 +41,14 @@ internal fun<T> lowerIntoRustBuffer(v: T, writeItem: (T, RustBufferBuilder) -> U
         throw e
     }
 }
+{% endif %}
 
 // For every type used in the interface, we provide helper methods for conveniently
 // lifting and lowering that type from C-compatible data, and for reading and writing
 // values of that type in a buffer.
 
 {% for typ in ci.iter_types() %}
-{% let canonical_type_name = typ.canonical_name()|class_name_kt %}
+{% let canonical_type_name = typ|ffi_converter_name %}
 {%- match typ -%}
 
 {% when Type::Boolean -%}
@@ -51,6 +69,29 @@ internal fun Boolean.write(buf: RustBufferBuilder) {
     buf.putByte(this.lower())
 }
 
+{% when Type::Char -%}
+
+// A Kotlin `Char` is a single UTF-16 code unit, so it can only hold code points in the
+// Basic Multilingual Plane. We refuse to lift anything else rather than silently truncating it.
+internal fun Char.Companion.lift(v: Int): Char {
+    if (v < 0 || v > 0xFFFF) {
+        throw InternalException("Char can't represent the code point 0x${v.toString(16)}")
+    }
+    return v.toChar()
+}
+
+internal fun Char.Companion.read(buf: ByteBuffer): Char {
+    return Char.lift(buf.getInt())
+}
+
+internal fun Char.lower(): Int {
+    return this.code
+}
+
+internal fun Char.write(buf: RustBufferBuilder) {
+    buf.putInt(this.lower())
+}
+
 {% when Type::Int8 -%}
 
 internal fun Byte.Companion.lift(v: Byte): Byte {
@@ -125,6 +166,10 @@ internal fun Long.write(buf: RustBufferBuilder) {
 
 {% when Type::UInt8 -%}
 
+// The unsigned types are passed over the FFI as the signed type of the same size, and the
+// conversions between them keep the same bits, so e.g. `u32::MAX` arrives as `-1` and is
+// lifted back into `UInt.MAX_VALUE`. Nothing is narrowed, so nothing can overflow.
+
 @ExperimentalUnsignedTypes
 internal fun UByte.Companion.lift(v: Byte): UByte {
     return v.toUByte()
@@ -211,6 +256,83 @@ internal fun ULong.write(buf: RustBufferBuilder) {
     buf.putLong(this.toLong())
 }
 
+{% when Type::Int128 -%}
+{%- let type_name = typ|type_kt(config) %}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        // 128-bit integers are written as 16 bytes of big-endian two's complement,
+        // which is exactly what the `BigInteger` constructor expects.
+        val byteArr = ByteArray(16)
+        buf.get(byteArr)
+        return {{ type_name }}(byteArr)
+    }
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
+    if (v.bitLength() > 127) {
+        throw IllegalArgumentException("Value $v does not fit in an i128")
+    }
+    // `toByteArray()` gives the minimal two's complement representation,
+    // so we sign-extend it out to the full 16 bytes.
+    val byteArr = v.toByteArray()
+    val padding = if (v.signum() < 0) (-1).toByte() else 0.toByte()
+    for (i in byteArr.size until 16) {
+        buf.putByte(padding)
+    }
+    buf.put(byteArr)
+}
+
+{% when Type::UInt128 -%}
+{%- let type_name = typ|type_kt(config) %}
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        // 128-bit integers are written as 16 bytes in big-endian order,
+        // which we always interpret as a positive magnitude.
+        val byteArr = ByteArray(16)
+        buf.get(byteArr)
+        return {{ type_name }}(1, byteArr)
+    }
+}
+
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
+    if (v.signum() < 0 || v.bitLength() > 128) {
+        throw IllegalArgumentException("Value $v does not fit in a u128")
+    }
+    // `toByteArray()` may include an extra leading zero byte for the sign bit,
+    // which we need to skip in order to fit into exactly 16 bytes.
+    val byteArr = v.toByteArray()
+    for (i in byteArr.size until 16) {
+        buf.putByte(0.toByte())
+    }
+    buf.put(if (byteArr.size > 16) byteArr.copyOfRange(byteArr.size - 16, byteArr.size) else byteArr)
+}
+
 {% when Type::Float32 -%}
 
 internal fun Float.Companion.lift(v: Float): Float {
@@ -250,21 +372,43 @@ internal fun Double.write(buf: RustBufferBuilder) {
 
 {% when Type::String -%}
 
+{% if config.strict_utf8() -%}
+// Decodes the bytes of a string from Rust, throwing rather than replacing any invalid UTF-8
+// with U+FFFD, so that a bug which corrupts the serialized bytes doesn't go unnoticed.
+internal fun decodeUtf8(byteArr: ByteArray): String {
+    try {
+        return Charsets.UTF_8.newDecoder()
+            .onMalformedInput(java.nio.charset.CodingErrorAction.REPORT)
+            .onUnmappableCharacter(java.nio.charset.CodingErrorAction.REPORT)
+            .decode(ByteBuffer.wrap(byteArr))
+            .toString()
+    } catch (e: java.nio.charset.CharacterCodingException) {
+        throw InternalException("invalid UTF-8 in a string from Rust")
+    }
+}
+{%- else -%}
+internal fun decodeUtf8(byteArr: ByteArray): String {
+    return byteArr.toString(Charsets.UTF_8)
+}
+{%- endif %}
+
 internal fun String.Companion.lift(rbuf: RustBuffer.ByValue): String {
     try {
         val byteArr = ByteArray(rbuf.len)
         rbuf.asByteBuffer()!!.get(byteArr)
-        return byteArr.toString(Charsets.UTF_8)
+        return decodeUtf8(byteArr)
     } finally {
         RustBuffer.free(rbuf)
     }
 }
 
 internal fun String.Companion.read(buf: ByteBuffer): String {
-    val len = buf.getInt()
-    val byteArr = ByteArray(len)
-    buf.get(byteArr)
-    return byteArr.toString(Charsets.UTF_8)
+    return readChecked("String") {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return decodeUtf8(byteArr)
+    }
 }
 
 internal fun String.lower(): RustBuffer.ByValue {
@@ -283,7 +427,7 @@ internal fun String.write(buf: RustBufferBuilder) {
 }
 
 {% when Type::Timestamp -%}
-{% let type_name = typ|type_kt %}
+{% let type_name = typ|type_kt(config) %}
 
 internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
     return liftFromRustBuffer(rbuf) { buf ->
@@ -292,16 +436,20 @@ internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_na
 }
 
 internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
-    val seconds = buf.getLong()
-    // Type mismatch (should be u32) but we check for overflow/underflow below
-    val nanoseconds = buf.getInt().toLong()
-    if (nanoseconds < 0) {
-        throw java.time.DateTimeException("Instant nanoseconds exceed minimum or maximum supported by uniffi")
-    }
-    if (seconds >= 0) {
-        return {{ type_name }}.EPOCH.plus(java.time.Duration.ofSeconds(seconds, nanoseconds))
-    } else {
-        return {{ type_name }}.EPOCH.minus(java.time.Duration.ofSeconds(-seconds, nanoseconds))
+    return readChecked("{{ type_name }}") {
+        val seconds = buf.getLong()
+        // Type mismatch (should be u32) but we check for overflow/underflow below
+        val nanoseconds = buf.getInt().toLong()
+        if (nanoseconds < 0) {
+            throw java.time.DateTimeException("Instant nanoseconds exceed minimum or maximum supported by uniffi")
+        }
+        // The nanoseconds are relative to the epoch in the same direction as the seconds, and
+        // unlike negating `seconds` this can't overflow; it throws if the value is out of range.
+        if (seconds >= 0) {
+            return {{ type_name }}.ofEpochSecond(seconds, nanoseconds)
+        } else {
+            return {{ type_name }}.ofEpochSecond(seconds, -nanoseconds)
+        }
     }
 }
 
@@ -332,7 +480,7 @@ internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferB
 }
 
 {% when Type::Duration -%}
-{% let type_name = typ|type_kt %}
+{% let type_name = typ|type_kt(config) %}
 
 internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
     return liftFromRustBuffer(rbuf) { buf ->
@@ -341,17 +489,19 @@ internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_na
 }
 
 internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
-    // Type mismatch (should be u64) but we check for overflow/underflow below
-    val seconds = buf.getLong()
-    // Type mismatch (should be u32) but we check for overflow/underflow below
-    val nanoseconds = buf.getInt().toLong()
-    if (seconds < 0) {
-        throw java.time.DateTimeException("Duration exceeds minimum or maximum value supported by uniffi")
+    return readChecked("{{ type_name }}") {
+        // Type mismatch (should be u64) but we check for overflow/underflow below
+        val seconds = buf.getLong()
+        // Type mismatch (should be u32) but we check for overflow/underflow below
+        val nanoseconds = buf.getInt().toLong()
+        if (seconds < 0) {
+            throw java.time.DateTimeException("Duration exceeds minimum or maximum value supported by uniffi")
+        }
+        if (nanoseconds < 0) {
+            throw java.time.DateTimeException("Duration nanoseconds exceed minimum or maximum supported by uniffi")
+        }
+        return {{ type_name }}.ofSeconds(seconds, nanoseconds)
     }
-    if (nanoseconds < 0) {
-        throw java.time.DateTimeException("Duration nanoseconds exceed minimum or maximum supported by uniffi")
-    }
-    return {{ type_name }}.ofSeconds(seconds, nanoseconds)
 }
 
 internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
@@ -379,120 +529,295 @@ internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferB
 }
 
 {% when Type::Optional with (inner_type) -%}
-{% let inner_type_name = inner_type|type_kt %}
+{% let type_name = typ|type_kt(config) %}
+{#- Nullable types don't nest in Kotlin, so if the inner type is nullable then we wrap it in `Some`. #}
+{% let nested = inner_type|is_nullable_kt(config) %}
 
-// Helper functions for pasing values of type {{ typ|type_kt }}
+// Helper functions for pasing values of type {{ type_name }}
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ inner_type_name }}? {
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
     return liftFromRustBuffer(rbuf) { buf ->
         read{{ canonical_type_name }}(buf)
     }
 }
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ inner_type_name }}? {
-    if (buf.get().toInt() == 0) {
-        return null
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        {%- if nested %}
+        return Some({{ "buf"|read_kt(inner_type, config) }})
+        {%- else %}
+        return {{ "buf"|read_kt(inner_type, config) }}
+        {%- endif %}
     }
-    return {{ "buf"|read_kt(inner_type) }}
 }
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun lower{{ canonical_type_name }}(v: {{ inner_type_name }}?): RustBuffer.ByValue {
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
     return lowerIntoRustBuffer(v) { v, buf ->
         write{{ canonical_type_name }}(v, buf)
     }
 }
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun write{{ canonical_type_name }}(v: {{ inner_type_name }}?, buf: RustBufferBuilder) {
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
     if (v == null) {
         buf.putByte(0)
     } else {
         buf.putByte(1)
-        {{ "v"|write_kt("buf", inner_type) }}
+        {%- if nested %}
+        {{ "v.value"|write_kt("buf", inner_type, config) }}
+        {%- else %}
+        {{ "v"|write_kt("buf", inner_type, config) }}
+        {%- endif %}
+    }
+}
+
+{% when Type::Bytes -%}
+
+// Helper functions for pasing values of type {{ typ|type_kt(config) }}
+// These copy the bytes in bulk, rather than reading and writing each element separately.
+
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): ByteArray {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
     }
 }
 
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): ByteArray {
+    return readChecked("ByteArray") {
+        val len = buf.getInt()
+        if (len < 0) {
+            throw RuntimeException("invalid byte array length, something is very wrong!!")
+        }
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return byteArr
+    }
+}
+
+internal fun lower{{ canonical_type_name }}(v: ByteArray): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+internal fun write{{ canonical_type_name }}(v: ByteArray, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    buf.put(v)
+}
+
 {% when Type::Sequence with (inner_type) -%}
-{% let inner_type_name = inner_type|type_kt %}
+{#- This is a `List<T>` or some kind of array, depending on the `sequence_type` config option. #}
+{% let type_name = typ|type_kt(config) %}
 
-// Helper functions for pasing values of type {{ typ|type_kt }}
+// Helper functions for pasing values of type {{ type_name }}
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): List<{{ inner_type_name }}> {
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
     return liftFromRustBuffer(rbuf) { buf ->
         read{{ canonical_type_name }}(buf)
     }
 }
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun read{{ canonical_type_name }}(buf: ByteBuffer): List<{{ inner_type_name }}> {
-    val len = buf.getInt()
-    return List<{{ inner_type|type_kt }}>(len) {
-        {{ "buf"|read_kt(inner_type) }}
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        val len = buf.getInt()
+        {%- if config.read_only_lists() %}
+        return java.util.Collections.unmodifiableList({{ type_name }}(len) {
+            {{ "buf"|read_kt(inner_type, config) }}
+        })
+        {%- else %}
+        return {{ type_name }}(len) {
+            {{ "buf"|read_kt(inner_type, config) }}
+        }
+        {%- endif %}
     }
 }
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun lower{{ canonical_type_name }}(v: List<{{ inner_type_name }}>): RustBuffer.ByValue {
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
     return lowerIntoRustBuffer(v) { v, buf ->
         write{{ canonical_type_name }}(v, buf)
     }
 }
 
 {% call kt::unsigned_types_annotation(inner_type) %}
-internal fun write{{ canonical_type_name }}(v: List<{{ inner_type_name }}>, buf: RustBufferBuilder) {
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
     buf.putInt(v.size)
     v.forEach {
-        {{ "it"|write_kt("buf", inner_type) }}
+        {{ "it"|write_kt("buf", inner_type, config) }}
     }
 }
 
-{% when Type::Map with (inner_type) -%}
-{% let inner_type_name = inner_type|type_kt %}
+{% when Type::Map with (key_type, value_type) -%}
+{% let key_type_name = key_type|type_kt(config) %}
+{% let value_type_name = value_type|type_kt(config) %}
 
-// Helper functions for pasing values of type {{ typ|type_kt }}
+// Helper functions for pasing values of type {{ typ|type_kt(config) }}
 
-{% call kt::unsigned_types_annotation(inner_type) %}
-internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<String, {{ inner_type_name }}> {
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): Map<{{ key_type_name }}, {{ value_type_name }}> {
     return liftFromRustBuffer(rbuf) { buf ->
         read{{ canonical_type_name }}(buf)
     }
 }
 
-{% call kt::unsigned_types_annotation(inner_type) %}
-internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<String, {{ inner_type_name }}> {
-    // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
-    val items : MutableMap<String, {{ inner_type_name }}> = mutableMapOf()
-    val len = buf.getInt()
-    repeat(len) {
-        val k = String.read(buf)
-        val v = {{ "buf"|read_kt(inner_type) }}
-        items[k] = v
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): Map<{{ key_type_name }}, {{ value_type_name }}> {
+    return readChecked("Map<{{ key_type_name }}, {{ value_type_name }}>") {
+        // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
+        val items : MutableMap<{{ key_type_name }}, {{ value_type_name }}> = mutableMapOf()
+        val len = buf.getInt()
+        repeat(len) {
+            val k = {{ "buf"|read_kt(key_type, config) }}
+            val v = {{ "buf"|read_kt(value_type, config) }}
+            items[k] = v
+        }
+        {%- if config.immutable_collections() %}
+        return java.util.Collections.unmodifiableMap(items)
+        {%- else %}
+        return items
+        {%- endif %}
     }
-    return items
 }
 
-{% call kt::unsigned_types_annotation(inner_type) %}
-internal fun lower{{ canonical_type_name }}(m: Map<String, {{ inner_type_name }}>): RustBuffer.ByValue {
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lower{{ canonical_type_name }}(m: Map<{{ key_type_name }}, {{ value_type_name }}>): RustBuffer.ByValue {
     return lowerIntoRustBuffer(m) { m, buf ->
         write{{ canonical_type_name }}(m, buf)
     }
 }
 
-{% call kt::unsigned_types_annotation(inner_type) %}
-internal fun write{{ canonical_type_name }}(v: Map<String, {{ inner_type_name }}>, buf: RustBufferBuilder) {
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun write{{ canonical_type_name }}(v: Map<{{ key_type_name }}, {{ value_type_name }}>, buf: RustBufferBuilder) {
     buf.putInt(v.size)
     // The parens on `(k, v)` here ensure we're calling the right method,
     // which is important for compatibility with older android devices.
     // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
     v.forEach { (k, v) ->
-        k.write(buf)
-        {{ "v"|write_kt("buf", inner_type) }}
+        {{ "k"|write_kt("buf", key_type, config) }}
+        {{ "v"|write_kt("buf", value_type, config) }}
+    }
+}
+
+{% when Type::Tuple with (element_types) -%}
+{% let type_name = typ|type_kt(config) %}
+
+// Helper functions for pasing values of type {{ type_name }}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lift{{ canonical_type_name }}(rbuf: RustBuffer.ByValue): {{ type_name }} {
+    return liftFromRustBuffer(rbuf) { buf ->
+        read{{ canonical_type_name }}(buf)
+    }
+}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        {%- for element_type in element_types %}
+        val v{{ loop.index }} = {{ "buf"|read_kt(element_type, config) }}
+        {%- endfor %}
+        return {{ element_types.len()|tuple_class_kt }}({% for element_type in element_types %}v{{ loop.index }}{% if !loop.last %}{{ ", " }}{% endif %}{% endfor %})
+    }
+}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        write{{ canonical_type_name }}(v, buf)
+    }
+}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
+    {%- for element_type in element_types %}
+    {%- let index = loop.index %}
+    {{ format!("v.component{}()", index)|write_kt("buf", element_type, config) }}
+    {%- endfor %}
+}
+
+{% when Type::Stream with (inner_type) -%}
+{% let type_name = typ|type_kt(config) %}
+{% let inner_type_name = inner_type|type_kt(config) %}
+
+// Helper functions for receiving values of type {{ type_name }}, which are read from Rust
+// one at a time while the flow is being collected. The stream is freed once collection
+// finishes, whether or not it was read to the end, so the flow can only be collected once.
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lift{{ canonical_type_name }}(handle: Long): {{ type_name }} {
+    val collected = AtomicBoolean(false)
+    return kotlinx.coroutines.flow.flow {
+        check(!collected.getAndSet(true)) { "A stream can only be collected once" }
+        try {
+            while (true) {
+                val next: List<{{ inner_type_name }}> = liftFromRustBuffer(rustCall() { status ->
+                    _UniFFILib.INSTANCE.{{ ci.ffi_stream_next(typ).name() }}(handle, status)
+                }) { buf ->
+                    if (buf.get().toInt() == 0) listOf() else listOf({{ "buf"|read_kt(inner_type, config) }})
+                }
+                if (next.isEmpty()) {
+                    break
+                }
+                emit(next[0])
+            }
+        } finally {
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.{{ ci.ffi_stream_free(typ).name() }}(handle, status)
+            }
+        }
     }
 }
 
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        return lift{{ canonical_type_name }}(buf.getLong())
+    }
+}
+
+{% when Type::Wrapped with { name, prim } -%}
+{%- match config.custom_type(name) %}
+{%- when Some with (custom) %}
+{% let type_name = typ|type_kt(config) %}
+{% let ffi_type_name = prim|ffi_type_kt %}
+
+// Helper functions for passing values of type {{ type_name }}, by converting them to and from
+// a {{ prim|type_kt(config) }} which is what actually gets passed over the FFI.
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lift{{ canonical_type_name }}(v: {{ ffi_type_name }}): {{ type_name }} {
+    return {{ custom.converter() }}.lift({{ "v"|lift_kt(prim, config) }})
+}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun read{{ canonical_type_name }}(buf: ByteBuffer): {{ type_name }} {
+    return readChecked("{{ type_name }}") {
+        return {{ custom.converter() }}.lift({{ "buf"|read_kt(prim, config) }})
+    }
+}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun lower{{ canonical_type_name }}(v: {{ type_name }}): {{ ffi_type_name }} {
+    val builtinValue = {{ custom.converter() }}.lower(v)
+    return {{ "builtinValue"|lower_kt(prim, config) }}
+}
+
+{% call kt::unsigned_types_annotation(typ) %}
+internal fun write{{ canonical_type_name }}(v: {{ type_name }}, buf: RustBufferBuilder) {
+    val builtinValue = {{ custom.converter() }}.lower(v)
+    {{ "builtinValue"|write_kt("buf", prim, config) }}
+}
+{%- when None %}
+{# Custom types without a converter are passed over the FFI using the helpers for their builtin type #}
+{%- endmatch %}
+
 {% when Type::Enum with (enum_name) -%}
 {# Helpers for Enum types are defined inline with the Enum class #}
 
@@ -3,13 +3,13 @@
 // pointer to the underlying data.
 
 @Structure.FieldOrder("capacity", "len", "data")
-open class RustBuffer : Structure() {
+{% call kt::keep_annotation() %}{{ config.visibility() }} open class RustBuffer : Structure() {
     @JvmField var capacity: Int = 0
     @JvmField var len: Int = 0
     @JvmField var data: Pointer? = null
 
-    class ByValue : RustBuffer(), Structure.ByValue
-    class ByReference : RustBuffer(), Structure.ByReference
+    {% call kt::keep_annotation() %}class ByValue : RustBuffer(), Structure.ByValue
+    {% call kt::keep_annotation() %}class ByReference : RustBuffer(), Structure.ByReference
 
     companion object {
         internal fun alloc(size: Int = 0) = rustCall() { status ->
@@ -32,19 +32,36 @@ open class RustBuffer : Structure() {
         }
 }
 
+{% if self.needs_foreign_bytes() %}
 // This is a helper for safely passing byte references into the rust code.
-// It's not actually used at the moment, because there aren't many things that you
-// can take a direct pointer to in the JVM, and if we're going to copy something
-// then we might as well copy it into a `RustBuffer`. But it's here for API
-// completeness.
+// We use it for `[ByRef] bytes` arguments, which the rust code reads in place
+// for the duration of the call. JNA can't give us a direct pointer to the contents of a
+// `ByteArray`, so we copy it into native memory first, but that still saves the cost of
+// writing it into a `RustBuffer` and then copying it out again on the rust side.
 
 @Structure.FieldOrder("len", "data")
-open class ForeignBytes : Structure() {
+{% call kt::keep_annotation() %}{{ config.visibility() }} open class ForeignBytes : Structure() {
     @JvmField var len: Int = 0
     @JvmField var data: Pointer? = null
 
-    class ByValue : ForeignBytes(), Structure.ByValue
+    {% call kt::keep_annotation() %}class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        // The native memory is owned by the returned struct, which keeps it alive
+        // until the struct itself is garbage-collected after the call.
+        internal fun lower(v: ByteArray): ForeignBytes.ByValue {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = v.size
+            if (v.isNotEmpty()) {
+                val mem = com.sun.jna.Memory(v.size.toLong())
+                mem.write(0, v, 0, v.size)
+                bytes.data = mem
+            }
+            return bytes
+        }
+    }
 }
+{% endif %}
 
 
 // A helper for structured writing of data into a `RustBuffer`.
@@ -54,12 +71,14 @@ open class ForeignBytes : Structure() {
 // TODO: we should benchmark writing things into a `RustBuffer` versus building
 // up a bytearray and then copying it across.
 
-class RustBufferBuilder() {
+{{ config.visibility() }} class RustBufferBuilder() {
     var rbuf = RustBuffer.ByValue()
     var bbuf: ByteBuffer? = null
 
     init {
-        val rbuf = RustBuffer.alloc(16) // Totally arbitrary initial size
+        // This defaults to a totally arbitrary initial size, but can be set using the
+        // `rust_buffer_initial_capacity` config option.
+        val rbuf = RustBuffer.alloc({{ config.rust_buffer_initial_capacity() }})
         rbuf.writeField("len", 0)
         this.setRustBuffer(rbuf)
     }
@@ -1,15 +1,48 @@
+{%- if func.is_async() %}
+
+{% call kt::function_docstring(func, "") -%}
+{% call kt::unsigned_types_annotation(func) %}
+{% call kt::jvm_overloads_annotation(func) %}
+{% call kt::function_annotations(func, "") -%}
+{{ config.visibility() }} {% call kt::actual() %}suspend fun {{ func.name()|fn_name_kt(config) }}({%- call kt::arg_list_actual(func) -%}){% match func.return_type() %}{% when Some with (return_type) %}: {{ return_type|type_kt(config) }}{% else %}{% endmatch %} {
+    val _result = suspendCoroutine<RustBuffer.ByValue> { continuation ->
+        {% call kt::to_async_ffi_call(func) %}
+    }
+    return liftFromRustBuffer(_result) { buf ->
+        when (buf.get()) {
+            CALLBACK_SUCCESS -> {% match func.return_type() %}{% when Some with (return_type) %}{{ "buf"|read_kt(return_type, config) }}{% else %}Unit{% endmatch %}
+            {%- match func.throws() %}
+            {%- when Some with (e) %}
+            CALLBACK_ERROR -> throw {{ e|exception_name_kt(config) }}.read(buf)
+            {%- else %}
+            {%- endmatch %}
+            else -> throw InternalException(String.read(buf))
+        }
+    }
+}
+
+{%- else %}
 {%- match func.return_type() -%}
 {%- when Some with (return_type) %}
 
+{% call kt::function_docstring(func, "") -%}
 {% call kt::unsigned_types_annotation(func) %}
-fun {{ func.name()|fn_name_kt }}({%- call kt::arg_list_decl(func) -%}): {{ return_type|type_kt }} {
+{% call kt::jvm_overloads_annotation(func) %}
+{% call kt::function_annotations(func, "") -%}
+{{ config.visibility() }} {% call kt::actual() %}fun {{ func.name()|fn_name_kt(config) }}({%- call kt::arg_list_actual(func) -%}): {{ return_type|type_kt(config) }} {
     val _retval = {% call kt::to_ffi_call(func) %}
-    return {{ "_retval"|lift_kt(return_type) }}
+    return {{ "_retval"|lift_kt(return_type, config) }}
 }
 
 {% when None -%}
+{#- Nothing to lift, so this only checks the call status, which throws if there was an error. #}
 
+{% call kt::function_docstring(func, "") -%}
 {% call kt::unsigned_types_annotation(func) %}
-fun {{ func.name()|fn_name_kt }}({% call kt::arg_list_decl(func) %}) =
+{% call kt::jvm_overloads_annotation(func) %}
+{% call kt::function_annotations(func, "") -%}
+{{ config.visibility() }} {% call kt::actual() %}fun {{ func.name()|fn_name_kt(config) }}({% call kt::arg_list_actual(func) %}) {
     {% call kt::to_ffi_call(func) %}
+}
 {% endmatch %}
+{%- endif %}
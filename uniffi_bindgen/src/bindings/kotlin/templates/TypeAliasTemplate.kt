@@ -0,0 +1,2 @@
+{% call kt::unsigned_types_annotation(type_) %}
+{{ config.visibility() }} typealias {{ name|class_name_kt(config) }} = {{ type_|aliased_type_kt(config) }}
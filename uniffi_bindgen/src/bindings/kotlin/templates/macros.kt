@@ -5,33 +5,84 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
+    {%- call _begin_intercept(func) -%}
+    {%- call _lend_callbacks(func) -%}
     {%- match func.throws() %}
     {%- when Some with (e) %}
-    rustCallWithError({{ e|exception_name_kt}})
+    rustCallWithError({{ e|exception_name_kt(config) }})
     {%- else %}
     rustCall()
     {%- endmatch %} { status ->
     _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %},{% endif %}status)
-}
+}{% call _end_lend_callbacks(func) %}{% call _end_intercept() %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) %}
+    {%- call _begin_intercept(func) -%}
+    {%- call _lend_callbacks(func) -%}
     {%- match func.throws() %}
     {%- when Some with (e) %}
-    rustCallWithError({{ e|exception_name_kt}})
+    rustCallWithError({{ e|exception_name_kt(config) }})
     {%- else %}
     rustCall()
     {%- endmatch %} { status ->
     _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}(
         {{- prefix }}, {% call _arg_list_ffi_call(func) %}{% if func.arguments().len() > 0 %}, {% endif %}status)
-}
+}{% call _end_lend_callbacks(func) %}{% call _end_intercept() %}
 {%- endmacro %}
 
+// Wrap the call in the configured `call_interceptor`, if there is one, passing it the name of
+// the FFI function. Without one, the call isn't wrapped in anything.
+{%- macro _begin_intercept(func) -%}
+    {%- match config.call_interceptor() -%}
+    {%- when Some with (interceptor) -%}
+    {{ interceptor }}.intercept("{{ func.ffi_func().name() }}") { {%- when None %}
+    {%- endmatch -%}
+{%- endmacro -%}
+
+{%- macro _end_intercept() -%}
+    {%- if config.call_interceptor().is_some() %} }{% endif -%}
+{%- endmacro -%}
+
+// Register any `[ByRef]` callbacks for just the duration of the call, with the handles in
+// scope for `_arg_list_ffi_call` to pass in their place.
+{%- macro _lend_callbacks(func) -%}
+    {%- for arg in func.arguments() -%}
+    {%- if arg.is_lent_callback() -%}
+    {{ arg.type_()|ffi_converter_name }}Internals.lend({{ arg.name()|var_name_kt(config) }}) { {{ arg.name()|var_name_kt(config) }}Handle ->{{ " " }}{% endif -%}
+    {%- endfor -%}
+{%- endmacro -%}
+
+{%- macro _end_lend_callbacks(func) -%}
+    {%- for arg in func.arguments() -%}
+    {%- if arg.is_lent_callback() %} }{% endif -%}
+    {%- endfor -%}
+{%- endmacro -%}
+
+
+// Start a call to an async function, passing in the continuation to resume once it completes.
+{%- macro to_async_ffi_call(func) -%}
+    val _handle = FutureContinuations.insert(continuation)
+    try {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.{{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}FutureContinuations, _handle, status)
+        }
+    } catch (e: Throwable) {
+        FutureContinuations.remove(_handle)
+        throw e
+    }
+{%- endmacro -%}
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
-        {{- arg.name()|lower_kt(arg.type_()) }}
-        {%- if !loop.last %}, {% endif %}
+        {%- if arg.is_borrowed_bytes() %}ForeignBytes.lower({{ arg.name()|var_name_kt(config) }})
+        {%- else %}
+        {%- if arg.is_lent_callback() %}{{ arg.name()|var_name_kt(config) }}Handle
+        {%- else %}
+        {{- arg.name()|lower_kt(arg.type_(), config) }}
+        {%- endif %}
+        {%- endif %}
+        {%- if !loop.last %}{{ ", " }}{% endif %}
     {%- endfor %}
 {%- endmacro -%}
 
@@ -40,23 +91,47 @@
 // Note the var_name_kt and type_kt filters.
 -#}
 
+{#-
+// Read an argument to a callback interface method, which is lent to the
+// callback rather than owned by it if it's marked as `[ByRef]`.
+-#}
+{%- macro read_callback_arg(arg) -%}
+{%- if arg.by_ref() -%}
+{{ "buf"|read_borrowed_kt(arg.type_(), config) }}
+{%- else -%}
+{{ "buf"|read_kt(arg.type_(), config) }}
+{%- endif -%}
+{%- endmacro -%}
+
 {% macro arg_list_decl(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name_kt }}: {{ arg.type_()|type_kt -}}
+        {{ arg.name()|var_name_kt(config) }}: {{ arg.type_()|type_kt(config) -}}
         {%- match arg.default_value() %}
-        {%- when Some with(literal) %} = {{ literal|literal_kt }}
+        {%- when Some with(literal) %} = {{ literal|default_kt(arg.type_(), config) }}
         {%- else %}
         {%- endmatch %}
-        {%- if !loop.last %}, {% endif -%}
+        {%- if !loop.last %}{{ ", " }}{% endif -%}
     {%- endfor %}
 {%- endmacro %}
 
 {% macro arg_list_protocol(func) %}
     {%- for arg in func.arguments() -%}
-        {{ arg.name()|var_name_kt }}: {{ arg.type_()|type_kt -}}
-        {%- if !loop.last %}, {% endif -%}
+        {{ arg.name()|var_name_kt(config) }}: {{ arg.type_()|type_kt(config) -}}
+        {%- if !loop.last %}{{ ", " }}{% endif -%}
     {%- endfor %}
 {%- endmacro %}
+
+{#-
+// Arglist for a function or constructor that's the `actual` of an `expect` declaration when
+// generating multiplatform bindings, which mustn't repeat the default values of the arguments.
+-#}
+{% macro arg_list_actual(func) %}
+    {%- if config.multiplatform() -%}
+    {%- call arg_list_protocol(func) -%}
+    {%- else -%}
+    {%- call arg_list_decl(func) -%}
+    {%- endif -%}
+{%- endmacro %}
 {#-
 // Arglist as used in the _UniFFILib function declations.
 // Note unfiltered name but type_ffi filters.
@@ -68,7 +143,115 @@
     uniffi_out_err: RustCallStatus
 {%- endmacro -%}
 
+{#-
+// KDoc comment for an item that might have a docstring.
+// The docstring_kt filter leaves us indented ready for the item itself.
+-#}
+{%- macro docstring(defn, indent) %}
+{%- if config.generate_docstrings() %}
+{%- match defn.docstring() %}
+{%- when Some with (text) %}{{ text|docstring_kt(indent) }}
+{%- else %}
+{%- endmatch %}
+{%- endif %}
+{%- endmacro -%}
+
+{#-
+// KDoc comment for a function or method that might have a docstring,
+// with tags for its arguments and return value.
+-#}
+{%- macro function_docstring(func, indent) %}
+{%- if config.generate_docstrings() %}
+{%- match func.docstring() %}
+{%- when Some with (text) %}{{ text|function_docstring_kt(func.arguments(), func.return_type().is_some(), indent, config) }}
+{%- else %}
+{%- endmatch %}
+{%- endif %}
+{%- endmacro -%}
+
+// Declare the exception that a function can throw, for the benefit of Java callers
+{%- macro throws_annotation(func) -%}
+{%- match func.throws() -%}
+{%- when Some with (e) -%}@Throws({{ e|exception_name_kt(config) }}::class)
+{%- else -%}
+{%- endmatch -%}
+{%- endmacro -%}
+
+{#-
+// The annotations that every function, method and constructor can have, like `@Deprecated`.
+// Each one that applies is followed by a line break and the indent, ready for the next, so
+// this goes last, straight before the signature. Any KDoc comment has to come before it.
+-#}
+{%- macro function_annotations(func, indent) -%}
+{%- if func.deprecated().is_some() -%}
+{% call deprecated_annotation(func) %}
+{{ indent }}
+{%- endif -%}
+{%- if func.throws().is_some() -%}
+{% call throws_annotation(func) %}
+{{ indent }}
+{%- endif -%}
+{%- endmacro -%}
+
+{#-
+// Everything that goes before the signature of a function or method, in the order that Kotlin
+// needs. The KDoc comment has to come first, or it isn't attached to the declaration.
+-#}
+{%- macro function_decorations(func, indent) -%}
+{%- call function_docstring(func, indent) -%}
+{%- call function_annotations(func, indent) -%}
+{%- endmacro -%}
+
+// Generate overloads for Java callers if the trailing arguments have default values
+{%- macro jvm_overloads_annotation(func) -%}
+{%- if config.jvm_overloads() -%}
+{%- for arg in func.arguments() -%}
+{%- if loop.last && arg.default_value().is_some() -%}@JvmOverloads{%- endif -%}
+{%- endfor -%}
+{%- endif -%}
+{%- endmacro -%}
+
+// Read or write the discriminant of a flat enum. This is always an `Int` in the generated
+// code, but might be serialized using fewer bytes if the enum has a `[Repr]` attribute.
+// Discriminants are never negative, so the narrower types are read without sign extension,
+// which `get()` and `getShort()` would otherwise do for values with the top bit set. They're
+// written by truncating the `Int` back down to the same bits. A `u32` doesn't need either,
+// since its discriminants have to fit in an `i32`, so it's read and written like the default.
+{%- macro read_discriminant(e) -%}
+{%- match e.discriminant_type() -%}
+{%- when Type::UInt8 -%}buf.get().toInt() and 0xff
+{%- when Type::UInt16 -%}buf.getShort().toInt() and 0xffff
+{%- else -%}buf.getInt()
+{%- endmatch -%}
+{%- endmacro -%}
+
+{%- macro write_discriminant(e, value) -%}
+{%- match e.discriminant_type() -%}
+{%- when Type::UInt8 -%}buf.put({{ value }}.toByte())
+{%- when Type::UInt16 -%}buf.putShort({{ value }}.toShort())
+{%- else -%}buf.putInt({{ value }})
+{%- endmatch -%}
+{%- endmacro -%}
+
+// Mark an item from a `[Deprecated="message"]` attribute as deprecated
+{%- macro deprecated_annotation(item) -%}
+{%- match item.deprecated() -%}
+{%- when Some with (message) -%}@Deprecated({{ message|string_literal_kt }})
+{%- else -%}
+{%- endmatch -%}
+{%- endmacro -%}
+
+// Keep a class that JNA uses via reflection from being stripped or renamed by R8
+{%- macro keep_annotation() -%}
+{%- if config.android_keep() -%}{{ "@Keep " }}{% endif -%}
+{%- endmacro -%}
+
+// Mark a declaration as the `actual` of one in the common source set, for multiplatform bindings
+{%- macro actual() -%}
+{%- if config.multiplatform() %}{{ "actual " }}{% endif -%}
+{%- endmacro -%}
+
 // Add annotation if there are unsigned types
 {%- macro unsigned_types_annotation(member) -%}
-{% if ci.item_contains_unsigned_types(member) %}@ExperimentalUnsignedTypes{% endif %}
+{% if config.use_unsigned_types() && ci.item_contains_unsigned_types(member) %}@ExperimentalUnsignedTypes{% endif %}
 {%- endmacro -%}
@@ -1,9 +1,6 @@
-// This file was autogenerated by some hot garbage in the `uniffi` crate.
-// Trust me, you don't want to mess with it!
-
-@file:Suppress("NAME_SHADOWING")
-
-package {{ config.package_name() }};
+{% let external_imports = ci|external_imports_kt(config) -%}
+{% let package_name = config.package_name() -%}
+{% include "Header.kt" %}
 
 // Common helper code.
 //
@@ -17,18 +14,6 @@ package {{ config.package_name() }};
 // compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
 // helpers directly inline like we're doing here.
 
-import com.sun.jna.Library
-import com.sun.jna.Native
-import com.sun.jna.Pointer
-import com.sun.jna.Structure
-import java.nio.ByteBuffer
-import java.nio.ByteOrder
-import java.util.concurrent.atomic.AtomicLong
-import java.util.concurrent.atomic.AtomicBoolean
-import java.util.concurrent.atomic.AtomicReference
-import java.util.concurrent.locks.ReentrantLock
-import kotlin.concurrent.withLock
-
 {% include "RustBufferTemplate.kt" %}
 
 {% include "RustBufferHelpers.kt" %}
@@ -38,24 +23,54 @@ import kotlin.concurrent.withLock
 {% include "Helpers.kt" %}
 
 // Public interface members begin here.
+{%- if !config.multiplatform() %}
+// Custom types
+{% for (name, prim) in ci.iter_wrapped_types() %}
+{% include "CustomTypeTemplate.kt" %}
+{%- endfor %}
+
+// Type aliases
+{% for (name, type_) in config.type_aliases() %}
+{% include "TypeAliasTemplate.kt" %}
+{%- endfor %}
+
+// Constants
+{% for c in ci.iter_constant_definitions() %}
+{% include "ConstantTemplate.kt" %}
+{%- endfor %}
+{%- endif %}
+
+{%- if !config.split_files() %}
 // Public facing enums
 {% for e in ci.iter_enum_definitions() %}
 {% include "EnumTemplate.kt" %}
 {%- endfor -%}
+{%- endif %}
 
 // Error definitions
 {% include "ErrorTemplate.kt" %}
 
+{%- for error in ci.iter_error_definitions() %}
+{%- if ci.is_error_used_as_value(error.name()) %}
+// The error {{ error.name() }} is also used as a value, so it gets a sealed class of its own
+{%- let e = error.wrapped_enum() %}
+{% include "EnumTemplate.kt" %}
+{%- endif %}
+{%- endfor %}
+
+{%- if !config.split_files() %}
 // Public facing records
 {%- for rec in ci.iter_record_definitions() %}
 {% include "RecordTemplate.kt" %}
 {% endfor %}
+{%- endif %}
 
 // Namespace functions
 {% for func in ci.iter_function_definitions() %}
 {% include "TopLevelFunctionTemplate.kt" %}
 {% endfor %}
 
+{%- if !config.split_files() %}
 // Objects
 {% for obj in ci.iter_object_definitions() %}
 {% include "ObjectTemplate.kt" %}
@@ -65,5 +80,6 @@ import kotlin.concurrent.withLock
 {% for cbi in ci.iter_callback_interface_definitions() %}
 {% include "CallbackInterfaceTemplate.kt" %}
 {% endfor %}
+{%- endif %}
 
 {% import "macros.kt" as kt %}
@@ -9,6 +9,7 @@
 
 use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::path::Path;
 
@@ -20,13 +21,16 @@ pub mod python;
 pub mod ruby;
 pub mod swift;
 
+pub use kotlin::generate_bindings as generate_kotlin_bindings;
+pub use kotlin::validate as validate_kotlin_bindings;
+
 /// Enumeration of all foreign language targets currently supported by this crate.
 ///
 /// The functions in this module will delegate to a language-specific backend based
 /// on the provided `TargetLanguage`. For convenience of calling code we also provide
 /// a few `TryFrom` implementations to help guess the correct target language from
 /// e.g. a file extension of command-line argument.
-#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TargetLanguage {
     Kotlin,
     Swift,
@@ -76,6 +80,16 @@ pub struct Config {
     ruby: ruby::Config,
 }
 
+impl Config {
+    /// The prefix to use for the names of FFI functions, if it should differ from the namespace.
+    ///
+    /// This is configured in the `[bindings.kotlin]` section, but it applies to the scaffolding
+    /// and to the bindings for every language, since they all need to agree on the names.
+    pub(crate) fn ffi_namespace(&self) -> Option<&str> {
+        self.kotlin.ffi_namespace()
+    }
+}
+
 impl From<&ComponentInterface> for Config {
     fn from(ci: &ComponentInterface) -> Self {
         Config {
@@ -99,20 +113,26 @@ impl MergeWith for Config {
 }
 
 /// Generate foreign language bindings from a compiled `uniffi` library.
+///
+/// Returns a description of anything that the bindings handle in a lossy way, which is
+/// currently only reported by the Kotlin backend.
 pub fn write_bindings<P>(
     config: &Config,
     ci: &ComponentInterface,
     out_dir: P,
     language: TargetLanguage,
     try_format_code: bool,
-) -> Result<()>
+) -> Result<Vec<String>>
 where
     P: AsRef<Path>,
 {
     let out_dir = out_dir.as_ref();
+    let mut warnings = Vec::new();
     match language {
         TargetLanguage::Kotlin => {
-            kotlin::write_bindings(&config.kotlin, ci, out_dir, try_format_code)?
+            let kotlin_warnings =
+                kotlin::write_bindings(&config.kotlin, ci, out_dir, try_format_code)?;
+            warnings.extend(kotlin_warnings.iter().map(ToString::to_string));
         }
         TargetLanguage::Swift => {
             swift::write_bindings(&config.swift, ci, out_dir, try_format_code)?
@@ -122,7 +142,36 @@ where
         }
         TargetLanguage::Ruby => ruby::write_bindings(&config.ruby, ci, out_dir, try_format_code)?,
     }
-    Ok(())
+    Ok(warnings)
+}
+
+/// Generate foreign language bindings for several languages at once, as file names and contents.
+///
+/// Every language is generated from the same `ComponentInterface`, so the UDL only needs to be
+/// parsed once, and the bindings are guaranteed to agree on what the interface looks like.
+/// Nothing is written to the filesystem, and the code isn't formatted.
+pub fn generate_binding_files(
+    config: &Config,
+    ci: &ComponentInterface,
+    languages: &[TargetLanguage],
+) -> Result<HashMap<TargetLanguage, Vec<(String, String)>>> {
+    let mut files = HashMap::new();
+    for &language in languages {
+        let language_files = match language {
+            TargetLanguage::Kotlin => kotlin::generate_binding_files(&config.kotlin, ci)?,
+            TargetLanguage::Swift => swift::generate_binding_files(&config.swift, ci)?,
+            TargetLanguage::Python => vec![(
+                format!("{}.py", ci.namespace()),
+                python::generate_python_bindings(&config.python, ci)?,
+            )],
+            TargetLanguage::Ruby => vec![(
+                format!("{}.rb", ci.namespace()),
+                ruby::generate_ruby_bindings(&config.ruby, ci)?,
+            )],
+        };
+        files.insert(language, language_files);
+    }
+    Ok(files)
 }
 
 /// Compile generated foreign language bindings so they're ready for use.
@@ -161,3 +210,35 @@ where
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_several_languages_can_be_generated_at_once() {
+        let ci =
+            ComponentInterface::from_webidl("namespace example { u32 get_answer(); };").unwrap();
+        let config = Config::from(&ci);
+        let files = generate_binding_files(
+            &config,
+            &ci,
+            &[TargetLanguage::Kotlin, TargetLanguage::Python],
+        )
+        .unwrap();
+        assert_eq!(files.len(), 2);
+        let kotlin = &files[&TargetLanguage::Kotlin];
+        assert_eq!(kotlin[0].0, "example.kt");
+        assert!(kotlin[0].1.contains("fun getAnswer("));
+        let python = &files[&TargetLanguage::Python];
+        assert_eq!(python[0].0, "example.py");
+        assert!(python[0].1.contains("def get_answer("));
+        // Both languages call the same FFI functions, since they share the interface.
+        let ffi_name = ci.iter_function_definitions()[0]
+            .ffi_func()
+            .name()
+            .to_string();
+        assert!(kotlin[0].1.contains(&ffi_name));
+        assert!(python[0].1.contains(&ffi_name));
+    }
+}
@@ -76,6 +76,9 @@ mod filters {
             FFIType::RustBuffer => "RustBuffer".to_string(),
             FFIType::ForeignBytes => "ForeignBytes".to_string(),
             FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
+            FFIType::FutureCallback => {
+                unreachable!("[Async] functions are rejected before rendering")
+            }
         })
     }
 
@@ -132,6 +135,18 @@ mod filters {
         Ok(nm.to_string().to_shouty_snake_case())
     }
 
+    /// Get the size and `struct` format used to serialize an enum discriminant of the given type,
+    /// as arguments for `_unpack_from` or `_pack_into`.
+    pub fn discriminant_format_py(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::UInt8 => r#"1, ">B""#,
+            Type::UInt16 => r#"2, ">H""#,
+            Type::UInt32 => r#"4, ">I""#,
+            _ => r#"4, ">i""#,
+        }
+        .into())
+    }
+
     pub fn coerce_py(nm: &dyn fmt::Display, type_: &Type) -> Result<String, askama::Error> {
         Ok(match type_ {
             Type::Int8
@@ -142,8 +157,15 @@ mod filters {
             | Type::UInt32
             | Type::Int64
             | Type::UInt64 => format!("int({})", nm), // TODO: check max/min value
+            Type::Int128 | Type::UInt128 => {
+                panic!("No support for 128-bit integers in Python, yet")
+            }
+            Type::Char => panic!("No support for char in Python, yet"),
+            Type::Stream(_) => panic!("No support for streams in Python, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Python, yet"),
             Type::Float32 | Type::Float64 => format!("float({})", nm),
             Type::Boolean => format!("bool({})", nm),
+            Type::Bytes => format!("bytes({})", nm),
             Type::String
             | Type::Object(_)
             | Type::Enum(_)
@@ -154,10 +176,10 @@ mod filters {
             Type::CallbackInterface(_) => panic!("No support for coercing callback interfaces yet"),
             Type::Optional(t) => format!("(None if {} is None else {})", nm, coerce_py(nm, t)?),
             Type::Sequence(t) => format!("list({} for x in {})", coerce_py(&"x", t)?, nm),
-            Type::Map(t) => format!(
+            Type::Map(k, v) => format!(
                 "dict(({},{}) for (k, v) in {}.items())",
-                coerce_py(&"k", &Type::String)?,
-                coerce_py(&"v", t)?,
+                coerce_py(&"k", k)?,
+                coerce_py(&"v", v)?,
                 nm
             ),
             Type::Wrapped { prim, .. } => coerce_py(nm, prim.as_ref())?,
@@ -177,16 +199,23 @@ mod filters {
             | Type::UInt64
             | Type::Float32
             | Type::Float64 => nm.to_string(),
+            Type::Int128 | Type::UInt128 => {
+                panic!("No support for 128-bit integers in Python, yet")
+            }
+            Type::Char => panic!("No support for char in Python, yet"),
+            Type::Stream(_) => panic!("No support for streams in Python, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Python, yet"),
             Type::Boolean => format!("(1 if {} else 0)", nm),
             Type::String => format!("RustBuffer.allocFromString({})", nm),
-            Type::Object(_) => format!("({}._pointer)", nm),
+            Type::Bytes => format!("RustBuffer.allocFromBytes({})", nm),
+            Type::Object(name) => format!("{}._lower({})", class_name_py(name)?, nm),
             Type::CallbackInterface(_) => panic!("No support for lowering callback interfaces yet"),
             Type::Error(_) => panic!("No support for lowering errors, yet"),
             Type::Enum(_)
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_)
+            | Type::Map(_, _)
             | Type::Timestamp
             | Type::Duration => format!(
                 "RustBuffer.allocFrom{}({})",
@@ -208,17 +237,24 @@ mod filters {
             | Type::UInt32
             | Type::Int64
             | Type::UInt64 => format!("int({})", nm),
+            Type::Int128 | Type::UInt128 => {
+                panic!("No support for 128-bit integers in Python, yet")
+            }
+            Type::Char => panic!("No support for char in Python, yet"),
+            Type::Stream(_) => panic!("No support for streams in Python, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Python, yet"),
             Type::Float32 | Type::Float64 => format!("float({})", nm),
             Type::Boolean => format!("(True if {} else False)", nm),
             Type::String => format!("{}.consumeIntoString()", nm),
-            Type::Object(name) => format!("{}._make_instance_({})", class_name_py(name)?, nm),
+            Type::Bytes => format!("{}.consumeIntoBytes()", nm),
+            Type::Object(name) => format!("{}._lift({})", class_name_py(name)?, nm),
             Type::CallbackInterface(_) => panic!("No support for lifting callback interfaces, yet"),
             Type::Error(_) => panic!("No support for lowering errors, yet"),
             Type::Enum(_)
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_)
+            | Type::Map(_, _)
             | Type::Timestamp
             | Type::Duration => format!(
                 "{}.consumeInto{}()",
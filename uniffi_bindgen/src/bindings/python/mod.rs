@@ -17,6 +17,7 @@ pub mod gen_python;
 pub use gen_python::{Config, PythonWrapper};
 
 use super::super::interface::ComponentInterface;
+use crate::BindgenError;
 
 // Generate python bindings for the given ComponentInterface, in the given output directory.
 
@@ -48,9 +49,21 @@ pub fn write_bindings(
 
 pub fn generate_python_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
+    for func in ci.iter_function_definitions() {
+        if func.is_async() {
+            return Err(BindgenError::UnsupportedType {
+                name: func.name().to_string(),
+                message: format!(
+                    "No support for [Async] functions in Python, yet: \"{}\"",
+                    func.name()
+                ),
+            }
+            .into());
+        }
+    }
     PythonWrapper::new(config.clone(), ci)
         .render()
-        .map_err(|_| anyhow::anyhow!("failed to render python bindings"))
+        .context("failed to render python bindings")
 }
 
 /// Execute the specifed python script, with environment based on the generated
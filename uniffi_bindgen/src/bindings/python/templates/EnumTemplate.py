@@ -8,7 +8,7 @@
 
 class {{ e.name()|class_name_py }}(enum.Enum):
     {% for variant in e.variants() -%}
-    {{ variant.name()|enum_name_py }} = {{ loop.index }}
+    {{ variant.name()|enum_name_py }} = {{ variant.discriminant() }}
     {% endfor %}
 
 {% else %}
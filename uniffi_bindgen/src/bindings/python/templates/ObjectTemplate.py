@@ -22,6 +22,28 @@ class {{ obj.name()|class_name_py }}(object):
         inst._pointer = pointer
         return inst
 
+    # Helpers for passing instances of this class over the FFI, either directly as a
+    # pointer or written into a buffer as part of some other type.
+    @classmethod
+    def _lift(cls, pointer):
+        return cls._make_instance_(pointer)
+
+    @classmethod
+    def _lower(cls, value):
+        if not isinstance(value, cls):
+            raise TypeError("Expected {} instance, {} found".format(cls.__name__, value.__class__.__name__))
+        return value._pointer
+
+    @classmethod
+    def _read(cls, stream):
+        # The Rust code always expects pointers written as 8 bytes,
+        # and will fail to compile if they don't fit in that size.
+        return cls._lift(stream._unpack_from(8, ">Q"))
+
+    @classmethod
+    def _write(cls, value, builder):
+        builder._pack_into(8, ">Q", cls._lower(value))
+
     {% for cons in obj.alternate_constructors() -%}
     @classmethod
     def {{ cons.name()|fn_name_py }}(cls, {% call py::arg_list_decl(cons) %}):
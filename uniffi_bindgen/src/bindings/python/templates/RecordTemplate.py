@@ -3,6 +3,9 @@ class {{ rec.name()|class_name_py }}(object):
         {%- for field in rec.fields() %}
         self.{{ field.name()|var_name_py }} = {{ field.name()|var_name_py }}
         {%- endfor %}
+        {%- if rec.fields().is_empty() %}
+        pass
+        {%- endif %}
 
     def __str__(self):
         return "{{ rec.name()|class_name_py }}({% for field in rec.fields() %}{{ field.name() }}={}{% if loop.last %}{% else %}, {% endif %}{% endfor %})".format({% for field in rec.fields() %}self.{{ field.name() }}{% if loop.last %}{% else %}, {% endif %}{% endfor %})
@@ -121,6 +121,13 @@ class RustBufferTypeBuilder(object):
         builder._pack_into(4, ">i", len(utf8Bytes))
         builder.write(utf8Bytes)
 
+    {% when Type::Bytes -%}
+
+    @staticmethod
+    def writeBytes(builder, v):
+        builder._pack_into(4, ">i", len(v))
+        builder.write(v)
+
     {% when Type::Timestamp -%}
 
     @staticmethod
@@ -150,15 +157,10 @@ class RustBufferTypeBuilder(object):
 
     {% when Type::Object with (object_name) -%}
     # The Object type {{ object_name }}.
-    # We write the pointer value directly - what could possibly go wrong?
 
-    @classmethod
-    def write{{ canonical_type_name }}(cls, builder, v):
-        if not isinstance(v, {{ object_name|class_name_py }}):
-            raise TypeError("Expected {{ object_name|class_name_py }} instance, {} found".format(v.__class__.__name__))
-        # The Rust code always expects pointers written as 8 bytes,
-        # and will fail to compile if they don't fit in that size.
-        cls.writeU64(builder, v._pointer)
+    @staticmethod
+    def write{{ canonical_type_name }}(builder, v):
+        {{ object_name|class_name_py }}._write(v, builder)
 
     {% when Type::Enum with (enum_name) -%}
     {%- let e = ci.get_enum_definition(enum_name).unwrap() -%}
@@ -167,11 +169,11 @@ class RustBufferTypeBuilder(object):
     @classmethod
     def write{{ canonical_type_name }}(cls, builder, v):
         {%- if e.is_flat() %}
-        builder._pack_into(4, ">i", v.value)
+        builder._pack_into({{ e.discriminant_type()|discriminant_format_py }}, v.value)
         {%- else -%}
         {%- for variant in e.variants() %}
         if v.is_{{ variant.name()|var_name_py }}():
-            builder._pack_into(4, ">i", {{ loop.index }})
+            builder._pack_into(4, ">i", {{ variant.discriminant() }})
             {%- for field in variant.fields() %}
             cls.write{{ field.type_().canonical_name()|class_name_py }}(builder, v.{{ field.name() }})
             {%- endfor %}
@@ -187,6 +189,9 @@ class RustBufferTypeBuilder(object):
         {%- for field in rec.fields() %}
         cls.write{{ field.type_().canonical_name()|class_name_py }}(builder, v.{{ field.name() }})
         {%- endfor %}
+        {%- if rec.fields().is_empty() %}
+        pass
+        {%- endif %}
 
     {% when Type::Optional with (inner_type) -%}
     # The Optional<T> type for {{ inner_type.canonical_name() }}.
@@ -208,15 +213,15 @@ class RustBufferTypeBuilder(object):
         for item in items:
             cls.write{{ inner_type.canonical_name()|class_name_py }}(builder, item)
 
-    {% when Type::Map with (inner_type) -%}
-    # The Map<T> type for {{ inner_type.canonical_name() }}.
+    {% when Type::Map with (key_type, value_type) -%}
+    # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
     @classmethod
     def write{{ canonical_type_name }}(cls, builder, items):
         builder._pack_into(4, ">i", len(items))
         for (k, v) in items.items():
-            cls.writeString(builder, k)
-            cls.write{{ inner_type.canonical_name()|class_name_py }}(builder, v)
+            cls.write{{ key_type.canonical_name()|class_name_py }}(builder, k)
+            cls.write{{ value_type.canonical_name()|class_name_py }}(builder, v)
 
     {% when Type::Wrapped with { name, prim } %}
 
@@ -118,6 +118,15 @@ class RustBufferTypeReader(object):
         utf8Bytes = stream.read(size)
         return utf8Bytes.decode("utf-8")
 
+    {% when Type::Bytes -%}
+
+    @staticmethod
+    def readBytes(stream):
+        size = stream._unpack_from(4, ">i")
+        if size < 0:
+            raise InternalError("Unexpected negative byte string length")
+        return bytes(stream.read(size))
+
 
     {% when Type::Timestamp -%}
     # The Timestamp type.
@@ -152,10 +161,7 @@ class RustBufferTypeReader(object):
 
     @staticmethod
     def read{{ canonical_type_name }}(stream):
-        # The Rust code always expects pointers written as 8 bytes,
-        # and will fail to compile if they don't fit in that size.
-        pointer = stream._unpack_from(8, ">Q")
-        return {{ object_name|class_name_py }}._make_instance_(pointer)
+        return {{ object_name|class_name_py }}._read(stream)
 
     {% when Type::Enum with (enum_name) -%}
     {%- let e = ci.get_enum_definition(enum_name).unwrap() -%}
@@ -163,12 +169,12 @@ class RustBufferTypeReader(object):
 
     @classmethod
     def read{{ canonical_type_name }}(cls, stream):
-        variant = stream._unpack_from(4, ">i")
+        variant = stream._unpack_from({{ e.discriminant_type()|discriminant_format_py }})
         {% if e.is_flat() -%}
         return {{ enum_name|class_name_py }}(variant)
         {%- else -%}
         {%- for variant in e.variants() %}
-        if variant == {{ loop.index }}:
+        if variant == {{ variant.discriminant() }}:
             {%- if variant.has_fields() %}
             return {{ enum_name|class_name_py }}.{{ variant.name()|enum_name_py }}(
                 {%- for field in variant.fields() %}
@@ -201,7 +207,7 @@ class RustBufferTypeReader(object):
     {%- for variant in e.variants() %}
 
     @classmethod
-    def readVariant{{ loop.index}}Of{{ canonical_type_name }}(cls, stream):
+    def readVariant{{ variant.discriminant() }}Of{{ canonical_type_name }}(cls, stream):
         {%- if e.is_flat() %}
         return {{ error_name|class_name_py }}.{{ variant.name()|class_name_py }}(cls.readString(stream))
         {%- else %}
@@ -256,8 +262,8 @@ class RustBufferTypeReader(object):
             count -= 1
         return items
 
-    {% when Type::Map with (inner_type) -%}
-    # The Map<T> type for {{ inner_type.canonical_name() }}.
+    {% when Type::Map with (key_type, value_type) -%}
+    # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
     @classmethod
     def read{{ canonical_type_name }}(cls, stream):
@@ -266,8 +272,8 @@ class RustBufferTypeReader(object):
             raise InternalError("Unexpected negative map size")
         items = {}
         while count > 0:
-            key = cls.readString(stream)
-            items[key] = cls.read{{ inner_type.canonical_name()|class_name_py }}(stream)
+            key = cls.read{{ key_type.canonical_name()|class_name_py }}(stream)
+            items[key] = cls.read{{ value_type.canonical_name()|class_name_py }}(stream)
             count -= 1
         return items
 
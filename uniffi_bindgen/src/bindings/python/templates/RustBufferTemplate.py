@@ -76,6 +76,19 @@ class RustBuffer(ctypes.Structure):
         with self.consumeWithStream() as stream:
             return stream.read(stream.remaining()).decode("utf-8")
 
+    {% when Type::Bytes -%}
+    # The primitive Bytes type.
+
+    @staticmethod
+    def allocFromBytes(value):
+        with RustBuffer.allocWithBuilder() as builder:
+            RustBufferTypeBuilder.writeBytes(builder, value)
+            return builder.finalize()
+
+    def consumeIntoBytes(self):
+        with self.consumeWithStream() as stream:
+            return RustBufferTypeReader.readBytes(stream)
+
     {% when Type::Timestamp -%}
 
     @staticmethod
@@ -154,8 +167,8 @@ class RustBuffer(ctypes.Structure):
         with self.consumeWithStream() as stream:
             return RustBufferTypeReader.read{{ canonical_type_name }}(stream)
 
-    {% when Type::Map with (inner_type) -%}
-    # The Map<T> type for {{ inner_type.canonical_name() }}.
+    {% when Type::Map with (key_type, value_type) -%}
+    # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
     @staticmethod
     def allocFrom{{ canonical_type_name }}(v):
@@ -179,5 +192,17 @@ class ForeignBytes(ctypes.Structure):
         ("data", ctypes.POINTER(ctypes.c_char)),
     ]
 
+    @staticmethod
+    def fromBytes(value):
+        # Used for `[ByRef] bytes` arguments, which the rust code reads in place
+        # for the duration of the call. We hold on to the underlying buffer so that it
+        # stays alive for as long as the struct does.
+        if not value:
+            return ForeignBytes(len=0, data=None)
+        buf = ctypes.create_string_buffer(bytes(value), len(value))
+        foreignBytes = ForeignBytes(len=len(value), data=ctypes.cast(buf, ctypes.POINTER(ctypes.c_char)))
+        foreignBytes._buf = buf
+        return foreignBytes
+
     def __str__(self):
         return "ForeignBytes(len={}, data={})".format(self.len, self.data[0:self.len])
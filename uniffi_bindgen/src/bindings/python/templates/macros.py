@@ -32,7 +32,10 @@ rust_call(
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}ForeignBytes.fromBytes({{ arg.name() }})
+        {%- else %}
         {{- arg.name()|lower_py(arg.type_()) }}
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -94,8 +94,11 @@ mod filters {
             FFIType::Float64 => ":double".to_string(),
             FFIType::RustArcPtr => ":pointer".to_string(),
             FFIType::RustBuffer => "RustBuffer.by_value".to_string(),
-            FFIType::ForeignBytes => "ForeignBytes".to_string(),
+            FFIType::ForeignBytes => "ForeignBytes.by_value".to_string(),
             FFIType::ForeignCallback => unimplemented!("Callback interfaces are not implemented"),
+            FFIType::FutureCallback => {
+                unreachable!("[Async] functions are rejected before rendering")
+            }
         })
     }
 
@@ -147,6 +150,18 @@ mod filters {
         Ok(format!("{}{}", prefix, nm.to_snake_case()))
     }
 
+    /// Get the size and `pack` format used to serialize an enum discriminant of the given type,
+    /// as arguments for `unpack_from` or `pack_into`.
+    pub fn discriminant_format_rb(type_: &Type) -> Result<String, askama::Error> {
+        Ok(match type_ {
+            Type::UInt8 => "1, 'C'",
+            Type::UInt16 => "2, 'S>'",
+            Type::UInt32 => "4, 'L>'",
+            _ => "4, 'l>'",
+        }
+        .into())
+    }
+
     pub fn enum_name_rb(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
         Ok(nm.to_string().to_shouty_snake_case())
     }
@@ -161,12 +176,17 @@ mod filters {
             | Type::UInt32
             | Type::Int64
             | Type::UInt64 => format!("{}.to_i", nm), // TODO: check max/min value
+            Type::Int128 | Type::UInt128 => panic!("No support for 128-bit integers in Ruby, yet"),
+            Type::Char => panic!("No support for char in Ruby, yet"),
+            Type::Stream(_) => panic!("No support for streams in Ruby, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Ruby, yet"),
             Type::Float32 | Type::Float64 => format!("{}.to_f", nm),
             Type::Boolean => format!("{} ? true : false", nm),
             Type::Object(_) | Type::Enum(_) | Type::Error(_) | Type::Record(_) => nm.to_string(),
             Type::String => format!("{}.to_s", nm),
             Type::Timestamp => panic!("No support for timestamps in Ruby, yet"),
             Type::Duration => panic!("No support for durations in Ruby, yet"),
+            Type::Bytes => panic!("No support for bytes in Ruby, yet"),
             Type::CallbackInterface(_) => panic!("No support for coercing callback interfaces yet"),
             Type::Optional(t) => format!("({} ? {} : nil)", nm, coerce_rb(nm, t)?),
             Type::Sequence(t) => {
@@ -177,9 +197,9 @@ mod filters {
                     format!("{}.map {{ |v| {} }}", nm, coerce_code)
                 }
             }
-            Type::Map(t) => {
-                let k_coerce_code = coerce_rb(&"k", &Type::String)?;
-                let v_coerce_code = coerce_rb(&"v", t)?;
+            Type::Map(k, v) => {
+                let k_coerce_code = coerce_rb(&"k", k)?;
+                let v_coerce_code = coerce_rb(&"v", v)?;
 
                 if k_coerce_code == "k" && v_coerce_code == "v" {
                     nm.to_string()
@@ -207,10 +227,15 @@ mod filters {
             | Type::UInt64
             | Type::Float32
             | Type::Float64 => nm.to_string(),
+            Type::Int128 | Type::UInt128 => panic!("No support for 128-bit integers in Ruby, yet"),
+            Type::Char => panic!("No support for char in Ruby, yet"),
+            Type::Stream(_) => panic!("No support for streams in Ruby, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Ruby, yet"),
             Type::Boolean => format!("({} ? 1 : 0)", nm),
             Type::String => format!("RustBuffer.allocFromString({})", nm),
             Type::Timestamp => panic!("No support for timestamps in Ruby, yet"),
             Type::Duration => panic!("No support for durations in Ruby, yet"),
+            Type::Bytes => panic!("No support for bytes in Ruby, yet"),
             Type::Object(name) => format!("({}._uniffi_lower {})", class_name_rb(name)?, nm),
             Type::CallbackInterface(_) => panic!("No support for lowering callback interfaces yet"),
             Type::Error(_) => panic!("No support for lowering errors, yet"),
@@ -218,7 +243,7 @@ mod filters {
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_) => format!(
+            | Type::Map(_, _) => format!(
                 "RustBuffer.alloc_from_{}({})",
                 class_name_rb(&type_.canonical_name())?,
                 nm
@@ -238,11 +263,16 @@ mod filters {
             | Type::UInt32
             | Type::Int64
             | Type::UInt64 => format!("{}.to_i", nm),
+            Type::Int128 | Type::UInt128 => panic!("No support for 128-bit integers in Ruby, yet"),
+            Type::Char => panic!("No support for char in Ruby, yet"),
+            Type::Stream(_) => panic!("No support for streams in Ruby, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Ruby, yet"),
             Type::Float32 | Type::Float64 => format!("{}.to_f", nm),
             Type::Boolean => format!("1 == {}", nm),
             Type::String => format!("{}.consumeIntoString", nm),
             Type::Timestamp => panic!("No support for timestamps in Ruby, yet"),
             Type::Duration => panic!("No support for durations in Ruby, yet"),
+            Type::Bytes => panic!("No support for bytes in Ruby, yet"),
             Type::Object(name) => format!("{}._uniffi_allocate({})", class_name_rb(name)?, nm),
             Type::CallbackInterface(_) => panic!("No support for lifting callback interfaces, yet"),
             Type::Error(_) => panic!("No support for lowering errors, yet"),
@@ -250,7 +280,7 @@ mod filters {
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_) => format!(
+            | Type::Map(_, _) => format!(
                 "{}.consumeInto{}",
                 nm,
                 class_name_rb(&type_.canonical_name())?
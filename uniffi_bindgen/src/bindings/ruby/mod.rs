@@ -17,6 +17,7 @@ pub mod gen_ruby;
 pub use gen_ruby::{Config, RubyWrapper};
 
 use super::super::interface::ComponentInterface;
+use crate::BindgenError;
 
 // Generate ruby bindings for the given ComponentInterface, in the given output directory.
 
@@ -52,9 +53,21 @@ pub fn write_bindings(
 
 pub fn generate_ruby_bindings(config: &Config, ci: &ComponentInterface) -> Result<String> {
     use askama::Template;
+    for func in ci.iter_function_definitions() {
+        if func.is_async() {
+            return Err(BindgenError::UnsupportedType {
+                name: func.name().to_string(),
+                message: format!(
+                    "No support for [Async] functions in Ruby, yet: \"{}\"",
+                    func.name()
+                ),
+            }
+            .into());
+        }
+    }
     RubyWrapper::new(config.clone(), ci)
         .render()
-        .map_err(|_| anyhow::anyhow!("failed to render ruby bindings"))
+        .context("failed to render ruby bindings")
 }
 
 /// Execute the specifed ruby script, with environment based on the generated
@@ -2,7 +2,7 @@
 
 class {{ e.name()|class_name_rb }}
   {% for variant in e.variants() -%}
-  {{ variant.name()|enum_name_rb }} = {{ loop.index }}
+  {{ variant.name()|enum_name_rb }} = {{ variant.discriminant() }}
   {% endfor %}
 end
 
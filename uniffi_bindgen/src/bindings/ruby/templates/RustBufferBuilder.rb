@@ -119,11 +119,11 @@ class RustBufferBuilder
 
   def write_{{ canonical_type_name }}(v)
     {%- if e.is_flat() %}
-    pack_into(4, 'l>', v)
+    pack_into({{ e.discriminant_type()|discriminant_format_rb }}, v)
     {%- else -%}
     {%- for variant in e.variants() %}
     if v.{{ variant.name()|var_name_rb }}?
-      pack_into(4, 'l>', {{ loop.index }})
+      pack_into(4, 'l>', {{ variant.discriminant() }})
       {%- for field in variant.fields() %}
       self.write_{{ field.type_().canonical_name()|class_name_rb }}(v.{{ field.name() }})
       {%- endfor %}
@@ -165,15 +165,15 @@ class RustBufferBuilder
     end
   end
 
-  {% when Type::Map with (inner_type) -%}
-  # The Map<T> type for {{ inner_type.canonical_name() }}.
+  {% when Type::Map with (key_type, value_type) -%}
+  # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
   def write_{{ canonical_type_name }}(items)
     pack_into(4, 'l>', items.size)
 
     items.each do |k, v|
-      write_String(k)
-      self.write_{{ inner_type.canonical_name()|class_name_rb }}(v)
+      self.write_{{ key_type.canonical_name()|class_name_rb }}(k)
+      self.write_{{ value_type.canonical_name()|class_name_rb }}(v)
     end
   end
 
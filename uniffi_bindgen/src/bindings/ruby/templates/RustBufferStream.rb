@@ -119,10 +119,10 @@ class RustBufferStream
   # The Enum type {{ enum_name }}.
 
   def read{{ canonical_type_name }}
-    variant = unpack_from 4, 'l>'
+    variant = unpack_from {{ e.discriminant_type()|discriminant_format_rb }}
     {% if e.is_flat() -%}
     {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
+    if variant == {{ variant.discriminant() }}
       return {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}
     end
     {%- endfor %}
@@ -130,7 +130,7 @@ class RustBufferStream
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
     {%- else -%}
     {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
+    if variant == {{ variant.discriminant() }}
         {%- if variant.has_fields() %}
         return {{ enum_name|class_name_rb }}::{{ variant.name()|enum_name_rb }}.new(
             {%- for field in variant.fields() %}
@@ -155,7 +155,7 @@ class RustBufferStream
     variant = unpack_from 4, 'l>'
     {% if e.is_flat() -%}
     {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
+    if variant == {{ variant.discriminant() }}
       return {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
         readString()
       )
@@ -165,7 +165,7 @@ class RustBufferStream
     raise InternalError, 'Unexpected variant tag for {{ canonical_type_name }}'
     {%- else -%}
     {%- for variant in e.variants() %}
-    if variant == {{ loop.index }}
+    if variant == {{ variant.discriminant() }}
         {%- if variant.has_fields() %}
         return {{ error_name|class_name_rb }}::{{ variant.name()|class_name_rb }}.new(
             {%- for field in variant.fields() %}
@@ -226,8 +226,8 @@ class RustBufferStream
     items
   end
 
-  {% when Type::Map with (inner_type) -%}
-  # The Map<T> type for {{ inner_type.canonical_name() }}.
+  {% when Type::Map with (key_type, value_type) -%}
+  # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
   def read{{ canonical_type_name }}
     count = unpack_from 4, 'l>'
@@ -235,8 +235,8 @@ class RustBufferStream
 
     items = {}
     count.times do
-      key = readString
-      items[key] = read{{ inner_type.canonical_name()|class_name_rb }}
+      key = read{{ key_type.canonical_name()|class_name_rb }}
+      items[key] = read{{ value_type.canonical_name()|class_name_rb }}
     end
 
     items
@@ -146,8 +146,8 @@ class RustBuffer < FFI::Struct
     end
   end
 
-  {% when Type::Map with (inner_type) -%}
-  # The Map<T> type for {{ inner_type.canonical_name() }}.
+  {% when Type::Map with (key_type, value_type) -%}
+  # The Map<K, V> type for {{ key_type.canonical_name() }} keys and {{ value_type.canonical_name() }} values.
 
   def self.alloc_from_{{ canonical_type_name }}(v)
     RustBuffer.allocWithBuilder do |builder|
@@ -173,6 +173,24 @@ module UniFFILib
     layout :len,      :int32,
            :data,     :pointer
 
+    # Used for `[ByRef] bytes` arguments, which the rust code reads in place
+    # for the duration of the call. We hold on to the memory pointer so that it
+    # stays alive for as long as the struct does.
+    def self.from_bytes(value)
+      bytes = value.pack('C*')
+      foreign_bytes = new
+      foreign_bytes[:len] = bytes.bytesize
+      if bytes.empty?
+        foreign_bytes[:data] = FFI::Pointer::NULL
+      else
+        data = FFI::MemoryPointer.new(:uint8, bytes.bytesize)
+        data.put_bytes(0, bytes)
+        foreign_bytes.instance_variable_set(:@data_pointer, data)
+        foreign_bytes[:data] = data
+      end
+      foreign_bytes
+    end
+
     def len
       self[:len]
     end
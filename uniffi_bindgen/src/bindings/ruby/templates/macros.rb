@@ -31,7 +31,10 @@
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}UniFFILib::ForeignBytes.from_bytes({{ arg.name() }})
+        {%- else %}
         {{- arg.name()|lower_rb(arg.type_()) }}
+        {%- endif %}
         {%- if !loop.last %},{% endif %}
     {%- endfor %}
 {%- endmacro -%}
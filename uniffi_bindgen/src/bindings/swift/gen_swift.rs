@@ -176,10 +176,15 @@ mod filters {
             Type::UInt32 => "UInt32".into(),
             Type::Int64 => "Int64".into(),
             Type::UInt64 => "UInt64".into(),
+            Type::Int128 | Type::UInt128 => panic!("No support for 128-bit integers in Swift, yet"),
+            Type::Char => panic!("No support for char in Swift, yet"),
+            Type::Stream(_) => panic!("No support for streams in Swift, yet"),
+            Type::Tuple(_) => panic!("No support for tuples in Swift, yet"),
             Type::Float32 => "Float".into(),
             Type::Float64 => "Double".into(),
             Type::Boolean => "Bool".into(),
             Type::String => "String".into(),
+            Type::Bytes => "Data".into(),
             Type::Timestamp => "Date".into(),
             Type::Duration => "TimeInterval".into(),
             Type::Enum(name)
@@ -189,7 +194,7 @@ mod filters {
             | Type::CallbackInterface(name) => class_name_swift(name)?,
             Type::Optional(type_) => format!("{}?", type_swift(type_)?),
             Type::Sequence(type_) => format!("[{}]", type_swift(type_)?),
-            Type::Map(type_) => format!("[String:{}]", type_swift(type_)?),
+            Type::Map(k, v) => format!("[{}:{}]", type_swift(k)?, type_swift(v)?),
             Type::External { .. } => panic!("No support for lifting types, yet"),
             Type::Wrapped { .. } => panic!("No support for lifting types, yet"),
         })
@@ -213,6 +218,9 @@ mod filters {
             FFIType::RustBuffer => "RustBuffer".into(),
             FFIType::ForeignBytes => "ForeignBytes".into(),
             FFIType::ForeignCallback => "ForeignCallback  _Nonnull".to_string(),
+            FFIType::FutureCallback => {
+                unreachable!("[Async] functions are rejected before rendering")
+            }
         })
     }
 
@@ -29,7 +29,7 @@
 //!  * How to read from and write into a byte buffer.
 //!
 
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{bail, Context, Result};
 use std::{
     ffi::OsString,
     fs::File,
@@ -42,6 +42,7 @@ pub mod gen_swift;
 pub use gen_swift::{BridgingHeader, Config, ModuleMap, SwiftWrapper};
 
 use super::super::interface::ComponentInterface;
+use crate::BindgenError;
 
 /// The Swift bindings generated from a [`ComponentInterface`].
 ///
@@ -111,17 +112,57 @@ pub fn write_bindings(
 ///
 pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bindings> {
     use askama::Template;
+    for func in ci.iter_function_definitions() {
+        if func.is_async() {
+            return Err(BindgenError::UnsupportedType {
+                name: func.name().to_string(),
+                message: format!(
+                    "No support for [Async] functions in Swift, yet: \"{}\"",
+                    func.name()
+                ),
+            }
+            .into());
+        }
+    }
+    for cbi in ci.iter_callback_interface_definitions() {
+        for meth in cbi.methods() {
+            if meth.arguments().iter().any(|arg| arg.by_ref()) {
+                return Err(BindgenError::UnsupportedType {
+                    name: cbi.name().to_string(),
+                    message: format!(
+                        "No support for [ByRef] callback interface arguments in Swift, yet: \"{}.{}\"",
+                        cbi.name(),
+                        meth.name()
+                    ),
+                }
+                .into());
+            }
+        }
+    }
+    for rec in ci.iter_record_definitions() {
+        // Swift structs can't contain themselves, even through an optional.
+        if rec.fields().iter().any(|field| field.is_boxed()) {
+            return Err(BindgenError::UnsupportedType {
+                name: rec.name().to_string(),
+                message: format!(
+                    "No support for records that contain themselves in Swift, yet: \"{}\"",
+                    rec.name()
+                ),
+            }
+            .into());
+        }
+    }
     let header = BridgingHeader::new(config, ci)
         .render()
-        .map_err(|_| anyhow!("failed to render Swift bridging header"))?;
+        .context("failed to render Swift bridging header")?;
     let library = SwiftWrapper::new(config, ci)
         .render()
-        .map_err(|_| anyhow!("failed to render Swift library"))?;
+        .context("failed to render Swift library")?;
     let modulemap = if config.generate_module_map() {
         Some(
             ModuleMap::new(config, ci)
                 .render()
-                .map_err(|_| anyhow!("failed to render Swift modulemap"))?,
+                .context("failed to render Swift modulemap")?,
         )
     } else {
         None
@@ -133,6 +174,27 @@ pub fn generate_bindings(config: &Config, ci: &ComponentInterface) -> Result<Bin
     })
 }
 
+/// Generate UniFFI component bindings for Swift, as a list of file names and contents.
+///
+/// These are the same files that [`write_bindings`] writes out, i.e. the `.swift` file, the
+/// `.h` file and, if it's enabled, the `.modulemap` file.
+pub fn generate_binding_files(
+    config: &Config,
+    ci: &ComponentInterface,
+) -> Result<Vec<(String, String)>> {
+    let Bindings {
+        header,
+        library,
+        modulemap,
+    } = generate_bindings(config, ci)?;
+    let mut files = vec![
+        (format!("{}.swift", config.module_name()), library),
+        (config.header_filename(), header),
+    ];
+    files.extend(modulemap.map(|modulemap| (config.modulemap_filename(), modulemap)));
+    Ok(files)
+}
+
 /// Compile UniFFI component bindings for Swift for use from the `swift` command-line.
 ///
 /// This is a utility function to help with running Swift tests. While the `swift` command-line
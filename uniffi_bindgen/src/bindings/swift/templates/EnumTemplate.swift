@@ -9,10 +9,10 @@ public enum {{ e.name()|class_name_swift }} {
 
 extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
     fileprivate static func read(from buf: Reader) throws -> {{ e.name()|class_name_swift }} {
-        let variant: Int32 = try buf.readInt()
+        let variant: {{ e.discriminant_type()|type_swift }} = try buf.readInt()
         switch variant {
         {% for variant in e.variants() %}
-        case {{ loop.index }}: return .{{ variant.name()|enum_variant_swift }}{% if variant.has_fields() -%}(
+        case {{ variant.discriminant() }}: return .{{ variant.name()|enum_variant_swift }}{% if variant.has_fields() -%}(
             {% for field in variant.fields() -%}
             {{ field.name()|var_name_swift }}: try {{ "buf"|read_swift(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
             {% endfor -%}
@@ -27,13 +27,13 @@ extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
         {% for variant in e.variants() %}
         {% if variant.has_fields() %}
         case let .{{ variant.name()|enum_variant_swift }}({% for field in variant.fields() %}{{ field.name()|var_name_swift }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
-            buf.writeInt(Int32({{ loop.index }}))
+            buf.writeInt({{ e.discriminant_type()|type_swift }}({{ variant.discriminant() }}))
             {% for field in variant.fields() -%}
             {{ field.name()|var_name_swift }}.write(into: buf)
             {% endfor -%}
         {% else %}
         case .{{ variant.name()|enum_variant_swift }}:
-            buf.writeInt(Int32({{ loop.index }}))
+            buf.writeInt({{ e.discriminant_type()|type_swift }}({{ variant.discriminant() }}))
         {% endif %}
         {%- endfor %}
         }
@@ -67,7 +67,7 @@ extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
         {% if e.is_flat() %}
 
         {% for variant in e.variants() %}
-        case {{ loop.index }}: return .{{ variant.name()|class_name_swift }}(
+        case {{ variant.discriminant() }}: return .{{ variant.name()|class_name_swift }}(
             message: try String.read(from: buf)
         )
         {% endfor %}
@@ -75,7 +75,7 @@ extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
        {% else %}
 
         {% for variant in e.variants() %}
-        case {{ loop.index }}: return .{{ variant.name()|class_name_swift }}{% if variant.has_fields() -%}(
+        case {{ variant.discriminant() }}: return .{{ variant.name()|class_name_swift }}{% if variant.has_fields() -%}(
             {% for field in variant.fields() -%}
             {{ field.name()|var_name_swift }}: try {{ "buf"|read_swift(field.type_()) }}{% if loop.last %}{% else %},{% endif %}
             {% endfor -%}
@@ -94,7 +94,7 @@ extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
 
         {% for variant in e.variants() %}
         case let .{{ variant.name()|class_name_swift }}(message):
-            buf.writeInt(Int32({{ loop.index }}))
+            buf.writeInt(Int32({{ variant.discriminant() }}))
             message.write(into: buf)
         {%- endfor %}
 
@@ -103,13 +103,13 @@ extension {{ e.name()|class_name_swift }}: ViaFfiUsingByteBuffer, ViaFfi {
         {% for variant in e.variants() %}
         {% if variant.has_fields() %}
         case let .{{ variant.name()|class_name_swift }}({% for field in variant.fields() %}{{ field.name()|var_name_swift }}{%- if loop.last -%}{%- else -%},{%- endif -%}{% endfor %}):
-            buf.writeInt(Int32({{ loop.index }}))
+            buf.writeInt(Int32({{ variant.discriminant() }}))
             {% for field in variant.fields() -%}
             {{ field.name()|var_name_swift }}.write(into: buf)
             {% endfor -%}
         {% else %}
         case .{{ variant.name()|class_name_swift }}:
-            buf.writeInt(Int32({{ loop.index }}))
+            buf.writeInt(Int32({{ variant.discriminant() }}))
         {% endif %}
         {%- endfor %}
 
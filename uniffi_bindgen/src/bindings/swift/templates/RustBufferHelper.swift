@@ -209,13 +209,13 @@ extension Array: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Element: Seri
 {% endif %}
 
 {% if ci.contains_map_types() %}
-extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key == String, Value: Serializable {
+extension Dictionary: ViaFfiUsingByteBuffer, ViaFfi, Serializable where Key: Serializable, Value: Serializable {
     fileprivate static func read(from buf: Reader) throws -> Self {
         let len: Int32 = try buf.readInt()
-        var dict = [String: Value]()
+        var dict = [Key: Value]()
         dict.reserveCapacity(Int(len))
         for _ in 0..<len {
-            dict[try String.read(from: buf)] = try Value.read(from: buf)
+            dict[try Key.read(from: buf)] = try Value.read(from: buf)
         }
         return dict
     }
@@ -274,6 +274,20 @@ extension String: ViaFfi {
     }
 }
 
+{% when Type::Bytes -%}
+extension Data: ViaFfiUsingByteBuffer, ViaFfi {
+    fileprivate static func read(from buf: Reader) throws -> Self {
+        let len: Int32 = try buf.readInt()
+        return Data(try buf.readBytes(count: Int(len)))
+    }
+
+    fileprivate func write(into buf: Writer) {
+        let len = Int32(self.count)
+        buf.writeInt(len)
+        buf.writeBytes(self)
+    }
+}
+
 {% when Type::Boolean -%}
 extension Bool: ViaFfi {
     fileprivate typealias FfiType = Int8
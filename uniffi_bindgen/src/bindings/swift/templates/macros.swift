@@ -5,7 +5,7 @@
 #}
 
 {%- macro to_ffi_call(func) -%}
-{% call try(func) %}
+{% call try(func) %}{% call _borrow_bytes(func) %}
     {% match func.throws() %}
     {% when Some with (e) %}
     rustCallWithError({{ e|class_name_swift }}.self) {
@@ -13,11 +13,11 @@
     rustCall() {
     {% endmatch %}
     {{ func.ffi_func().name() }}({% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}$0)
-}
+}{% call _end_borrow_bytes(func) %}
 {%- endmacro -%}
 
 {%- macro to_ffi_call_with_prefix(prefix, func) -%}
-{% call try(func) %}
+{% call try(func) %}{% call _borrow_bytes(func) %}
     {%- match func.throws() %}
     {%- when Some with (e) %}
     rustCallWithError({{ e|class_name_swift }}.self) {
@@ -27,16 +27,35 @@
     {{ func.ffi_func().name() }}(
         {{- prefix }}, {% call _arg_list_ffi_call(func) -%}{% if func.arguments().len() > 0 %}, {% endif %}$0
     )
-}
+}{% call _end_borrow_bytes(func) %}
 {%- endmacro %}
 
 {%- macro _arg_list_ffi_call(func) %}
     {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %}ForeignBytes(bufferPointer: {{ arg.name()|var_name_swift }}Ptr.bindMemory(to: UInt8.self))
+        {%- else %}
         {{- arg.name()|var_name_swift|lower_swift(arg.type_()) }}
+        {%- endif %}
         {%- if !loop.last %}, {% endif -%}
     {%- endfor %}
 {%- endmacro -%}
 
+{#-
+// `[ByRef] bytes` arguments are passed to rust as a pointer to the contents of the `Data`,
+// which is only valid inside a `withUnsafeBytes` closure wrapped around the call.
+-#}
+{%- macro _borrow_bytes(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %} {{ arg.name()|var_name_swift }}.withUnsafeBytes { {{ arg.name()|var_name_swift }}Ptr in try{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
+{%- macro _end_borrow_bytes(func) %}
+    {%- for arg in func.arguments() %}
+        {%- if arg.is_borrowed_bytes() %} }{% endif %}
+    {%- endfor %}
+{%- endmacro -%}
+
 {#-
 // Arglist as used in Swift declarations of methods, functions and constructors.
 // Note the var_name_swift and type_swift filters.
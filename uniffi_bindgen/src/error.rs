@@ -0,0 +1,124 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # The errors returned by the code generator.
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// An error from generating the scaffolding or the bindings for a component.
+///
+/// Internally the generator uses `anyhow` for most of its errors, which are just printed by the
+/// command-line tool. Tools that use the generator as a library might want to tell the different
+/// kinds of problem apart, for example to skip a component whose bindings aren't supported rather
+/// than failing the whole build, so the entry points in the crate root return a `BindgenError`.
+///
+/// The checks that find a specific kind of problem, like an unsupported type, return it as a
+/// `BindgenError` wrapped in an `anyhow::Error`, and converting that back into a `BindgenError`
+/// recovers it, even if some context has been added on the way. Callers of the functions that
+/// still return an `anyhow::Error`, like [`generate_kotlin_bindings`], can get at it with
+/// `err.downcast_ref::<BindgenError>()`.
+///
+/// [`generate_kotlin_bindings`]: crate::bindings::generate_kotlin_bindings
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum BindgenError {
+    /// A file couldn't be read or written.
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The UDL couldn't be parsed, or doesn't describe a valid component.
+    Parse(anyhow::Error),
+    /// The config file couldn't be read or parsed.
+    Config {
+        path: PathBuf,
+        source: anyhow::Error,
+    },
+    /// The bindings for the target language don't support a type, or a way of using it.
+    ///
+    /// The `name` is the name of the type in the UDL, and the `message` says what isn't supported.
+    UnsupportedType { name: String, message: String },
+    /// Two different things would be given the same name in the generated code.
+    ///
+    /// The `name` is the name that they'd share, and the `message` says what they are.
+    NameCollision { name: String, message: String },
+    /// A template failed to render.
+    Template(askama::Error),
+    /// Anything else, like an unknown target language.
+    Other(anyhow::Error),
+}
+
+impl fmt::Display for BindgenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "Failed to access {:?}: {}", path, source),
+            Self::Parse(err) => write!(f, "Failed to parse UDL: {}", err),
+            Self::Config { path, source } => {
+                write!(f, "Failed to read config file {:?}: {}", path, source)
+            }
+            Self::UnsupportedType { message, .. } | Self::NameCollision { message, .. } => {
+                write!(f, "{}", message)
+            }
+            Self::Template(err) => write!(f, "Failed to render template: {}", err),
+            Self::Other(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for BindgenError {
+    // The message of the wrapped error is part of our own, so its source is the next one down.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) | Self::Config { source: err, .. } | Self::Other(err) => err.source(),
+            Self::Io { source, .. } => source.source(),
+            _ => None,
+        }
+    }
+}
+
+impl From<askama::Error> for BindgenError {
+    fn from(err: askama::Error) -> Self {
+        Self::Template(err)
+    }
+}
+
+// Recovers a `BindgenError` or a template error that's been wrapped in the `anyhow::Error`.
+impl From<anyhow::Error> for BindgenError {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<BindgenError>() {
+            Ok(err) => return err,
+            Err(err) => err,
+        };
+        match err.downcast::<askama::Error>() {
+            Ok(err) => Self::Template(err),
+            Err(err) => Self::Other(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_errors_are_recovered_through_context() {
+        let err: anyhow::Error = BindgenError::NameCollision {
+            name: "Foo".into(),
+            message: "Foo would be generated twice".into(),
+        }
+        .into();
+        let err = BindgenError::from(err.context("failed to generate bindings"));
+        assert!(matches!(err, BindgenError::NameCollision { ref name, .. } if name == "Foo"));
+        assert_eq!(err.to_string(), "Foo would be generated twice");
+
+        let err: Result<(), askama::Error> = Err(askama::Error::Fmt(fmt::Error));
+        let err = BindgenError::from(err.context("failed to render bindings").unwrap_err());
+        assert!(matches!(err, BindgenError::Template(_)));
+
+        let err = BindgenError::from(anyhow::anyhow!("something else"));
+        assert!(matches!(err, BindgenError::Other(_)));
+    }
+}
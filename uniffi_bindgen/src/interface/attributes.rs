@@ -25,17 +25,42 @@ use anyhow::{bail, Result};
 /// may influence the properties of things like functions and arguments.
 #[derive(Debug, Clone, Hash)]
 pub(super) enum Attribute {
+    Async,
     ByRef,
+    // `[ByHandle] dictionary` - A record that's passed by a handle to it in Rust, like an object.
+    ByHandle,
+    // `[Constants] interface` - An interface that only declares module-level constants.
+    Constants,
+    // `[Deprecated="message"]` - Mark the item as deprecated in the foreign language bindings.
+    Deprecated(String),
     Enum,
     Error,
+    ExcludeFromEquality,
     Name(String),
+    // `[Property]` - A method without arguments that the bindings can expose as a read-only property.
+    Property,
+    // `[Repr=u8]` - The integer type used to serialize the discriminant of a flat enum.
+    Repr(ReprType),
+    // `[Rename="OldName"]` - The name to use for an enum variant in the foreign language bindings.
+    Rename(String),
     SelfType(SelfType),
     Threadsafe, // N.B. the `[Threadsafe]` attribute is deprecated and will be removed
     Throws(String),
     // `[External="crate_name"]` - We can `use crate_name::...` for the type.
     External(String),
     // Something hand-written in this crate which wraps a primitive type.
+    // `[Custom]` is the preferred spelling, but `[Wrapped]` is still accepted.
     Wrapped,
+    // `[Map] typedef (K or V) Name` - A map from keys of type `K` to values of type `V`.
+    Map,
+    // `[Stream] typedef T Name` - A stream of values of type `T`, returned from Rust.
+    Stream,
+    // `[Tuple] typedef (A or B) Name` - A tuple of values with types `A` and `B`.
+    Tuple,
+    // `[Alias] typedef T Name` - Another name for the type `T`, for bindings that can use one.
+    Alias,
+    // `[Validate="min=0, max=100"]` - Constraints on the value of a dictionary member.
+    Validate(Vec<ValidateRule>),
 }
 
 impl Attribute {
@@ -57,20 +82,37 @@ impl TryFrom<&weedle::attribute::ExtendedAttribute<'_>> for Attribute {
         match weedle_attribute {
             // Matches plain named attributes like "[ByRef"].
             weedle::attribute::ExtendedAttribute::NoArgs(attr) => match (attr.0).0 {
+                "Async" => Ok(Attribute::Async),
                 "ByRef" => Ok(Attribute::ByRef),
+                "ByHandle" => Ok(Attribute::ByHandle),
+                "Constants" => Ok(Attribute::Constants),
                 "Enum" => Ok(Attribute::Enum),
                 "Error" => Ok(Attribute::Error),
+                "ExcludeFromEquality" => Ok(Attribute::ExcludeFromEquality),
                 "Threadsafe" => Ok(Attribute::Threadsafe),
-                "Wrapped" => Ok(Attribute::Wrapped),
+                "Custom" | "Wrapped" => Ok(Attribute::Wrapped),
+                "Map" => Ok(Attribute::Map),
+                "Stream" => Ok(Attribute::Stream),
+                "Tuple" => Ok(Attribute::Tuple),
+                "Alias" => Ok(Attribute::Alias),
+                "Property" => Ok(Attribute::Property),
                 _ => anyhow::bail!("ExtendedAttributeNoArgs not supported: {:?}", (attr.0).0),
             },
             // Matches assignment-style attributes like ["Throws=Error"]
             weedle::attribute::ExtendedAttribute::Ident(identity) => {
                 match identity.lhs_identifier.0 {
                     "Name" => Ok(Attribute::Name(name_from_id_or_string(&identity.rhs))),
+                    "Deprecated" => {
+                        Ok(Attribute::Deprecated(name_from_id_or_string(&identity.rhs)))
+                    }
                     "Throws" => Ok(Attribute::Throws(name_from_id_or_string(&identity.rhs))),
                     "Self" => Ok(Attribute::SelfType(SelfType::try_from(&identity.rhs)?)),
+                    "Repr" => Ok(Attribute::Repr(ReprType::try_from(&identity.rhs)?)),
+                    "Rename" => Ok(Attribute::Rename(name_from_id_or_string(&identity.rhs))),
                     "External" => Ok(Attribute::External(name_from_id_or_string(&identity.rhs))),
+                    "Validate" => Ok(Attribute::Validate(ValidateRule::parse_list(
+                        &name_from_id_or_string(&identity.rhs),
+                    )?)),
                     _ => anyhow::bail!(
                         "Attribute identity Identifier not supported: {:?}",
                         identity.lhs_identifier.0
@@ -116,11 +158,35 @@ where
         validator(attr)?;
     }
 
+    // The duplicate check above doesn't catch e.g. `[Throws=A, Throws=B]`, and the code
+    // that handles errors on both sides of the FFI assumes there's only one of them.
+    let mut throws = attrs.iter().filter_map(|attr| match attr {
+        Attribute::Throws(name) => Some(name),
+        _ => None,
+    });
+    if let (Some(first), Some(second)) = (throws.next(), throws.next()) {
+        bail!(
+            "Only one Throws attribute is supported, but found {:?} and {:?}; \
+             consider combining them into a single error enum",
+            first,
+            second
+        );
+    }
+
     Ok(attrs)
 }
 
+/// Find the message from a `[Deprecated="message"]` attribute, if there is one.
+fn get_deprecated(attrs: &[Attribute]) -> Option<&str> {
+    attrs.iter().find_map(|attr| match attr {
+        Attribute::Deprecated(message) => Some(message.as_ref()),
+        _ => None,
+    })
+}
+
 /// Attributes that can be attached to an `enum` definition in the UDL.
-/// There's only one case here: using `[Error]` to mark an enum as an error class.
+/// This supports using `[Error]` to mark an enum as an error class, `[Repr=u8]` (or `u16`, `u32`)
+/// to choose how the discriminant is serialized, and `[Deprecated]`.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct EnumAttributes(Vec<Attribute>);
 
@@ -128,6 +194,17 @@ impl EnumAttributes {
     pub fn contains_error_attr(&self) -> bool {
         self.0.iter().any(|attr| attr.is_error())
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        get_deprecated(&self.0)
+    }
+
+    pub(super) fn get_repr(&self) -> Option<&ReprType> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Repr(repr) => Some(repr),
+            _ => None,
+        })
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for EnumAttributes {
@@ -136,10 +213,15 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for EnumAttributes {
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Error => Ok(()),
+            Attribute::Error | Attribute::Deprecated(_) | Attribute::Repr(_) => Ok(()),
             _ => bail!(format!("{:?} not supported for enums", attr)),
         })?;
-        Ok(Self(attrs))
+        let attrs = Self(attrs);
+        // Errors are always serialized with an `i32` discriminant.
+        if attrs.contains_error_attr() && attrs.get_repr().is_some() {
+            bail!("Repr not supported for errors");
+        }
+        Ok(attrs)
     }
 }
 
@@ -153,10 +235,50 @@ impl<T: TryInto<EnumAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for E
     }
 }
 
+/// Attributes that can be attached to a `dictionary` definition in the UDL.
+/// This only supports `[ByHandle]`, for passing a record by a handle to it in Rust.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct DictionaryAttributes(Vec<Attribute>);
+
+impl DictionaryAttributes {
+    pub(super) fn by_handle(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ByHandle))
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for DictionaryAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::ByHandle => Ok(()),
+            _ => bail!(format!("{:?} not supported for dictionaries", attr)),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<DictionaryAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for DictionaryAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on a function.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for functions that
-/// can produce an error.
+/// can produce an error, and the `[Async]` attribute for functions that
+/// are implemented as an `async fn` in Rust. Functions can also be marked
+/// as `[Deprecated="message"]`.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct FunctionAttributes(Vec<Attribute>);
 
@@ -169,6 +291,14 @@ impl FunctionAttributes {
             _ => None,
         })
     }
+
+    pub(super) fn is_async(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Async))
+    }
+
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        get_deprecated(&self.0)
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttributes {
@@ -177,7 +307,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FunctionAttribut
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::Throws(_) => Ok(()),
+            Attribute::Async | Attribute::Throws(_) | Attribute::Deprecated(_) => Ok(()),
             _ => bail!(format!("{:?} not supported for functions", attr)),
         })?;
         Ok(Self(attrs))
@@ -234,6 +364,56 @@ impl<T: TryInto<ArgumentAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
     }
 }
 
+/// Represents UDL attributes that might appear on a `dictionary` member.
+///
+/// This supports the `[ExcludeFromEquality]` attribute for fields that should not
+/// be considered when comparing records in the foreign language bindings, and
+/// `[Validate]` for fields whose values are constrained.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct FieldAttributes(Vec<Attribute>);
+
+impl FieldAttributes {
+    pub fn excluded_from_equality(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::ExcludeFromEquality))
+    }
+
+    /// All of the rules from the field's `[Validate]` attributes, in the order they're written.
+    pub fn validate_rules(&self) -> Vec<ValidateRule> {
+        self.0
+            .iter()
+            .flat_map(|attr| match attr {
+                Attribute::Validate(rules) => rules.clone(),
+                _ => vec![],
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for FieldAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::ExcludeFromEquality | Attribute::Validate(_) => Ok(()),
+            _ => bail!(format!("{:?} not supported for dictionary members", attr)),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<FieldAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for FieldAttributes {
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 /// Represents UDL attributes that might appear on an `interface` definition.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct InterfaceAttributes(Vec<Attribute>);
@@ -247,11 +427,21 @@ impl InterfaceAttributes {
         self.0.iter().any(|attr| attr.is_error())
     }
 
+    pub fn contains_constants_attr(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Constants))
+    }
+
     pub fn threadsafe(&self) -> bool {
         self.0
             .iter()
             .any(|attr| matches!(attr, Attribute::Threadsafe))
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        get_deprecated(&self.0)
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttributes {
@@ -260,13 +450,19 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for InterfaceAttribu
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Constants => Ok(()),
             Attribute::Enum => Ok(()),
             Attribute::Error => Ok(()),
             Attribute::Threadsafe => Ok(()),
+            Attribute::Deprecated(_) => Ok(()),
             _ => bail!(format!("{:?} not supported for interface definition", attr)),
         })?;
-        // Can't be both `[Threadsafe]` and an `[Enum]`.
-        if attrs.len() > 1 {
+        // Can't be e.g. both `[Threadsafe]` and an `[Enum]`, but any of them can be deprecated.
+        let kinds = attrs
+            .iter()
+            .filter(|attr| !matches!(attr, Attribute::Deprecated(_)))
+            .count();
+        if kinds > 1 {
             bail!("conflicting attributes on interface definition");
         }
         Ok(Self(attrs))
@@ -308,6 +504,10 @@ impl ConstructorAttributes {
             _ => None,
         })
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        get_deprecated(&self.0)
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttributes {
@@ -318,6 +518,7 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::Throws(_) => Ok(()),
             Attribute::Name(_) => Ok(()),
+            Attribute::Deprecated(_) => Ok(()),
             _ => bail!(format!("{:?} not supported for constructors", attr)),
         })?;
         Ok(Self(attrs))
@@ -327,7 +528,8 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for ConstructorAttri
 /// Represents UDL attributes that might appear on a method.
 ///
 /// This supports the `[Throws=ErrorName]` attribute for methods that can produce
-/// an error, and the `[Self=ByArc]` attribute for methods that take `Arc<Self>` as receiver.
+/// an error, the `[Self=ByArc]` attribute for methods that take `Arc<Self>` as receiver,
+/// and the `[Property]` attribute for getters that can be exposed as properties.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct MethodAttributes(Vec<Attribute>);
 
@@ -346,6 +548,16 @@ impl MethodAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::SelfType(SelfType::ByArc)))
     }
+
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        get_deprecated(&self.0)
+    }
+
+    pub(super) fn is_property(&self) -> bool {
+        self.0
+            .iter()
+            .any(|attr| matches!(attr, Attribute::Property))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes {
@@ -356,12 +568,58 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for MethodAttributes
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
             Attribute::SelfType(_) => Ok(()),
             Attribute::Throws(_) => Ok(()),
+            Attribute::Deprecated(_) => Ok(()),
+            Attribute::Property => Ok(()),
             _ => bail!(format!("{:?} not supported for methods", attr)),
         })?;
         Ok(Self(attrs))
     }
 }
 
+/// Represents UDL attributes that might appear on a variant of an `[Enum] interface`.
+///
+/// The supported ones are `[Deprecated="message"]` and `[Rename="OldName"]`.
+#[derive(Debug, Clone, Hash, Default)]
+pub(super) struct VariantAttributes(Vec<Attribute>);
+
+impl VariantAttributes {
+    pub(super) fn get_deprecated(&self) -> Option<&str> {
+        get_deprecated(&self.0)
+    }
+
+    pub(super) fn get_rename(&self) -> Option<&str> {
+        self.0.iter().find_map(|attr| match attr {
+            Attribute::Rename(name) => Some(name.as_ref()),
+            _ => None,
+        })
+    }
+}
+
+impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for VariantAttributes {
+    type Error = anyhow::Error;
+    fn try_from(
+        weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
+    ) -> Result<Self, Self::Error> {
+        let attrs = parse_attributes(weedle_attributes, |attr| match attr {
+            Attribute::Deprecated(_) | Attribute::Rename(_) => Ok(()),
+            _ => bail!(format!("{:?} not supported for enum variants", attr)),
+        })?;
+        Ok(Self(attrs))
+    }
+}
+
+impl<T: TryInto<VariantAttributes, Error = anyhow::Error>> TryFrom<Option<T>>
+    for VariantAttributes
+{
+    type Error = anyhow::Error;
+    fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
+        match value {
+            None => Ok(Default::default()),
+            Some(v) => v.try_into(),
+        }
+    }
+}
+
 impl<T: TryInto<MethodAttributes, Error = anyhow::Error>> TryFrom<Option<T>> for MethodAttributes {
     type Error = anyhow::Error;
     fn try_from(value: Option<T>) -> Result<Self, Self::Error> {
@@ -397,9 +655,74 @@ impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for SelfType {
     }
 }
 
+/// Represents the integer types that can be used for the discriminant of a flat enum.
+///
+/// These mirror a `#[repr(u8)]` etc on the Rust enum, although the Rust code doesn't actually
+/// need to use that repr, since the discriminant is written out explicitly. Enums without a
+/// `[Repr]` attribute serialize their discriminant as an `i32`.
+#[derive(Debug, Clone, Copy, Hash)]
+pub(super) enum ReprType {
+    U8,
+    U16,
+    U32,
+}
+
+impl TryFrom<&weedle::attribute::IdentifierOrString<'_>> for ReprType {
+    type Error = anyhow::Error;
+    fn try_from(nm: &weedle::attribute::IdentifierOrString<'_>) -> Result<Self, Self::Error> {
+        Ok(match nm {
+            weedle::attribute::IdentifierOrString::Identifier(identifier) => match identifier.0 {
+                "u8" => ReprType::U8,
+                "u16" => ReprType::U16,
+                "u32" => ReprType::U32,
+                _ => bail!("Unsupported Repr type: {:?}", identifier.0),
+            },
+            weedle::attribute::IdentifierOrString::String(_) => {
+                bail!("Unsupported Repr type: {:?}", nm)
+            }
+        })
+    }
+}
+
+/// A single rule from a `[Validate]` attribute on a dictionary member.
+///
+/// The attribute holds a comma-separated list of rules, like `[Validate="min=1, max=10"]`.
+/// The bounds are kept as they're written, since they can only be checked against the type
+/// of the field once it's known.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub(super) enum ValidateRule {
+    // `nonEmpty` - A string, bytes, sequence or map that must have at least one element.
+    NonEmpty,
+    // `min=N` - A number that must be at least `N`.
+    Min(String),
+    // `max=N` - A number that must be at most `N`.
+    Max(String),
+}
+
+impl ValidateRule {
+    fn parse_list(rules: &str) -> Result<Vec<Self>> {
+        rules
+            .split(',')
+            .map(|rule| {
+                let rule = rule.trim();
+                Ok(
+                    match rule.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                        None if rule == "nonEmpty" => ValidateRule::NonEmpty,
+                        Some(("min", value)) => ValidateRule::Min(value.to_string()),
+                        Some(("max", value)) => ValidateRule::Max(value.to_string()),
+                        _ => bail!("Unsupported Validate rule: {:?}", rule),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
 /// Represents UDL attributes that might appear on a typedef
 ///
-/// This supports the `[External="crate_name"]` and `[Wrapped]` attributes for types.
+/// This supports the `[External="crate_name"]` and `[Custom]` (or `[Wrapped]`) attributes for types,
+/// as well as `[Map]` for declaring a map whose keys aren't strings, `[Stream]` for declaring
+/// a stream of values, `[Tuple]` for declaring a tuple and `[Alias]` for naming a type.
 #[derive(Debug, Clone, Hash, Default)]
 pub(super) struct TypedefAttributes(Vec<Attribute>);
 
@@ -419,6 +742,22 @@ impl TypedefAttributes {
             .iter()
             .any(|attr| matches!(attr, Attribute::Wrapped { .. }))
     }
+
+    pub(super) fn is_map(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Map))
+    }
+
+    pub(super) fn is_stream(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Stream))
+    }
+
+    pub(super) fn is_tuple(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Tuple))
+    }
+
+    pub(super) fn is_alias(&self) -> bool {
+        self.0.iter().any(|attr| matches!(attr, Attribute::Alias))
+    }
 }
 
 impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttributes {
@@ -427,7 +766,12 @@ impl TryFrom<&weedle::attribute::ExtendedAttributeList<'_>> for TypedefAttribute
         weedle_attributes: &weedle::attribute::ExtendedAttributeList<'_>,
     ) -> Result<Self, Self::Error> {
         let attrs = parse_attributes(weedle_attributes, |attr| match attr {
-            Attribute::External { .. } | Attribute::Wrapped => Ok(()),
+            Attribute::External { .. }
+            | Attribute::Wrapped
+            | Attribute::Map
+            | Attribute::Stream
+            | Attribute::Tuple
+            | Attribute::Alias => Ok(()),
             _ => bail!(format!("{:?} not supported for typedefs", attr)),
         })?;
         Ok(Self(attrs))
@@ -504,6 +848,25 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_repr() -> Result<()> {
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Repr=u8").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::Repr(ReprType::U8)));
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Repr=i8").unwrap();
+        let err = Attribute::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported Repr type: \"i8\"");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Repr=u16]").unwrap();
+        let attrs = EnumAttributes::try_from(&node)?;
+        assert!(matches!(attrs.get_repr(), Some(ReprType::U16)));
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Error, Repr=u16]").unwrap();
+        let err = EnumAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Repr not supported for errors");
+        Ok(())
+    }
+
     #[test]
     fn test_threadsafe() -> Result<()> {
         let (_, node) = weedle::attribute::ExtendedAttribute::parse("Threadsafe").unwrap();
@@ -528,6 +891,62 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_deprecated() -> Result<()> {
+        let (_, node) =
+            weedle::attribute::ExtendedAttribute::parse("Deprecated=\"use bar instead\"").unwrap();
+        let attr = Attribute::try_from(&node)?;
+        assert!(matches!(attr, Attribute::Deprecated(msg) if msg == "use bar instead"));
+
+        let (_, node) = weedle::attribute::ExtendedAttribute::parse("Deprecated").unwrap();
+        let err = Attribute::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ExtendedAttributeNoArgs not supported: \"Deprecated\""
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deprecated_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Throws=Error, Deprecated=\"no\"]")
+                .unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_deprecated(), Some("no")));
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_deprecated(), Some("no")));
+        let attrs = ConstructorAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_deprecated(), Some("no")));
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Enum, Deprecated=\"no\"]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.contains_enum_attr());
+        assert!(matches!(attrs.get_deprecated(), Some("no")));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(attrs.get_deprecated().is_none());
+        assert!(attrs.get_rename().is_none());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Rename=\"Old\", Deprecated=\"no\"]")
+                .unwrap();
+        let attrs = VariantAttributes::try_from(&node).unwrap();
+        assert!(matches!(attrs.get_rename(), Some("Old")));
+        assert!(matches!(attrs.get_deprecated(), Some("no")));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Rename=Old]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Rename(\"Old\") not supported for methods");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
+        let err = VariantAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "ByRef not supported for enum variants");
+    }
+
     #[test]
     fn test_unsupported() {
         let (_, node) =
@@ -563,6 +982,34 @@ mod test {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
         let attrs = FunctionAttributes::try_from(&node).unwrap();
         assert!(matches!(attrs.get_throws_err(), None));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse(
+            "[Throws=NetworkError, Throws=ParseError]",
+        )
+        .unwrap();
+        let err = FunctionAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Only one Throws attribute is supported, but found \"NetworkError\" and \"ParseError\"; \
+             consider combining them into a single error enum"
+        );
+    }
+
+    #[test]
+    fn test_async_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Async, Throws=Error]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_async());
+        assert!(matches!(attrs.get_throws_err(), Some("Error")));
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Throws=Error]").unwrap();
+        let attrs = FunctionAttributes::try_from(&node).unwrap();
+        assert!(!attrs.is_async());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Async]").unwrap();
+        let err = MethodAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Async not supported for methods");
     }
 
     #[test]
@@ -599,6 +1046,11 @@ mod test {
         assert!(attrs.get_self_by_arc());
         assert!(attrs.get_throws_err().is_some());
 
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Property]").unwrap();
+        let attrs = MethodAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_property());
+        assert!(!attrs.get_self_by_arc());
+
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Self=ByArc]").unwrap();
         let attrs = MethodAttributes::try_from(&node).unwrap();
         assert!(attrs.get_self_by_arc());
@@ -664,6 +1116,51 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_exclude_from_equality_attribute() {
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[ExcludeFromEquality]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(attrs.excluded_from_equality());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(!attrs.excluded_from_equality());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[ByRef]").unwrap();
+        let err = FieldAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "ByRef not supported for dictionary members"
+        );
+    }
+
+    #[test]
+    fn test_validate_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse(
+            r#"[Validate="nonEmpty", Validate="min=1, max = 10"]"#,
+        )
+        .unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert_eq!(
+            attrs.validate_rules(),
+            vec![
+                ValidateRule::NonEmpty,
+                ValidateRule::Min("1".into()),
+                ValidateRule::Max("10".into()),
+            ]
+        );
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[]").unwrap();
+        let attrs = FieldAttributes::try_from(&node).unwrap();
+        assert!(attrs.validate_rules().is_empty());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse(r#"[Validate="positive"]"#).unwrap();
+        let err = FieldAttributes::try_from(&node).unwrap_err();
+        assert_eq!(err.to_string(), "Unsupported Validate rule: \"positive\"");
+    }
+
     #[test]
     fn test_threadsafe_attribute() {
         let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Threadsafe]").unwrap();
@@ -698,6 +1195,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_constants_attribute() {
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Constants]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(attrs.contains_constants_attr());
+        assert!(!attrs.contains_enum_attr());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Enum]").unwrap();
+        let attrs = InterfaceAttributes::try_from(&node).unwrap();
+        assert!(!attrs.contains_constants_attr());
+
+        let (_, node) =
+            weedle::attribute::ExtendedAttributeList::parse("[Constants, Enum]").unwrap();
+        let err = InterfaceAttributes::try_from(&node).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "conflicting attributes on interface definition"
+        );
+    }
+
     #[test]
     fn test_other_attributes_not_supported_for_interfaces() {
         let (_, node) =
@@ -715,11 +1232,34 @@ mod test {
         let attrs = TypedefAttributes::try_from(&node).unwrap();
         assert!(attrs.is_wrapped());
 
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Custom]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_wrapped());
+
         let (_, node) =
             weedle::attribute::ExtendedAttributeList::parse("[External=crate_name]").unwrap();
         let attrs = TypedefAttributes::try_from(&node).unwrap();
         assert!(!attrs.is_wrapped());
         assert_eq!(attrs.get_crate_name(), "crate_name");
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Map]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_map());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Stream]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_stream());
+        assert!(!attrs.is_wrapped());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Tuple]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_tuple());
+        assert!(!attrs.is_stream());
+
+        let (_, node) = weedle::attribute::ExtendedAttributeList::parse("[Alias]").unwrap();
+        let attrs = TypedefAttributes::try_from(&node).unwrap();
+        assert!(attrs.is_alias());
+        assert!(!attrs.is_wrapped());
     }
 
     #[test]
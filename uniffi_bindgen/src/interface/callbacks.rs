@@ -47,6 +47,7 @@ pub struct CallbackInterface {
     pub(super) name: String,
     pub(super) methods: Vec<Method>,
     pub(super) ffi_init_callback: FFIFunction,
+    pub(super) docstring: Option<String>,
 }
 
 impl CallbackInterface {
@@ -55,6 +56,7 @@ impl CallbackInterface {
             name,
             methods: Default::default(),
             ffi_init_callback: Default::default(),
+            docstring: None,
         }
     }
 
@@ -74,6 +76,10 @@ impl CallbackInterface {
         &self.ffi_init_callback
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub(super) fn derive_ffi_funcs(&mut self, ci_prefix: &str) {
         self.ffi_init_callback.name = format!("ffi_{}_{}_init_callback", ci_prefix, self.name);
         self.ffi_init_callback.arguments = vec![FFIArgument {
@@ -112,11 +118,35 @@ impl APIConverter<CallbackInterface> for weedle::CallbackInterfaceDefinition<'_>
             bail!("callback interface inheritence is not supported");
         }
         let mut object = CallbackInterface::new(self.identifier.0.to_string());
+        object.docstring = ci.resolve_docstring(self.identifier.0);
         for member in &self.members.body {
             match member {
                 weedle::interface::InterfaceMember::Operation(t) => {
                     let mut method: Method = t.convert(ci)?;
+                    if method.is_property() {
+                        bail!(
+                            "[Property] isn't supported for callback interface methods: \"{}.{}\"",
+                            object.name,
+                            method.name()
+                        );
+                    }
                     method.object_name.push_str(object.name.as_str());
+                    // Rust lends `[ByRef]` arguments to the foreign code without adding a
+                    // reference, which only makes sense for objects.
+                    for arg in method.arguments() {
+                        if arg.by_ref() && !matches!(arg.type_(), Type::Object(_)) {
+                            bail!(
+                                "Only objects can be passed [ByRef] to callback interface methods: \"{}.{}\"",
+                                object.name,
+                                method.name()
+                            );
+                        }
+                    }
+                    if method.throws().is_some() {
+                        // Unexpected errors from the foreign code are reported to Rust as a string,
+                        // so make sure the bindings know how to handle the `String` type.
+                        ci.types.add_known_type(Type::String)?;
+                    }
                     object.methods.push(method);
                 }
                 _ => bail!(
@@ -175,4 +205,34 @@ mod test {
         assert_eq!(callbacks_two.methods()[0].name(), "two");
         assert_eq!(callbacks_two.methods()[1].name(), "too");
     }
+
+    #[test]
+    fn test_objects_can_be_passed_by_ref() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Counter {
+                constructor();
+            };
+            callback interface Observer {
+                void on_change([ByRef] Counter counter, u32 value);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Observer").unwrap();
+        let args = cbi.methods()[0].arguments();
+        assert!(args[0].by_ref());
+        assert!(!args[1].by_ref());
+
+        const UDL_STRING: &str = r#"
+            namespace test{};
+            callback interface Observer {
+                void on_change([ByRef] string name);
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_STRING).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Only objects can be passed [ByRef] to callback interface methods: \"Observer.on_change\""
+        );
+    }
 }
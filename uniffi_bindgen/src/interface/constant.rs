@@ -0,0 +1,196 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Constant definitions for a `ComponentInterface`.
+//!
+//! This module converts constant definitions from UDL into structures that
+//! can be added to a `ComponentInterface`. WebIDL only allows constants to be
+//! declared inside an interface, so module-level constants are declared inside
+//! an `interface` with a special `[Constants]` attribute, like this:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! # namespace example {};
+//! [Constants]
+//! interface Limits {
+//!   const u32 MAX_LEN = 4096;
+//!   const boolean STRICT = true;
+//! };
+//! # "##)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! Will result in a [`Constant`] member being added to the resulting [`ComponentInterface`]
+//! for each constant. The name of the interface is only used to group the constants in
+//! the UDL, and doesn't appear in the `ComponentInterface` at all:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! # namespace example {};
+//! # [Constants]
+//! # interface Limits {
+//! #   const u32 MAX_LEN = 4096;
+//! #   const boolean STRICT = true;
+//! # };
+//! # "##)?;
+//! use uniffi_bindgen::interface::{Literal, Type};
+//! let c = ci.get_constant_definition("MAX_LEN").unwrap();
+//! assert_eq!(c.name(), "MAX_LEN");
+//! assert_eq!(c.type_(), Type::UInt32);
+//! assert!(matches!(c.value(), Literal::UInt(4096, _, _)));
+//! assert!(ci.get_object_definition("Limits").is_none());
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! WebIDL only supports booleans and numbers as constants, so there's no way to
+//! declare e.g. a string constant.
+
+use anyhow::{bail, Result};
+
+use super::literal::{convert_const_value, Literal};
+use super::types::{IterTypes, Type, TypeIterator};
+use super::{APIConverter, ComponentInterface};
+
+/// Represents a constant value exposed at the top level of the component.
+///
+/// Constants don't have any presence in the FFI; the foreign language bindings
+/// just declare them with the value from the UDL.
+#[derive(Debug, Clone)]
+pub struct Constant {
+    pub(super) name: String,
+    pub(super) type_: Type,
+    pub(super) value: Literal,
+    pub(super) docstring: Option<String>,
+}
+
+impl Constant {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_(&self) -> Type {
+        self.type_.clone()
+    }
+
+    pub fn value(&self) -> &Literal {
+        &self.value
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+}
+
+impl IterTypes for Constant {
+    fn iter_types(&self) -> TypeIterator<'_> {
+        self.type_.iter_types()
+    }
+}
+
+impl APIConverter<Vec<Constant>> for weedle::InterfaceDefinition<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Vec<Constant>> {
+        if self.inheritance.is_some() {
+            bail!("interface inheritence is not supported for constants interfaces");
+        }
+        // We don't need to check `self.attributes` here; if calling code has dispatched
+        // to this impl then we already know there was a `[Constants]` attribute.
+        self.members
+            .body
+            .iter()
+            .map(|member| match member {
+                weedle::interface::InterfaceMember::Const(c) => c.convert(ci),
+                _ => bail!(
+                    "interface member type {:?} not supported in constants interface",
+                    member
+                ),
+            })
+            .collect()
+    }
+}
+
+impl APIConverter<Constant> for weedle::interface::ConstMember<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Constant> {
+        if self.attributes.is_some() {
+            bail!("constant attributes are not supported yet");
+        }
+        let type_ = ci.resolve_type_expression(&self.const_type)?;
+        let value = convert_const_value(&self.const_value, &type_)?;
+        Ok(Constant {
+            name: self.identifier.0.to_string(),
+            type_,
+            value,
+            docstring: ci.resolve_docstring(self.identifier.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_constants() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Constants]
+            interface Limits {
+                const u8 SMALL = 0x10;
+                const i64 OFFSET = -1;
+                const double RATIO = 0.5;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(ci.iter_constant_definitions().len(), 3);
+        let small = ci.get_constant_definition("SMALL").unwrap();
+        assert_eq!(small.type_(), Type::UInt8);
+        assert!(matches!(small.value(), Literal::UInt(16, _, Type::UInt8)));
+        let offset = ci.get_constant_definition("OFFSET").unwrap();
+        assert!(matches!(offset.value(), Literal::Int(-1, _, Type::Int64)));
+        let ratio = ci.get_constant_definition("RATIO").unwrap();
+        assert!(matches!(ratio.value(), Literal::Float(v, Type::Float64) if v == "0.5"));
+        assert!(ci.iter_object_definitions().is_empty());
+    }
+
+    #[test]
+    fn test_constants_must_be_valid() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Constants]
+            interface Limits {
+                const boolean FLAG = 1;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot coerce literal 1 into a non-integer type"
+        );
+
+        const UDL2: &str = r#"
+            namespace test{};
+            [Constants]
+            interface Limits {
+                const u32 MAX_LEN = 4096;
+                void oops();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("interface member type Operation"));
+
+        const UDL3: &str = r#"
+            namespace test{};
+            [Constants]
+            interface Limits { const u32 MAX_LEN = 4096; };
+            [Constants]
+            interface MoreLimits { const u32 MAX_LEN = 8192; };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "duplicate constant definition: \"MAX_LEN\""
+        );
+    }
+}
@@ -0,0 +1,165 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! # Docstrings for items in a `ComponentInterface`.
+//!
+//! The WebIDL parser that we use discards comments, but it's convenient for consumers
+//! to be able to document their API directly in the UDL file, like this:
+//!
+//! ```
+//! # let ci = uniffi_bindgen::interface::ComponentInterface::from_webidl(r##"
+//! namespace example {
+//!     /// Say hello.
+//!     ///
+//!     /// You might like to say hello to someone else, too.
+//!     string hello();
+//! };
+//! # "##)?;
+//! # assert_eq!(ci.get_function_definition("hello").unwrap().docstring(), Some("Say hello.\n\nYou might like to say hello to someone else, too."));
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+//!
+//! The parser gives us back the identifiers of each item as slices of the original
+//! input string, so we can recover the `///` comment lines immediately above the item
+//! by looking at where in the input each identifier slice lives.
+
+/// Finds the docstring, if any, attached to an item in a UDL source string.
+///
+/// This is only populated for the duration of `ComponentInterface::from_webidl`, and only
+/// knows how to look up identifiers that are slices of the string from which it was created.
+#[derive(Debug, Default)]
+pub(super) struct DocstringFinder {
+    source: String,
+    base: usize,
+}
+
+impl DocstringFinder {
+    pub(super) fn new(source: &str) -> Self {
+        DocstringFinder {
+            source: source.to_string(),
+            base: source.as_ptr() as usize,
+        }
+    }
+
+    /// Get the docstring for the item whose identifier is the given slice of the source.
+    ///
+    /// This is made up of the contiguous `///` lines that precede the line on which the identifier
+    /// appears, skipping over any lines that contain only attributes.
+    pub(super) fn find(&self, identifier: &str) -> Option<String> {
+        let offset = (identifier.as_ptr() as usize).checked_sub(self.base)?;
+        if self.source.get(offset..offset + identifier.len())? != identifier {
+            return None;
+        }
+        let line_start = self.source[..offset].rfind('\n').map_or(0, |i| i + 1);
+        // If something else has been declared on the same line before the identifier,
+        // then the preceding comment belongs to that rather than to this item.
+        let prefix = self.source[..offset][line_start..].trim();
+        if prefix.contains('{')
+            || prefix.contains(';')
+            || prefix.trim_end_matches('"').contains('"')
+        {
+            return None;
+        }
+        let mut lines = Vec::new();
+        for line in self.source[..line_start].lines().rev() {
+            let line = line.trim();
+            if let Some(doc) = line.strip_prefix("///") {
+                lines.push(doc.strip_prefix(' ').unwrap_or(doc).trim_end());
+            } else if !(line.starts_with('[') && line.ends_with(']')) {
+                break;
+            }
+        }
+        if lines.is_empty() {
+            return None;
+        }
+        lines.reverse();
+        Some(lines.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::ComponentInterface;
+    use super::*;
+
+    #[test]
+    fn test_finds_preceding_doc_comment_lines() {
+        let udl = "namespace test {\n    /// First line.\n    ///\n    ///   Indented.\n    [Throws=Error]\n    void first();\n    void second();\n};";
+        let finder = DocstringFinder::new(udl);
+        let first = &udl[udl.find("first").unwrap()..][..5];
+        assert_eq!(finder.find(first).unwrap(), "First line.\n\n  Indented.");
+        let second = &udl[udl.find("second").unwrap()..][..6];
+        assert!(finder.find(second).is_none());
+    }
+
+    #[test]
+    fn test_ignores_identifiers_not_from_the_source() {
+        let udl = "/// A comment.\nenum Testing { \"one\", \"two\" };";
+        let finder = DocstringFinder::new(udl);
+        assert!(finder.find(&"Testing".to_string()).is_none());
+        let testing = &udl[udl.find("Testing").unwrap()..][..7];
+        assert_eq!(finder.find(testing).unwrap(), "A comment.");
+        // Variants declared on the same line as the enum don't inherit its docstring.
+        let one = &udl[udl.find("one").unwrap()..][..3];
+        assert!(finder.find(one).is_none());
+        let two = &udl[udl.find("two").unwrap()..][..3];
+        assert!(finder.find(two).is_none());
+    }
+
+    #[test]
+    fn test_docstrings_are_attached_to_items() {
+        const UDL: &str = r#"
+            namespace test {
+                /// A function.
+                void func();
+            };
+            /// A record.
+            dictionary Rec {
+                u32 field;
+            };
+            /// An enum.
+            enum Flat {
+                /// The first variant.
+                "one",
+                "two"
+            };
+            /// An enum with data.
+            [Enum]
+            interface WithData {
+                /// The only variant.
+                One(u32 first);
+            };
+            /// An object.
+            interface Obj {
+                /// A method.
+                void meth();
+            };
+            /// A callback interface.
+            callback interface Callback {
+                void call();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let func = ci.get_function_definition("func").unwrap();
+        assert_eq!(func.docstring(), Some("A function."));
+        let rec = ci.get_record_definition("Rec").unwrap();
+        assert_eq!(rec.docstring(), Some("A record."));
+        let flat = ci.get_enum_definition("Flat").unwrap();
+        assert_eq!(flat.docstring(), Some("An enum."));
+        assert_eq!(flat.variants()[0].docstring(), Some("The first variant."));
+        assert_eq!(flat.variants()[1].docstring(), None);
+        let with_data = ci.get_enum_definition("WithData").unwrap();
+        assert_eq!(with_data.docstring(), Some("An enum with data."));
+        assert_eq!(
+            with_data.variants()[0].docstring(),
+            Some("The only variant.")
+        );
+        let obj = ci.get_object_definition("Obj").unwrap();
+        assert_eq!(obj.docstring(), Some("An object."));
+        assert_eq!(obj.methods()[0].docstring(), Some("A method."));
+        let cbi = ci.get_callback_interface_definition("Callback").unwrap();
+        assert_eq!(cbi.docstring(), Some("A callback interface."));
+        assert_eq!(cbi.methods()[0].docstring(), None);
+    }
+}
@@ -76,8 +76,12 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+
 use anyhow::{bail, Result};
 
+use super::attributes::{EnumAttributes, InterfaceAttributes, ReprType, VariantAttributes};
 use super::record::Field;
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -87,12 +91,16 @@ use super::{APIConverter, ComponentInterface};
 ///
 /// Enums are passed across the FFI by serializing to a bytebuffer, with a
 /// i32 indicating the variant followed by the serialization of each field.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 pub struct Enum {
     pub(super) name: String,
     pub(super) variants: Vec<Variant>,
     // "Flat" enums do not have, and will never have, variants with associated data.
     pub(super) flat: bool,
+    // The integer type used to serialize the discriminant, from a `[Repr=u8]` attribute.
+    pub(super) discriminant_type: Type,
+    pub(super) docstring: Option<String>,
+    pub(super) deprecated: Option<String>,
 }
 
 impl Enum {
@@ -113,6 +121,23 @@ impl Enum {
     pub fn is_flat(&self) -> bool {
         self.flat
     }
+
+    /// The integer type used to serialize the discriminant that identifies each variant.
+    ///
+    /// This is always `Int32` unless the enum has a `[Repr]` attribute, which is only
+    /// supported on flat enums declared using the `enum` syntax.
+    pub fn discriminant_type(&self) -> Type {
+        self.discriminant_type.clone()
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
+    /// The message from a `[Deprecated="message"]` attribute, if there was one.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
 }
 
 impl IterTypes for Enum {
@@ -121,27 +146,56 @@ impl IterTypes for Enum {
     }
 }
 
+impl Hash for Enum {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring or deprecation message in the hash
+        // calculation, because they don't affect the FFI in any way.
+        self.name.hash(state);
+        self.variants.hash(state);
+        self.flat.hash(state);
+        self.discriminant_type.hash(state);
+    }
+}
+
 // Note that we have two `APIConverter` impls here - one for the `enum` case
 // and one for the `[Enum] interface` case.
 
 impl APIConverter<Enum> for weedle::EnumDefinition<'_> {
-    fn convert(&self, _ci: &mut ComponentInterface) -> Result<Enum> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Enum> {
+        let mut variants = Vec::new();
+        let mut discriminants = Vec::new();
+        for v in self.values.body.list.iter() {
+            // Variants can be given an explicit discriminant like `"name = 100"`.
+            let (name, discriminant) = match v.0.split_once('=') {
+                None => (v.0.trim(), None),
+                Some((name, value)) => match value.trim().parse::<u32>() {
+                    Ok(value) => (name.trim(), Some(value)),
+                    Err(_) => bail!("Invalid discriminant for enum variant: {:?}", v.0),
+                },
+            };
+            variants.push(Variant {
+                name: name.to_string(),
+                docstring: ci.resolve_docstring(name),
+                ..Default::default()
+            });
+            discriminants.push(discriminant);
+        }
+        let attributes = EnumAttributes::try_from(self.attributes.as_ref())?;
+        let discriminant_type = match attributes.get_repr() {
+            Some(ReprType::U8) => Type::UInt8,
+            Some(ReprType::U16) => Type::UInt16,
+            Some(ReprType::U32) => Type::UInt32,
+            None => Type::Int32,
+        };
+        assign_discriminants(&mut variants, &discriminants, &discriminant_type)?;
         Ok(Enum {
             name: self.identifier.0.to_string(),
-            variants: self
-                .values
-                .body
-                .list
-                .iter()
-                .map::<Result<_>, _>(|v| {
-                    Ok(Variant {
-                        name: v.0.to_string(),
-                        ..Default::default()
-                    })
-                })
-                .collect::<Result<Vec<_>>>()?,
+            variants,
             // Enums declared using the `enum` syntax can never have variants with fields.
             flat: true,
+            discriminant_type,
+            docstring: ci.resolve_docstring(self.identifier.0),
+            deprecated: attributes.get_deprecated().map(String::from),
         })
     }
 }
@@ -151,35 +205,90 @@ impl APIConverter<Enum> for weedle::InterfaceDefinition<'_> {
         if self.inheritance.is_some() {
             bail!("interface inheritence is not supported for enum interfaces");
         }
-        // We don't need to check `self.attributes` here; if calling code has dispatched
-        // to this impl then we already know there was an `[Enum]` attribute.
+        // If calling code has dispatched to this impl then we already know there was
+        // an `[Enum]` attribute, but it might also have been deprecated.
+        let attributes = InterfaceAttributes::try_from(self.attributes.as_ref())?;
+        let mut variants = self
+            .members
+            .body
+            .iter()
+            .map::<Result<Variant>, _>(|member| match member {
+                weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
+                _ => bail!(
+                    "interface member type {:?} not supported in enum interface",
+                    member
+                ),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let discriminants = vec![None; variants.len()];
+        assign_discriminants(&mut variants, &discriminants, &Type::Int32)?;
         Ok(Enum {
             name: self.identifier.0.to_string(),
-            variants: self
-                .members
-                .body
-                .iter()
-                .map::<Result<Variant>, _>(|member| match member {
-                    weedle::interface::InterfaceMember::Operation(t) => Ok(t.convert(ci)?),
-                    _ => bail!(
-                        "interface member type {:?} not supported in enum interface",
-                        member
-                    ),
-                })
-                .collect::<Result<Vec<_>>>()?,
+            variants,
             // Enums declared using the `[Enum] interface` syntax might have variants with fields.
             flat: false,
+            discriminant_type: Type::Int32,
+            docstring: ci.resolve_docstring(self.identifier.0),
+            deprecated: attributes.get_deprecated().map(String::from),
         })
     }
 }
 
+/// Give each variant its discriminant, the value that identifies it when serialized.
+///
+/// Like in Rust, any variant without an explicit discriminant gets one more than the variant
+/// before it. Unlike in Rust, the first variant defaults to 1 rather than 0, for compatibility
+/// with the original sequential numbering of variants.
+///
+/// Every discriminant must fit in the `discriminant_type` that it will be serialized as.
+/// That's also limited to the range of an `i32` for a `u32`, because some of the foreign
+/// languages don't have unsigned integers, so it's really just there for symmetry with Rust.
+fn assign_discriminants(
+    variants: &mut [Variant],
+    explicit: &[Option<u32>],
+    discriminant_type: &Type,
+) -> Result<()> {
+    let (max, type_name) = match discriminant_type {
+        Type::UInt8 => (u32::from(u8::MAX), "a u8"),
+        Type::UInt16 => (u32::from(u16::MAX), "a u16"),
+        _ => (i32::MAX as u32, "an i32"),
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut next = Some(1);
+    for (variant, explicit) in variants.iter_mut().zip(explicit) {
+        let discriminant = match explicit.or(next) {
+            Some(d) if d <= max => d,
+            _ => bail!(
+                "Discriminant for enum variant {:?} must fit in {}",
+                variant.name,
+                type_name
+            ),
+        };
+        if !seen.insert(discriminant) {
+            bail!(
+                "Duplicate discriminant {} for enum variant {:?}",
+                discriminant,
+                variant.name
+            );
+        }
+        variant.discriminant = discriminant;
+        next = discriminant.checked_add(1);
+    }
+    Ok(())
+}
+
 /// Represents an individual variant in an Enum.
 ///
-/// Each variant has a name and zero or more fields.
-#[derive(Debug, Clone, Default, Hash)]
+/// Each variant has a name, a discriminant, and zero or more fields.
+#[derive(Debug, Clone, Default)]
 pub struct Variant {
     pub(super) name: String,
     pub(super) fields: Vec<Field>,
+    pub(super) discriminant: u32,
+    pub(super) docstring: Option<String>,
+    pub(super) deprecated: Option<String>,
+    // The name from a `[Rename="OldName"]` attribute, if there was one.
+    pub(super) rename: Option<String>,
 }
 
 impl Variant {
@@ -193,6 +302,29 @@ impl Variant {
     pub fn has_fields(&self) -> bool {
         !self.fields.is_empty()
     }
+
+    /// The name to give this variant in the foreign language bindings.
+    ///
+    /// This is the same as the Rust name unless the variant has a `[Rename="OldName"]` attribute,
+    /// which keeps the foreign name stable when the Rust variant is renamed. It's only the name
+    /// that changes, since the variant is still serialized using its `discriminant`.
+    pub fn foreign_name(&self) -> &str {
+        self.rename.as_deref().unwrap_or(&self.name)
+    }
+
+    /// The value written to identify this variant when the enum is serialized.
+    pub fn discriminant(&self) -> u32 {
+        self.discriminant
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
+    /// The message from a `[Deprecated="message"]` attribute, if there was one.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
 }
 
 impl IterTypes for Variant {
@@ -201,6 +333,16 @@ impl IterTypes for Variant {
     }
 }
 
+impl Hash for Variant {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring, deprecation message or rename in the hash
+        // calculation, because they don't affect the FFI in any way.
+        self.name.hash(state);
+        self.fields.hash(state);
+        self.discriminant.hash(state);
+    }
+}
+
 impl APIConverter<Variant> for weedle::interface::OperationInterfaceMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Variant> {
         if self.special.is_some() {
@@ -216,17 +358,23 @@ impl APIConverter<Variant> for weedle::interface::OperationInterfaceMember<'_> {
         if self.identifier.is_some() {
             bail!("enum interface members must not have a method name");
         }
-        let name: String = {
+        let name: &str = {
             use weedle::types::{
                 NonAnyType::Identifier, ReturnType, SingleType::NonAny, Type::Single,
             };
             match &self.return_type {
-                ReturnType::Type(Single(NonAny(Identifier(id)))) => id.type_.0.to_owned(),
+                ReturnType::Type(Single(NonAny(Identifier(id)))) => id.type_.0,
                 _ => bail!("enum interface members must have plain identifers as names"),
             }
         };
+        let attributes = VariantAttributes::try_from(self.attributes.as_ref())?;
         Ok(Variant {
-            name,
+            name: name.to_owned(),
+            docstring: ci.resolve_docstring(name),
+            deprecated: attributes.get_deprecated().map(String::from),
+            rename: attributes.get_rename().map(String::from),
+            // This is filled in once we've seen all the variants.
+            discriminant: 0,
             fields: self
                 .args
                 .body
@@ -266,6 +414,9 @@ impl APIConverter<Field> for weedle::argument::SingleArgument<'_> {
             type_,
             required: false,
             default: None,
+            excluded_from_equality: false,
+            constraints: vec![],
+            boxed: false,
         })
     }
 }
@@ -291,6 +442,97 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_explicit_discriminants() {
+        const UDL: &str = r#"
+            namespace test{};
+            enum Sequential { "one", "two" };
+            enum Opcode { "Ping = 1", "Pong", "Close = 100", "Reset" };
+            [Enum]
+            interface WithData {
+                One();
+                Two(u32 first);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let discriminants = |name: &str| {
+            ci.get_enum_definition(name)
+                .unwrap()
+                .variants()
+                .iter()
+                .map(|v| (v.name().to_string(), v.discriminant()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            discriminants("Sequential"),
+            vec![("one".to_string(), 1), ("two".to_string(), 2)]
+        );
+        assert_eq!(
+            discriminants("Opcode"),
+            vec![
+                ("Ping".to_string(), 1),
+                ("Pong".to_string(), 2),
+                ("Close".to_string(), 100),
+                ("Reset".to_string(), 101)
+            ]
+        );
+        assert_eq!(
+            discriminants("WithData"),
+            vec![("One".to_string(), 1), ("Two".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn test_invalid_discriminants() {
+        let err = ComponentInterface::from_webidl(
+            r#"namespace test{}; enum Testing { "one = 2", "two = 2" };"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Duplicate discriminant 2 for enum variant \"two\""
+        );
+        let err =
+            ComponentInterface::from_webidl(r#"namespace test{}; enum Testing { "one = -1" };"#)
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid discriminant for enum variant: \"one = -1\""
+        );
+        let err = ComponentInterface::from_webidl(
+            r#"namespace test{}; enum Testing { "one = 2147483648" };"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Discriminant for enum variant \"one\" must fit in an i32"
+        );
+        let err = ComponentInterface::from_webidl(
+            r#"namespace test{}; [Repr=u8] enum Testing { "one = 255", "two" };"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Discriminant for enum variant \"two\" must fit in a u8"
+        );
+    }
+
+    #[test]
+    fn test_discriminant_types() {
+        const UDL: &str = r#"
+            namespace test{};
+            enum Default { "one" };
+            [Repr=u8] enum Small { "one", "two = 255" };
+            [Repr=u16] enum Medium { "one = 1000" };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let discriminant_type =
+            |name: &str| ci.get_enum_definition(name).unwrap().discriminant_type();
+        assert_eq!(discriminant_type("Default"), Type::Int32);
+        assert_eq!(discriminant_type("Small"), Type::UInt8);
+        assert_eq!(discriminant_type("Medium"), Type::UInt16);
+    }
+
     #[test]
     fn test_associated_data() {
         const UDL: &str = r##"
@@ -415,4 +657,23 @@ mod test {
             Some(FFIType::RustBuffer)
         ));
     }
+
+    #[test]
+    fn test_renamed_variants() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Enum]
+            interface Shape {
+                [Rename="Round"]
+                Circle(double radius);
+                Square(double side);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let e = ci.get_enum_definition("Shape").unwrap();
+        assert_eq!(e.variants()[0].name(), "Circle");
+        assert_eq!(e.variants()[0].foreign_name(), "Round");
+        assert_eq!(e.variants()[0].discriminant(), 1);
+        assert_eq!(e.variants()[1].foreign_name(), "Square");
+    }
 }
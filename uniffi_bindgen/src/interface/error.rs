@@ -129,6 +129,14 @@ impl Error {
     pub fn is_flat(&self) -> bool {
         self.enum_.is_flat()
     }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.enum_.docstring()
+    }
+
+    pub fn deprecated(&self) -> Option<&str> {
+        self.enum_.deprecated()
+    }
 }
 
 impl IterTypes for Error {
@@ -12,6 +12,9 @@
 //! need to know about them. But as a developer working on UniFFI itself, you're likely
 //! to spend a lot of time thinking about how these low-level types are used to represent
 //! the higher-level "interface types" from the [`super::types::Type`] enum.
+
+use std::fmt;
+
 /// Represents the restricted set of low-level types that can be used to construct
 /// the C-style FFI layer between a rust component and its foreign language bindings.
 ///
@@ -45,10 +48,37 @@ pub enum FFIType {
     /// A pointer to a single function in to the foreign language.
     /// This function contains all the machinery to make callbacks work on the foreign language side.
     ForeignCallback,
+    /// A pointer to a function in the foreign language that receives the result of an async
+    /// function call, once the corresponding Rust future has completed.
+    FutureCallback,
     // TODO: you can imagine a richer structural typesystem here, e.g. `Ref<String>` or something.
     // We don't need that yet and it's possible we never will, so it isn't here for now.
 }
 
+/// These are the names of the variants, written out so that they stay the same in the
+/// signatures from [`FFIFunction::signature`] even if the variants are renamed.
+impl fmt::Display for FFIType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FFIType::UInt8 => "UInt8",
+            FFIType::Int8 => "Int8",
+            FFIType::UInt16 => "UInt16",
+            FFIType::Int16 => "Int16",
+            FFIType::UInt32 => "UInt32",
+            FFIType::Int32 => "Int32",
+            FFIType::UInt64 => "UInt64",
+            FFIType::Int64 => "Int64",
+            FFIType::Float32 => "Float32",
+            FFIType::Float64 => "Float64",
+            FFIType::RustArcPtr => "RustArcPtr",
+            FFIType::RustBuffer => "RustBuffer",
+            FFIType::ForeignBytes => "ForeignBytes",
+            FFIType::ForeignCallback => "ForeignCallback",
+            FFIType::FutureCallback => "FutureCallback",
+        })
+    }
+}
+
 /// Represents an "extern C"-style function that will be part of the FFI.
 ///
 /// These can't be declared explicitly in the UDL, but rather, are derived automatically
@@ -72,6 +102,23 @@ impl FFIFunction {
     pub fn return_type(&self) -> Option<&FFIType> {
         self.return_type.as_ref()
     }
+
+    /// A one-line description of the function's ABI, like `name(UInt32, RustBuffer) -> RustBuffer`.
+    ///
+    /// The names of the arguments aren't part of the ABI, so they're left out. Neither is the
+    /// `RustCallStatus` that every FFI function takes as its last argument.
+    pub fn signature(&self) -> String {
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|arg| arg.type_.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &self.return_type {
+            Some(return_type) => format!("{}({}) -> {}", self.name, arguments, return_type),
+            None => format!("{}({})", self.name, arguments),
+        }
+    }
 }
 
 /// Represents an argument to an FFI function.
@@ -37,7 +37,7 @@ use std::hash::{Hash, Hasher};
 use anyhow::{bail, Result};
 
 use super::attributes::{ArgumentAttributes, FunctionAttributes};
-use super::ffi::{FFIArgument, FFIFunction};
+use super::ffi::{FFIArgument, FFIFunction, FFIType};
 use super::literal::{convert_default_value, Literal};
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -55,6 +55,7 @@ pub struct Function {
     pub(super) return_type: Option<Type>,
     pub(super) ffi_func: FFIFunction,
     pub(super) attributes: FunctionAttributes,
+    pub(super) docstring: Option<String>,
 }
 
 impl Function {
@@ -88,12 +89,39 @@ impl Function {
             .map(|name| Type::Error(name.to_owned()))
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
+    pub fn deprecated(&self) -> Option<&str> {
+        self.attributes.get_deprecated()
+    }
+
+    /// Whether this function is an `async fn` in Rust.
+    ///
+    /// Rather than returning a value, the FFI function for an async function takes a
+    /// `FutureCallback` that will be called with the serialized result once the Rust
+    /// future has completed, along with an opaque `u64` to pass back to that callback.
+    pub fn is_async(&self) -> bool {
+        self.attributes.is_async()
+    }
+
     pub fn derive_ffi_func(&mut self, ci_prefix: &str) -> Result<()> {
-        self.ffi_func.name.push_str(ci_prefix);
-        self.ffi_func.name.push('_');
-        self.ffi_func.name.push_str(&self.name);
+        self.ffi_func.name = format!("{}_{}", ci_prefix, self.name);
         self.ffi_func.arguments = self.arguments.iter().map(|arg| arg.into()).collect();
-        self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.into());
+        if self.is_async() {
+            self.ffi_func.arguments.push(FFIArgument {
+                name: "uniffi_callback".to_string(),
+                type_: FFIType::FutureCallback,
+            });
+            self.ffi_func.arguments.push(FFIArgument {
+                name: "uniffi_callback_data".to_string(),
+                type_: FFIType::UInt64,
+            });
+            self.ffi_func.return_type = None;
+        } else {
+            self.ffi_func.return_type = self.return_type.as_ref().map(|rt| rt.into());
+        }
         Ok(())
     }
 }
@@ -137,15 +165,27 @@ impl APIConverter<Function> for weedle::namespace::NamespaceMember<'_> {
 impl APIConverter<Function> for weedle::namespace::OperationNamespaceMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Function> {
         let return_type = ci.resolve_return_type_expression(&self.return_type)?;
+        let name = match self.identifier {
+            None => bail!("anonymous functions are not supported {:?}", self),
+            Some(id) => id.0.to_string(),
+        };
+        let arguments: Vec<Argument> = self.args.body.list.convert(ci)?;
+        let attributes = FunctionAttributes::try_from(self.attributes.as_ref())?;
+        // The future for an async function outlives the FFI call that created it,
+        // so it can't hold on to any borrowed arguments.
+        if attributes.is_async() && arguments.iter().any(|arg| arg.by_ref) {
+            bail!(
+                "async function {:?} can't take arguments by reference",
+                name
+            );
+        }
         Ok(Function {
-            name: match self.identifier {
-                None => bail!("anonymous functions are not supported {:?}", self),
-                Some(id) => id.0.to_string(),
-            },
+            name,
             return_type,
-            arguments: self.args.body.list.convert(ci)?,
+            arguments,
             ffi_func: Default::default(),
-            attributes: FunctionAttributes::try_from(self.attributes.as_ref())?,
+            attributes,
+            docstring: self.identifier.and_then(|id| ci.resolve_docstring(id.0)),
         })
     }
 }
@@ -172,6 +212,21 @@ impl Argument {
     pub fn by_ref(&self) -> bool {
         self.by_ref
     }
+    /// Whether this is a `[ByRef] bytes` argument.
+    ///
+    /// These are passed over the FFI as `ForeignBytes` rather than being copied into a
+    /// `RustBuffer`, so that the Rust code can read them in place as a `&[u8]`. This is
+    /// only safe because the Rust code can't hold on to a borrowed argument beyond the call.
+    pub fn is_borrowed_bytes(&self) -> bool {
+        self.by_ref && self.type_ == Type::Bytes
+    }
+    /// Whether this is a `[ByRef]` callback interface argument.
+    ///
+    /// Rust only borrows these for the duration of the call, so the foreign code registers
+    /// the callback just for that call and unregisters it again once the call returns.
+    pub fn is_lent_callback(&self) -> bool {
+        self.by_ref && matches!(self.type_, Type::CallbackInterface(_))
+    }
     pub fn default_value(&self) -> Option<Literal> {
         self.default.clone()
     }
@@ -187,7 +242,11 @@ impl From<&Argument> for FFIArgument {
     fn from(a: &Argument) -> FFIArgument {
         FFIArgument {
             name: a.name.clone(),
-            type_: (&a.type_).into(),
+            type_: if a.is_borrowed_bytes() {
+                FFIType::ForeignBytes
+            } else {
+                (&a.type_).into()
+            },
         }
     }
 }
@@ -263,4 +322,86 @@ mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_borrowed_callbacks_are_lent_for_the_call() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                void run([ByRef] Progress borrowed, Progress owned, [ByRef] string other);
+            };
+            callback interface Progress {
+                void update(u8 percent);
+            };
+        "##,
+        )?;
+
+        let func = ci.get_function_definition("run").unwrap();
+        let args = func.arguments();
+        assert!(args[0].is_lent_callback());
+        assert!(!args[1].is_lent_callback());
+        assert!(!args[2].is_lent_callback());
+        Ok(())
+    }
+
+    #[test]
+    fn test_borrowed_bytes_are_passed_as_foreign_bytes() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                void consume([ByRef] bytes borrowed, bytes owned, [ByRef] sequence<u8> other);
+            };
+        "##,
+        )?;
+
+        let func = ci.get_function_definition("consume").unwrap();
+        let args = func.arguments();
+        assert!(args[0].is_borrowed_bytes());
+        assert!(!args[1].is_borrowed_bytes());
+        assert!(!args[2].is_borrowed_bytes());
+        let ffi_args = func.ffi_func().arguments();
+        assert_eq!(ffi_args[0].type_(), FFIType::ForeignBytes);
+        assert_eq!(ffi_args[1].type_(), FFIType::RustBuffer);
+        assert_eq!(ffi_args[2].type_(), FFIType::RustBuffer);
+        Ok(())
+    }
+
+    #[test]
+    fn test_async_function() -> Result<()> {
+        let ci = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                [Async]
+                string fetch(u32 id);
+                void sync();
+            };
+        "##,
+        )?;
+
+        let func = ci.get_function_definition("fetch").unwrap();
+        assert!(func.is_async());
+        assert!(func.ffi_func().return_type().is_none());
+        let ffi_args = func.ffi_func().arguments();
+        assert_eq!(ffi_args.len(), 3);
+        assert_eq!(ffi_args[1].name(), "uniffi_callback");
+        assert_eq!(ffi_args[1].type_(), FFIType::FutureCallback);
+        assert_eq!(ffi_args[2].type_(), FFIType::UInt64);
+        assert!(!ci.get_function_definition("sync").unwrap().is_async());
+        assert!(ci.contains_async_functions());
+
+        let err = ComponentInterface::from_webidl(
+            r##"
+            namespace test {
+                [Async]
+                void fetch([ByRef] string id);
+            };
+        "##,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "async function \"fetch\" can't take arguments by reference"
+        );
+        Ok(())
+    }
 }
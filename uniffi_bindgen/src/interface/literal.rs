@@ -5,14 +5,14 @@
 //! # Support for literal values.
 //!
 //! This module provides support for interpreting literal values from the UDL,
-//! which appear in places such as default arguments.
+//! which appear in places such as default arguments and constants.
 
 use anyhow::{bail, Result};
 
 use super::types::Type;
 
 // Represents a literal value.
-// Used for e.g. default argument values.
+// Used for e.g. default argument values and constants.
 #[derive(Debug, Clone, Hash)]
 pub enum Literal {
     Boolean(bool),
@@ -20,8 +20,8 @@ pub enum Literal {
     // Integers are represented as the widest representation we can.
     // Number formatting vary with language and radix, so we avoid a lot of parsing and
     // formatting duplication by using only signed and unsigned variants.
-    UInt(u64, Radix, Type),
-    Int(i64, Radix, Type),
+    UInt(u128, Radix, Type),
+    Int(i128, Radix, Type),
     // Pass the string representation through as typed in the UDL.
     // This avoids a lot of uncertainty around precision and accuracy,
     // though bindings for languages less sophisticated number parsing than WebIDL
@@ -42,66 +42,66 @@ pub enum Radix {
     Hexadecimal = 16,
 }
 
-pub(super) fn convert_default_value(
-    default_value: &weedle::literal::DefaultValue<'_>,
-    type_: &Type,
-) -> Result<Literal> {
-    fn convert_integer(literal: &weedle::literal::IntegerLit<'_>, type_: &Type) -> Result<Literal> {
-        let (string, radix) = match literal {
-            weedle::literal::IntegerLit::Dec(v) => (v.0, Radix::Decimal),
-            weedle::literal::IntegerLit::Hex(v) => (v.0, Radix::Hexadecimal),
-            weedle::literal::IntegerLit::Oct(v) => (v.0, Radix::Octal),
-        };
-        // This is the radix of the parsed number, passed to `from_str_radix`.
-        let src_radix = radix as u32;
-        // This radix tells the backends how to represent the number in the output languages.
-        let dest_radix = if string == "0" || string.starts_with('-') {
-            // 1. weedle parses "0" as an octal literal, but we most likely want to treat this as a decimal.
-            // 2. Explicitly negatively signed hex numbers won't convert via i64 very well if they're not 64 bit.
-            //    For ease of implementation, output will use decimal.
-            Radix::Decimal
-        } else {
-            radix
-        };
-
-        // Clippy seems to think we should be using `strip_prefix` here, but
-        // it seems confused as to what this is actually doing.
-        #[allow(clippy::manual_strip)]
-        let string = if string.starts_with('-') {
-            ("-".to_string() + string[1..].trim_start_matches("0x")).to_lowercase()
-        } else {
-            string.trim_start_matches("0x").to_lowercase()
-        };
+fn convert_integer(literal: &weedle::literal::IntegerLit<'_>, type_: &Type) -> Result<Literal> {
+    let (string, radix) = match literal {
+        weedle::literal::IntegerLit::Dec(v) => (v.0, Radix::Decimal),
+        weedle::literal::IntegerLit::Hex(v) => (v.0, Radix::Hexadecimal),
+        weedle::literal::IntegerLit::Oct(v) => (v.0, Radix::Octal),
+    };
+    // This is the radix of the parsed number, passed to `from_str_radix`.
+    let src_radix = radix as u32;
+    // This radix tells the backends how to represent the number in the output languages.
+    let dest_radix = if string == "0" || string.starts_with('-') {
+        // 1. weedle parses "0" as an octal literal, but we most likely want to treat this as a decimal.
+        // 2. Explicitly negatively signed hex numbers won't convert via i64 very well if they're not 64 bit.
+        //    For ease of implementation, output will use decimal.
+        Radix::Decimal
+    } else {
+        radix
+    };
+
+    // Clippy seems to think we should be using `strip_prefix` here, but
+    // it seems confused as to what this is actually doing.
+    #[allow(clippy::manual_strip)]
+    let string = if string.starts_with('-') {
+        ("-".to_string() + string[1..].trim_start_matches("0x")).to_lowercase()
+    } else {
+        string.trim_start_matches("0x").to_lowercase()
+    };
+
+    Ok(match type_ {
+        Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 | Type::Int128 => Literal::Int(
+            i128::from_str_radix(&string, src_radix)?,
+            dest_radix,
+            type_.clone(),
+        ),
+        Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 | Type::UInt128 => Literal::UInt(
+            u128::from_str_radix(&string, src_radix)?,
+            dest_radix,
+            type_.clone(),
+        ),
+
+        _ => bail!("Cannot coerce literal {} into a non-integer type", string),
+    })
+}
 
-        Ok(match type_ {
-            Type::Int8 | Type::Int16 | Type::Int32 | Type::Int64 => Literal::Int(
-                i64::from_str_radix(&string, src_radix)?,
-                dest_radix,
-                type_.clone(),
-            ),
-            Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64 => Literal::UInt(
-                u64::from_str_radix(&string, src_radix)?,
-                dest_radix,
-                type_.clone(),
-            ),
-
-            _ => bail!("Cannot coerce literal {} into a non-integer type", string),
-        })
-    }
+fn convert_float(literal: &weedle::literal::FloatLit<'_>, type_: &Type) -> Result<Literal> {
+    let string = match literal {
+        weedle::literal::FloatLit::Value(v) => v.0,
 
-    fn convert_float(literal: &weedle::literal::FloatLit<'_>, type_: &Type) -> Result<Literal> {
-        let string = match literal {
-            weedle::literal::FloatLit::Value(v) => v.0,
+        _ => bail!("Infinity and NaN is not currently supported"),
+    };
 
-            _ => bail!("Infinity and NaN is not currently supported"),
-        };
-
-        Ok(match type_ {
-            Type::Float32 | Type::Float64 => Literal::Float(string.to_string(), type_.clone()),
-            _ => bail!("Cannot coerce literal {} into a non-float type", string),
-        })
-    }
+    Ok(match type_ {
+        Type::Float32 | Type::Float64 => Literal::Float(string.to_string(), type_.clone()),
+        _ => bail!("Cannot coerce literal {} into a non-float type", string),
+    })
+}
 
+pub(super) fn convert_default_value(
+    default_value: &weedle::literal::DefaultValue<'_>,
+    type_: &Type,
+) -> Result<Literal> {
     Ok(match (default_value, type_) {
         (weedle::literal::DefaultValue::Boolean(b), Type::Boolean) => Literal::Boolean(b.0),
         (weedle::literal::DefaultValue::String(s), Type::String) => {
@@ -126,6 +126,22 @@ pub(super) fn convert_default_value(
     })
 }
 
+/// Convert the value of a WebIDL `const` into a literal of the given type.
+///
+/// These are more restricted than default values, because WebIDL only allows
+/// booleans and numbers as constants.
+pub(super) fn convert_const_value(
+    const_value: &weedle::literal::ConstValue<'_>,
+    type_: &Type,
+) -> Result<Literal> {
+    Ok(match (const_value, type_) {
+        (weedle::literal::ConstValue::Boolean(b), Type::Boolean) => Literal::Boolean(b.0),
+        (weedle::literal::ConstValue::Integer(i), _) => convert_integer(i, type_)?,
+        (weedle::literal::ConstValue::Float(i), _) => convert_float(i, type_)?,
+        _ => bail!("No support for {:?} constant yet", const_value),
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -146,6 +162,14 @@ mod test {
             parse_and_convert("-12", Type::Int32)?,
             Literal::Int(-12, Radix::Decimal, Type::Int32)
         ));
+        assert!(matches!(
+            parse_and_convert("340282366920938463463374607431768211455", Type::UInt128)?,
+            Literal::UInt(std::u128::MAX, Radix::Decimal, Type::UInt128)
+        ));
+        assert!(matches!(
+            parse_and_convert("-170141183460469231731687303715884105728", Type::Int128)?,
+            Literal::Int(std::i128::MIN, Radix::Decimal, Type::Int128)
+        ));
         assert!(
             matches!(parse_and_convert("3.14", Type::Float32)?, Literal::Float(v, Type::Float32) if v == "3.14")
         );
@@ -169,6 +193,34 @@ mod test {
         ));
         Ok(())
     }
+    #[test]
+    fn test_const_value_conversion() -> Result<()> {
+        let convert = |expr: &str, t: Type| {
+            let (_, node) = weedle::literal::ConstValue::parse(expr).unwrap();
+            convert_const_value(&node, &t)
+        };
+        assert!(matches!(
+            convert("4096", Type::UInt32)?,
+            Literal::UInt(4096, Radix::Decimal, Type::UInt32)
+        ));
+        assert!(matches!(
+            convert("0xff", Type::UInt8)?,
+            Literal::UInt(255, Radix::Hexadecimal, Type::UInt8)
+        ));
+        assert!(matches!(
+            convert("true", Type::Boolean)?,
+            Literal::Boolean(true)
+        ));
+        assert!(
+            matches!(convert("0.5", Type::Float64)?, Literal::Float(v, Type::Float64) if v == "0.5")
+        );
+        assert!(convert("null", Type::Optional(Box::new(Type::UInt32)))
+            .unwrap_err()
+            .to_string()
+            .starts_with("No support for"));
+        Ok(())
+    }
+
     #[test]
     fn test_error_on_type_mismatch() {
         assert_eq!(
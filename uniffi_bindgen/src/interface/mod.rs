@@ -45,7 +45,7 @@
 //!   * Error messages and general developer experience leave a lot to be desired.
 
 use std::{
-    collections::{hash_map::DefaultHasher, HashSet},
+    collections::{hash_map::DefaultHasher, BTreeSet, HashMap, HashSet},
     convert::TryFrom,
     hash::{Hash, Hasher},
     str::FromStr,
@@ -60,6 +60,10 @@ use types::{IterTypes, TypeIterator, TypeUniverse};
 mod attributes;
 mod callbacks;
 pub use callbacks::CallbackInterface;
+mod constant;
+pub use constant::Constant;
+mod docstring;
+use docstring::DocstringFinder;
 mod enum_;
 pub use enum_::Enum;
 mod error;
@@ -73,7 +77,7 @@ pub use namespace::Namespace;
 mod object;
 pub use object::{Constructor, Method, Object};
 mod record;
-pub use record::{Field, Record};
+pub use record::{Constraint, Field, Record};
 
 pub mod ffi;
 pub use ffi::{FFIArgument, FFIFunction, FFIType};
@@ -91,6 +95,11 @@ pub struct ComponentInterface {
     types: TypeUniverse,
     /// The unique prefix that we'll use for namespacing when exposing this component's API.
     namespace: String,
+    /// An alternative to `namespace` for prefixing the names of FFI functions, if configured.
+    ffi_namespace_override: Option<String>,
+    /// If this is a subset of a larger interface, the checksum of the full interface, which
+    /// is what the scaffolding will have been generated from.
+    subset_checksum: Option<u64>,
     /// The high-level API provided by the component.
     enums: Vec<Enum>,
     records: Vec<Record>,
@@ -98,11 +107,25 @@ pub struct ComponentInterface {
     objects: Vec<Object>,
     callback_interfaces: Vec<CallbackInterface>,
     errors: Vec<Error>,
+    constants: Vec<Constant>,
+    /// Used to find the docstrings for items while we're parsing the WebIDL, one per UDL file.
+    docstrings: Vec<DocstringFinder>,
 }
 
 impl<'ci> ComponentInterface {
     /// Parse a `ComponentInterface` from a string containing a WebIDL definition.
     pub fn from_webidl(idl: &str) -> Result<Self> {
+        Self::from_webidl_files(&[("<udl>", idl)])
+    }
+
+    /// Parse a single `ComponentInterface` from several WebIDL definitions, given as pairs of
+    /// the name of each file (used in error messages) and its contents.
+    ///
+    /// This lets a large interface be split across multiple UDL files. Each file can use the
+    /// types defined in any of the others, and the namespace functions can be spread between
+    /// them, but every file that has a `namespace` definition has to use the same name, and
+    /// nothing can be defined in more than one file.
+    pub fn from_webidl_files(files: &[(&str, &str)]) -> Result<Self> {
         let mut ci = Self {
             uniffi_version: env!("CARGO_PKG_VERSION").to_string(),
             ..Default::default()
@@ -113,11 +136,52 @@ impl<'ci> ComponentInterface {
         // Note we use `weedle::Definitions::parse` instead of `weedle::parse` so
         // on parse errors we can see how far weedle got, which helps locate the problem.
         use weedle::Parse; // this trait must be in scope for parse to work.
-        let (remaining, defns) = weedle::Definitions::parse(idl.trim()).unwrap();
-        if !remaining.is_empty() {
-            println!("Error parsing the IDL. Text remaining to be parsed is:");
-            println!("{}", remaining);
-            bail!("parse error");
+        let mut defns = Vec::new();
+        // The name of each namespace and top-level definition, and the file it came from.
+        let mut namespace: Option<(&str, &str)> = None;
+        let mut defined_in: HashMap<&str, &str> = HashMap::new();
+        for &(file_name, idl) in files {
+            let idl = idl.trim();
+            ci.docstrings.push(DocstringFinder::new(idl));
+            let (remaining, file_defns) = weedle::Definitions::parse(idl).unwrap();
+            if !remaining.is_empty() {
+                println!("Error parsing the IDL. Text remaining to be parsed is:");
+                println!("{}", remaining);
+                bail!("parse error");
+            }
+            let mut has_namespace = false;
+            for defn in file_defns.iter() {
+                if let weedle::Definition::Namespace(d) = defn {
+                    if has_namespace {
+                        bail!("duplicate namespace definition");
+                    }
+                    has_namespace = true;
+                    match namespace {
+                        Some((name, other_file)) if name != d.identifier.0 => bail!(
+                            "Namespace {:?} in {} doesn't match namespace {:?} in {}",
+                            d.identifier.0,
+                            file_name,
+                            name,
+                            other_file
+                        ),
+                        _ => namespace = Some((d.identifier.0, file_name)),
+                    }
+                }
+                for name in defined_names(defn) {
+                    match defined_in.get(name) {
+                        Some(other_file) if *other_file != file_name => bail!(
+                            "{:?} is defined in both {} and {}",
+                            name,
+                            other_file,
+                            file_name
+                        ),
+                        _ => {
+                            defined_in.insert(name, file_name);
+                        }
+                    }
+                }
+            }
+            defns.extend(file_defns);
         }
         // Unconditionally add the String type, which is used by the panic handling
         let _ = ci.types.add_known_type(Type::String);
@@ -126,7 +190,9 @@ impl<'ci> ComponentInterface {
         ci.types.add_type_definitions_from(defns.as_slice())?;
         // With those names resolved, we can build a complete representation of the API.
         APIBuilder::process(&defns, &mut ci)?;
+        ci.docstrings.clear();
         ci.check_consistency()?;
+        ci.box_recursive_fields()?;
         // Now that the high-level API is settled, we can derive the low-level FFI.
         ci.derive_ffi_funcs()?;
         Ok(ci)
@@ -173,6 +239,16 @@ impl<'ci> ComponentInterface {
         self.functions.iter().find(|f| f.name == name)
     }
 
+    /// List the definitions for every Constant in the interface.
+    pub fn iter_constant_definitions(&self) -> Vec<Constant> {
+        self.constants.to_vec()
+    }
+
+    /// Get a Constant definition by name, or None if no such Constant is defined.
+    pub fn get_constant_definition(&self, name: &str) -> Option<&Constant> {
+        self.constants.iter().find(|c| c.name == name)
+    }
+
     /// List the definitions for every Object type in the interface.
     pub fn iter_object_definitions(&self) -> Vec<Object> {
         self.objects.to_vec()
@@ -228,6 +304,24 @@ impl<'ci> ComponentInterface {
             .collect()
     }
 
+    /// Get the names declared with `[Alias] typedef`, and the types that they stand for.
+    ///
+    /// Aliases don't affect the FFI, so bindings that don't support them can ignore them.
+    pub fn iter_type_aliases(&self) -> Vec<(String, Type)> {
+        self.types
+            .iter_type_aliases()
+            .map(|(name, type_)| (name.to_string(), type_.clone()))
+            .collect()
+    }
+
+    /// Get all the `Type::Stream` types in the interface.
+    pub fn iter_stream_types(&self) -> Vec<Type> {
+        self.types
+            .iter_known_types()
+            .filter(|t| matches!(t, Type::Stream(_)))
+            .collect()
+    }
+
     /// Iterate over all known types in the interface.
     pub fn iter_types(&self) -> Vec<Type> {
         self.types.iter_known_types().collect()
@@ -283,7 +377,37 @@ impl<'ci> ComponentInterface {
     pub fn contains_map_types(&self) -> bool {
         self.types
             .iter_known_types()
-            .any(|t| matches!(t, Type::Map(_)))
+            .any(|t| matches!(t, Type::Map(_, _)))
+    }
+
+    /// Check whether the interface contains any unsigned integer types
+    pub fn contains_unsigned_types(&self) -> bool {
+        self.types
+            .iter_known_types()
+            .any(|t| matches!(t, Type::UInt8 | Type::UInt16 | Type::UInt32 | Type::UInt64))
+    }
+
+    /// Check whether an error is used as an ordinary value anywhere in the interface.
+    ///
+    /// Errors are usually only thrown, but they can also be passed as arguments, returned
+    /// as values, or held in fields, which some bindings need a separate representation for.
+    pub fn is_error_used_as_value(&self, name: &str) -> bool {
+        let error_type = Type::Error(name.to_string());
+        self.functions
+            .iter()
+            .map(IterTypes::iter_types)
+            .chain(self.objects.iter().map(IterTypes::iter_types))
+            .chain(self.callback_interfaces.iter().map(IterTypes::iter_types))
+            .chain(self.records.iter().map(IterTypes::iter_types))
+            .chain(self.enums.iter().map(IterTypes::iter_types))
+            .chain(self.errors.iter().map(IterTypes::iter_types))
+            .flatten()
+            .any(|t| *t == error_type)
+    }
+
+    /// Check whether the interface contains any async functions
+    pub fn contains_async_functions(&self) -> bool {
+        self.functions.iter().any(|f| f.is_async())
     }
 
     /// Calculate a numeric checksum for this ComponentInterface.
@@ -309,6 +433,9 @@ impl<'ci> ComponentInterface {
     /// ensure the guarantees above, or if it might be sensitive to e.g. compiler-driven re-ordering
     /// of struct field. Let's see how it goes...
     pub fn checksum(&self) -> u64 {
+        if let Some(checksum) = self.subset_checksum {
+            return checksum;
+        }
         let mut hasher = DefaultHasher::new();
         // Our implementation of `Hash` mixes in all of the public API of the component,
         // as well as the version string of uniffi.
@@ -331,11 +458,122 @@ impl<'ci> ComponentInterface {
     pub fn ffi_namespace(&self) -> String {
         format!(
             "{}_{:x}",
-            self.namespace,
+            self.ffi_namespace_override
+                .as_deref()
+                .unwrap_or(&self.namespace),
             (self.checksum() & 0x000000000000FFFF) as u16
         )
     }
 
+    /// Use a different prefix from the namespace for the names of FFI functions.
+    ///
+    /// This makes it possible to link more than one copy of the same component into an
+    /// application, by giving each of them a distinct prefix. It doesn't change `namespace()`,
+    /// and the checksum is still appended to the new prefix. The scaffolding and the bindings
+    /// need to agree on the prefix, so they should both be generated with the same override.
+    pub fn set_ffi_namespace(&mut self, prefix: &str) -> Result<()> {
+        let is_valid_identifier = prefix
+            .chars()
+            .enumerate()
+            .all(|(i, c)| c == '_' || c.is_ascii_alphabetic() || (i > 0 && c.is_ascii_digit()));
+        if prefix.is_empty() || !is_valid_identifier {
+            bail!("Invalid FFI namespace: {:?}", prefix);
+        }
+        self.ffi_namespace_override = Some(prefix.to_string());
+        self.derive_ffi_funcs()
+    }
+
+    /// Make a copy of the interface that only contains the named functions, constants and types,
+    /// along with all of the types that they depend on.
+    ///
+    /// This is for generating bindings to just the part of a large component that a particular
+    /// consumer uses. The FFI function names are unchanged, so the bindings for the subset can
+    /// still be used with the scaffolding for the full interface.
+    pub fn subset<S: AsRef<str>>(&self, names: &[S]) -> Result<ComponentInterface> {
+        let mut functions = Vec::new();
+        let mut constants = Vec::new();
+        let mut pending = Vec::new();
+        for name in names {
+            let name = name.as_ref();
+            if let Some(func) = self.get_function_definition(name) {
+                pending.extend(self.iter_types_in_item(func).cloned());
+                pending.extend(func.throws_type());
+                functions.push(func.clone());
+            } else if let Some(constant) = self.get_constant_definition(name) {
+                pending.extend(self.iter_types_in_item(constant).cloned());
+                constants.push(constant.clone());
+            } else if let Some(type_) = self.get_type(name) {
+                pending.push(type_);
+            } else {
+                bail!("Can't include unknown item {:?} in the interface", name);
+            }
+        }
+        // The String type is always used, to report panics.
+        let mut reachable = BTreeSet::new();
+        reachable.insert(Type::String);
+        while let Some(type_) = pending.pop() {
+            if !reachable.insert(type_.clone()) {
+                continue;
+            }
+            pending.extend(self.iter_types_in_item(&type_).cloned());
+            // The errors thrown by methods aren't part of the types that they contain,
+            // so we have to follow those separately.
+            let methods = match &type_ {
+                Type::Object(name) => self.get_object_definition(name).map(|obj| {
+                    let constructors = obj.constructors().into_iter().map(|c| c.throws_type());
+                    let methods = obj.methods().into_iter().map(|m| m.throws_type());
+                    constructors.chain(methods).collect::<Vec<_>>()
+                }),
+                Type::CallbackInterface(name) => self
+                    .get_callback_interface_definition(name)
+                    .map(|cbi| cbi.methods().into_iter().map(|m| m.throws_type()).collect()),
+                _ => None,
+            };
+            pending.extend(methods.into_iter().flatten().flatten());
+        }
+        let has_type = |type_: Type| reachable.contains(&type_);
+        Ok(ComponentInterface {
+            uniffi_version: self.uniffi_version.clone(),
+            types: self.types.subset(|type_| reachable.contains(type_)),
+            namespace: self.namespace.clone(),
+            ffi_namespace_override: self.ffi_namespace_override.clone(),
+            subset_checksum: Some(self.checksum()),
+            enums: self
+                .enums
+                .iter()
+                .filter(|e| has_type(Type::Enum(e.name().to_string())))
+                .cloned()
+                .collect(),
+            records: self
+                .records
+                .iter()
+                .filter(|rec| has_type(Type::Record(rec.name().to_string())))
+                .cloned()
+                .collect(),
+            functions,
+            objects: self
+                .objects
+                .iter()
+                .filter(|obj| has_type(Type::Object(obj.name().to_string())))
+                .cloned()
+                .collect(),
+            callback_interfaces: self
+                .callback_interfaces
+                .iter()
+                .filter(|cbi| has_type(Type::CallbackInterface(cbi.name().to_string())))
+                .cloned()
+                .collect(),
+            errors: self
+                .errors
+                .iter()
+                .filter(|e| has_type(Type::Error(e.name().to_string())))
+                .cloned()
+                .collect(),
+            constants,
+            docstrings: Default::default(),
+        })
+    }
+
     /// Builtin FFI function for allocating a new `RustBuffer`.
     /// This is needed so that the foreign language bindings can create buffers in which to pass
     /// complex data types across the FFI.
@@ -398,10 +636,51 @@ impl<'ci> ComponentInterface {
         }
     }
 
+    /// FFI function for reading the next value from a stream.
+    /// This is needed so that the foreign language bindings can pull values out of a stream
+    /// returned from Rust. It returns a serialized `Option` of the stream's item type, which
+    /// is `None` once the stream has been exhausted.
+    pub fn ffi_stream_next(&self, stream: &Type) -> FFIFunction {
+        FFIFunction {
+            name: format!(
+                "ffi_{}_{}_next",
+                self.ffi_namespace(),
+                stream.canonical_name().to_lowercase()
+            ),
+            arguments: vec![FFIArgument {
+                name: "handle".to_string(),
+                type_: FFIType::UInt64,
+            }],
+            return_type: Some(FFIType::RustBuffer),
+        }
+    }
+
+    /// FFI function for freeing a stream.
+    /// This is needed so that the foreign language bindings can release a stream once they've
+    /// finished reading from it, whether or not it was exhausted.
+    pub fn ffi_stream_free(&self, stream: &Type) -> FFIFunction {
+        FFIFunction {
+            name: format!(
+                "ffi_{}_{}_free",
+                self.ffi_namespace(),
+                stream.canonical_name().to_lowercase()
+            ),
+            arguments: vec![FFIArgument {
+                name: "handle".to_string(),
+                type_: FFIType::UInt64,
+            }],
+            return_type: None,
+        }
+    }
+
     /// List the definitions of all FFI functions in the interface.
     ///
     /// The set of FFI functions is derived automatically from the set of higher-level types
-    /// along with the builtin FFI helper functions.
+    /// along with the builtin FFI helper functions. These are exactly the functions exported
+    /// by the generated scaffolding, and declared by the foreign language bindings. They're
+    /// listed in a stable order: the members of each object, then callback interfaces, then
+    /// top-level functions, each in the order they appear in the UDL, then the functions for
+    /// reading from each stream type, followed by the builtins.
     pub fn iter_ffi_function_definitions(&self) -> Vec<FFIFunction> {
         self.objects
             .iter()
@@ -418,6 +697,11 @@ impl<'ci> ComponentInterface {
                     .map(|cb| cb.ffi_init_callback.clone()),
             )
             .chain(self.functions.iter().map(|f| f.ffi_func.clone()))
+            .chain(
+                self.iter_stream_types()
+                    .into_iter()
+                    .flat_map(|t| vec![self.ffi_stream_next(&t), self.ffi_stream_free(&t)]),
+            )
             .chain(
                 vec![
                     self.ffi_rustbuffer_alloc(),
@@ -431,6 +715,38 @@ impl<'ci> ComponentInterface {
             .collect()
     }
 
+    /// List the names of the symbols exported by the generated scaffolding.
+    ///
+    /// These are the names from [`ComponentInterface::iter_ffi_function_definitions`], in the same
+    /// order, for use by external tooling such as generating a linker version script.
+    pub fn iter_ffi_function_names(&self) -> Vec<String> {
+        self.iter_ffi_function_definitions()
+            .into_iter()
+            .map(|f| f.name)
+            .collect()
+    }
+
+    /// List the signatures of the FFI functions, sorted by name, for detecting ABI changes.
+    ///
+    /// Each one is formatted by [`FFIFunction::signature`], so two versions of a component can
+    /// be compared using an ordinary diff tool. The checksum in the name of every FFI function
+    /// changes along with any change to the interface, so it's left out here, so that the diff
+    /// only shows the functions whose signatures changed, or that were added or removed.
+    pub fn iter_ffi_function_signatures(&self) -> Vec<String> {
+        let ffi_namespace = self.ffi_namespace();
+        let prefix = self
+            .ffi_namespace_override
+            .as_deref()
+            .unwrap_or(&self.namespace);
+        let mut signatures: Vec<String> = self
+            .iter_ffi_function_definitions()
+            .iter()
+            .map(|f| f.signature().replacen(&ffi_namespace, prefix, 1))
+            .collect();
+        signatures.sort();
+        signatures
+    }
+
     //
     // Private methods for building a ComponentInterface.
     //
@@ -468,12 +784,20 @@ impl<'ci> ComponentInterface {
         })
     }
 
+    /// Find the docstring for an item, given the identifier slice that weedle parsed for it.
+    fn resolve_docstring(&self, identifier: &str) -> Option<String> {
+        self.docstrings
+            .iter()
+            .find_map(|finder| finder.find(identifier))
+    }
+
     /// Called by `APIBuilder` impls to add a newly-parsed namespace definition to the `ComponentInterface`.
     fn add_namespace_definition(&mut self, defn: Namespace) -> Result<()> {
-        if !self.namespace.is_empty() {
+        // When the interface is split across files, each one can repeat the namespace.
+        if !self.namespace.is_empty() && self.namespace != defn.name {
             bail!("duplicate namespace definition");
         }
-        self.namespace.push_str(&defn.name);
+        self.namespace = defn.name;
         Ok(())
     }
 
@@ -521,6 +845,17 @@ impl<'ci> ComponentInterface {
         self.errors.push(defn);
     }
 
+    /// Called by `APIBuilder` impls to add a newly-parsed constant definition to the `ComponentInterface`.
+    fn add_constant_definition(&mut self, defn: Constant) -> Result<()> {
+        // Constants are not a type, so we have to check for duplicates here
+        // rather than relying on the type-finding pass to catch them.
+        if self.constants.iter().any(|c| c.name == defn.name) {
+            bail!("duplicate constant definition: \"{}\"", defn.name);
+        }
+        self.constants.push(defn);
+        Ok(())
+    }
+
     /// Perform global consistency checks on the declared interface.
     ///
     /// This method checks for consistency problems in the declared interface
@@ -541,9 +876,189 @@ impl<'ci> ComponentInterface {
                 }
             }
         }
+        // Enum variants that carry associated data can't be expressed as a literal,
+        // so the bindings can assume that any enum literal names a variant of a flat enum.
+        let default_values = self
+            .functions
+            .iter()
+            .flat_map(|f| f.arguments())
+            .chain(self.objects.iter().flat_map(|o| {
+                o.constructors()
+                    .into_iter()
+                    .flat_map(|c| c.arguments())
+                    .chain(o.methods().into_iter().flat_map(|m| m.arguments()))
+            }))
+            .filter_map(|arg| arg.default_value())
+            .chain(
+                self.records
+                    .iter()
+                    .flat_map(|r| r.fields())
+                    .filter_map(|field| field.default_value()),
+            );
+        for literal in default_values {
+            if let Literal::Enum(variant, Type::Enum(name)) = literal {
+                match self.get_enum_definition(&name) {
+                    Some(e) if !e.is_flat() => bail!(
+                        "Default values are not supported for enums with associated data: \"{}.{}\"",
+                        name,
+                        variant
+                    ),
+                    _ => (),
+                }
+            }
+        }
+        // Errors thrown by callback interfaces have to be reconstructed on the Rust side, which
+        // is only possible if the UDL describes all of their data.
+        for cbi in self.callback_interfaces.iter() {
+            for meth in cbi.methods() {
+                if let Some(name) = meth.throws() {
+                    match self.get_error_definition(name) {
+                        None => bail!("Callback interface method {}.{} throws unknown error \"{}\"", cbi.name(), meth.name(), name),
+                        Some(e) if e.is_flat() => bail!(
+                            "Callback interface methods can only throw errors declared as `[Error] interface`: \"{}.{}\"",
+                            cbi.name(),
+                            meth.name()
+                        ),
+                        _ => (),
+                    }
+                }
+            }
+        }
+        // Streams are read lazily by the foreign language code from a handle owned by Rust,
+        // so they can't be serialized into other types, or passed back into Rust.
+        for stream in self.iter_stream_types() {
+            if let Type::Stream(item) = &stream {
+                if item.iter_types().any(|t| matches!(t, Type::Stream(_))) {
+                    bail!(
+                        "Streams cannot contain other streams: \"{}\"",
+                        stream.canonical_name()
+                    );
+                }
+            }
+        }
+        let contains_stream = |t: &Type| t.iter_types().any(|t| matches!(t, Type::Stream(_)));
+        let returns_nested_stream = |t: Option<&Type>| match t {
+            Some(Type::Stream(_)) | None => false,
+            Some(t) => contains_stream(t),
+        };
+        for f in self.functions.iter() {
+            if f.arguments().iter().any(|a| contains_stream(&a.type_()))
+                || returns_nested_stream(f.return_type())
+            {
+                bail!(
+                    "Streams can only be used as the return type of a function or method: \"{}\"",
+                    f.name()
+                );
+            }
+            if f.is_async() && matches!(f.return_type(), Some(Type::Stream(_))) {
+                bail!("Async functions cannot return streams: \"{}\"", f.name());
+            }
+        }
+        for obj in self.objects.iter() {
+            for cons in obj.constructors() {
+                if cons.arguments().iter().any(|a| contains_stream(&a.type_())) {
+                    bail!("Streams can only be used as the return type of a function or method: \"{}.{}\"", obj.name(), cons.name());
+                }
+            }
+            for meth in obj.methods() {
+                if meth.arguments().iter().any(|a| contains_stream(&a.type_()))
+                    || returns_nested_stream(meth.return_type())
+                {
+                    bail!("Streams can only be used as the return type of a function or method: \"{}.{}\"", obj.name(), meth.name());
+                }
+            }
+        }
+        let in_record = self
+            .records
+            .iter()
+            .find(|r| r.iter_types().any(|t| matches!(t, Type::Stream(_))))
+            .map(|r| r.name());
+        let in_enum = self
+            .enums
+            .iter()
+            .find(|e| e.iter_types().any(|t| matches!(t, Type::Stream(_))))
+            .map(|e| e.name());
+        let in_error = self
+            .errors
+            .iter()
+            .find(|e| e.iter_types().any(|t| matches!(t, Type::Stream(_))))
+            .map(|e| e.name());
+        let in_callback = self
+            .callback_interfaces
+            .iter()
+            .find(|cb| cb.iter_types().any(|t| matches!(t, Type::Stream(_))))
+            .map(|cb| cb.name());
+        if let Some(name) = in_record.or(in_enum).or(in_error).or(in_callback) {
+            bail!(
+                "Streams can only be used as the return type of a function or method: \"{}\"",
+                name
+            );
+        }
+        Ok(())
+    }
+
+    /// Find the fields of records that refer back to the record itself, and so need a `Box` in Rust.
+    ///
+    /// A record can contain itself through an optional field, like the children of a node in a
+    /// tree, but Rust can't work out how large such a record is unless the field is boxed. That
+    /// isn't needed for sequences and maps, which already keep their contents on the heap.
+    fn box_recursive_fields(&mut self) -> Result<()> {
+        let mut boxed = Vec::new();
+        for (i, rec) in self.records.iter().enumerate() {
+            for (j, field) in rec.fields.iter().enumerate() {
+                if field.type_ == rec.type_() {
+                    bail!(
+                        "Record {:?} can't contain itself, except through an optional field, a sequence or a map",
+                        rec.name
+                    );
+                }
+                if self.contains_record_by_value(&field.type_, &rec.name, &mut HashSet::new()) {
+                    boxed.push((i, j));
+                }
+            }
+        }
+        for (i, j) in boxed {
+            self.records[i].fields[j].boxed = true;
+        }
         Ok(())
     }
 
+    // Whether a value of the given type would hold a value of the named record inline in Rust,
+    // rather than behind a pointer.
+    fn contains_record_by_value(
+        &self,
+        type_: &Type,
+        record: &str,
+        seen: &mut HashSet<String>,
+    ) -> bool {
+        let fields = match type_ {
+            Type::Record(name) if name == record => return true,
+            Type::Optional(t) => return self.contains_record_by_value(t, record, seen),
+            Type::Tuple(ts) => {
+                return ts
+                    .iter()
+                    .any(|t| self.contains_record_by_value(t, record, seen))
+            }
+            Type::Record(name) if seen.insert(name.clone()) => self
+                .get_record_definition(name)
+                .map(|rec| rec.fields().into_iter().cloned().collect())
+                .unwrap_or_default(),
+            Type::Enum(name) if seen.insert(name.clone()) => self
+                .get_enum_definition(name)
+                .map(|e| {
+                    e.variants()
+                        .into_iter()
+                        .flat_map(|v| v.fields().into_iter().cloned())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::<Field>::new(),
+        };
+        fields
+            .iter()
+            .any(|f| self.contains_record_by_value(&f.type_, record, seen))
+    }
+
     /// Automatically derive the low-level FFI functions from the high-level types in the interface.
     ///
     /// This should only be called after the high-level types have been completed defined, otherwise
@@ -563,6 +1078,30 @@ impl<'ci> ComponentInterface {
     }
 }
 
+/// The names of the things that a top-level WebIDL definition adds to the interface, for
+/// finding any that are defined in more than one file.
+fn defined_names<'a>(defn: &weedle::Definition<'a>) -> Vec<&'a str> {
+    match defn {
+        weedle::Definition::Namespace(d) => d
+            .members
+            .body
+            .iter()
+            .filter_map(|member| match member {
+                weedle::namespace::NamespaceMember::Operation(op) => {
+                    op.identifier.as_ref().map(|id| id.0)
+                }
+                _ => None,
+            })
+            .collect(),
+        weedle::Definition::Enum(d) => vec![d.identifier.0],
+        weedle::Definition::Dictionary(d) => vec![d.identifier.0],
+        weedle::Definition::Interface(d) => vec![d.identifier.0],
+        weedle::Definition::CallbackInterface(d) => vec![d.identifier.0],
+        weedle::Definition::Typedef(d) => vec![d.identifier.0],
+        _ => vec![],
+    }
+}
+
 /// Convenience implementation for parsing a `ComponentInterface` from a string.
 impl FromStr for ComponentInterface {
     type Err = anyhow::Error;
@@ -579,12 +1118,15 @@ impl Hash for ComponentInterface {
         // anyway, so it's safe to ignore it.
         self.uniffi_version.hash(state);
         self.namespace.hash(state);
+        // The `ffi_namespace_override` only changes the names of the FFI functions, and the point
+        // of the checksum is to detect changes to their signatures, so it's not included.
         self.enums.hash(state);
         self.records.hash(state);
         self.functions.hash(state);
         self.objects.hash(state);
         self.callback_interfaces.hash(state);
         self.errors.hash(state);
+        // Constants don't affect the FFI in any way, so they're not included either.
     }
 }
 
@@ -742,12 +1284,23 @@ impl APIBuilder for weedle::Definition<'_> {
                 }
             }
             weedle::Definition::Dictionary(d) => {
-                let rec = d.convert(ci)?;
-                ci.add_record_definition(rec);
+                let attrs = attributes::DictionaryAttributes::try_from(d.attributes.as_ref())?;
+                if attrs.by_handle() {
+                    let obj: Object = d.convert(ci)?;
+                    ci.add_object_definition(obj);
+                } else {
+                    let rec: Record = d.convert(ci)?;
+                    ci.add_record_definition(rec);
+                }
             }
             weedle::Definition::Interface(d) => {
                 let attrs = attributes::InterfaceAttributes::try_from(d.attributes.as_ref())?;
-                if attrs.contains_enum_attr() {
+                if attrs.contains_constants_attr() {
+                    let constants: Vec<Constant> = d.convert(ci)?;
+                    for c in constants {
+                        ci.add_constant_definition(c)?;
+                    }
+                } else if attrs.contains_enum_attr() {
                     let e = d.convert(ci)?;
                     ci.add_enum_definition(e);
                 } else if attrs.contains_error_attr() {
@@ -848,6 +1401,183 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_webidl_files_are_merged_into_one_interface() {
+        let ci = ComponentInterface::from_webidl_files(&[
+            (
+                "main.udl",
+                r#"
+                    namespace todolist {
+                        /// Get the default list.
+                        TodoList get_default_list();
+                    };
+                    interface TodoList {
+                        sequence<TodoEntry> get_entries();
+                    };
+                "#,
+            ),
+            (
+                "entries.udl",
+                r#"
+                    namespace todolist {
+                        TodoEntry create_entry(string text);
+                    };
+                    /// An entry in a list.
+                    dictionary TodoEntry {
+                        string text;
+                    };
+                "#,
+            ),
+            ("empty.udl", ""),
+        ])
+        .unwrap();
+        assert_eq!(ci.namespace(), "todolist");
+        assert_eq!(ci.iter_function_definitions().len(), 2);
+        assert!(ci.get_object_definition("TodoList").is_some());
+        let entry = ci.get_record_definition("TodoEntry").unwrap();
+        // Docstrings are found in whichever file the item came from.
+        assert_eq!(entry.docstring(), Some("An entry in a list."));
+        let func = ci.get_function_definition("get_default_list").unwrap();
+        assert_eq!(func.docstring(), Some("Get the default list."));
+
+        // The result is the same as if everything had been in a single file.
+        let single = ComponentInterface::from_webidl(
+            r#"
+                namespace todolist {
+                    TodoList get_default_list();
+                    TodoEntry create_entry(string text);
+                };
+                interface TodoList {
+                    sequence<TodoEntry> get_entries();
+                };
+                dictionary TodoEntry {
+                    string text;
+                };
+            "#,
+        )
+        .unwrap();
+        assert_eq!(ci.checksum(), single.checksum());
+    }
+
+    #[test]
+    fn test_merging_webidl_files_reports_conflicts() {
+        let err = ComponentInterface::from_webidl_files(&[
+            ("a.udl", "namespace first {};"),
+            ("b.udl", "namespace second {};"),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Namespace \"second\" in b.udl doesn't match namespace \"first\" in a.udl"
+        );
+
+        let err = ComponentInterface::from_webidl_files(&[
+            (
+                "a.udl",
+                "namespace test {}; dictionary Entry { string text; };",
+            ),
+            ("b.udl", "enum Entry { \"one\" };"),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"Entry\" is defined in both a.udl and b.udl"
+        );
+
+        let err = ComponentInterface::from_webidl_files(&[
+            ("a.udl", "namespace test { void hello(); };"),
+            ("b.udl", "namespace test { void hello(); };"),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "\"hello\" is defined in both a.udl and b.udl"
+        );
+    }
+
+    #[test]
+    fn test_ffi_function_names_are_listed_in_a_stable_order() {
+        const UDL: &str = r#"
+            namespace test {
+                void second();
+                void first();
+            };
+            interface Counter {
+                constructor();
+                void increment();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let ns = ci.ffi_namespace();
+        let expected: Vec<String> = vec![
+            format!("ffi_{}_Counter_object_free", ns),
+            format!("{}_Counter_new", ns),
+            format!("{}_Counter_increment", ns),
+            format!("{}_second", ns),
+            format!("{}_first", ns),
+            format!("ffi_{}_rustbuffer_alloc", ns),
+            format!("ffi_{}_rustbuffer_from_bytes", ns),
+            format!("ffi_{}_rustbuffer_free", ns),
+            format!("ffi_{}_rustbuffer_reserve", ns),
+        ];
+        assert_eq!(ci.iter_ffi_function_names(), expected);
+        let definitions = ci.iter_ffi_function_definitions();
+        assert_eq!(definitions[2].arguments()[0].type_(), FFIType::RustArcPtr);
+        assert_eq!(definitions[5].return_type(), Some(&FFIType::RustBuffer));
+    }
+
+    #[test]
+    fn test_ffi_function_signatures_only_differ_where_the_abi_does() {
+        let v1 = ComponentInterface::from_webidl(
+            "namespace test { u32 add(u32 a, u32 b); void reset(); };",
+        )
+        .unwrap();
+        let v2 = ComponentInterface::from_webidl(
+            "namespace test { u64 add(u32 left, u32 right); void reset(); };",
+        )
+        .unwrap();
+        let old = v1.iter_ffi_function_signatures();
+        let new = v2.iter_ffi_function_signatures();
+        let mut sorted = old.clone();
+        sorted.sort();
+        assert_eq!(old, sorted);
+        assert!(old.contains(&"test_reset()".to_string()));
+        assert!(old.contains(&"ffi_test_rustbuffer_free(RustBuffer)".to_string()));
+        // The checksums differ, but only the function whose return type changed shows up.
+        assert_ne!(v1.ffi_namespace(), v2.ffi_namespace());
+        let removed: Vec<_> = old.iter().filter(|s| !new.contains(s)).collect();
+        let added: Vec<_> = new.iter().filter(|s| !old.contains(s)).collect();
+        assert_eq!(removed, vec!["test_add(UInt32, UInt32) -> UInt32"]);
+        assert_eq!(added, vec!["test_add(UInt32, UInt32) -> UInt64"]);
+    }
+
+    #[test]
+    fn test_ffi_namespace_can_be_overridden() {
+        let mut ci = ComponentInterface::from_webidl(UDL2).unwrap();
+        let checksum = ci.checksum();
+        ci.set_ffi_namespace("vendored_hello").unwrap();
+        assert_eq!(ci.namespace(), "hello");
+        assert_eq!(ci.checksum(), checksum);
+        let ns = ci.ffi_namespace();
+        assert!(ns.starts_with("vendored_hello_"));
+        assert_eq!(
+            ci.get_function_definition("world")
+                .unwrap()
+                .ffi_func()
+                .name(),
+            format!("{}_world", ns)
+        );
+        assert_eq!(
+            ci.ffi_rustbuffer_alloc().name(),
+            format!("ffi_{}_rustbuffer_alloc", ns)
+        );
+
+        let err = ci.set_ffi_namespace("not-valid").unwrap_err();
+        assert_eq!(err.to_string(), "Invalid FFI namespace: \"not-valid\"");
+        assert!(ci.set_ffi_namespace("1st").is_err());
+        assert!(ci.set_ffi_namespace("").is_err());
+    }
+
     #[test]
     fn test_duplicate_type_names_are_an_error() {
         const UDL: &str = r#"
@@ -917,6 +1647,128 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_enums_with_data_cannot_be_default_values() {
+        const UDL: &str = r#"
+            namespace test{
+                void takes_flat(optional Flat flat = "one");
+            };
+            enum Flat { "one", "two" };
+            [Enum]
+            interface WithData {
+                One();
+                Two(u32 field);
+            };
+            dictionary Rec {
+                WithData field = "One";
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Default values are not supported for enums with associated data: \"WithData.One\""
+        );
+    }
+
+    #[test]
+    fn test_callback_interfaces_can_only_throw_rich_errors() {
+        const UDL: &str = r#"
+            namespace test{};
+            [Error]
+            interface RichError {
+                Failed(string reason);
+            };
+            callback interface Fallible {
+                [Throws=RichError]
+                u32 try_it();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let cbi = ci.get_callback_interface_definition("Fallible").unwrap();
+        assert_eq!(cbi.methods()[0].throws(), Some("RichError"));
+
+        const UDL_FLAT: &str = r#"
+            namespace test{};
+            [Error]
+            enum FlatError { "Failed" };
+            callback interface Fallible {
+                [Throws=FlatError]
+                u32 try_it();
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_FLAT).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Callback interface methods can only throw errors declared as `[Error] interface`: \"Fallible.try_it\""
+        );
+    }
+
+    #[test]
+    fn test_streams_are_only_returned_from_functions() {
+        const UDL: &str = r#"
+            namespace test{
+                Ticks ticks();
+            };
+            [Stream]
+            typedef u32 Ticks;
+            interface Clock {
+                constructor();
+                Ticks ticks();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert_eq!(
+            ci.iter_stream_types(),
+            vec![Type::Stream(Box::new(Type::UInt32))]
+        );
+        let names = ci.iter_ffi_function_names();
+        let next = ci
+            .ffi_stream_next(&Type::Stream(Box::new(Type::UInt32)))
+            .name;
+        assert!(next.ends_with("_streamu32_next"));
+        assert!(names.contains(&next));
+
+        const UDL_ARG: &str = r#"
+            namespace test{
+                void count(Ticks ticks);
+            };
+            [Stream]
+            typedef u32 Ticks;
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_ARG).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Streams can only be used as the return type of a function or method: \"count\""
+        );
+
+        const UDL_RECORD: &str = r#"
+            namespace test{};
+            [Stream]
+            typedef u32 Ticks;
+            dictionary Clock {
+                Ticks ticks;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_RECORD).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Streams can only be used as the return type of a function or method: \"Clock\""
+        );
+
+        const UDL_NESTED: &str = r#"
+            namespace test{
+                sequence<Ticks> ticks();
+            };
+            [Stream]
+            typedef u32 Ticks;
+        "#;
+        let err = ComponentInterface::from_webidl(UDL_NESTED).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Streams can only be used as the return type of a function or method: \"ticks\""
+        );
+    }
+
     #[test]
     fn test_contains_optional_types() {
         let mut ci = ComponentInterface {
@@ -963,7 +1815,10 @@ mod test {
         // check that `contains_map_types` returns true when there is a Map type in the interface
         assert!(ci
             .types
-            .add_type_definition("Map{}", Type::Map(Box::new(Type::Boolean)))
+            .add_type_definition(
+                "Map{}",
+                Type::Map(Box::new(Type::String), Box::new(Type::Boolean))
+            )
             .is_ok());
         assert_eq!(ci.contains_map_types(), true);
     }
@@ -978,6 +1833,7 @@ mod test {
         "#;
         let ci = ComponentInterface::from_webidl(UDL).unwrap();
         assert!(!ci.item_contains_unsigned_types(&Type::Object("Testing".into())));
+        assert!(!ci.contains_unsigned_types());
     }
 
     #[test]
@@ -996,5 +1852,78 @@ mod test {
         "#;
         let ci = ComponentInterface::from_webidl(UDL).unwrap();
         assert!(ci.item_contains_unsigned_types(&Type::Object("TestObj".into())));
+        assert!(ci.contains_unsigned_types());
+    }
+
+    #[test]
+    fn test_subset_follows_dependencies() {
+        const UDL: &str = r#"
+            namespace test {
+                Point origin();
+                [Throws=ParseError]
+                Shape parse(string text);
+                void unrelated(Unused unused);
+            };
+            dictionary Point { f64 x; f64 y; };
+            dictionary Unused { u8 value; };
+            [Error]
+            enum ParseError { "Empty" };
+            [Error]
+            enum DrawError { "Offscreen" };
+            interface Shape {
+                [Throws=DrawError]
+                void draw(Point at);
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let subset = ci.subset(&["parse"]).unwrap();
+        assert_eq!(subset.iter_function_definitions().len(), 1);
+        assert!(subset.get_object_definition("Shape").is_some());
+        assert!(subset.get_record_definition("Point").is_some());
+        assert!(subset.get_error_definition("ParseError").is_some());
+        // Errors thrown by methods of the object are included too.
+        assert!(subset.get_error_definition("DrawError").is_some());
+        assert!(subset.get_record_definition("Unused").is_none());
+        assert!(!subset.iter_types().contains(&Type::UInt8));
+        // The FFI functions keep their names, so that they still match the scaffolding.
+        assert_eq!(subset.ffi_namespace(), ci.ffi_namespace());
+        assert_eq!(
+            subset.ffi_rustbuffer_alloc().name(),
+            ci.ffi_rustbuffer_alloc().name()
+        );
+
+        let subset = ci.subset(&["Point"]).unwrap();
+        assert!(subset.iter_function_definitions().is_empty());
+        assert_eq!(subset.iter_record_definitions().len(), 1);
+        assert!(subset.get_object_definition("Shape").is_none());
+
+        let err = ci.subset(&["missing"]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Can't include unknown item \"missing\" in the interface"
+        );
+    }
+
+    #[test]
+    fn test_errors_used_as_values() {
+        const UDL: &str = r#"
+            namespace test {
+                [Throws=OnlyThrown]
+                void fails();
+                [Throws=AlsoReturned]
+                AlsoReturned? last_error();
+            };
+            dictionary Report { sequence<InField> errors; };
+            [Error]
+            enum OnlyThrown { "Oops" };
+            [Error]
+            interface AlsoReturned { Failed(string reason); };
+            [Error]
+            interface InField { Failed(string reason); };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(!ci.is_error_used_as_value("OnlyThrown"));
+        assert!(ci.is_error_used_as_value("AlsoReturned"));
+        assert!(ci.is_error_used_as_value("InField"));
     }
 }
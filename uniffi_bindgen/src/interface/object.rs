@@ -54,6 +54,7 @@ use anyhow::{bail, Result};
 use super::attributes::{ConstructorAttributes, InterfaceAttributes, MethodAttributes};
 use super::ffi::{FFIArgument, FFIFunction, FFIType};
 use super::function::Argument;
+use super::record::Field;
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
 
@@ -61,7 +62,8 @@ use super::{APIConverter, ComponentInterface};
 /// have methods called on it, and so on - basically your classic Object Oriented Programming
 /// type of deal, except without elaborate inheritence hierarchies.
 ///
-/// In UDL these correspond to the `interface` keyword.
+/// In UDL these correspond to the `interface` keyword. A `[ByHandle] dictionary` is also an
+/// object, with a constructor that takes each of its fields and a method that reads each one.
 ///
 /// At the FFI layer, objects are represented by an opaque integer handle and a set of functions
 /// a common prefix. The object's constuctors are functions that return new objects by handle,
@@ -78,6 +80,9 @@ pub struct Object {
     pub(super) methods: Vec<Method>,
     pub(super) ffi_func_free: FFIFunction,
     pub(super) uses_deprecated_threadsafe_attribute: bool,
+    pub(super) docstring: Option<String>,
+    pub(super) deprecated: Option<String>,
+    pub(super) record_by_handle: bool,
 }
 
 impl Object {
@@ -88,6 +93,9 @@ impl Object {
             methods: Default::default(),
             ffi_func_free: Default::default(),
             uses_deprecated_threadsafe_attribute: false,
+            docstring: None,
+            deprecated: None,
+            record_by_handle: false,
         }
     }
 
@@ -128,6 +136,24 @@ impl Object {
         self.uses_deprecated_threadsafe_attribute
     }
 
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
+    /// The message from a `[Deprecated="message"]` attribute, if there was one.
+    pub fn deprecated(&self) -> Option<&str> {
+        self.deprecated.as_deref()
+    }
+
+    /// Whether this was declared as a `[ByHandle] dictionary`.
+    ///
+    /// The Rust struct is then an ordinary record, so rather than calling into its `impl`, the
+    /// scaffolding builds it from the constructor's arguments, and each method clones the field
+    /// that it's named after.
+    pub fn is_record_by_handle(&self) -> bool {
+        self.record_by_handle
+    }
+
     pub fn derive_ffi_funcs(&mut self, ci_prefix: &str) -> Result<()> {
         self.ffi_func_free.name = format!("ffi_{}_{}_object_free", ci_prefix, self.name);
         self.ffi_func_free.arguments = vec![FFIArgument {
@@ -182,8 +208,11 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
             None => Default::default(),
         };
         object.uses_deprecated_threadsafe_attribute = attributes.threadsafe();
+        object.docstring = ci.resolve_docstring(self.identifier.0);
+        object.deprecated = attributes.get_deprecated().map(String::from);
         // Convert each member into a constructor or method, guarding against duplicate names.
         let mut member_names = HashSet::new();
+        let mut property_names = HashSet::new();
         for member in &self.members.body {
             match member {
                 weedle::interface::InterfaceMember::Constructor(t) => {
@@ -198,6 +227,20 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
                     if !member_names.insert(method.name.clone()) {
                         bail!("Duplicate interface member name: \"{}\"", method.name())
                     }
+                    if method.is_property() {
+                        if !method.arguments.is_empty() || method.return_type.is_none() {
+                            bail!(
+                                "[Property] methods must take no arguments and return a value: \"{}\"",
+                                method.name()
+                            );
+                        }
+                        if method.throws().is_some() {
+                            bail!("[Property] methods can't throw: \"{}\"", method.name());
+                        }
+                        if !property_names.insert(method.property_name().to_string()) {
+                            bail!("Duplicate property name: \"{}\"", method.property_name());
+                        }
+                    }
                     method.object_name.push_str(object.name.as_str());
                     object.methods.push(method);
                 }
@@ -212,6 +255,55 @@ impl APIConverter<Object> for weedle::InterfaceDefinition<'_> {
     }
 }
 
+impl APIConverter<Object> for weedle::DictionaryDefinition<'_> {
+    fn convert(&self, ci: &mut ComponentInterface) -> Result<Object> {
+        if self.inheritance.is_some() {
+            bail!("dictionary inheritence is not supported");
+        }
+        let mut object = Object::new(self.identifier.0.to_string());
+        object.record_by_handle = true;
+        object.docstring = ci.resolve_docstring(self.identifier.0);
+        let fields: Vec<Field> = self.members.body.convert(ci)?;
+        for field in &fields {
+            if field.excluded_from_equality || !field.constraints.is_empty() {
+                bail!(
+                    "Field attributes aren't supported in a [ByHandle] dictionary: \"{}\"",
+                    field.name
+                );
+            }
+            if field.name == "new" {
+                bail!("the field name \"new\" is reserved for the constructor of a [ByHandle] dictionary");
+            }
+        }
+        object.constructors.push(Constructor {
+            arguments: fields
+                .iter()
+                .map(|field| Argument {
+                    name: field.name.clone(),
+                    type_: field.type_.clone(),
+                    by_ref: false,
+                    optional: false,
+                    default: field.default.clone(),
+                })
+                .collect(),
+            ..Default::default()
+        });
+        object.methods = fields
+            .into_iter()
+            .map(|field| Method {
+                name: field.name,
+                object_name: object.name.clone(),
+                return_type: Some(field.type_),
+                arguments: vec![],
+                ffi_func: Default::default(),
+                attributes: Default::default(),
+                docstring: None,
+            })
+            .collect();
+        Ok(object)
+    }
+}
+
 // Represents a constructor for an object type.
 //
 // In the FFI, this will be a function that returns a pointer to an instance
@@ -251,12 +343,12 @@ impl Constructor {
             .map(|name| Type::Error(name.to_owned()))
     }
 
+    pub fn deprecated(&self) -> Option<&str> {
+        self.attributes.get_deprecated()
+    }
+
     fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) {
-        self.ffi_func.name.push_str(ci_prefix);
-        self.ffi_func.name.push('_');
-        self.ffi_func.name.push_str(obj_prefix);
-        self.ffi_func.name.push('_');
-        self.ffi_func.name.push_str(&self.name);
+        self.ffi_func.name = format!("{}_{}_{}", ci_prefix, obj_prefix, self.name);
         self.ffi_func.arguments = self.arguments.iter().map(Into::into).collect();
         self.ffi_func.return_type = Some(FFIType::RustArcPtr);
     }
@@ -324,6 +416,7 @@ pub struct Method {
     pub(super) arguments: Vec<Argument>,
     pub(super) ffi_func: FFIFunction,
     pub(super) attributes: MethodAttributes,
+    pub(super) docstring: Option<String>,
 }
 
 impl Method {
@@ -374,12 +467,26 @@ impl Method {
         self.attributes.get_self_by_arc()
     }
 
+    pub fn deprecated(&self) -> Option<&str> {
+        self.attributes.get_deprecated()
+    }
+
+    /// Whether the method is a getter marked `[Property]`, for bindings to expose as a property.
+    pub fn is_property(&self) -> bool {
+        self.attributes.is_property()
+    }
+
+    /// The name of the property for a `[Property]` method, without any `get_` prefix.
+    pub fn property_name(&self) -> &str {
+        self.name.strip_prefix("get_").unwrap_or(&self.name)
+    }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
     pub fn derive_ffi_func(&mut self, ci_prefix: &str, obj_prefix: &str) -> Result<()> {
-        self.ffi_func.name.push_str(ci_prefix);
-        self.ffi_func.name.push('_');
-        self.ffi_func.name.push_str(obj_prefix);
-        self.ffi_func.name.push('_');
-        self.ffi_func.name.push_str(&self.name);
+        self.ffi_func.name = format!("{}_{}_{}", ci_prefix, obj_prefix, self.name);
         self.ffi_func.arguments = self.full_arguments().iter().map(Into::into).collect();
         self.ffi_func.return_type = self.return_type.as_ref().map(Into::into);
         Ok(())
@@ -440,6 +547,7 @@ impl APIConverter<Method> for weedle::interface::OperationInterfaceMember<'_> {
             return_type,
             ffi_func: Default::default(),
             attributes: MethodAttributes::try_from(self.attributes.as_ref())?,
+            docstring: self.identifier.and_then(|id| ci.resolve_docstring(id.0)),
         })
     }
 }
@@ -579,4 +687,109 @@ mod test {
         let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
         assert_eq!(err.to_string(), "Duplicate interface member name: \"new\"");
     }
+
+    #[test]
+    fn test_property_methods() {
+        const UDL: &str = r#"
+            namespace test{};
+            interface Testing {
+                [Property]
+                u32 get_width();
+                [Property]
+                string name();
+                u32 height();
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let obj = ci.get_object_definition("Testing").unwrap();
+        let properties: Vec<_> = obj
+            .methods()
+            .into_iter()
+            .filter(|m| m.is_property())
+            .map(|m| m.property_name())
+            .collect();
+        assert_eq!(properties, vec!["width", "name"]);
+
+        for (udl, message) in &[
+            (
+                "[Property] u32 width(u32 scale);",
+                "[Property] methods must take no arguments and return a value: \"width\"",
+            ),
+            (
+                "[Property] void width();",
+                "[Property] methods must take no arguments and return a value: \"width\"",
+            ),
+            (
+                "[Property, Throws=TestError] u32 width();",
+                "[Property] methods can't throw: \"width\"",
+            ),
+            (
+                "[Property] u32 width(); [Property] u32 get_width();",
+                "Duplicate property name: \"width\"",
+            ),
+        ] {
+            let udl = format!(
+                "namespace test{{}}; [Error] enum TestError {{ \"Oops\" }}; interface Testing {{ {} }};",
+                udl
+            );
+            let err = ComponentInterface::from_webidl(&udl).unwrap_err();
+            assert_eq!(err.to_string(), *message);
+        }
+    }
+
+    #[test]
+    fn test_dictionaries_by_handle_are_objects() {
+        const UDL: &str = r#"
+            namespace test{
+                Document load(string path);
+            };
+            [ByHandle]
+            dictionary Document {
+                string title;
+                sequence<string> lines;
+                u32 version = 1;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        assert!(ci.iter_record_definitions().is_empty());
+        assert_eq!(
+            ci.get_type("Document"),
+            Some(Type::Object("Document".into()))
+        );
+
+        let obj = ci.get_object_definition("Document").unwrap();
+        assert!(obj.is_record_by_handle());
+        let cons = obj.primary_constructor().unwrap();
+        let args = cons.arguments();
+        assert_eq!(
+            args.iter().map(|arg| arg.name()).collect::<Vec<_>>(),
+            vec!["title", "lines", "version"]
+        );
+        assert!(args[2].default_value().is_some());
+        // There's a method to read each field, which takes nothing but the handle.
+        let methods = obj.methods();
+        assert_eq!(methods.len(), 3);
+        assert_eq!(methods[1].name(), "lines");
+        assert!(methods[1].arguments().is_empty());
+        assert_eq!(
+            methods[1].return_type(),
+            Some(&Type::Sequence(Box::new(Type::String)))
+        );
+    }
+
+    #[test]
+    fn test_dictionaries_by_handle_reject_field_attributes() {
+        const UDL: &str = r#"
+            namespace test{};
+            [ByHandle]
+            dictionary Document {
+                [ExcludeFromEquality] string title;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Field attributes aren't supported in a [ByHandle] dictionary: \"title\""
+        );
+    }
 }
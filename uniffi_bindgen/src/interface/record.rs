@@ -44,8 +44,13 @@
 //! # Ok::<(), anyhow::Error>(())
 //! ```
 
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+
 use anyhow::{bail, Result};
+use weedle::Parse;
 
+use super::attributes::{FieldAttributes, ValidateRule};
 use super::literal::{convert_default_value, Literal};
 use super::types::{IterTypes, Type, TypeIterator};
 use super::{APIConverter, ComponentInterface};
@@ -55,10 +60,11 @@ use super::{APIConverter, ComponentInterface};
 /// In the FFI these are represented as a byte buffer, which one side explicitly
 /// serializes the data into and the other serializes it out of. So I guess they're
 /// kind of like "pass by clone" values.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 pub struct Record {
     pub(super) name: String,
     pub(super) fields: Vec<Field>,
+    pub(super) docstring: Option<String>,
 }
 
 impl Record {
@@ -75,6 +81,20 @@ impl Record {
     pub fn fields(&self) -> Vec<&Field> {
         self.fields.iter().collect()
     }
+
+    pub fn docstring(&self) -> Option<&str> {
+        self.docstring.as_deref()
+    }
+
+    /// Whether any fields of this record should be left out when comparing it for equality.
+    pub fn has_fields_excluded_from_equality(&self) -> bool {
+        self.fields.iter().any(|f| f.excluded_from_equality)
+    }
+
+    /// Whether any fields of this record have constraints that are checked when it's created.
+    pub fn has_constrained_fields(&self) -> bool {
+        self.fields.iter().any(|f| !f.constraints.is_empty())
+    }
 }
 
 impl IterTypes for Record {
@@ -83,6 +103,15 @@ impl IterTypes for Record {
     }
 }
 
+impl Hash for Record {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include the docstring in the hash calculation, because
+        // it doesn't affect the FFI in any way.
+        self.name.hash(state);
+        self.fields.hash(state);
+    }
+}
+
 impl APIConverter<Record> for weedle::DictionaryDefinition<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Record> {
         if self.attributes.is_some() {
@@ -94,17 +123,22 @@ impl APIConverter<Record> for weedle::DictionaryDefinition<'_> {
         Ok(Record {
             name: self.identifier.0.to_string(),
             fields: self.members.body.convert(ci)?,
+            docstring: ci.resolve_docstring(self.identifier.0),
         })
     }
 }
 
 // Represents an individual field on a Record.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone)]
 pub struct Field {
     pub(super) name: String,
     pub(super) type_: Type,
     pub(super) required: bool,
     pub(super) default: Option<Literal>,
+    pub(super) excluded_from_equality: bool,
+    pub(super) constraints: Vec<Constraint>,
+    // Whether the field refers back to the record that it's in, and so has to be boxed in Rust.
+    pub(super) boxed: bool,
 }
 
 impl Field {
@@ -117,6 +151,34 @@ impl Field {
     pub fn default_value(&self) -> Option<Literal> {
         self.default.clone()
     }
+    pub fn excluded_from_equality(&self) -> bool {
+        self.excluded_from_equality
+    }
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+    pub fn is_optional(&self) -> bool {
+        matches!(self.type_, Type::Optional(_))
+    }
+
+    /// Whether the field holds a `Box` in Rust, because it refers back to the record it's in.
+    ///
+    /// For an optional field, like the children of a node in a tree, the box goes inside the
+    /// `Option`, so a field declared as `Node? left` is an `Option<Box<Node>>` in Rust.
+    pub fn is_boxed(&self) -> bool {
+        self.boxed
+    }
+}
+
+impl Hash for Field {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // We don't include `excluded_from_equality` or `constraints` in the hash calculation,
+        // because they only affect the foreign language bindings, not the FFI.
+        self.name.hash(state);
+        self.type_.hash(state);
+        self.required.hash(state);
+        self.default.hash(state);
+    }
 }
 
 impl IterTypes for Field {
@@ -127,9 +189,7 @@ impl IterTypes for Field {
 
 impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
     fn convert(&self, ci: &mut ComponentInterface) -> Result<Field> {
-        if self.attributes.is_some() {
-            bail!("dictionary member attributes are not supported yet");
-        }
+        let attributes = FieldAttributes::try_from(self.attributes.as_ref())?;
         let type_ = ci.resolve_type_expression(&self.type_)?;
         if let Type::Object(_) = type_ {
             bail!("Objects cannot currently appear in record fields");
@@ -138,15 +198,94 @@ impl APIConverter<Field> for weedle::dictionary::DictionaryMember<'_> {
             None => None,
             Some(v) => Some(convert_default_value(&v.value, &type_)?),
         };
+        let constraints = match attributes
+            .validate_rules()
+            .iter()
+            .map(|rule| Constraint::from_rule(rule, &type_))
+            .collect::<Result<_>>()
+        {
+            Ok(constraints) => constraints,
+            Err(e) => bail!("Invalid [Validate] for field {}: {}", self.identifier.0, e),
+        };
         Ok(Field {
             name: self.identifier.0.to_string(),
             type_,
             required: self.required.is_some(),
             default,
+            excluded_from_equality: attributes.excluded_from_equality(),
+            constraints,
+            boxed: false,
+        })
+    }
+}
+
+/// A constraint on the value of a record field, from a `[Validate]` attribute in the UDL.
+///
+/// The foreign language bindings check these when a record is created, including when it's
+/// lifted from Rust. The constraints on an optional field only apply when it has a value.
+#[derive(Debug, Clone)]
+pub enum Constraint {
+    /// The string, bytes, sequence or map must not be empty.
+    NonEmpty,
+    /// The number must be greater than or equal to the literal, which has the field's type.
+    Min(Literal),
+    /// The number must be less than or equal to the literal, which has the field's type.
+    Max(Literal),
+}
+
+impl Constraint {
+    fn from_rule(rule: &ValidateRule, type_: &Type) -> Result<Self> {
+        let type_ = match type_ {
+            Type::Optional(inner) => inner.as_ref(),
+            _ => type_,
+        };
+        Ok(match rule {
+            ValidateRule::NonEmpty => match type_ {
+                Type::String | Type::Bytes | Type::Sequence(_) | Type::Map(..) => {
+                    Constraint::NonEmpty
+                }
+                _ => bail!("nonEmpty is not supported for {}", type_.canonical_name()),
+            },
+            ValidateRule::Min(value) => Constraint::Min(convert_bound(value, type_)?),
+            ValidateRule::Max(value) => Constraint::Max(convert_bound(value, type_)?),
         })
     }
 }
 
+fn convert_bound(value: &str, type_: &Type) -> Result<Literal> {
+    match type_ {
+        Type::UInt8
+        | Type::Int8
+        | Type::UInt16
+        | Type::Int16
+        | Type::UInt32
+        | Type::Int32
+        | Type::UInt64
+        | Type::Int64
+        | Type::UInt128
+        | Type::Int128
+        | Type::Float32
+        | Type::Float64 => {}
+        _ => bail!(
+            "min and max are not supported for {}",
+            type_.canonical_name()
+        ),
+    }
+    match weedle::literal::DefaultValue::parse(value) {
+        // Unlike a default value, a whole number is a fine bound for a float.
+        Ok(("", weedle::literal::DefaultValue::Integer(_)))
+            if matches!(type_, Type::Float32 | Type::Float64) =>
+        {
+            Ok(Literal::Float(value.to_string(), type_.clone()))
+        }
+        Ok(("", literal @ weedle::literal::DefaultValue::Integer(_)))
+        | Ok(("", literal @ weedle::literal::DefaultValue::Float(_))) => {
+            convert_default_value(&literal, type_)
+        }
+        _ => bail!("{:?} is not a number", value),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::literal::Radix;
@@ -235,4 +374,124 @@ mod test {
             .iter()
             .any(|t| t.canonical_name() == "TypeTesting"));
     }
+
+    #[test]
+    fn test_fields_excluded_from_equality() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Plain {
+                u32 id;
+            };
+            dictionary Cached {
+                u32 id;
+                [ExcludeFromEquality] string cached;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Plain").unwrap();
+        assert!(!record.has_fields_excluded_from_equality());
+        let record = ci.get_record_definition("Cached").unwrap();
+        assert!(record.has_fields_excluded_from_equality());
+        assert!(!record.fields()[0].excluded_from_equality());
+        assert!(record.fields()[1].excluded_from_equality());
+    }
+
+    #[test]
+    fn test_field_constraints() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Plain {
+                u32 id;
+            };
+            dictionary Person {
+                [Validate="nonEmpty"] string name;
+                [Validate="min=0, max=150"] u8 age;
+                [Validate="min=-1.5"] f64? score;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Plain").unwrap();
+        assert!(!record.has_constrained_fields());
+        let record = ci.get_record_definition("Person").unwrap();
+        assert!(record.has_constrained_fields());
+        assert!(matches!(
+            record.fields()[0].constraints(),
+            [Constraint::NonEmpty]
+        ));
+        assert!(matches!(
+            record.fields()[1].constraints(),
+            [
+                Constraint::Min(Literal::UInt(0, Radix::Decimal, Type::UInt8)),
+                Constraint::Max(Literal::UInt(150, Radix::Decimal, Type::UInt8)),
+            ]
+        ));
+        assert!(matches!(
+            record.fields()[2].constraints(),
+            [Constraint::Min(Literal::Float(s, Type::Float64))] if s == "-1.5"
+        ));
+    }
+
+    #[test]
+    fn test_field_constraints_must_suit_the_type() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Person {
+                [Validate="min=1"] string name;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid [Validate] for field name: min and max are not supported for string"
+        );
+
+        const UDL2: &str = r#"
+            namespace test{};
+            dictionary Person {
+                [Validate="max=old"] u8 age;
+            };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid [Validate] for field age: \"old\" is not a number"
+        );
+    }
+
+    #[test]
+    fn test_recursive_fields_are_boxed() {
+        const UDL: &str = r#"
+            namespace test{};
+            dictionary Tree {
+                u32 value;
+                Tree? left;
+                sequence<Tree> others;
+            };
+            dictionary Parent {
+                Child? child;
+            };
+            dictionary Child {
+                Parent? parent;
+            };
+        "#;
+        let ci = ComponentInterface::from_webidl(UDL).unwrap();
+        let record = ci.get_record_definition("Tree").unwrap();
+        assert!(!record.fields()[0].is_boxed());
+        assert!(record.fields()[1].is_boxed());
+        // Sequences already keep their contents on the heap.
+        assert!(!record.fields()[2].is_boxed());
+        // Records that contain each other are both boxed, so it doesn't matter which comes first.
+        assert!(ci.get_record_definition("Parent").unwrap().fields()[0].is_boxed());
+        assert!(ci.get_record_definition("Child").unwrap().fields()[0].is_boxed());
+
+        const UDL2: &str = r#"
+            namespace test{};
+            dictionary Infinite { Infinite inner; };
+        "#;
+        let err = ComponentInterface::from_webidl(UDL2).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Record \"Infinite\" can't contain itself, except through an optional field, a sequence or a map"
+        );
+    }
 }
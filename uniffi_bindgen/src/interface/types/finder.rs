@@ -21,8 +21,10 @@ use std::convert::TryFrom;
 
 use anyhow::{bail, Result};
 
-use super::super::attributes::{EnumAttributes, InterfaceAttributes, TypedefAttributes};
-use super::{Type, TypeUniverse};
+use super::super::attributes::{
+    DictionaryAttributes, EnumAttributes, InterfaceAttributes, TypedefAttributes,
+};
+use super::{Type, TypeUniverse, MAX_TUPLE_ELEMENTS};
 
 /// Trait to help with an early "type discovery" phase when processing the UDL.
 ///
@@ -58,11 +60,15 @@ impl TypeFinder for weedle::Definition<'_> {
 impl TypeFinder for weedle::InterfaceDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
         let name = self.identifier.0.to_string();
+        let attrs = InterfaceAttributes::try_from(self.attributes.as_ref())?;
         // Some enum types are defined using an `interface` with a special attribute.
-        if InterfaceAttributes::try_from(self.attributes.as_ref())?.contains_enum_attr() {
+        if attrs.contains_enum_attr() {
             types.add_type_definition(self.identifier.0, Type::Enum(name))
-        } else if InterfaceAttributes::try_from(self.attributes.as_ref())?.contains_error_attr() {
+        } else if attrs.contains_error_attr() {
             types.add_type_definition(self.identifier.0, Type::Error(name))
+        } else if attrs.contains_constants_attr() {
+            // A `[Constants] interface` is just somewhere to declare constants, not a type.
+            Ok(())
         } else {
             types.add_type_definition(self.identifier.0, Type::Object(name))
         }
@@ -72,7 +78,12 @@ impl TypeFinder for weedle::InterfaceDefinition<'_> {
 impl TypeFinder for weedle::DictionaryDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
         let name = self.identifier.0.to_string();
-        types.add_type_definition(self.identifier.0, Type::Record(name))
+        // A `[ByHandle]` dictionary is passed around like an object.
+        if DictionaryAttributes::try_from(self.attributes.as_ref())?.by_handle() {
+            types.add_type_definition(self.identifier.0, Type::Object(name))
+        } else {
+            types.add_type_definition(self.identifier.0, Type::Record(name))
+        }
     }
 }
 
@@ -95,8 +106,66 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
         // If we wanted simple `typedef`s, it would be as easy as:
         // > let t = types.resolve_type_expression(&self.type_)?;
         // > types.add_type_definition(name, t)
-        // But we don't - `typedef`s are reserved for external types.
-        if attrs.is_wrapped() {
+        // But we don't - `typedef`s are reserved for external types, and for naming maps,
+        // streams and tuples. The exception is `[Alias]`, which is only allowed for the types
+        // that are tedious to spell out.
+        if attrs.is_alias() {
+            types.begin_type_alias(name);
+            let aliased = types.resolve_type_expression(&self.type_);
+            types.end_type_alias();
+            types.add_type_alias(name, aliased?)
+        } else if attrs.is_map() {
+            // A `record<K, V>` can only have string keys, so maps with other keys are declared
+            // by name. WebIDL's only syntax for a pair of types is a union, so we borrow it to
+            // list the key type and then the value type.
+            let members = match &self.type_.type_ {
+                weedle::types::Type::Union(union) if union.q_mark.is_none() => &union.type_.body.list,
+                _ => bail!("Maps must be declared with a key type and a value type, like `[Map] typedef (i32 or string) {};`", name),
+            };
+            let (key, value) = match members.as_slice() {
+                [weedle::types::UnionMemberType::Single(k), weedle::types::UnionMemberType::Single(v)] => (k, v),
+                _ => bail!("Maps must be declared with a key type and a value type, like `[Map] typedef (i32 or string) {};`", name),
+            };
+            let key = types.resolve_type_expression(key)?;
+            check_map_key_type(&key)?;
+            let value = types.resolve_type_expression(value)?;
+            let map = types.add_known_type(Type::Map(Box::new(key), Box::new(value)))?;
+            types.add_type_definition(name, map)
+        } else if attrs.is_tuple() {
+            // Tuples are likewise only referred to by name. WebIDL's only syntax for a list of
+            // types is a union, so we borrow it to list the element types in order.
+            let union = match &self.type_.type_ {
+                weedle::types::Type::Union(union) if union.q_mark.is_none() => &union.type_,
+                _ => bail!("Tuples must be declared with a list of types, like `[Tuple] typedef (i32 or string) {};`", name),
+            };
+            let elements = union
+                .body
+                .list
+                .iter()
+                .map(|member| match member {
+                    weedle::types::UnionMemberType::Single(t) => types.resolve_type_expression(t),
+                    weedle::types::UnionMemberType::Union(_) => bail!(
+                        "Tuples cannot be nested directly; declare the inner tuple separately: {}",
+                        name
+                    ),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            if elements.len() > MAX_TUPLE_ELEMENTS {
+                bail!(
+                    "Tuples can have at most {} elements: {}",
+                    MAX_TUPLE_ELEMENTS,
+                    name
+                );
+            }
+            let tuple = types.add_known_type(Type::Tuple(elements))?;
+            types.add_type_definition(name, tuple)
+        } else if attrs.is_stream() {
+            // Streams are only ever referred to by name in the UDL, since there's no WebIDL
+            // syntax for a new kind of generic type. The name doesn't appear anywhere else.
+            let item = types.resolve_type_expression(&self.type_)?;
+            let stream = types.add_known_type(Type::Stream(item.into()))?;
+            types.add_type_definition(name, stream)
+        } else if attrs.is_wrapped() {
             // A local type which wraps a primitive and for which we will generate an
             // `FfiConverter` implementation.
             let prim = types.resolve_type_expression(&self.type_)?;
@@ -122,6 +191,27 @@ impl TypeFinder for weedle::TypedefDefinition<'_> {
     }
 }
 
+// Map keys need to be hashable and comparable in Rust and in all the foreign languages,
+// which rules out floats, and anything that's compared by identity rather than by value.
+fn check_map_key_type(t: &Type) -> Result<()> {
+    match t {
+        Type::Int8
+        | Type::UInt8
+        | Type::Int16
+        | Type::UInt16
+        | Type::Int32
+        | Type::UInt32
+        | Type::Int64
+        | Type::UInt64
+        | Type::Boolean
+        | Type::Char
+        | Type::String
+        | Type::Enum(_)
+        | Type::Record(_) => Ok(()),
+        _ => bail!("unsupported type for map keys: {:?}", t),
+    }
+}
+
 impl TypeFinder for weedle::CallbackInterfaceDefinition<'_> {
     fn add_type_definitions_to(&self, types: &mut TypeUniverse) -> Result<()> {
         if self.attributes.is_some() {
@@ -212,6 +302,18 @@ mod test {
 
             [Wrapped]
             typedef string ExternalWrapping;
+
+            [Map]
+            typedef (u32 or string?) NamesById;
+
+            [Custom]
+            typedef i64 CustomType;
+
+            [Stream]
+            typedef u32 Ticks;
+
+            [Tuple]
+            typedef (i32 or string?) Entry;
         "#,
             |types| {
                 assert!(
@@ -222,6 +324,25 @@ mod test {
                     matches!(types.get_type_definition("ExternalWrapping").unwrap(), Type::Wrapped { name, prim }
                                                                                      if name == "ExternalWrapping" && prim == Box::new(Type::String))
                 );
+                assert_eq!(
+                    types.get_type_definition("NamesById").unwrap(),
+                    Type::Map(
+                        Box::new(Type::UInt32),
+                        Box::new(Type::Optional(Box::new(Type::String)))
+                    )
+                );
+                assert!(
+                    matches!(types.get_type_definition("CustomType").unwrap(), Type::Wrapped { name, prim }
+                                                                               if name == "CustomType" && prim == Box::new(Type::Int64))
+                );
+                assert_eq!(
+                    types.get_type_definition("Ticks").unwrap(),
+                    Type::Stream(Box::new(Type::UInt32))
+                );
+                assert_eq!(
+                    types.get_type_definition("Entry").unwrap(),
+                    Type::Tuple(vec![Type::Int32, Type::Optional(Box::new(Type::String))])
+                );
             },
         );
     }
@@ -235,6 +356,73 @@ mod test {
         err.to_string()
     }
 
+    #[test]
+    fn test_maps_must_have_a_key_and_value_type() {
+        assert_eq!(
+            get_err("[Map] typedef i32 Single;"),
+            "Maps must be declared with a key type and a value type, like `[Map] typedef (i32 or string) Single;`"
+        );
+        assert_eq!(
+            get_err("[Map] typedef (i32 or string or boolean) Triple;"),
+            "Maps must be declared with a key type and a value type, like `[Map] typedef (i32 or string) Triple;`"
+        );
+        assert_eq!(
+            get_err("[Map] typedef (f64 or string) ByFloat;"),
+            "unsupported type for map keys: Float64"
+        );
+    }
+
+    #[test]
+    fn test_tuples_must_list_their_elements() {
+        assert_eq!(
+            get_err("[Tuple] typedef i32 Single;"),
+            "Tuples must be declared with a list of types, like `[Tuple] typedef (i32 or string) Single;`"
+        );
+        assert_eq!(
+            get_err("[Tuple] typedef (u8 or u8 or u8 or u8 or u8 or u8 or u8) Big;"),
+            "Tuples can have at most 6 elements: Big"
+        );
+    }
+
+    #[test]
+    fn test_type_aliases() {
+        test_a_finding(
+            r#"
+            dictionary TodoEntry {};
+            [Alias] typedef sequence<TodoEntry> TodoList;
+            [Alias] typedef record<DOMString, TodoList> TodoLists;
+        "#,
+            |types| {
+                let list = Type::Sequence(Box::new(Type::Record("TodoEntry".into())));
+                let lists = Type::Map(Box::new(Type::String), Box::new(list.clone()));
+                assert_eq!(types.get_type_definition("TodoList"), Some(list.clone()));
+                assert_eq!(types.get_type_definition("TodoLists"), Some(lists.clone()));
+                assert_eq!(
+                    types.iter_type_aliases().collect::<Vec<_>>(),
+                    vec![("TodoList", &list), ("TodoLists", &lists)]
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn test_type_aliases_must_be_for_complex_types_and_not_recursive() {
+        assert_eq!(
+            get_err("[Alias] typedef u32 Ticks;"),
+            "Only sequences, maps and optional types can be aliased, so Ticks can't be an alias for u32"
+        );
+        assert_eq!(
+            get_err("[Alias] typedef sequence<Tree> Tree;"),
+            "Type alias Tree can't refer to itself"
+        );
+        assert_eq!(
+            get_err(
+                "[Alias] typedef sequence<string> Names; [Alias] typedef sequence<string> Labels;"
+            ),
+            "Names and Labels are both aliases for Sequencestring"
+        );
+    }
+
     #[test]
     #[should_panic]
     fn test_typedef_error_on_no_attr() {
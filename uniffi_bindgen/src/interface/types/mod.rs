@@ -21,7 +21,10 @@
 //! about how these API-level types map into the lower-level types of the FFI layer as represented
 //! by the [`ffi::FFIType`](super::ffi::FFIType) enum, but that's a detail that is invisible to end users.
 
-use std::{collections::hash_map::Entry, collections::BTreeSet, collections::HashMap};
+use std::{
+    collections::hash_map::Entry, collections::BTreeMap, collections::BTreeSet,
+    collections::HashMap,
+};
 
 use anyhow::{bail, Result};
 
@@ -32,6 +35,10 @@ pub(super) use finder::TypeFinder;
 mod resolver;
 pub(super) use resolver::{resolve_builtin_type, TypeResolver};
 
+/// The largest tuples that can be passed over the FFI, which is as far as the `uniffi` crate
+/// implements `FfiConverter` for them.
+pub const MAX_TUPLE_ELEMENTS: usize = 6;
+
 /// Represents all the different high-level types that can be used in a component interface.
 /// At this level we identify user-defined types by name, without knowing any details
 /// of their internal structure apart from what type of thing they are (record, enum, etc).
@@ -46,10 +53,15 @@ pub enum Type {
     Int32,
     UInt64,
     Int64,
+    UInt128,
+    Int128,
     Float32,
     Float64,
     Boolean,
+    Char,
     String,
+    // A blob of bytes, which unlike `Sequence(UInt8)` gets a native byte string type.
+    Bytes,
     Timestamp,
     Duration,
     // Types defined in the component API, each of which has a string name.
@@ -61,7 +73,11 @@ pub enum Type {
     // Structurally recursive types.
     Optional(Box<Type>),
     Sequence(Box<Type>),
-    Map(/* String, */ Box<Type>),
+    Map(Box<Type>, Box<Type>),
+    // A stream of values that the foreign language code pulls from Rust one at a time.
+    Stream(Box<Type>),
+    // A tuple of values with the given types, which has at least two elements.
+    Tuple(Vec<Type>),
     // An FfiConverter we `use` from an external crate
     External { name: String, crate_name: String },
     // A local type we will generate an FfiConverter via wrapping a primitive.
@@ -86,10 +102,14 @@ impl Type {
             Type::UInt32 => "u32".into(),
             Type::Int64 => "i64".into(),
             Type::UInt64 => "u64".into(),
+            Type::Int128 => "i128".into(),
+            Type::UInt128 => "u128".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
             Type::String => "string".into(),
+            Type::Bytes => "bytes".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             // API defined types.
             // Note that these all get unique names, and the parser ensures that the names do not
             // conflict with a builtin type. We add a prefix to the name to guard against pathological
@@ -110,7 +130,14 @@ impl Type {
             // acccidentally generating name collisions.
             Type::Optional(t) => format!("Optional{}", t.canonical_name()),
             Type::Sequence(t) => format!("Sequence{}", t.canonical_name()),
-            Type::Map(t) => format!("Map{}", t.canonical_name()),
+            Type::Map(k, v) => format!("Map{}{}", k.canonical_name(), v.canonical_name()),
+            Type::Stream(t) => format!("Stream{}", t.canonical_name()),
+            // The arity keeps this unambiguous when tuples are nested.
+            Type::Tuple(ts) => format!(
+                "Tuple{}{}",
+                ts.len(),
+                ts.iter().map(Type::canonical_name).collect::<String>()
+            ),
             // A type that exists externally.
             Type::External { name, .. } | Type::Wrapped { name, .. } => format!("Type{}", name),
         }
@@ -138,20 +165,30 @@ impl From<&Type> for FFIType {
             Type::Float64 => FFIType::Float64,
             // Booleans lower into an Int8, to work around a bug in JNA.
             Type::Boolean => FFIType::Int8,
+            // Chars lower into their Unicode code point.
+            Type::Char => FFIType::UInt32,
             // Strings are always owned rust values.
             // We might add a separate type for borrowed strings in future.
             Type::String => FFIType::RustBuffer,
+            // As are bytes, which are copied to the other side in bulk.
+            Type::Bytes => FFIType::RustBuffer,
             // Objects are pointers to an Arc<>
             Type::Object(_) => FFIType::RustArcPtr,
             // Callback interfaces are passed as opaque integer handles.
             Type::CallbackInterface(_) => FFIType::UInt64,
+            // So are streams, which the foreign language code passes back to Rust to read from.
+            Type::Stream(_) => FFIType::UInt64,
+            // There's no portable way to pass 128-bit integers over the C ABI,
+            // so they get serialized into a bytebuffer like the compound types below.
+            Type::UInt128 | Type::Int128 => FFIType::RustBuffer,
             // Other types are serialized into a bytebuffer and deserialized on the other side.
             Type::Enum(_)
             | Type::Error(_)
             | Type::Record(_)
             | Type::Optional(_)
             | Type::Sequence(_)
-            | Type::Map(_)
+            | Type::Map(_, _)
+            | Type::Tuple(_)
             | Type::Timestamp
             | Type::Duration
             | Type::External { .. } => FFIType::RustBuffer,
@@ -176,6 +213,10 @@ pub(crate) struct TypeUniverse {
     type_definitions: HashMap<String, Type>,
     // All the types in the universe, by canonical type name, in a well-defined order.
     all_known_types: BTreeSet<Type>,
+    // The names declared with `[Alias] typedef`, which are also in `type_definitions`.
+    type_aliases: BTreeMap<String, Type>,
+    // The alias whose type is being resolved, if any, so that it can't refer to itself.
+    alias_being_resolved: Option<String>,
 }
 
 impl TypeUniverse {
@@ -207,6 +248,55 @@ impl TypeUniverse {
         }
     }
 
+    /// Add a name for a [Type] that the bindings can use in place of spelling it out.
+    ///
+    /// Only sequences, maps and optional types can be aliased, since those are the ones that get
+    /// tedious to repeat, and each of them can only have one alias.
+    pub(super) fn add_type_alias(&mut self, name: &str, type_: Type) -> Result<()> {
+        if !matches!(
+            type_,
+            Type::Sequence(_) | Type::Map(_, _) | Type::Optional(_)
+        ) {
+            bail!(
+                "Only sequences, maps and optional types can be aliased, so {} can't be an alias for {}",
+                name,
+                type_.canonical_name()
+            );
+        }
+        if let Some((other, _)) = self.type_aliases.iter().find(|(_, t)| **t == type_) {
+            bail!(
+                "{} and {} are both aliases for {}",
+                other,
+                name,
+                type_.canonical_name()
+            );
+        }
+        self.add_type_definition(name, type_.clone())?;
+        self.type_aliases.insert(name.to_string(), type_);
+        Ok(())
+    }
+
+    /// Note that the type of the alias `name` is about to be resolved.
+    pub(super) fn begin_type_alias(&mut self, name: &str) {
+        self.alias_being_resolved = Some(name.to_string());
+    }
+
+    pub(super) fn end_type_alias(&mut self) {
+        self.alias_being_resolved = None;
+    }
+
+    /// Whether `name` is the alias whose type is being resolved.
+    pub(super) fn is_resolving_type_alias(&self, name: &str) -> bool {
+        self.alias_being_resolved.as_deref() == Some(name)
+    }
+
+    /// Iterator over the type aliases in this universe, ordered by name.
+    pub fn iter_type_aliases(&self) -> impl Iterator<Item = (&str, &Type)> + '_ {
+        self.type_aliases
+            .iter()
+            .map(|(name, type_)| (name.as_str(), type_))
+    }
+
     /// Get the [Type] corresponding to a given name, if any.
     pub(super) fn get_type_definition(&self, name: &str) -> Option<Type> {
         self.type_definitions.get(name).cloned()
@@ -236,6 +326,31 @@ impl TypeUniverse {
     pub fn iter_known_types(&self) -> impl Iterator<Item = Type> + '_ {
         self.all_known_types.iter().cloned()
     }
+
+    /// Make a copy of the universe that only contains the types matching a predicate.
+    pub(super) fn subset(&self, keep: impl Fn(&Type) -> bool) -> TypeUniverse {
+        TypeUniverse {
+            type_definitions: self
+                .type_definitions
+                .iter()
+                .filter(|(_, type_)| keep(type_))
+                .map(|(name, type_)| (name.clone(), type_.clone()))
+                .collect(),
+            all_known_types: self
+                .all_known_types
+                .iter()
+                .filter(|t| keep(t))
+                .cloned()
+                .collect(),
+            type_aliases: self
+                .type_aliases
+                .iter()
+                .filter(|(_, type_)| keep(type_))
+                .map(|(name, type_)| (name.clone(), type_.clone()))
+                .collect(),
+            alias_being_resolved: None,
+        }
+    }
 }
 
 /// An abstract type for an iterator over &Type references.
@@ -286,7 +401,16 @@ impl<T: IterTypes> IterTypes for Option<T> {
 impl IterTypes for Type {
     fn iter_types(&self) -> TypeIterator<'_> {
         let nested_types = match self {
-            Type::Optional(t) | Type::Sequence(t) | Type::Map(t) => Some(t.iter_types()),
+            Type::Optional(t)
+            | Type::Sequence(t)
+            | Type::Stream(t)
+            | Type::Wrapped { prim: t, .. } => Some(t.iter_types()),
+            Type::Map(k, v) => {
+                Some(Box::new(k.iter_types().chain(v.iter_types())) as TypeIterator<'_>)
+            }
+            Type::Tuple(ts) => {
+                Some(Box::new(ts.iter().flat_map(IterTypes::iter_types)) as TypeIterator<'_>)
+            }
             _ => None,
         };
         Box::new(std::iter::once(self).chain(nested_types.into_iter().flatten()))
@@ -308,6 +432,7 @@ mod test_type {
         // Non-exhaustive, but gives a bit of a flavour of what we want.
         assert_eq!(Type::UInt8.canonical_name(), "u8");
         assert_eq!(Type::String.canonical_name(), "string");
+        assert_eq!(Type::Bytes.canonical_name(), "bytes");
         assert_eq!(
             Type::Optional(Box::new(Type::Sequence(Box::new(Type::Object(
                 "Example".into()
@@ -315,6 +440,18 @@ mod test_type {
             .canonical_name(),
             "OptionalSequenceTypeExample"
         );
+        assert_eq!(
+            Type::Map(Box::new(Type::Int32), Box::new(Type::Record("Foo".into()))).canonical_name(),
+            "Mapi32TypeFoo"
+        );
+        assert_eq!(
+            Type::Tuple(vec![
+                Type::Tuple(vec![Type::Int32, Type::String]),
+                Type::Optional(Box::new(Type::Boolean))
+            ])
+            .canonical_name(),
+            "Tuple2Tuple2i32stringOptionalbool"
+        );
     }
 }
 
@@ -66,6 +66,18 @@ impl TypeResolver for weedle::types::NonAnyType<'_> {
     }
 }
 
+impl TypeResolver for &weedle::types::ConstType<'_> {
+    fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
+        match self {
+            weedle::types::ConstType::Boolean(t) => t.resolve_type_expression(types),
+            weedle::types::ConstType::Identifier(t) => t.resolve_type_expression(types),
+            weedle::types::ConstType::Integer(t) => t.resolve_type_expression(types),
+            weedle::types::ConstType::FloatingPoint(t) => t.resolve_type_expression(types),
+            _ => bail!("no support for constant type {:?}", self),
+        }
+    }
+}
+
 impl TypeResolver for &weedle::types::AttributedNonAnyType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
         if self.attributes.is_some() {
@@ -121,10 +133,23 @@ impl TypeResolver for weedle::types::SequenceType<'_> {
 
 impl TypeResolver for weedle::types::RecordType<'_> {
     fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
-        let t = (&self.generics.body.2).resolve_type_expression(types)?;
-        // Maps always have string keys, make sure the `String` type is known.
-        types.add_known_type(Type::String)?;
-        types.add_known_type(Type::Map(Box::new(t)))
+        let k = self.generics.body.0.resolve_type_expression(types)?;
+        let v = (&self.generics.body.2).resolve_type_expression(types)?;
+        types.add_known_type(Type::Map(Box::new(k), Box::new(v)))
+    }
+}
+
+// The version of weedle that we use only parses string types as the keys of a `record`, so maps
+// with any other type of key are declared with a `[Map]` typedef instead.
+impl TypeResolver for weedle::types::StringType {
+    fn resolve_type_expression(&self, types: &mut TypeUniverse) -> Result<Type> {
+        match self {
+            weedle::types::StringType::DOM(_) => types.add_known_type(Type::String),
+            _ => bail!(
+                "WebIDL Byte or USV string type not implemented ({:?}); consider using DOMString or string",
+                self
+            ),
+        }
     }
 }
 
@@ -134,6 +159,9 @@ impl TypeResolver for weedle::common::Identifier<'_> {
             Some(type_) => types.add_known_type(type_),
             None => match types.get_type_definition(self.0) {
                 Some(type_) => types.add_known_type(type_),
+                None if types.is_resolving_type_alias(self.0) => {
+                    bail!("Type alias {} can't refer to itself", self.0)
+                }
                 None => bail!("unknown type reference: {}", self.0),
             },
         }
@@ -171,6 +199,7 @@ impl TypeResolver for weedle::types::DoubleType {
 pub(in super::super) fn resolve_builtin_type(name: &str) -> Option<Type> {
     match name {
         "string" => Some(Type::String),
+        "bytes" => Some(Type::Bytes),
         "u8" => Some(Type::UInt8),
         "i8" => Some(Type::Int8),
         "u16" => Some(Type::UInt16),
@@ -179,8 +208,11 @@ pub(in super::super) fn resolve_builtin_type(name: &str) -> Option<Type> {
         "i32" => Some(Type::Int32),
         "u64" => Some(Type::UInt64),
         "i64" => Some(Type::Int64),
+        "u128" => Some(Type::UInt128),
+        "i128" => Some(Type::Int128),
         "f32" => Some(Type::Float32),
         "f64" => Some(Type::Float64),
+        "char" => Some(Type::Char),
         "timestamp" => Some(Type::Timestamp),
         "duration" => Some(Type::Duration),
         _ => None,
@@ -213,6 +245,18 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_bytes_are_not_a_sequence() {
+        let mut types = TypeUniverse::default();
+        let (_, expr) = weedle::types::Type::parse("bytes").unwrap();
+        assert_eq!(types.resolve_type_expression(expr).unwrap(), Type::Bytes);
+        let (_, expr) = weedle::types::Type::parse("sequence<u8>").unwrap();
+        assert_eq!(
+            types.resolve_type_expression(expr).unwrap(),
+            Type::Sequence(Box::new(Type::UInt8))
+        );
+    }
+
     #[test]
     fn test_resolving_optional_type_adds_inner_type() {
         let mut types = TypeUniverse::default();
@@ -246,16 +290,16 @@ mod test {
     }
 
     #[test]
-    fn test_resolving_map_type_adds_string_and_inner_type() {
+    fn test_resolving_map_type_adds_key_and_value_types() {
         let mut types = TypeUniverse::default();
         assert_eq!(types.iter_known_types().count(), 0);
         let (_, expr) = weedle::types::Type::parse("record<DOMString, float>").unwrap();
         let t = types.resolve_type_expression(expr).unwrap();
-        assert_eq!(t.canonical_name(), "Mapf32");
+        assert_eq!(t.canonical_name(), "Mapstringf32");
         assert_eq!(types.iter_known_types().count(), 3);
         assert!(types
             .iter_known_types()
-            .any(|t| t.canonical_name() == "Mapf32"));
+            .any(|t| t.canonical_name() == "Mapstringf32"));
         assert!(types
             .iter_known_types()
             .any(|t| t.canonical_name() == "string"));
@@ -264,6 +308,16 @@ mod test {
             .any(|t| t.canonical_name() == "f32"));
     }
 
+    #[test]
+    fn test_resolving_map_type_with_unsupported_keys() {
+        let mut types = TypeUniverse::default();
+        let (_, expr) = weedle::types::Type::parse("record<USVString, string>").unwrap();
+        let err = types.resolve_type_expression(expr).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("WebIDL Byte or USV string type not implemented"));
+    }
+
     #[test]
     fn test_error_on_unknown_type() -> Result<()> {
         let mut types = TypeUniverse::default();
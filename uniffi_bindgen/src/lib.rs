@@ -94,7 +94,7 @@
 
 const BINDGEN_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-use anyhow::{anyhow, bail, Context, Result};
+use anyhow::{anyhow, bail, Result};
 use serde::{Deserialize, Serialize};
 use std::convert::TryInto;
 use std::io::prelude::*;
@@ -107,9 +107,12 @@ use std::{
 };
 
 pub mod bindings;
+mod error;
 pub mod interface;
 pub mod scaffolding;
 
+pub use error::BindgenError;
+
 use bindings::TargetLanguage;
 use interface::ComponentInterface;
 use scaffolding::RustScaffolding;
@@ -121,16 +124,35 @@ pub fn generate_component_scaffolding<P: AsRef<Path>>(
     config_file_override: Option<P>,
     out_dir_override: Option<P>,
     format_code: bool,
-) -> Result<()> {
+) -> Result<(), BindgenError> {
+    generate_component_scaffolding_from_files(
+        &[udl_file],
+        config_file_override,
+        out_dir_override,
+        format_code,
+    )
+}
+
+// Like `generate_component_scaffolding`, but for an interface that's split across several UDL
+// files. The crate root, the output directory and the name of the output file are all found
+// from the first of them.
+pub fn generate_component_scaffolding_from_files<P: AsRef<Path>>(
+    udl_files: &[P],
+    config_file_override: Option<P>,
+    out_dir_override: Option<P>,
+    format_code: bool,
+) -> Result<(), BindgenError> {
     let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
     let out_dir_override = out_dir_override.as_ref().map(|p| p.as_ref());
-    let udl_file = udl_file.as_ref();
-    let component = parse_udl(udl_file)?;
-    let _config = get_config(
+    let udl_files: Vec<&Path> = udl_files.iter().map(|p| p.as_ref()).collect();
+    let mut component = parse_udl(&udl_files)?;
+    let udl_file = udl_files[0];
+    let config = get_config(
         &component,
         guess_crate_root(udl_file)?,
         config_file_override,
-    );
+    )?;
+    apply_config(&mut component, &config)?;
     let mut filename = Path::new(&udl_file)
         .file_stem()
         .ok_or_else(|| anyhow!("not a file"))?
@@ -138,46 +160,73 @@ pub fn generate_component_scaffolding<P: AsRef<Path>>(
     filename.push(".uniffi.rs");
     let mut out_dir = get_out_dir(udl_file, out_dir_override)?;
     out_dir.push(filename);
-    let mut f =
-        File::create(&out_dir).map_err(|e| anyhow!("Failed to create output file: {:?}", e))?;
-    write!(f, "{}", RustScaffolding::new(&component))
-        .map_err(|e| anyhow!("Failed to write output file: {:?}", e))?;
+    let io_error = |source| BindgenError::Io {
+        path: out_dir.clone(),
+        source,
+    };
+    let mut f = File::create(&out_dir).map_err(io_error)?;
+    write!(f, "{}", RustScaffolding::new(&component)).map_err(io_error)?;
     if format_code {
-        Command::new("rustfmt").arg(&out_dir).status()?;
+        Command::new("rustfmt")
+            .arg(&out_dir)
+            .status()
+            .map_err(io_error)?;
     }
     Ok(())
 }
 
 // Generate the bindings in the target languages that call the scaffolding
-// Rust code.
+// Rust code, returning warnings about anything they handle in a lossy way.
 pub fn generate_bindings<P: AsRef<Path>>(
     udl_file: P,
     config_file_override: Option<P>,
     target_languages: Vec<&str>,
     out_dir_override: Option<P>,
     try_format_code: bool,
-) -> Result<()> {
+) -> Result<Vec<String>, BindgenError> {
+    generate_bindings_from_files(
+        &[udl_file],
+        config_file_override,
+        target_languages,
+        out_dir_override,
+        try_format_code,
+    )
+}
+
+// Like `generate_bindings`, but for an interface that's split across several UDL files, which
+// are merged into a single `ComponentInterface` first. As for the scaffolding, the crate root
+// and the default output directory are found from the first of them.
+pub fn generate_bindings_from_files<P: AsRef<Path>>(
+    udl_files: &[P],
+    config_file_override: Option<P>,
+    target_languages: Vec<&str>,
+    out_dir_override: Option<P>,
+    try_format_code: bool,
+) -> Result<Vec<String>, BindgenError> {
     let out_dir_override = out_dir_override.as_ref().map(|p| p.as_ref());
     let config_file_override = config_file_override.as_ref().map(|p| p.as_ref());
-    let udl_file = udl_file.as_ref();
+    let udl_files: Vec<&Path> = udl_files.iter().map(|p| p.as_ref()).collect();
 
-    let component = parse_udl(udl_file)?;
+    let mut component = parse_udl(&udl_files)?;
+    let udl_file = udl_files[0];
     let config = get_config(
         &component,
         guess_crate_root(udl_file)?,
         config_file_override,
     )?;
+    apply_config(&mut component, &config)?;
     let out_dir = get_out_dir(&udl_file, out_dir_override)?;
+    let mut warnings = Vec::new();
     for language in target_languages {
-        bindings::write_bindings(
+        warnings.extend(bindings::write_bindings(
             &config.bindings,
             &component,
             &out_dir,
             language.try_into()?,
             try_format_code,
-        )?;
+        )?);
     }
-    Ok(())
+    Ok(warnings)
 }
 
 // Run tests against the foreign language bindings (generated and compiled at the same time).
@@ -214,8 +263,9 @@ pub fn run_tests<P: AsRef<Path>>(
     for (lang, test_scripts) in language_tests {
         for udl_file in udl_files {
             let crate_root = guess_crate_root(Path::new(udl_file))?;
-            let component = parse_udl(Path::new(udl_file))?;
+            let mut component = parse_udl(&[Path::new(udl_file)])?;
             let config = get_config(&component, crate_root, config_file_override)?;
+            apply_config(&mut component, &config)?;
             bindings::write_bindings(&config.bindings, &component, &cdylib_dir, lang, true)?;
             bindings::compile_bindings(&config.bindings, &component, &cdylib_dir, lang)?;
         }
@@ -247,7 +297,7 @@ fn get_config(
     component: &ComponentInterface,
     crate_root: &Path,
     config_file_override: Option<&Path>,
-) -> Result<Config> {
+) -> Result<Config, BindgenError> {
     let default_config: Config = component.into();
 
     let config_file: Option<PathBuf> = match config_file_override {
@@ -257,16 +307,25 @@ fn get_config(
 
     match config_file {
         Some(path) => {
-            let contents = slurp_file(&path)
-                .with_context(|| format!("Failed to read config file from {:?}", &path))?;
-            let loaded_config: Config = toml::de::from_str(&contents)
-                .with_context(|| format!("Failed to generate config from file {:?}", &path))?;
-            Ok(loaded_config.merge_with(&default_config))
+            let loaded_config = slurp_file(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|contents| Ok(toml::de::from_str::<Config>(&contents)?))
+                .map_err(|source| BindgenError::Config { path, source })?;
+            Ok(merge_configs(vec![default_config, loaded_config]))
         }
         None => Ok(default_config),
     }
 }
 
+// Apply the parts of the config that change the `ComponentInterface` itself, so that the
+// scaffolding and the bindings for every language see the same changes.
+fn apply_config(component: &mut ComponentInterface, config: &Config) -> Result<()> {
+    if let Some(ffi_namespace) = config.bindings.ffi_namespace() {
+        component.set_ffi_namespace(ffi_namespace)?;
+    }
+    Ok(())
+}
+
 fn get_out_dir(udl_file: &Path, out_dir_override: Option<&Path>) -> Result<PathBuf> {
     Ok(match out_dir_override {
         Some(s) => {
@@ -282,14 +341,28 @@ fn get_out_dir(udl_file: &Path, out_dir_override: Option<&Path>) -> Result<PathB
     })
 }
 
-fn parse_udl(udl_file: &Path) -> Result<ComponentInterface> {
-    let udl =
-        slurp_file(udl_file).map_err(|_| anyhow!("Failed to read UDL from {:?}", &udl_file))?;
-    udl.parse::<interface::ComponentInterface>()
-        .map_err(|e| anyhow!("Failed to parse UDL: {}", e))
+fn parse_udl(udl_files: &[&Path]) -> Result<ComponentInterface, BindgenError> {
+    if udl_files.is_empty() {
+        return Err(BindgenError::Other(anyhow!("No UDL files were given")));
+    }
+    let udls = udl_files
+        .iter()
+        .map(|udl_file| {
+            let udl = slurp_file(udl_file).map_err(|source| BindgenError::Io {
+                path: udl_file.to_path_buf(),
+                source,
+            })?;
+            Ok((udl_file.display().to_string(), udl))
+        })
+        .collect::<Result<Vec<_>, BindgenError>>()?;
+    let udls: Vec<(&str, &str)> = udls
+        .iter()
+        .map(|(name, udl)| (name.as_str(), udl.as_str()))
+        .collect();
+    ComponentInterface::from_webidl_files(&udls).map_err(BindgenError::Parse)
 }
 
-fn slurp_file(file_name: &Path) -> Result<String> {
+fn slurp_file(file_name: &Path) -> std::io::Result<String> {
     let mut contents = String::new();
     let mut f = File::open(file_name)?;
     f.read_to_string(&mut contents)?;
@@ -314,6 +387,18 @@ pub trait MergeWith {
     fn merge_with(&self, other: &Self) -> Self;
 }
 
+/// Merge an ordered list of configs, with later entries taking precedence over earlier ones.
+///
+/// This happens on a per-field basis, so any option that a config leaves unset is inherited
+/// from the configs before it. For example, you could pass the defaults for a crate, followed
+/// by a workspace config that sets a shared `package_name`, followed by a per-crate config
+/// that only overrides the `cdylib_name`.
+pub fn merge_configs<T: MergeWith + Default>(configs: impl IntoIterator<Item = T>) -> T {
+    configs
+        .into_iter()
+        .fold(T::default(), |merged, config| config.merge_with(&merged))
+}
+
 impl MergeWith for Config {
     fn merge_with(&self, other: &Self) -> Self {
         Config {
@@ -363,7 +448,7 @@ pub fn run_main() -> Result<()> {
                         .long("--no-format")
                         .help("Do not try to format the generated bindings"),
                 )
-                .arg(clap::Arg::with_name("udl_file").required(true))
+                .arg(clap::Arg::with_name("udl_file").required(true).multiple(true).help("UDL file(s) to generate the bindings from, which are merged into a single interface"))
                 .arg(
                     clap::Arg::with_name("config")
                     .long("--config-path")
@@ -398,7 +483,7 @@ pub fn run_main() -> Result<()> {
                         .long("--no-format")
                         .help("Do not format the generated code with rustfmt (useful for maintainers)"),
                 )
-                .arg(clap::Arg::with_name("udl_file").required(true)),
+                .arg(clap::Arg::with_name("udl_file").required(true).multiple(true).help("UDL file(s) to generate the scaffolding from, which are merged into a single interface")),
         )
         .subcommand(
             clap::SubCommand::with_name("test")
@@ -415,15 +500,20 @@ pub fn run_main() -> Result<()> {
         )
         .get_matches();
     match matches.subcommand() {
-        ("generate", Some(m)) => crate::generate_bindings(
-            m.value_of_os("udl_file").unwrap(), // Required
-            m.value_of_os("config"),
-            m.values_of("language").unwrap().collect(), // Required
-            m.value_of_os("out_dir"),
-            !m.is_present("no_format"),
-        )?,
-        ("scaffolding", Some(m)) => crate::generate_component_scaffolding(
-            m.value_of_os("udl_file").unwrap(), // Required
+        ("generate", Some(m)) => {
+            let warnings = crate::generate_bindings_from_files(
+                &m.values_of_os("udl_file").unwrap().collect::<Vec<_>>(), // Required
+                m.value_of_os("config"),
+                m.values_of("language").unwrap().collect(), // Required
+                m.value_of_os("out_dir"),
+                !m.is_present("no_format"),
+            )?;
+            for warning in warnings {
+                eprintln!("Warning: {}", warning);
+            }
+        }
+        ("scaffolding", Some(m)) => crate::generate_component_scaffolding_from_files(
+            &m.values_of_os("udl_file").unwrap().collect::<Vec<_>>(), // Required
             m.value_of_os("config"),
             m.value_of_os("out_dir"),
             !m.is_present("no_format"),
@@ -462,4 +552,42 @@ mod test {
         let not_a_crate_root = &this_crate_root.join("./src/templates");
         assert!(guess_crate_root(&not_a_crate_root.join("./src/example.udl")).is_err());
     }
+
+    #[test]
+    fn test_merge_configs_prefers_later_entries() {
+        let parse = |s: &str| -> bindings::kotlin::Config { toml::from_str(s).unwrap() };
+        let merged = merge_configs(vec![
+            parse("package_name = \"default\"\ncdylib_name = \"default\""),
+            parse("package_name = \"workspace\""),
+            parse("cdylib_name = \"crate\""),
+        ]);
+        assert_eq!(merged.package_name(), "workspace");
+        assert_eq!(merged.cdylib_name(), "crate");
+        let empty: Vec<bindings::kotlin::Config> = vec![];
+        assert_eq!(merge_configs(empty).package_name(), "uniffi");
+    }
+
+    #[test]
+    fn test_generate_bindings_reports_what_went_wrong() {
+        let crate_root =
+            env::temp_dir().join(format!("uniffi-bindgen-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&crate_root);
+        std::fs::create_dir_all(crate_root.join("src")).unwrap();
+        std::fs::write(crate_root.join("Cargo.toml"), "").unwrap();
+        let udl_file = crate_root.join("src/test.udl");
+        let config_file = crate_root.join("uniffi.toml");
+        let generate = || {
+            generate_bindings(&udl_file, None, vec!["kotlin"], Some(&crate_root), false)
+                .unwrap_err()
+        };
+
+        assert!(matches!(generate(), BindgenError::Io { path, .. } if path == udl_file));
+        std::fs::write(&udl_file, "namespace test { void missing_semicolon() }").unwrap();
+        assert!(matches!(generate(), BindgenError::Parse(_)));
+        std::fs::write(&udl_file, "namespace test {};").unwrap();
+        std::fs::write(&config_file, "[bindings.kotlin]\nsplit_files = \"yes\"").unwrap();
+        assert!(matches!(generate(), BindgenError::Config { .. }));
+
+        std::fs::remove_dir_all(&crate_root).unwrap();
+    }
 }
@@ -36,10 +36,14 @@ mod filters {
             Type::UInt32 => "u32".into(),
             Type::Int64 => "i64".into(),
             Type::UInt64 => "u64".into(),
+            Type::Int128 => "i128".into(),
+            Type::UInt128 => "u128".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
             Type::String => "String".into(),
+            Type::Bytes => "std::vec::Vec<u8>".into(),
             Type::Timestamp => "std::time::SystemTime".into(),
             Type::Duration => "std::time::Duration".into(),
             Type::Enum(name) | Type::Record(name) | Type::Error(name) => name.clone(),
@@ -47,7 +51,19 @@ mod filters {
             Type::CallbackInterface(name) => format!("Box<dyn {}>", name),
             Type::Optional(t) => format!("std::option::Option<{}>", type_rs(t)?),
             Type::Sequence(t) => format!("std::vec::Vec<{}>", type_rs(t)?),
-            Type::Map(t) => format!("std::collections::HashMap<String, {}>", type_rs(t)?),
+            Type::Stream(t) => format!("uniffi::Stream<{}>", type_rs(t)?),
+            Type::Tuple(ts) => format!(
+                "({})",
+                ts.iter()
+                    .map(type_rs)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::Map(k, v) => format!(
+                "std::collections::HashMap<{}, {}>",
+                type_rs(k)?,
+                type_rs(v)?
+            ),
             Type::External { .. } => panic!("External types coming to a uniffi near you soon!"),
             Type::Wrapped { .. } => panic!("Wrapped types coming to a uniffi near you soon!"),
         })
@@ -69,6 +85,7 @@ mod filters {
             FFIType::RustBuffer => "uniffi::RustBuffer".into(),
             FFIType::ForeignBytes => "uniffi::ForeignBytes".into(),
             FFIType::ForeignCallback => "uniffi::ForeignCallback".into(),
+            FFIType::FutureCallback => "uniffi::FutureCallback".into(),
         })
     }
 
@@ -98,9 +115,18 @@ mod filters {
             // inner type.
             Type::Optional(inner) => format!("std::option::Option<{}>", ffi_converter_name(inner)?),
             Type::Sequence(inner) => format!("std::vec::Vec<{}>", ffi_converter_name(inner)?),
-            Type::Map(inner) => format!(
-                "std::collections::HashMap<String, {}>",
-                ffi_converter_name(inner)?
+            Type::Stream(inner) => format!("uniffi::Stream<{}>", ffi_converter_name(inner)?),
+            Type::Tuple(ts) => format!(
+                "({})",
+                ts.iter()
+                    .map(ffi_converter_name)
+                    .collect::<Result<Vec<_>, _>>()?
+                    .join(", ")
+            ),
+            Type::Map(k, v) => format!(
+                "std::collections::HashMap<{}, {}>",
+                ffi_converter_name(k)?,
+                ffi_converter_name(v)?
             ),
             // External and Wrapped bytes have FfiConverters with a predictable name based on the type name.
             Type::Wrapped { name, .. } | Type::External { name, .. } => {
@@ -115,10 +141,14 @@ mod filters {
             Type::UInt32 => "u32".into(),
             Type::Int64 => "i64".into(),
             Type::UInt64 => "u64".into(),
+            Type::Int128 => "i128".into(),
+            Type::UInt128 => "u128".into(),
             Type::Float32 => "f32".into(),
             Type::Float64 => "f64".into(),
             Type::String => "String".into(),
             Type::Boolean => "bool".into(),
+            Type::Char => "char".into(),
+            Type::Bytes => "uniffi::FfiConverterBytes".into(),
         })
     }
 
@@ -132,6 +162,28 @@ mod filters {
         ))
     }
 
+    // Map a record field to Rust code that specifies its FfiConverter implementation.
+    //
+    // This is the same as `ffi_converter` on the field's type, except that fields which refer
+    // back to their record are boxed, inside any `Option`s, like `Option<Box<Tree>>`.
+    pub fn field_ffi_converter(field: &Field) -> Result<String, askama::Error> {
+        fn boxed_ffi_converter_name(type_: &Type) -> Result<String, askama::Error> {
+            Ok(match type_ {
+                Type::Optional(inner) => {
+                    format!("std::option::Option<{}>", boxed_ffi_converter_name(inner)?)
+                }
+                _ => format!("std::boxed::Box<{}>", ffi_converter_name(type_)?),
+            })
+        }
+        if !field.is_boxed() {
+            return ffi_converter(&field.type_());
+        }
+        Ok(format!(
+            "<{} as uniffi::FfiConverter>",
+            boxed_ffi_converter_name(&field.type_())?
+        ))
+    }
+
     // Turns a `crate-name` into the `crate_name` the .rs code needs to specify.
     pub fn crate_name_rs(nm: &dyn fmt::Display) -> Result<String, askama::Error> {
         Ok(nm.to_string().to_snake_case())
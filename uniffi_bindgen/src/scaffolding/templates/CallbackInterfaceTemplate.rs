@@ -7,8 +7,12 @@
 //  * a proxy `struct` which implements the `trait` that the Callback Interface corresponds to. This 
 //    is the object that client code interacts with.
 //    - for each method, arguments will be packed into a `RustBuffer` and sent over the `ForeignCallback` to be 
-//      unpacked and called. The return value is packed into another `RustBuffer` and sent back to Rust.
+//      unpacked and called. Objects passed `[ByRef]` are lent to the foreign code rather than cloned.
+//      The return value is packed into another `RustBuffer` and sent back to Rust.
+//      For methods that can fail, the return value is preceded by a status code, and may be replaced by an error.
 //    - a `Drop` `impl`, which tells the foreign language to forget about the real callback object.
+//  * an `FfiConverter` which lifts handles into proxies, and lowers proxies back into their handles, so that
+//    passing a callback back to the foreign language returns the original object.
 #}
 {% let trait_name = cbi.name() -%}
 {% let trait_impl = cbi.type_()|ffi_converter_name -%}
@@ -33,8 +37,11 @@ struct {{ trait_impl }} {
 
 impl Drop for {{ trait_impl }} {
     fn drop(&mut self) {
-        let callback = {{ foreign_callback_internals }}.get_callback().unwrap();
-        unsafe { callback(self.handle, uniffi::IDX_CALLBACK_FREE, Default::default()) };
+        // A proxy that was passed back to the foreign language has already handed its handle over.
+        if {{ foreign_callback_internals }}.unregister_proxy(self as *const Self as usize).is_some() {
+            let callback = {{ foreign_callback_internals }}.get_callback().unwrap();
+            unsafe { callback(self.handle, uniffi::IDX_CALLBACK_FREE, Default::default()) };
+        }
     }
 }
 
@@ -46,9 +53,13 @@ impl {{ trait_name }} for {{ trait_impl }} {
     {#- Method declaration #}
     fn {{ meth.name() -}}
     ({% call rs::arg_list_decl_with_prefix("&self", meth) %})
+    {%- match meth.throws() %}
+    {%- when Some with (e) %} -> Result<{% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|type_rs }}{% else %}(){% endmatch %}, {{ e }}>
+    {% else -%}
     {%- match meth.return_type() %}
     {%- when Some with (return_type) %} -> {{ return_type|type_rs }}
     {% else -%}
+    {%- endmatch -%}
     {%- endmatch -%} { 
     {#- Method body #}
         uniffi::deps::log::debug!("{{ cbi.name() }}.{{ meth.name() }}");
@@ -60,7 +71,13 @@ impl {{ trait_name }} for {{ trait_impl }} {
         let mut args_buf = Vec::new();
         {% endif -%}
         {%- for arg in meth.arguments() %}
+        {%- if arg.by_ref() %}
+        // Lend the object to the foreign code without adding a reference for it, which is
+        // only sound because it must not hold on to the object after the call returns.
+        <u64 as uniffi::FfiConverter>::write(std::sync::Arc::as_ptr({{ arg.name() }}) as u64, &mut args_buf);
+        {%- else %}
         {{ arg.type_()|ffi_converter }}::write({{ arg.name() }}, &mut args_buf);
+        {%- endif %}
         {%- endfor -%}
         let args_rbuf = uniffi::RustBuffer::from_vec(args_buf);
 
@@ -69,6 +86,21 @@ impl {{ trait_name }} for {{ trait_impl }} {
         let ret_rbuf = unsafe { callback(self.handle, {{ loop.index }}, args_rbuf) };
 
     {#- Unpacking the RustBuffer to return to Rust #}
+        {% match meth.throws_type() -%}
+        {% when Some with (e) -%}
+        let vec = ret_rbuf.destroy_into_vec();
+        let mut ret_buf = vec.as_slice();
+        match <i8 as uniffi::FfiConverter>::try_read(&mut ret_buf).unwrap() {
+            uniffi::CALLBACK_SUCCESS => Ok({% match meth.return_type() %}{% when Some with (return_type) %}{{ return_type|ffi_converter }}::try_read(&mut ret_buf).unwrap(){% else %}(){% endmatch %}),
+            uniffi::CALLBACK_ERROR => Err({{ e|ffi_converter }}::try_read(&mut ret_buf).unwrap()),
+            // The foreign code threw something that isn't declared in the UDL, which
+            // we have no way to represent in Rust, so the best we can do is panic.
+            _ => panic!(
+                "callback {{ cbi.name() }}.{{ meth.name() }} failed: {}",
+                <String as uniffi::FfiConverter>::try_read(&mut ret_buf).unwrap()
+            ),
+        }
+        {%- else -%}
         {% match meth.return_type() -%}
         {% when Some with (return_type) -%}
         let vec = ret_rbuf.destroy_into_vec();
@@ -77,6 +109,7 @@ impl {{ trait_name }} for {{ trait_impl }} {
         {%- else -%}
         uniffi::RustBuffer::destroy(ret_rbuf);
         {%- endmatch %}
+        {%- endmatch %}
     }
     {%- endfor %}
 }
@@ -86,25 +119,29 @@ unsafe impl uniffi::FfiConverter for {{ trait_impl }} {
     type RustType = Box<dyn {{ trait_name }}>;
     type FfiType = u64;
     
-    // Lower and write are tricky to implement because we have a dyn trait as our type.  There's
-    // probably a way to, but this carries lots of thread safety risks, down to impedence
-    // mismatches between Rust and foreign languages, and our uncertainty around implementations of
-    // concurrent handlemaps.
-    //
-    // The use case for them is also quite exotic: it's passing a foreign callback back to the foreign
-    // language.
-    //
-    // Until we have some certainty, and use cases, we shouldn't use them. 
-    fn lower(_obj: Self::RustType) -> Self::FfiType {
-        panic!("Lowering CallbackInterface not supported")
+    // We can't downcast a dyn trait, so to lower one we look up its address in the live proxies.
+    // That means only callbacks that came from the foreign language can be passed back to it, in
+    // which case their handle is passed back and the foreign language takes over freeing it.
+    // Implementations of the trait in Rust can't be lowered, since there's no foreign object
+    // for them.
+    fn lower(obj: Self::RustType) -> Self::FfiType {
+        let addr = &*obj as *const dyn {{ trait_name }} as *const u8 as usize;
+        match {{ foreign_callback_internals }}.unregister_proxy(addr) {
+            // Dropping the unregistered proxy doesn't free the handle.
+            Some(handle) => handle,
+            None => panic!("Lowering a {{ trait_name }} that isn't implemented by the foreign language is not supported"),
+        }
     }
 
-    fn write(_obj: Self::RustType, _buf: &mut std::vec::Vec<u8>) {
-        panic!("Writing CallbackInterface not supported")
+    fn write(obj: Self::RustType, buf: &mut std::vec::Vec<u8>) {
+        use uniffi::deps::bytes::BufMut;
+        buf.put_u64(<Self as uniffi::FfiConverter>::lower(obj));
     }
 
     fn try_lift(v: Self::FfiType) -> uniffi::deps::anyhow::Result<Self::RustType> {
-        Ok(Box::new(Self { handle: v }))
+        let proxy = Box::new(Self { handle: v });
+        {{ foreign_callback_internals }}.register_proxy(&*proxy as *const Self as usize, v);
+        Ok(proxy)
     }
 
     fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<Self::RustType> {
@@ -5,6 +5,9 @@
 //
 // We define a unit-struct to implement the trait to sidestep Rust's orphan rule (ADR-0006). It's
 // public so other crates can refer to it via an `[External='crate'] typedef`
+//
+// The discriminant is usually an `i32`, but flat enums can choose a narrower `[Repr]`
+// type, so we (de)serialize it using the builtin `FfiConverter` for whichever type it is.
 #}
 
 pub struct {{ e.type_()|ffi_converter_name }};
@@ -14,11 +17,10 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
     type RustType = {{ e.name() }};
 
     fn write(obj: Self::RustType, buf: &mut std::vec::Vec<u8>) {
-        use uniffi::deps::bytes::BufMut;
         match obj {
             {%- for variant in e.variants() %}
             {{ e.name() }}::{{ variant.name() }} { {% for field in variant.fields() %}{{ field.name() }}, {%- endfor %} } => {
-                buf.put_i32({{ loop.index }});
+                <{{ e.discriminant_type()|type_rs }} as uniffi::FfiConverter>::write({{ variant.discriminant() }}, buf);
                 {% for field in variant.fields() -%}
                 {{ field.type_()|ffi_converter }}::write({{ field.name() }}, buf);
                 {%- endfor %}
@@ -28,11 +30,9 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
     }
 
     fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ e.name() }}> {
-        use uniffi::deps::bytes::Buf;
-        uniffi::check_remaining(buf, 4)?;
-        Ok(match buf.get_i32() {
+        Ok(match <{{ e.discriminant_type()|type_rs }} as uniffi::FfiConverter>::try_read(buf)? {
             {%- for variant in e.variants() %}
-            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} {
+            {{ variant.discriminant() }} => {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} {
                 {% for field in variant.fields() %}
                 {{ field.name() }}: {{ field.type_()|ffi_converter }}::try_read(buf)?,
                 {%- endfor %}
@@ -25,7 +25,7 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
         match obj {
             {%- for variant in e.variants() %}
             {{ e.name() }}::{{ variant.name() }}{..} => {
-                buf.put_i32({{ loop.index }});
+                buf.put_i32({{ variant.discriminant() }});
                 <String as uniffi::FfiConverter>::write(msg, buf);
             },
             {%- endfor %}
@@ -51,7 +51,7 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
         match obj {
             {%- for variant in e.variants() %}
             {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} { {% for field in variant.fields() %}{{ field.name() }}, {%- endfor %} }{% else %}{..}{% endif %} => {
-                buf.put_i32({{ loop.index }});
+                buf.put_i32({{ variant.discriminant() }});
                 {% for field in variant.fields() -%}
                 {{ field.type_()|ffi_converter }}::write({{ field.name() }}, buf);
                 {%- endfor %}
@@ -67,7 +67,7 @@ impl uniffi::RustBufferFfiConverter for {{ e.type_()|ffi_converter_name }} {
         uniffi::check_remaining(buf, 4)?;
         Ok(match buf.get_i32() {
             {%- for variant in e.variants() %}
-            {{ loop.index }} => {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} {
+            {{ variant.discriminant() }} => {{ e.name() }}::{{ variant.name() }}{% if variant.has_fields() %} {
                 {% for field in variant.fields() %}
                 {{ field.name() }}: {{ field.type_()|ffi_converter }}::try_read(buf)?,
                 {%- endfor %}
@@ -58,8 +58,20 @@ pub extern "C" fn {{ ffi_free.name() }}(ptr: *const std::os::raw::c_void, call_s
         {%- call rs::arg_list_ffi_decl(meth.ffi_func()) %}
     ) {% call rs::return_signature(meth) %} {
         uniffi::deps::log::debug!("{{ meth.ffi_func().name() }}");
+        {%- if obj.is_record_by_handle() %}
+        // A `[ByHandle]` dictionary is an ordinary struct, so this reads the field directly.
+        uniffi::call_with_output(call_status, || {
+            let obj = {{ obj.type_()|ffi_converter }}::try_lift(ptr).unwrap();
+            {%- match meth.return_type() %}
+            {%- when Some with (return_type) %}
+            {{ return_type|ffi_converter }}::lower(obj.{{ meth.name() }}.clone())
+            {%- when None %}
+            {%- endmatch %}
+        })
+        {%- else %}
         // If the method does not have the same signature as declared in the UDL, then
         // this attempt to call it will fail with a (somewhat) helpful compiler error.
         {% call rs::to_rs_method_call(obj, meth) %}
+        {%- endif %}
     }
 {% endfor %}
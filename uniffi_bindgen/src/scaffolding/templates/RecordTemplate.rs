@@ -10,6 +10,10 @@
 pub struct {{ rec.type_()|ffi_converter_name }};
 
 #[doc(hidden)]
+{%- if rec.fields().is_empty() %}
+// A record with no fields doesn't write or read anything.
+#[allow(unused_variables)]
+{%- endif %}
 impl uniffi::RustBufferFfiConverter for {{ rec.type_()|ffi_converter_name }} {
     type RustType = {{ rec.name() }};
 
@@ -17,14 +21,14 @@ impl uniffi::RustBufferFfiConverter for {{ rec.type_()|ffi_converter_name }} {
         // If the provided struct doesn't match the fields declared in the UDL, then
         // the generated code here will fail to compile with somewhat helpful error.
         {%- for field in rec.fields() %}
-        {{ field.type_()|ffi_converter }}::write(obj.{{ field.name() }}, buf);
+        {{ field|field_ffi_converter }}::write(obj.{{ field.name() }}, buf);
         {%- endfor %}
     }
 
     fn try_read(buf: &mut &[u8]) -> uniffi::deps::anyhow::Result<{{ rec.name() }}> {
         Ok({{ rec.name() }} {
             {%- for field in rec.fields() %}
-                {{ field.name() }}: {{ field.type_()|ffi_converter }}::try_read(buf)?,
+                {{ field.name() }}: {{ field|field_ffi_converter }}::try_read(buf)?,
             {%- endfor %}
         })
     }
@@ -0,0 +1,32 @@
+{#
+// For each stream type returned by a function or method, we provide a pair of `pub extern "C"`
+// functions that the foreign language code can use to read values out of the stream, and to
+// free it once it's done.
+#}
+{% match stream %}
+{% when Type::Stream with (item) %}
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_stream_next(stream).name() }}(
+    handle: u64,
+    call_status: &mut uniffi::RustCallStatus
+) -> uniffi::RustBuffer {
+    uniffi::deps::log::debug!("{{ ci.ffi_stream_next(stream).name() }}");
+    uniffi::call_with_output(call_status, || unsafe {
+        uniffi::stream_next::<{{ item|ffi_converter_name }}>(handle)
+    })
+}
+
+#[doc(hidden)]
+#[no_mangle]
+pub extern "C" fn {{ ci.ffi_stream_free(stream).name() }}(
+    handle: u64,
+    call_status: &mut uniffi::RustCallStatus
+) {
+    uniffi::deps::log::debug!("{{ ci.ffi_stream_free(stream).name() }}");
+    uniffi::call_with_output(call_status, || unsafe {
+        uniffi::stream_free::<{{ item|ffi_converter_name }}>(handle)
+    })
+}
+{% else %}
+{% endmatch %}
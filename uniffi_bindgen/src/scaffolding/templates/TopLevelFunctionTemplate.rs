@@ -12,5 +12,9 @@ pub extern "C" fn {{ func.ffi_func().name() }}(
     // If the provided function does not match the signature specified in the UDL
     // then this attempt to call it will not compile, and will give guidance as to why.
     uniffi::deps::log::debug!("{{ func.ffi_func().name() }}");
+    {%- if func.is_async() %}
+    {% call rs::to_rs_async_function_call(func) %}
+    {%- else %}
     {% call rs::to_rs_function_call(func) %}
+    {%- endif %}
 }
@@ -8,8 +8,12 @@
 
 {%- macro _arg_list_rs_call(func) %}
     {%- for arg in func.full_arguments() %}
+        {%- if arg.is_borrowed_bytes() %}
+        {{- arg.name() }}.as_slice()
+        {%- else %}
         {%- if arg.by_ref() %}&{% endif %}
         {{- arg.type_()|ffi_converter }}::try_lift({{ arg.name() }}).unwrap()
+        {%- endif %}
         {%- if !loop.last %}, {% endif %}
     {%- endfor %}
 {%- endmacro -%}
@@ -29,7 +33,7 @@
     {{- prefix -}}
     {%- if meth.arguments().len() > 0 %}, {# whitespace #}
         {%- for arg in meth.arguments() %}
-            {{- arg.name() }}: {{ arg.type_()|type_rs -}}{% if loop.last %}{% else %},{% endif %}
+            {{- arg.name() }}: {% if arg.by_ref() %}&{% endif %}{{ arg.type_()|type_rs -}}{% if loop.last %}{% else %},{% endif %}
         {%- endfor %}
     {%- endif %}
 {%- endmacro -%}
@@ -41,7 +45,15 @@
 {% macro ret(func) %}{% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|ffi_converter }}::lower(_retval){% else %}_retval{% endmatch %}{% endmacro %}
 
 {% macro construct(obj, cons) %}
+    {%- if obj.is_record_by_handle() %}
+    {{- obj.name() }} {
+        {%- for arg in cons.arguments() %}
+        {{ arg.name() }}: {{ arg.type_()|ffi_converter }}::try_lift({{ arg.name() }}).unwrap(),
+        {%- endfor %}
+    }
+    {%- else %}
     {{- obj.name() }}::{% call to_rs_call(cons) -%}
+    {%- endif %}
 {% endmacro %}
 
 {% macro to_rs_constructor_call(obj, cons) %}
@@ -100,3 +112,39 @@ uniffi::call_with_output(call_status, || {
 })
 {% endmatch %}
 {% endmacro %}
+
+{#
+// For async functions, we get a future from the rust function and hand it over to `uniffi::run_future`,
+// along with a closure that writes a status code and the serialized result for the foreign callback.
+#}
+{% macro to_rs_async_function_call(func) %}
+uniffi::call_with_output(call_status, || {
+    uniffi::run_future(
+        {% call to_rs_call(func) %},
+        {%- match func.throws_type() %}
+        {%- when Some with (e) %}
+        |_output, buf| {
+            match _output.map_err(Into::into) {
+                Ok(_retval) => {
+                    uniffi::deps::bytes::BufMut::put_i8(buf, uniffi::CALLBACK_SUCCESS);
+                    {% call write_ret(func) %}
+                }
+                Err(err) => {
+                    uniffi::deps::bytes::BufMut::put_i8(buf, uniffi::CALLBACK_ERROR);
+                    {{ e|ffi_converter }}::write(err, buf);
+                }
+            }
+        },
+        {%- else %}
+        |_retval{% if func.return_type().is_none() %}: (){% endif %}, buf| {
+            uniffi::deps::bytes::BufMut::put_i8(buf, uniffi::CALLBACK_SUCCESS);
+            {% call write_ret(func) %}
+        },
+        {%- endmatch %}
+        uniffi_callback,
+        uniffi_callback_data,
+    )
+})
+{% endmacro %}
+
+{% macro write_ret(func) %}{% match func.return_type() %}{% when Some with (return_type) %}{{ return_type|ffi_converter }}::write(_retval, buf);{% else %}{% endmatch %}{% endmacro %}
@@ -40,6 +40,11 @@ uniffi::assert_compatible_version!("{{ uniffi_version }}"); // Please check that
 {% include "CallbackInterfaceTemplate.rs" %}
 {% endfor %}
 
+// Streams of values returned from Rust, corresponding to `[Stream]` typedefs in the UDL.
+{% for stream in ci.iter_stream_types() %}
+{% include "StreamTemplate.rs" %}
+{% endfor %}
+
 // External and Wrapped types
 {% include "ExternalTypesTemplate.rs" %}
 
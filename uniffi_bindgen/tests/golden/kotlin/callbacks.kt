@@ -0,0 +1,813 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+@file:OptIn(ExperimentalUnsignedTypes::class)
+
+package uniffi.callbacks;
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import java.util.concurrent.atomic.AtomicLong
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicReference
+import java.util.concurrent.locks.ReentrantLock
+import kotlin.concurrent.withLock
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.resume
+import kotlin.coroutines.suspendCoroutine
+
+// Common helper code.
+//
+// Ideally this would live in a separate .kt file where it can be unittested etc
+// in isolation, and perhaps even published as a re-useable package.
+//
+// However, it's important that the detils of how this helper code works (e.g. the
+// way that different builtin types are passed across the FFI) exactly match what's
+// expected by the Rust code on the other side of the interface. In practice right
+// now that means coming from the exact some version of `uniffi` that was used to
+// compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
+// helpers directly inline like we're doing here.
+
+// This is a helper for safely working with byte buffers returned from the Rust code.
+// A rust-owned buffer is represented by its capacity, its current length, and a
+// pointer to the underlying data.
+
+@Structure.FieldOrder("capacity", "len", "data")
+public open class RustBuffer : Structure() {
+    @JvmField var capacity: Int = 0
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : RustBuffer(), Structure.ByValue
+    class ByReference : RustBuffer(), Structure.ByReference
+
+    companion object {
+        internal fun alloc(size: Int = 0) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_callbacks_CHECKSUM_rustbuffer_alloc(size, status)
+        }
+
+        internal fun free(buf: RustBuffer.ByValue) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_callbacks_CHECKSUM_rustbuffer_free(buf, status)
+        }
+
+        internal fun reserve(buf: RustBuffer.ByValue, additional: Int) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_callbacks_CHECKSUM_rustbuffer_reserve(buf, additional, status)
+        }
+    }
+
+    @Suppress("TooGenericExceptionThrown")
+    fun asByteBuffer() =
+        this.data?.getByteBuffer(0, this.len.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+}
+
+
+// This is a helper for safely passing byte references into the rust code.
+// We use it for `[ByRef] bytes` arguments, which the rust code reads in place
+// for the duration of the call. JNA can't give us a direct pointer to the contents of a
+// `ByteArray`, so we copy it into native memory first, but that still saves the cost of
+// writing it into a `RustBuffer` and then copying it out again on the rust side.
+
+@Structure.FieldOrder("len", "data")
+public open class ForeignBytes : Structure() {
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        // The native memory is owned by the returned struct, which keeps it alive
+        // until the struct itself is garbage-collected after the call.
+        internal fun lower(v: ByteArray): ForeignBytes.ByValue {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = v.size
+            if (v.isNotEmpty()) {
+                val mem = com.sun.jna.Memory(v.size.toLong())
+                mem.write(0, v, 0, v.size)
+                bytes.data = mem
+            }
+            return bytes
+        }
+    }
+}
+
+
+
+// A helper for structured writing of data into a `RustBuffer`.
+// This is very similar to `java.nio.ByteBuffer` but it knows how to grow
+// the underlying `RustBuffer` on demand.
+//
+// TODO: we should benchmark writing things into a `RustBuffer` versus building
+// up a bytearray and then copying it across.
+
+public class RustBufferBuilder() {
+    var rbuf = RustBuffer.ByValue()
+    var bbuf: ByteBuffer? = null
+
+    init {
+        // This defaults to a totally arbitrary initial size, but can be set using the
+        // `rust_buffer_initial_capacity` config option.
+        val rbuf = RustBuffer.alloc(16)
+        rbuf.writeField("len", 0)
+        this.setRustBuffer(rbuf)
+    }
+
+    internal fun setRustBuffer(rbuf: RustBuffer.ByValue) {
+        this.rbuf = rbuf
+        this.bbuf = this.rbuf.data?.getByteBuffer(0, this.rbuf.capacity.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+            it.position(rbuf.len)
+        }
+    }
+
+    fun finalize() : RustBuffer.ByValue {
+        val rbuf = this.rbuf
+        // Ensure that the JVM-level field is written through to native memory
+        // before turning the buffer, in case its recipient uses it in a context
+        // JNA doesn't apply its automatic synchronization logic.
+        rbuf.writeField("len", this.bbuf!!.position())
+        this.setRustBuffer(RustBuffer.ByValue())
+        return rbuf
+    }
+
+    fun discard() {
+        val rbuf = this.finalize()
+        RustBuffer.free(rbuf)
+    }
+
+    internal fun reserve(size: Int, write: (ByteBuffer) -> Unit) {
+        // TODO: this will perform two checks to ensure we're not overflowing the buffer:
+        // one here where we check if it needs to grow, and another when we call a write
+        // method on the ByteBuffer. It might be cheaper to use exception-driven control-flow
+        // here, trying the write and growing if it throws a `BufferOverflowException`.
+        // Benchmarking needed.
+        if (this.bbuf!!.position() + size > this.rbuf.capacity) {
+            rbuf.writeField("len", this.bbuf!!.position())
+            this.setRustBuffer(RustBuffer.reserve(this.rbuf, size))
+        }
+        write(this.bbuf!!)
+    }
+
+    fun putByte(v: Byte) {
+        this.reserve(1) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+
+    fun putShort(v: Short) {
+        this.reserve(2) { bbuf ->
+            bbuf.putShort(v)
+        }
+    }
+
+    fun putInt(v: Int) {
+        this.reserve(4) { bbuf ->
+            bbuf.putInt(v)
+        }
+    }
+
+    fun putLong(v: Long) {
+        this.reserve(8) { bbuf ->
+            bbuf.putLong(v)
+        }
+    }
+
+    fun putFloat(v: Float) {
+        this.reserve(4) { bbuf ->
+            bbuf.putFloat(v)
+        }
+    }
+
+    fun putDouble(v: Double) {
+        this.reserve(8) { bbuf ->
+            bbuf.putDouble(v)
+        }
+    }
+
+    fun put(v: ByteArray) {
+        this.reserve(v.size) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+}
+
+// Helpers for reading primitive data types from a bytebuffer.
+
+internal fun<T> liftFromRustBuffer(rbuf: RustBuffer.ByValue, readItem: (ByteBuffer) -> T): T {
+    val buf = rbuf.asByteBuffer()!!
+    try {
+       val item = readItem(buf)
+       if (buf.hasRemaining()) {
+           throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+       }
+       return item
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+
+// The `read` helpers for compound values wrap their bodies in this, so that if the buffer from
+// Rust is shorter than we expect (say because the Rust code was built from a different version
+// of the component), we report which type we were reading rather than a bare NIO exception.
+// Nested values are checked too, so the innermost type that ran off the end gets named.
+internal inline fun<T> readChecked(typeName: String, read: () -> T): T {
+    try {
+        return read()
+    } catch (e: java.nio.BufferUnderflowException) {
+        throw InternalException("buffer overrun reading $typeName")
+    }
+}
+
+
+// This is the only place that compound values allocate a `RustBuffer` when they're lowered.
+// Their `write` helpers write any nested values straight into the same builder, so lowering
+// a deeply nested record still allocates just the one buffer (growing it as needed).
+internal fun<T> lowerIntoRustBuffer(v: T, writeItem: (T, RustBufferBuilder) -> Unit): RustBuffer.ByValue {
+    // TODO: maybe we can calculate some sort of initial size hint?
+    val buf = RustBufferBuilder()
+    try {
+        writeItem(v, buf)
+        return buf.finalize()
+    } catch (e: Throwable) {
+        buf.discard()
+        throw e
+    }
+}
+
+
+// For every type used in the interface, we provide helper methods for conveniently
+// lifting and lowering that type from C-compatible data, and for reading and writing
+// values of that type in a buffer.
+
+
+
+
+@ExperimentalUnsignedTypes
+internal fun UInt.Companion.lift(v: Int): UInt {
+    return v.toUInt()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.Companion.read(buf: ByteBuffer): UInt {
+    return UInt.lift(buf.getInt())
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.lower(): Int {
+    return this.toInt()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.write(buf: RustBufferBuilder) {
+    buf.putInt(this.toInt())
+}
+
+
+
+
+
+internal fun Boolean.Companion.lift(v: Byte): Boolean {
+    return v.toInt() != 0
+}
+
+internal fun Boolean.Companion.read(buf: ByteBuffer): Boolean {
+    return Boolean.lift(buf.get())
+}
+
+internal fun Boolean.lower(): Byte {
+    return if (this) 1.toByte() else 0.toByte()
+}
+
+internal fun Boolean.write(buf: RustBufferBuilder) {
+    buf.putByte(this.lower())
+}
+
+
+
+
+
+internal fun decodeUtf8(byteArr: ByteArray): String {
+    return byteArr.toString(Charsets.UTF_8)
+}
+
+internal fun String.Companion.lift(rbuf: RustBuffer.ByValue): String {
+    try {
+        val byteArr = ByteArray(rbuf.len)
+        rbuf.asByteBuffer()!!.get(byteArr)
+        return decodeUtf8(byteArr)
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+internal fun String.Companion.read(buf: ByteBuffer): String {
+    return readChecked("String") {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return decodeUtf8(byteArr)
+    }
+}
+
+internal fun String.lower(): RustBuffer.ByValue {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    // Ideally we'd pass these bytes to `ffi_bytebuffer_from_bytes`, but doing so would require us
+    // to copy them into a JNA `Memory`. So we might as well directly copy them into a `RustBuffer`.
+    val rbuf = RustBuffer.alloc(byteArr.size)
+    rbuf.asByteBuffer()!!.put(byteArr)
+    return rbuf
+}
+
+internal fun String.write(buf: RustBufferBuilder) {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    buf.putInt(byteArr.size)
+    buf.put(byteArr)
+}
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+@Synchronized
+public fun findLibraryName(componentName: String): String {
+    val libOverride = System.getProperty("uniffi.component.${componentName}.libraryOverride")
+    if (libOverride != null) {
+        return libOverride
+    }
+    return "uniffi_callbacks"
+}
+
+public inline fun <reified Lib : Library> loadIndirect(
+    componentName: String
+): Lib {
+    return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
+}
+
+// A JNA Library to expose the extern-C FFI definitions.
+// This is an implementation detail which will be called internally by the public API.
+
+internal interface _UniFFILib : Library {
+    companion object {
+        internal val INSTANCE: _UniFFILib by lazy { 
+            loadIndirect<_UniFFILib>(componentName = "callbacks")
+            .also { lib: _UniFFILib ->
+                CallbackInterfaceListenerInternals.register(lib)
+                }
+            
+        }
+    }
+
+    fun ffi_callbacks_CHECKSUM_Listener_init_callback(callback_stub: ForeignCallback,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun callbacks_CHECKSUM_notify(listener: Long,message: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun ffi_callbacks_CHECKSUM_rustbuffer_alloc(size: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_callbacks_CHECKSUM_rustbuffer_from_bytes(bytes: ForeignBytes.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_callbacks_CHECKSUM_rustbuffer_free(buf: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun ffi_callbacks_CHECKSUM_rustbuffer_reserve(buf: RustBuffer.ByValue,additional: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    
+}
+
+// A handful of classes and functions to support the generated data structures.
+// This would be a good candidate for isolating in its own ffi-support lib.
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+internal typealias Handle = Long
+internal class ConcurrentHandleMap<T>(
+    private val leftMap: MutableMap<Handle, T> = mutableMapOf(),
+    private val rightMap: MutableMap<T, Handle> = mutableMapOf()
+) {
+    private val lock = java.util.concurrent.locks.ReentrantLock()
+    private val currentHandle = AtomicLong(0L)
+    private val stride = 1L
+
+    fun insert(obj: T): Handle =
+        lock.withLock {
+            rightMap[obj] ?:
+                currentHandle.getAndAdd(stride)
+                    .also { handle ->
+                        leftMap[handle] = obj
+                        rightMap[obj] = handle
+                    }
+            }
+
+    fun <R> callWithResult(handle: Handle, fn: (T) -> R): R =
+        lock.withLock {
+            leftMap[handle] ?: throw RuntimeException("Panic: handle not in handlemap")
+        }.let { obj ->
+            fn.invoke(obj)
+        }
+
+    // Unlike `insert`, this always hands out a new handle, even if the object is already in the map.
+    fun insertUnique(obj: T): Handle =
+        lock.withLock {
+            currentHandle.getAndAdd(stride)
+                .also { handle ->
+                    leftMap[handle] = obj
+                    if (!rightMap.containsKey(obj)) {
+                        rightMap[obj] = handle
+                    }
+                }
+            }
+
+    fun get(handle: Handle) = lock.withLock {
+        leftMap[handle]
+    }
+
+    fun delete(handle: Handle) { 
+        this.remove(handle)
+    }
+
+    fun remove(handle: Handle): T? =
+        lock.withLock {
+            leftMap.remove(handle)?.let { obj ->
+                if (rightMap[obj] == handle) {
+                    rightMap.remove(obj)
+                }
+                obj
+            }
+        }
+}
+
+public interface ForeignCallback : com.sun.jna.Callback {
+    public fun invoke(handle: Long, method: Int, args: RustBuffer.ByValue): RustBuffer.ByValue
+}
+
+// Magic number for the Rust proxy to call using the same mechanism as every other method,
+// to free the callback once it's dropped by Rust.
+internal const val IDX_CALLBACK_FREE = 0
+
+internal abstract class CallbackInternals<CallbackInterface>(
+    val foreignCallback: ForeignCallback
+) {
+    val handleMap = ConcurrentHandleMap<CallbackInterface>()
+
+    // Registers the foreign callback with the Rust side.
+    // This method is generated for each callback interface.
+    abstract fun register(lib: _UniFFILib)
+
+    fun drop(handle: Long): RustBuffer.ByValue {
+        return handleMap.remove(handle).let { RustBuffer.ByValue() }
+    }
+
+    // Rust only passes a callback back to us if we gave it to Rust in the first place, and it hands
+    // its handle back to us when it does, so this returns the original object and frees the handle.
+    fun lift(n: Long): CallbackInterface =
+        handleMap.remove(n) ?: throw InternalException("Callback handle not found; this is a Uniffi bug")
+
+    fun read(buf: ByteBuffer) = lift(buf.getLong())
+
+    fun lower(v: CallbackInterface) =
+        handleMap.insert(v).also {
+            assert(handleMap.get(it) === v) { "Handle map is not returning the object we just placed there. This is a bug in the HandleMap." }
+        }
+
+    fun write(v: CallbackInterface, buf: RustBufferBuilder) =
+        buf.putLong(lower(v))
+
+    // Lends a callback to Rust for a single call, for `[ByRef]` arguments. It gets a handle of
+    // its own, so that a re-entrant call lending the same object can't unregister it early, and
+    // the handle is invalid as soon as the call returns.
+    inline fun <R> lend(v: CallbackInterface, call: (Handle) -> R): R {
+        val handle = handleMap.insertUnique(v)
+        try {
+            return call(handle)
+        } finally {
+            handleMap.remove(handle)
+        }
+    }
+}
+
+
+
+// Status codes at the start of the buffer returned by a callback method that can fail, or passed
+// to the callback for an async function, which must match the ones in the `uniffi` crate.
+internal const val CALLBACK_SUCCESS: Byte = 0
+internal const val CALLBACK_ERROR: Byte = 1
+internal const val CALLBACK_UNEXPECTED_ERROR: Byte = 2
+
+
+
+
+
+
+
+
+// Public interface members begin here.
+// Custom types
+
+
+// Type aliases
+
+
+// Constants
+
+// Public facing enums
+
+
+// Error definitions
+@Structure.FieldOrder("code", "error_buf")
+internal open class RustCallStatus : Structure() {
+    @JvmField var code: Int = 0
+    @JvmField var error_buf: RustBuffer.ByValue = RustBuffer.ByValue()
+
+    fun isSuccess(): Boolean {
+        return code == 0
+    }
+
+    fun isError(): Boolean {
+        return code == 1
+    }
+
+    fun isPanic(): Boolean {
+        return code == 2
+    }
+}
+
+public class InternalException(message: String) : Exception(message)
+
+// Each top-level error class has a companion object that can lift the error from the call status's rust buffer
+public interface CallStatusErrorHandler<E> {
+    fun lift(error_buf: RustBuffer.ByValue): E;
+}
+
+// Error ListenerError
+
+
+public sealed class ListenerException(): Exception() {
+
+    // Each variant is a nested class
+    
+    
+    
+    class Rejected(
+        val reason: String 
+        ) : ListenerException() {
+        // Rich errors don't carry a message from Rust, so describe the variant by its fields instead.
+        override val message
+            get() = "reason=${ reason }"
+    }
+    
+
+    companion object ErrorHandler : CallStatusErrorHandler<ListenerException> {
+        override fun lift(error_buf: RustBuffer.ByValue): ListenerException {
+            return liftFromRustBuffer(error_buf) { error_buf -> read(error_buf) }
+        }
+
+        fun read(error_buf: ByteBuffer): ListenerException {
+            return readChecked("ListenerException") {
+                
+                
+
+                return when(error_buf.getInt()) {
+                    1 -> ListenerException.Rejected(
+                        String.read(error_buf)
+                        )
+                    else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+
+    // This is used to send errors thrown by callback interface implementations back to Rust.
+    internal fun write(buf: RustBufferBuilder) {
+        when(this) {
+            is ListenerException.Rejected -> {
+                buf.putInt(1)
+                (this.reason).write(buf)
+                
+            }
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+
+    
+    
+}
+
+
+// Helpers for calling Rust
+// In practice we usually need to be synchronized to call this safely, so it doesn't
+// synchronize itself
+
+// Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
+internal inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+    var status = RustCallStatus();
+    val return_value = callback(status)
+    if (status.isSuccess()) {
+        return return_value
+    } else if (status.isError()) {
+        throw errorHandler.lift(status.error_buf)
+    } else if (status.isPanic()) {
+        // when the rust code sees a panic, it tries to construct a rustbuffer
+        // with the message.  but if that code panics, then it just sends back
+        // an empty buffer.
+        if (status.error_buf.len > 0) {
+            throw InternalException(String.lift(status.error_buf))
+        } else {
+            throw InternalException("Rust panic")
+        }
+    } else {
+        throw InternalException("Unknown rust call status: $status.code")
+    }
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+public object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+    override fun lift(error_buf: RustBuffer.ByValue): InternalException {
+        RustBuffer.free(error_buf)
+        return InternalException("Unexpected CALL_ERROR")
+    }
+}
+
+// Call a rust function that returns a plain value
+internal inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback);
+}
+// Public facing records
+
+// Namespace functions
+
+
+
+
+
+@ExperimentalUnsignedTypes
+
+public fun notify(listener: Listener, message: String) {
+    
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.callbacks_CHECKSUM_notify(CallbackInterfaceListenerInternals.lower(listener), message.lower(),status)
+}
+}
+
+
+// Objects
+
+
+// Callback Interfaces
+
+
+/**
+ * Receives messages sent by `notify`.
+ */
+public interface Listener {
+    /**
+     * Called once for each message.
+     *
+     * @param message
+     */
+    fun onMessage(message: String)
+    fun shouldStop(count: UInt): Boolean
+    @Throws(ListenerException::class)
+    fun onError(reason: String)
+    
+}
+
+
+internal class CallbackInterfaceListenerFFI : ForeignCallback {
+    @Suppress("TooGenericExceptionCaught")
+    override fun invoke(handle: Long, method: Int, args: RustBuffer.ByValue): RustBuffer.ByValue {
+        return CallbackInterfaceListenerInternals.handleMap.callWithResult(handle) { cb -> 
+            when (method) {
+                IDX_CALLBACK_FREE -> CallbackInterfaceListenerInternals.drop(handle)
+                1 -> this.invokeOnMessage(cb, args)
+                2 -> this.invokeShouldStop(cb, args)
+                3 -> this.invokeOnError(cb, args)
+                
+                // This should never happen, because an out of bounds method index won't
+                // ever be used. Once we can catch errors, we should return an InternalException.
+                // https://github.com/mozilla/uniffi-rs/issues/351
+                else -> RustBuffer.ByValue()
+            }
+        }
+    }
+
+    
+    private fun invokeOnMessage(kotlinCallbackInterface: Listener, args: RustBuffer.ByValue): RustBuffer.ByValue =
+        try {
+            val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a Uniffi bug")
+            kotlinCallbackInterface.onMessage(
+                    String.read(buf) 
+                    )
+            .let { RustBuffer.ByValue() }
+                // TODO catch errors and report them back to Rust. 
+                // https://github.com/mozilla/uniffi-rs/issues/351
+        } finally {
+            RustBuffer.free(args)
+        }
+
+    
+    private fun invokeShouldStop(kotlinCallbackInterface: Listener, args: RustBuffer.ByValue): RustBuffer.ByValue =
+        try {
+            val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a Uniffi bug")
+            kotlinCallbackInterface.shouldStop(
+                    UInt.read(buf) 
+                    )
+            .let { rval -> 
+                    val rbuf = RustBufferBuilder()
+                    rval.write(rbuf) 
+                    rbuf.finalize()
+                }
+                // TODO catch errors and report them back to Rust. 
+                // https://github.com/mozilla/uniffi-rs/issues/351
+        } finally {
+            RustBuffer.free(args)
+        }
+
+    
+    @Suppress("TooGenericExceptionCaught")
+    private fun invokeOnError(kotlinCallbackInterface: Listener, args: RustBuffer.ByValue): RustBuffer.ByValue {
+        try {
+             try {
+                val buf = args.asByteBuffer() ?: throw InternalException("No ByteBuffer in RustBuffer; this is a Uniffi bug")
+                kotlinCallbackInterface.onError(
+                    String.read(buf) 
+                    )
+            } catch (e: ListenerException) {
+                // Errors declared in the UDL are sent back to Rust, to be returned as an `Err`.
+                val rbuf = RustBufferBuilder()
+                rbuf.putByte(CALLBACK_ERROR)
+                e.write(rbuf)
+                return rbuf.finalize()
+            } catch (e: Throwable) {
+                // Anything else can't be represented in Rust, so we send back a description
+                // of what went wrong and the Rust side will panic.
+                val rbuf = RustBufferBuilder()
+                rbuf.putByte(CALLBACK_UNEXPECTED_ERROR)
+                e.toString().write(rbuf)
+                return rbuf.finalize()
+            }
+            val rbuf = RustBufferBuilder()
+            rbuf.putByte(CALLBACK_SUCCESS)
+            return rbuf.finalize()
+        } finally {
+            RustBuffer.free(args)
+        }
+    }
+
+    
+}
+
+internal object CallbackInterfaceListenerInternals: CallbackInternals<Listener>(
+    foreignCallback = CallbackInterfaceListenerFFI()
+) {
+    override fun register(lib: _UniFFILib) {
+        rustCall() { status ->
+            lib.ffi_callbacks_CHECKSUM_Listener_init_callback(this.foreignCallback, status)
+        }
+    }
+}
+
+
@@ -0,0 +1,733 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+
+package uniffi.enums;
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import java.util.concurrent.atomic.AtomicLong
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicReference
+import java.util.concurrent.locks.ReentrantLock
+import kotlin.concurrent.withLock
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.resume
+import kotlin.coroutines.suspendCoroutine
+
+// Common helper code.
+//
+// Ideally this would live in a separate .kt file where it can be unittested etc
+// in isolation, and perhaps even published as a re-useable package.
+//
+// However, it's important that the detils of how this helper code works (e.g. the
+// way that different builtin types are passed across the FFI) exactly match what's
+// expected by the Rust code on the other side of the interface. In practice right
+// now that means coming from the exact some version of `uniffi` that was used to
+// compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
+// helpers directly inline like we're doing here.
+
+// This is a helper for safely working with byte buffers returned from the Rust code.
+// A rust-owned buffer is represented by its capacity, its current length, and a
+// pointer to the underlying data.
+
+@Structure.FieldOrder("capacity", "len", "data")
+public open class RustBuffer : Structure() {
+    @JvmField var capacity: Int = 0
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : RustBuffer(), Structure.ByValue
+    class ByReference : RustBuffer(), Structure.ByReference
+
+    companion object {
+        internal fun alloc(size: Int = 0) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_enums_CHECKSUM_rustbuffer_alloc(size, status)
+        }
+
+        internal fun free(buf: RustBuffer.ByValue) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_enums_CHECKSUM_rustbuffer_free(buf, status)
+        }
+
+        internal fun reserve(buf: RustBuffer.ByValue, additional: Int) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_enums_CHECKSUM_rustbuffer_reserve(buf, additional, status)
+        }
+    }
+
+    @Suppress("TooGenericExceptionThrown")
+    fun asByteBuffer() =
+        this.data?.getByteBuffer(0, this.len.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+}
+
+
+// This is a helper for safely passing byte references into the rust code.
+// We use it for `[ByRef] bytes` arguments, which the rust code reads in place
+// for the duration of the call. JNA can't give us a direct pointer to the contents of a
+// `ByteArray`, so we copy it into native memory first, but that still saves the cost of
+// writing it into a `RustBuffer` and then copying it out again on the rust side.
+
+@Structure.FieldOrder("len", "data")
+public open class ForeignBytes : Structure() {
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        // The native memory is owned by the returned struct, which keeps it alive
+        // until the struct itself is garbage-collected after the call.
+        internal fun lower(v: ByteArray): ForeignBytes.ByValue {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = v.size
+            if (v.isNotEmpty()) {
+                val mem = com.sun.jna.Memory(v.size.toLong())
+                mem.write(0, v, 0, v.size)
+                bytes.data = mem
+            }
+            return bytes
+        }
+    }
+}
+
+
+
+// A helper for structured writing of data into a `RustBuffer`.
+// This is very similar to `java.nio.ByteBuffer` but it knows how to grow
+// the underlying `RustBuffer` on demand.
+//
+// TODO: we should benchmark writing things into a `RustBuffer` versus building
+// up a bytearray and then copying it across.
+
+public class RustBufferBuilder() {
+    var rbuf = RustBuffer.ByValue()
+    var bbuf: ByteBuffer? = null
+
+    init {
+        // This defaults to a totally arbitrary initial size, but can be set using the
+        // `rust_buffer_initial_capacity` config option.
+        val rbuf = RustBuffer.alloc(16)
+        rbuf.writeField("len", 0)
+        this.setRustBuffer(rbuf)
+    }
+
+    internal fun setRustBuffer(rbuf: RustBuffer.ByValue) {
+        this.rbuf = rbuf
+        this.bbuf = this.rbuf.data?.getByteBuffer(0, this.rbuf.capacity.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+            it.position(rbuf.len)
+        }
+    }
+
+    fun finalize() : RustBuffer.ByValue {
+        val rbuf = this.rbuf
+        // Ensure that the JVM-level field is written through to native memory
+        // before turning the buffer, in case its recipient uses it in a context
+        // JNA doesn't apply its automatic synchronization logic.
+        rbuf.writeField("len", this.bbuf!!.position())
+        this.setRustBuffer(RustBuffer.ByValue())
+        return rbuf
+    }
+
+    fun discard() {
+        val rbuf = this.finalize()
+        RustBuffer.free(rbuf)
+    }
+
+    internal fun reserve(size: Int, write: (ByteBuffer) -> Unit) {
+        // TODO: this will perform two checks to ensure we're not overflowing the buffer:
+        // one here where we check if it needs to grow, and another when we call a write
+        // method on the ByteBuffer. It might be cheaper to use exception-driven control-flow
+        // here, trying the write and growing if it throws a `BufferOverflowException`.
+        // Benchmarking needed.
+        if (this.bbuf!!.position() + size > this.rbuf.capacity) {
+            rbuf.writeField("len", this.bbuf!!.position())
+            this.setRustBuffer(RustBuffer.reserve(this.rbuf, size))
+        }
+        write(this.bbuf!!)
+    }
+
+    fun putByte(v: Byte) {
+        this.reserve(1) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+
+    fun putShort(v: Short) {
+        this.reserve(2) { bbuf ->
+            bbuf.putShort(v)
+        }
+    }
+
+    fun putInt(v: Int) {
+        this.reserve(4) { bbuf ->
+            bbuf.putInt(v)
+        }
+    }
+
+    fun putLong(v: Long) {
+        this.reserve(8) { bbuf ->
+            bbuf.putLong(v)
+        }
+    }
+
+    fun putFloat(v: Float) {
+        this.reserve(4) { bbuf ->
+            bbuf.putFloat(v)
+        }
+    }
+
+    fun putDouble(v: Double) {
+        this.reserve(8) { bbuf ->
+            bbuf.putDouble(v)
+        }
+    }
+
+    fun put(v: ByteArray) {
+        this.reserve(v.size) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+}
+
+// Helpers for reading primitive data types from a bytebuffer.
+
+internal fun<T> liftFromRustBuffer(rbuf: RustBuffer.ByValue, readItem: (ByteBuffer) -> T): T {
+    val buf = rbuf.asByteBuffer()!!
+    try {
+       val item = readItem(buf)
+       if (buf.hasRemaining()) {
+           throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+       }
+       return item
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+
+// The `read` helpers for compound values wrap their bodies in this, so that if the buffer from
+// Rust is shorter than we expect (say because the Rust code was built from a different version
+// of the component), we report which type we were reading rather than a bare NIO exception.
+// Nested values are checked too, so the innermost type that ran off the end gets named.
+internal inline fun<T> readChecked(typeName: String, read: () -> T): T {
+    try {
+        return read()
+    } catch (e: java.nio.BufferUnderflowException) {
+        throw InternalException("buffer overrun reading $typeName")
+    }
+}
+
+
+// This is the only place that compound values allocate a `RustBuffer` when they're lowered.
+// Their `write` helpers write any nested values straight into the same builder, so lowering
+// a deeply nested record still allocates just the one buffer (growing it as needed).
+internal fun<T> lowerIntoRustBuffer(v: T, writeItem: (T, RustBufferBuilder) -> Unit): RustBuffer.ByValue {
+    // TODO: maybe we can calculate some sort of initial size hint?
+    val buf = RustBufferBuilder()
+    try {
+        writeItem(v, buf)
+        return buf.finalize()
+    } catch (e: Throwable) {
+        buf.discard()
+        throw e
+    }
+}
+
+
+// For every type used in the interface, we provide helper methods for conveniently
+// lifting and lowering that type from C-compatible data, and for reading and writing
+// values of that type in a buffer.
+
+
+
+
+internal fun Double.Companion.lift(v: Double): Double {
+    return v
+}
+
+internal fun Double.Companion.read(buf: ByteBuffer): Double {
+    val v = buf.getDouble()
+    return v
+}
+
+internal fun Double.lower(): Double {
+    return this
+}
+
+internal fun Double.write(buf: RustBufferBuilder) {
+    buf.putDouble(this)
+}
+
+
+
+
+
+internal fun decodeUtf8(byteArr: ByteArray): String {
+    return byteArr.toString(Charsets.UTF_8)
+}
+
+internal fun String.Companion.lift(rbuf: RustBuffer.ByValue): String {
+    try {
+        val byteArr = ByteArray(rbuf.len)
+        rbuf.asByteBuffer()!!.get(byteArr)
+        return decodeUtf8(byteArr)
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+internal fun String.Companion.read(buf: ByteBuffer): String {
+    return readChecked("String") {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return decodeUtf8(byteArr)
+    }
+}
+
+internal fun String.lower(): RustBuffer.ByValue {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    // Ideally we'd pass these bytes to `ffi_bytebuffer_from_bytes`, but doing so would require us
+    // to copy them into a JNA `Memory`. So we might as well directly copy them into a `RustBuffer`.
+    val rbuf = RustBuffer.alloc(byteArr.size)
+    rbuf.asByteBuffer()!!.put(byteArr)
+    return rbuf
+}
+
+internal fun String.write(buf: RustBufferBuilder) {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    buf.putInt(byteArr.size)
+    buf.put(byteArr)
+}
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+@Synchronized
+public fun findLibraryName(componentName: String): String {
+    val libOverride = System.getProperty("uniffi.component.${componentName}.libraryOverride")
+    if (libOverride != null) {
+        return libOverride
+    }
+    return "uniffi_enums"
+}
+
+public inline fun <reified Lib : Library> loadIndirect(
+    componentName: String
+): Lib {
+    return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
+}
+
+// A JNA Library to expose the extern-C FFI definitions.
+// This is an implementation detail which will be called internally by the public API.
+
+internal interface _UniFFILib : Library {
+    companion object {
+        internal val INSTANCE: _UniFFILib by lazy { 
+            loadIndirect<_UniFFILib>(componentName = "enums")
+            
+            
+        }
+    }
+
+    fun ffi_enums_CHECKSUM_rustbuffer_alloc(size: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_enums_CHECKSUM_rustbuffer_from_bytes(bytes: ForeignBytes.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_enums_CHECKSUM_rustbuffer_free(buf: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun ffi_enums_CHECKSUM_rustbuffer_reserve(buf: RustBuffer.ByValue,additional: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    
+}
+
+// A handful of classes and functions to support the generated data structures.
+// This would be a good candidate for isolating in its own ffi-support lib.
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+// Public interface members begin here.
+// Custom types
+
+
+// Type aliases
+
+
+// Constants
+
+// Public facing enums
+
+
+
+
+
+/**
+ * A point of the compass.
+ */
+
+public enum class Direction {
+    
+    NORTH,
+    EAST,
+    SOUTH,
+    WEST;
+
+    companion object {
+        
+        internal fun lift(rbuf: RustBuffer.ByValue): Direction {
+            return liftFromRustBuffer(rbuf) { buf -> Direction.read(buf) }
+        }
+
+        internal fun read(buf: ByteBuffer): Direction {
+            return readChecked("Direction") {
+                return when(buf.getInt()) {
+                    1 -> NORTH
+                    2 -> EAST
+                    3 -> SOUTH
+                    4 -> WEST
+                    else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+
+    internal fun lower(): RustBuffer.ByValue {
+        return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
+    }
+
+    internal fun write(buf: RustBufferBuilder) {
+        // Variants can have explicit discriminants, so we can't rely on `ordinal` here.
+        val discriminant = when(this) {
+            NORTH -> 1
+            EAST -> 2
+            SOUTH -> 3
+            WEST -> 4
+        }
+        buf.putInt(discriminant)
+    }
+}
+
+
+
+
+
+
+
+public enum class Opcode {
+    
+    PING,
+    PONG,
+    CLOSE;
+
+    companion object {
+        
+        internal fun lift(rbuf: RustBuffer.ByValue): Opcode {
+            return liftFromRustBuffer(rbuf) { buf -> Opcode.read(buf) }
+        }
+
+        internal fun read(buf: ByteBuffer): Opcode {
+            return readChecked("Opcode") {
+                return when(buf.get().toInt() and 0xff) {
+                    1 -> PING
+                    2 -> PONG
+                    100 -> CLOSE
+                    else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+
+    internal fun lower(): RustBuffer.ByValue {
+        return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
+    }
+
+    internal fun write(buf: RustBufferBuilder) {
+        // Variants can have explicit discriminants, so we can't rely on `ordinal` here.
+        val discriminant = when(this) {
+            PING -> 1
+            PONG -> 2
+            CLOSE -> 100
+        }
+        buf.put(discriminant.toByte())
+    }
+}
+
+
+
+
+
+
+
+
+
+
+public sealed class Shape {
+    
+    object Point : Shape()
+    
+    
+    data class Circle(
+        val radius: Double 
+        ) : Shape()
+    
+    
+    data class Rectangle(
+        val width: Double, 
+        val height: Double 
+        ) : Shape()
+    
+
+    companion object {
+        internal fun lift(rbuf: RustBuffer.ByValue): Shape {
+            return liftFromRustBuffer(rbuf) { buf -> Shape.read(buf) }
+        }
+
+        internal fun read(buf: ByteBuffer): Shape {
+            return readChecked("Shape") {
+                return when(buf.getInt()) {
+                    1 -> Shape.Point
+                    2 -> Shape.Circle(
+                        Double.read(buf)
+                        )
+                    3 -> Shape.Rectangle(
+                        Double.read(buf),
+                        Double.read(buf)
+                        )
+                    else -> throw RuntimeException("invalid enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+
+    internal fun lower(): RustBuffer.ByValue {
+        return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
+    }
+
+    internal fun write(buf: RustBufferBuilder) {
+        when(this) {
+            is Shape.Point -> {
+                buf.putInt(1)
+                
+            }
+            is Shape.Circle -> {
+                buf.putInt(2)
+                (this.radius).write(buf)
+                
+            }
+            is Shape.Rectangle -> {
+                buf.putInt(3)
+                (this.width).write(buf)
+                (this.height).write(buf)
+                
+            }
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+
+    
+    
+}
+
+
+
+// Error definitions
+@Structure.FieldOrder("code", "error_buf")
+internal open class RustCallStatus : Structure() {
+    @JvmField var code: Int = 0
+    @JvmField var error_buf: RustBuffer.ByValue = RustBuffer.ByValue()
+
+    fun isSuccess(): Boolean {
+        return code == 0
+    }
+
+    fun isError(): Boolean {
+        return code == 1
+    }
+
+    fun isPanic(): Boolean {
+        return code == 2
+    }
+}
+
+public class InternalException(message: String) : Exception(message)
+
+// Each top-level error class has a companion object that can lift the error from the call status's rust buffer
+public interface CallStatusErrorHandler<E> {
+    fun lift(error_buf: RustBuffer.ByValue): E;
+}
+
+// Error ShapeError
+
+
+public sealed class ShapeException(): Exception() {
+
+    // Each variant is a nested class
+    
+    
+    
+    class InvalidSize(
+        val reason: String 
+        ) : ShapeException() {
+        // Rich errors don't carry a message from Rust, so describe the variant by its fields instead.
+        override val message
+            get() = "reason=${ reason }"
+    }
+    
+    class Unknown : ShapeException()
+    
+
+    companion object ErrorHandler : CallStatusErrorHandler<ShapeException> {
+        override fun lift(error_buf: RustBuffer.ByValue): ShapeException {
+            return liftFromRustBuffer(error_buf) { error_buf -> read(error_buf) }
+        }
+
+        fun read(error_buf: ByteBuffer): ShapeException {
+            return readChecked("ShapeException") {
+                
+                
+
+                return when(error_buf.getInt()) {
+                    1 -> ShapeException.InvalidSize(
+                        String.read(error_buf)
+                        )
+                    2 -> ShapeException.Unknown()
+                    else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+
+    // This is used to send errors thrown by callback interface implementations back to Rust.
+    internal fun write(buf: RustBufferBuilder) {
+        when(this) {
+            is ShapeException.InvalidSize -> {
+                buf.putInt(1)
+                (this.reason).write(buf)
+                
+            }
+            is ShapeException.Unknown -> {
+                buf.putInt(2)
+                
+            }
+        }.let { /* this makes the `when` an expression, which ensures it is exhaustive */ }
+    }
+
+    
+    
+}
+
+
+// Helpers for calling Rust
+// In practice we usually need to be synchronized to call this safely, so it doesn't
+// synchronize itself
+
+// Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
+internal inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+    var status = RustCallStatus();
+    val return_value = callback(status)
+    if (status.isSuccess()) {
+        return return_value
+    } else if (status.isError()) {
+        throw errorHandler.lift(status.error_buf)
+    } else if (status.isPanic()) {
+        // when the rust code sees a panic, it tries to construct a rustbuffer
+        // with the message.  but if that code panics, then it just sends back
+        // an empty buffer.
+        if (status.error_buf.len > 0) {
+            throw InternalException(String.lift(status.error_buf))
+        } else {
+            throw InternalException("Rust panic")
+        }
+    } else {
+        throw InternalException("Unknown rust call status: $status.code")
+    }
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+public object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+    override fun lift(error_buf: RustBuffer.ByValue): InternalException {
+        RustBuffer.free(error_buf)
+        return InternalException("Unexpected CALL_ERROR")
+    }
+}
+
+// Call a rust function that returns a plain value
+internal inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback);
+}
+// Public facing records
+
+// Namespace functions
+
+// Objects
+
+
+// Callback Interfaces
+
+
@@ -0,0 +1,1076 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+@file:OptIn(ExperimentalUnsignedTypes::class)
+
+package uniffi.objects;
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import java.util.concurrent.atomic.AtomicLong
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicReference
+import java.util.concurrent.locks.ReentrantLock
+import kotlin.concurrent.withLock
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.resume
+import kotlin.coroutines.suspendCoroutine
+
+// Common helper code.
+//
+// Ideally this would live in a separate .kt file where it can be unittested etc
+// in isolation, and perhaps even published as a re-useable package.
+//
+// However, it's important that the detils of how this helper code works (e.g. the
+// way that different builtin types are passed across the FFI) exactly match what's
+// expected by the Rust code on the other side of the interface. In practice right
+// now that means coming from the exact some version of `uniffi` that was used to
+// compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
+// helpers directly inline like we're doing here.
+
+// This is a helper for safely working with byte buffers returned from the Rust code.
+// A rust-owned buffer is represented by its capacity, its current length, and a
+// pointer to the underlying data.
+
+@Structure.FieldOrder("capacity", "len", "data")
+public open class RustBuffer : Structure() {
+    @JvmField var capacity: Int = 0
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : RustBuffer(), Structure.ByValue
+    class ByReference : RustBuffer(), Structure.ByReference
+
+    companion object {
+        internal fun alloc(size: Int = 0) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_objects_CHECKSUM_rustbuffer_alloc(size, status)
+        }
+
+        internal fun free(buf: RustBuffer.ByValue) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_objects_CHECKSUM_rustbuffer_free(buf, status)
+        }
+
+        internal fun reserve(buf: RustBuffer.ByValue, additional: Int) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_objects_CHECKSUM_rustbuffer_reserve(buf, additional, status)
+        }
+    }
+
+    @Suppress("TooGenericExceptionThrown")
+    fun asByteBuffer() =
+        this.data?.getByteBuffer(0, this.len.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+}
+
+
+// This is a helper for safely passing byte references into the rust code.
+// We use it for `[ByRef] bytes` arguments, which the rust code reads in place
+// for the duration of the call. JNA can't give us a direct pointer to the contents of a
+// `ByteArray`, so we copy it into native memory first, but that still saves the cost of
+// writing it into a `RustBuffer` and then copying it out again on the rust side.
+
+@Structure.FieldOrder("len", "data")
+public open class ForeignBytes : Structure() {
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        // The native memory is owned by the returned struct, which keeps it alive
+        // until the struct itself is garbage-collected after the call.
+        internal fun lower(v: ByteArray): ForeignBytes.ByValue {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = v.size
+            if (v.isNotEmpty()) {
+                val mem = com.sun.jna.Memory(v.size.toLong())
+                mem.write(0, v, 0, v.size)
+                bytes.data = mem
+            }
+            return bytes
+        }
+    }
+}
+
+
+
+// A helper for structured writing of data into a `RustBuffer`.
+// This is very similar to `java.nio.ByteBuffer` but it knows how to grow
+// the underlying `RustBuffer` on demand.
+//
+// TODO: we should benchmark writing things into a `RustBuffer` versus building
+// up a bytearray and then copying it across.
+
+public class RustBufferBuilder() {
+    var rbuf = RustBuffer.ByValue()
+    var bbuf: ByteBuffer? = null
+
+    init {
+        // This defaults to a totally arbitrary initial size, but can be set using the
+        // `rust_buffer_initial_capacity` config option.
+        val rbuf = RustBuffer.alloc(16)
+        rbuf.writeField("len", 0)
+        this.setRustBuffer(rbuf)
+    }
+
+    internal fun setRustBuffer(rbuf: RustBuffer.ByValue) {
+        this.rbuf = rbuf
+        this.bbuf = this.rbuf.data?.getByteBuffer(0, this.rbuf.capacity.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+            it.position(rbuf.len)
+        }
+    }
+
+    fun finalize() : RustBuffer.ByValue {
+        val rbuf = this.rbuf
+        // Ensure that the JVM-level field is written through to native memory
+        // before turning the buffer, in case its recipient uses it in a context
+        // JNA doesn't apply its automatic synchronization logic.
+        rbuf.writeField("len", this.bbuf!!.position())
+        this.setRustBuffer(RustBuffer.ByValue())
+        return rbuf
+    }
+
+    fun discard() {
+        val rbuf = this.finalize()
+        RustBuffer.free(rbuf)
+    }
+
+    internal fun reserve(size: Int, write: (ByteBuffer) -> Unit) {
+        // TODO: this will perform two checks to ensure we're not overflowing the buffer:
+        // one here where we check if it needs to grow, and another when we call a write
+        // method on the ByteBuffer. It might be cheaper to use exception-driven control-flow
+        // here, trying the write and growing if it throws a `BufferOverflowException`.
+        // Benchmarking needed.
+        if (this.bbuf!!.position() + size > this.rbuf.capacity) {
+            rbuf.writeField("len", this.bbuf!!.position())
+            this.setRustBuffer(RustBuffer.reserve(this.rbuf, size))
+        }
+        write(this.bbuf!!)
+    }
+
+    fun putByte(v: Byte) {
+        this.reserve(1) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+
+    fun putShort(v: Short) {
+        this.reserve(2) { bbuf ->
+            bbuf.putShort(v)
+        }
+    }
+
+    fun putInt(v: Int) {
+        this.reserve(4) { bbuf ->
+            bbuf.putInt(v)
+        }
+    }
+
+    fun putLong(v: Long) {
+        this.reserve(8) { bbuf ->
+            bbuf.putLong(v)
+        }
+    }
+
+    fun putFloat(v: Float) {
+        this.reserve(4) { bbuf ->
+            bbuf.putFloat(v)
+        }
+    }
+
+    fun putDouble(v: Double) {
+        this.reserve(8) { bbuf ->
+            bbuf.putDouble(v)
+        }
+    }
+
+    fun put(v: ByteArray) {
+        this.reserve(v.size) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+}
+
+// Helpers for reading primitive data types from a bytebuffer.
+
+internal fun<T> liftFromRustBuffer(rbuf: RustBuffer.ByValue, readItem: (ByteBuffer) -> T): T {
+    val buf = rbuf.asByteBuffer()!!
+    try {
+       val item = readItem(buf)
+       if (buf.hasRemaining()) {
+           throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+       }
+       return item
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+
+// The `read` helpers for compound values wrap their bodies in this, so that if the buffer from
+// Rust is shorter than we expect (say because the Rust code was built from a different version
+// of the component), we report which type we were reading rather than a bare NIO exception.
+// Nested values are checked too, so the innermost type that ran off the end gets named.
+internal inline fun<T> readChecked(typeName: String, read: () -> T): T {
+    try {
+        return read()
+    } catch (e: java.nio.BufferUnderflowException) {
+        throw InternalException("buffer overrun reading $typeName")
+    }
+}
+
+
+// This is the only place that compound values allocate a `RustBuffer` when they're lowered.
+// Their `write` helpers write any nested values straight into the same builder, so lowering
+// a deeply nested record still allocates just the one buffer (growing it as needed).
+internal fun<T> lowerIntoRustBuffer(v: T, writeItem: (T, RustBufferBuilder) -> Unit): RustBuffer.ByValue {
+    // TODO: maybe we can calculate some sort of initial size hint?
+    val buf = RustBufferBuilder()
+    try {
+        writeItem(v, buf)
+        return buf.finalize()
+    } catch (e: Throwable) {
+        buf.discard()
+        throw e
+    }
+}
+
+
+// For every type used in the interface, we provide helper methods for conveniently
+// lifting and lowering that type from C-compatible data, and for reading and writing
+// values of that type in a buffer.
+
+
+
+
+@ExperimentalUnsignedTypes
+internal fun UInt.Companion.lift(v: Int): UInt {
+    return v.toUInt()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.Companion.read(buf: ByteBuffer): UInt {
+    return UInt.lift(buf.getInt())
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.lower(): Int {
+    return this.toInt()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.write(buf: RustBufferBuilder) {
+    buf.putInt(this.toInt())
+}
+
+
+
+
+
+internal fun decodeUtf8(byteArr: ByteArray): String {
+    return byteArr.toString(Charsets.UTF_8)
+}
+
+internal fun String.Companion.lift(rbuf: RustBuffer.ByValue): String {
+    try {
+        val byteArr = ByteArray(rbuf.len)
+        rbuf.asByteBuffer()!!.get(byteArr)
+        return decodeUtf8(byteArr)
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+internal fun String.Companion.read(buf: ByteBuffer): String {
+    return readChecked("String") {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return decodeUtf8(byteArr)
+    }
+}
+
+internal fun String.lower(): RustBuffer.ByValue {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    // Ideally we'd pass these bytes to `ffi_bytebuffer_from_bytes`, but doing so would require us
+    // to copy them into a JNA `Memory`. So we might as well directly copy them into a `RustBuffer`.
+    val rbuf = RustBuffer.alloc(byteArr.size)
+    rbuf.asByteBuffer()!!.put(byteArr)
+    return rbuf
+}
+
+internal fun String.write(buf: RustBufferBuilder) {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    buf.putInt(byteArr.size)
+    buf.put(byteArr)
+}
+
+
+
+
+
+// Helper functions for pasing values of type ByteArray
+// These copy the bytes in bulk, rather than reading and writing each element separately.
+
+internal fun liftBytes(rbuf: RustBuffer.ByValue): ByteArray {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readBytes(buf)
+    }
+}
+
+internal fun readBytes(buf: ByteBuffer): ByteArray {
+    return readChecked("ByteArray") {
+        val len = buf.getInt()
+        if (len < 0) {
+            throw RuntimeException("invalid byte array length, something is very wrong!!")
+        }
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return byteArr
+    }
+}
+
+internal fun lowerBytes(v: ByteArray): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeBytes(v, buf)
+    }
+}
+
+internal fun writeBytes(v: ByteArray, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    buf.put(v)
+}
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type Counter?
+@ExperimentalUnsignedTypes
+internal fun liftOptionalTypeCounter(rbuf: RustBuffer.ByValue): Counter? {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readOptionalTypeCounter(buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun readOptionalTypeCounter(buf: ByteBuffer): Counter? {
+    return readChecked("Counter?") {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return Counter.read(buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun lowerOptionalTypeCounter(v: Counter?): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeOptionalTypeCounter(v, buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun writeOptionalTypeCounter(v: Counter?, buf: RustBufferBuilder) {
+    if (v == null) {
+        buf.putByte(0)
+    } else {
+        buf.putByte(1)
+        v.write(buf)
+    }
+}
+
+
+
+
+
+
+
+
+// Helper functions for receiving values of type kotlinx.coroutines.flow.Flow<String>, which are read from Rust
+// one at a time while the flow is being collected. The stream is freed once collection
+// finishes, whether or not it was read to the end, so the flow can only be collected once.
+
+
+internal fun liftStreamstring(handle: Long): kotlinx.coroutines.flow.Flow<String> {
+    val collected = AtomicBoolean(false)
+    return kotlinx.coroutines.flow.flow {
+        check(!collected.getAndSet(true)) { "A stream can only be collected once" }
+        try {
+            while (true) {
+                val next: List<String> = liftFromRustBuffer(rustCall() { status ->
+                    _UniFFILib.INSTANCE.ffi_objects_CHECKSUM_streamstring_next(handle, status)
+                }) { buf ->
+                    if (buf.get().toInt() == 0) listOf() else listOf(String.read(buf))
+                }
+                if (next.isEmpty()) {
+                    break
+                }
+                emit(next[0])
+            }
+        } finally {
+            rustCall() { status ->
+                _UniFFILib.INSTANCE.ffi_objects_CHECKSUM_streamstring_free(handle, status)
+            }
+        }
+    }
+}
+
+
+internal fun readStreamstring(buf: ByteBuffer): kotlinx.coroutines.flow.Flow<String> {
+    return readChecked("kotlinx.coroutines.flow.Flow<String>") {
+        return liftStreamstring(buf.getLong())
+    }
+}
+
+
+
+
+@Synchronized
+public fun findLibraryName(componentName: String): String {
+    val libOverride = System.getProperty("uniffi.component.${componentName}.libraryOverride")
+    if (libOverride != null) {
+        return libOverride
+    }
+    return "uniffi_objects"
+}
+
+public inline fun <reified Lib : Library> loadIndirect(
+    componentName: String
+): Lib {
+    return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
+}
+
+// A JNA Library to expose the extern-C FFI definitions.
+// This is an implementation detail which will be called internally by the public API.
+
+internal interface _UniFFILib : Library {
+    companion object {
+        internal val INSTANCE: _UniFFILib by lazy { 
+            loadIndirect<_UniFFILib>(componentName = "objects")
+            
+            
+        }
+    }
+
+    fun ffi_objects_CHECKSUM_Counter_object_free(ptr: Pointer,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun objects_CHECKSUM_Counter_new(initial: Int,
+    uniffi_out_err: RustCallStatus
+    ): Pointer
+
+    fun objects_CHECKSUM_Counter_with_limit(initial: Int,limit: Int,
+    uniffi_out_err: RustCallStatus
+    ): Pointer
+
+    fun objects_CHECKSUM_Counter_increment(ptr: Pointer,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun objects_CHECKSUM_Counter_value(ptr: Pointer,
+    uniffi_out_err: RustCallStatus
+    ): Int
+
+    fun objects_CHECKSUM_Counter_parent(ptr: Pointer,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun objects_CHECKSUM_count_later(delay_ms: Int,uniffi_callback: FutureCallback,uniffi_callback_data: Long,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun objects_CHECKSUM_current_count(
+    uniffi_out_err: RustCallStatus
+    ): Int
+
+    fun objects_CHECKSUM_checksum(data: ForeignBytes.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Int
+
+    fun objects_CHECKSUM_read_lines(path: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Long
+
+    fun ffi_objects_CHECKSUM_streamstring_next(handle: Long,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_objects_CHECKSUM_streamstring_free(handle: Long,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun ffi_objects_CHECKSUM_rustbuffer_alloc(size: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_objects_CHECKSUM_rustbuffer_from_bytes(bytes: ForeignBytes.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_objects_CHECKSUM_rustbuffer_free(buf: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun ffi_objects_CHECKSUM_rustbuffer_reserve(buf: RustBuffer.ByValue,additional: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    
+}
+
+// A handful of classes and functions to support the generated data structures.
+// This would be a good candidate for isolating in its own ffi-support lib.
+
+
+
+
+
+
+
+
+
+
+
+
+// Interface implemented by anything that can contain an object reference.
+//
+// Such types expose a `destroy()` method that must be called to cleanly
+// dispose of the contained objects. Failure to call this method may result
+// in memory leaks.
+//
+// The easiest way to ensure this method is called is to use the `.use`
+// helper method to execute a block and destroy the object at the end.
+public interface Disposable {
+    fun destroy()
+}
+
+public inline fun <T : Disposable?, R> T.use(block: (T) -> R) =
+    try {
+        block(this)
+    } finally {
+        try {
+            // N.B. our implementation is on the nullable type `Disposable?`.
+            this?.destroy()
+        } catch (e: Throwable) {
+            // swallow
+        }
+    }
+
+
+
+
+
+
+// The base class for all UniFFI Object types.
+//
+// This class provides core operations for working with the Rust `Arc<T>` pointer to
+// the live Rust struct on the other side of the FFI.
+//
+// There's some subtlety here, because we have to be careful not to operate on a Rust
+// struct after it has been dropped, and because we must expose a public API for freeing
+// the Kotlin wrapper object in lieu of reliable finalizers. The core requirements are:
+//
+//   * Each `FFIObject` instance holds an opaque pointer to the underlying Rust struct.
+//     Method calls need to read this pointer from the object's state and pass it in to
+//     the Rust FFI.
+//
+//   * When an `FFIObject` is no longer needed, its pointer should be passed to a
+//     special destructor function provided by the Rust FFI, which will drop the
+//     underlying Rust struct.
+//
+//   * Given an `FFIObject` instance, calling code is expected to call the special
+//     `destroy` method in order to free it after use, either by calling it explicitly
+//     or by using a higher-level helper like the `use` method. Failing to do so will
+//     leak the underlying Rust struct.
+//
+//   * We can't assume that calling code will do the right thing, and must be prepared
+//     to handle Kotlin method calls executing concurrently with or even after a call to
+//     `destroy`, and to handle multiple (possibly concurrent!) calls to `destroy`.
+//
+//   * We must never allow Rust code to operate on the underlying Rust struct after
+//     the destructor has been called, and must never call the destructor more than once.
+//     Doing so may trigger memory unsafety.
+//
+// If we try to implement this with mutual exclusion on access to the pointer, there is the
+// possibility of a race between a method call and a concurrent call to `destroy`:
+//
+//    * Thread A starts a method call, reads the value of the pointer, but is interrupted
+//      before it can pass the pointer over the FFI to Rust.
+//    * Thread B calls `destroy` and frees the underlying Rust struct.
+//    * Thread A resumes, passing the already-read pointer value to Rust and triggering
+//      a use-after-free.
+//
+// One possible solution would be to use a `ReadWriteLock`, with each method call taking
+// a read lock (and thus allowed to run concurrently) and the special `destroy` method
+// taking a write lock (and thus blocking on live method calls). However, we aim not to
+// generate methods with any hidden blocking semantics, and a `destroy` method that might
+// block if called incorrectly seems to meet that bar.
+//
+// So, we achieve our goals by giving each `FFIObject` an associated `AtomicLong` counter to track
+// the number of in-flight method calls, and an `AtomicBoolean` flag to indicate whether `destroy`
+// has been called. These are updated according to the following rules:
+//
+//    * The initial value of the counter is 1, indicating a live object with no in-flight calls.
+//      The initial value for the flag is false.
+//
+//    * At the start of each method call, we atomically check the counter.
+//      If it is 0 then the underlying Rust struct has already been destroyed and the call is aborted.
+//      If it is nonzero them we atomically increment it by 1 and proceed with the method call.
+//
+//    * At the end of each method call, we atomically decrement and check the counter.
+//      If it has reached zero then we destroy the underlying Rust struct.
+//
+//    * When `destroy` is called, we atomically flip the flag from false to true.
+//      If the flag was already true we silently fail.
+//      Otherwise we atomically decrement and check the counter.
+//      If it has reached zero then we destroy the underlying Rust struct.
+//
+// Astute readers may observe that this all sounds very similar to the way that Rust's `Arc<T>` works,
+// and indeed it is, with the addition of a flag to guard against multiple calls to `destroy`.
+//
+// The overall effect is that the underlying Rust struct is destroyed only when `destroy` has been
+// called *and* all in-flight method calls have completed, avoiding violating any of the expectations
+// of the underlying Rust code.
+//
+// In the future we may be able to replace some of this with automatic finalization logic, such as using
+// the new "Cleaner" functionaility in Java 9. The above scheme has been designed to work even if `destroy` is
+// invoked by garbage-collection machinery rather than by calling code (which by the way, it's apparently also
+// possible for the JVM to finalize an object while there is an in-flight call to one of its methods [1],
+// so there would still be some complexity here).
+//
+// Sigh...all of this for want of a robust finalization mechanism.
+//
+// [1] https://stackoverflow.com/questions/24376768/can-java-finalize-an-object-when-it-is-still-in-scope/24380219
+//
+public abstract class FFIObject(
+    protected val pointer: Pointer
+): Disposable, AutoCloseable {
+
+    val wasDestroyed = AtomicBoolean(false)
+    val callCounter = AtomicLong(1)
+
+    // Objects that Rust lends to a callback interface method for the duration of the call
+    // are still owned by Rust, so their pointer must never be freed from Kotlin.
+    internal val isBorrowed = AtomicBoolean(false)
+
+    open protected fun freeRustArcPtr() {
+        // To be overridden in subclasses.
+    }
+
+    override fun destroy() {
+        // Only allow a single call to this method.
+        // TODO: maybe we should log a warning if called more than once?
+        if (this.wasDestroyed.compareAndSet(false, true)) {
+            // This decrement always matches the initial count of 1 given at creation time.
+            if (this.callCounter.decrementAndGet() == 0L && !this.isBorrowed.get()) {
+                this.freeRustArcPtr()
+            }
+        }
+    }
+
+    @Synchronized
+    override fun close() {
+        this.destroy()
+    }
+
+    internal inline fun <R> callWithPointer(block: (ptr: Pointer) -> R): R {
+        // Check and increment the call counter, to keep the object alive.
+        // This needs a compare-and-set retry loop in case of concurrent updates.
+        do {
+            val c = this.callCounter.get()
+            if (c == 0L) {
+                throw IllegalStateException("${this.javaClass.simpleName} object has already been destroyed")
+            }
+            if (c == Long.MAX_VALUE) {
+                throw IllegalStateException("${this.javaClass.simpleName} call counter would overflow")
+            }
+        } while (! this.callCounter.compareAndSet(c, c + 1L))
+        // Now we can safely do the method call without the pointer being freed concurrently.
+        try {
+            return block(this.pointer)
+        } finally {
+            // This decrement aways matches the increment we performed above.
+            if (this.callCounter.decrementAndGet() == 0L && !this.isBorrowed.get()) {
+                this.freeRustArcPtr()
+            }
+        }
+    }
+}
+
+
+
+
+
+
+// Status codes at the start of the buffer returned by a callback method that can fail, or passed
+// to the callback for an async function, which must match the ones in the `uniffi` crate.
+internal const val CALLBACK_SUCCESS: Byte = 0
+internal const val CALLBACK_ERROR: Byte = 1
+internal const val CALLBACK_UNEXPECTED_ERROR: Byte = 2
+
+
+
+public interface FutureCallback : com.sun.jna.Callback {
+    public fun invoke(callbackData: Long, result: RustBuffer.ByValue)
+}
+
+// Keeps track of the coroutines that are suspended waiting for an async Rust function to complete,
+// and resumes them when Rust calls back with the result.
+//
+// This is a singleton so that JNA (which only keeps a weak reference to callback objects) can't
+// garbage-collect it while there are calls in flight.
+internal object FutureContinuations : FutureCallback {
+    private val continuations = java.util.concurrent.ConcurrentHashMap<Long, Continuation<RustBuffer.ByValue>>()
+    private val nextHandle = AtomicLong(0L)
+
+    fun insert(continuation: Continuation<RustBuffer.ByValue>): Long =
+        nextHandle.getAndIncrement().also { handle ->
+            continuations[handle] = continuation
+        }
+
+    fun remove(handle: Long) {
+        continuations.remove(handle)
+    }
+
+    override fun invoke(callbackData: Long, result: RustBuffer.ByValue) {
+        val continuation = continuations.remove(callbackData)
+        if (continuation != null) {
+            continuation.resume(result)
+        } else {
+            RustBuffer.free(result)
+        }
+    }
+}
+
+
+
+
+
+// Public interface members begin here.
+// Custom types
+
+
+// Type aliases
+
+
+// Constants
+
+
+@ExperimentalUnsignedTypes
+public const val MAX_COUNT: UInt = 1000u
+// Public facing enums
+
+
+// Error definitions
+@Structure.FieldOrder("code", "error_buf")
+internal open class RustCallStatus : Structure() {
+    @JvmField var code: Int = 0
+    @JvmField var error_buf: RustBuffer.ByValue = RustBuffer.ByValue()
+
+    fun isSuccess(): Boolean {
+        return code == 0
+    }
+
+    fun isError(): Boolean {
+        return code == 1
+    }
+
+    fun isPanic(): Boolean {
+        return code == 2
+    }
+}
+
+public class InternalException(message: String) : Exception(message)
+
+// Each top-level error class has a companion object that can lift the error from the call status's rust buffer
+public interface CallStatusErrorHandler<E> {
+    fun lift(error_buf: RustBuffer.ByValue): E;
+}
+
+// Error CounterError
+
+
+public sealed class CounterException(message: String): Exception(message) {
+        // Each variant is a nested class
+        // Flat enums carries a string error message, so no special implementation is necessary.
+        
+        class Overflow(message: String) : CounterException(message)
+        
+
+    companion object ErrorHandler : CallStatusErrorHandler<CounterException> {
+        override fun lift(error_buf: RustBuffer.ByValue): CounterException {
+            return liftFromRustBuffer(error_buf) { error_buf -> read(error_buf) }
+        }
+
+        fun read(error_buf: ByteBuffer): CounterException {
+            return readChecked("CounterException") {
+                
+                    return when(error_buf.getInt()) {
+                    1 -> CounterException.Overflow(String.read(error_buf))
+                    else -> throw RuntimeException("invalid error enum value, something is very wrong!!")
+                }
+            }
+        }
+    }
+
+    
+    
+}
+
+
+// Helpers for calling Rust
+// In practice we usually need to be synchronized to call this safely, so it doesn't
+// synchronize itself
+
+// Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
+internal inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+    var status = RustCallStatus();
+    val return_value = callback(status)
+    if (status.isSuccess()) {
+        return return_value
+    } else if (status.isError()) {
+        throw errorHandler.lift(status.error_buf)
+    } else if (status.isPanic()) {
+        // when the rust code sees a panic, it tries to construct a rustbuffer
+        // with the message.  but if that code panics, then it just sends back
+        // an empty buffer.
+        if (status.error_buf.len > 0) {
+            throw InternalException(String.lift(status.error_buf))
+        } else {
+            throw InternalException("Rust panic")
+        }
+    } else {
+        throw InternalException("Unknown rust call status: $status.code")
+    }
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+public object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+    override fun lift(error_buf: RustBuffer.ByValue): InternalException {
+        RustBuffer.free(error_buf)
+        return InternalException("Unexpected CALL_ERROR")
+    }
+}
+
+// Call a rust function that returns a plain value
+internal inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback);
+}
+// Public facing records
+
+// Namespace functions
+
+
+
+@ExperimentalUnsignedTypes
+
+public suspend fun countLater(delayMs: UInt): UInt {
+    val _result = suspendCoroutine<RustBuffer.ByValue> { continuation ->
+        val _handle = FutureContinuations.insert(continuation)
+    try {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.objects_CHECKSUM_count_later(delayMs.lower(), FutureContinuations, _handle, status)
+        }
+    } catch (e: Throwable) {
+        FutureContinuations.remove(_handle)
+        throw e
+    }
+    }
+    return liftFromRustBuffer(_result) { buf ->
+        when (buf.get()) {
+            CALLBACK_SUCCESS -> UInt.read(buf)
+            else -> throw InternalException(String.read(buf))
+        }
+    }
+}
+
+
+
+@ExperimentalUnsignedTypes
+
+@Deprecated("use Counter.value instead")
+public fun currentCount(): UInt {
+    val _retval = 
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_current_count(status)
+}
+    return UInt.lift(_retval)
+}
+
+
+
+
+@ExperimentalUnsignedTypes
+
+public fun checksum(data: ByteArray): UInt {
+    val _retval = 
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_checksum(ForeignBytes.lower(data),status)
+}
+    return UInt.lift(_retval)
+}
+
+
+
+
+
+
+public fun readLines(path: String): kotlinx.coroutines.flow.Flow<String> {
+    val _retval = 
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_read_lines(path.lower(),status)
+}
+    return liftStreamstring(_retval)
+}
+
+
+// Objects
+
+/**
+ * Counts upwards from an initial value.
+ */
+@ExperimentalUnsignedTypes
+
+public interface CounterInterface {
+    /**
+     * Adds one to the counter.
+     */
+    @Throws(CounterException::class)
+    fun increment()
+    fun value(): UInt
+    fun parent(): Counter?
+    
+}
+
+/**
+ * Counts upwards from an initial value.
+ */
+@ExperimentalUnsignedTypes
+
+public class Counter(
+    pointer: Pointer
+) : FFIObject(pointer), CounterInterface {
+    
+    constructor(initial: UInt) :
+        this(
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_Counter_new(initial.lower(),status)
+})
+
+    /**
+     * Disconnect the object from the underlying Rust object.
+     * 
+     * It can be called more than once, but once called, interacting with the object
+     * causes an `IllegalStateException`.
+     * 
+     * Clients **must** call this method once done with the object, or cause a memory leak.
+     */
+    override protected fun freeRustArcPtr() {
+        rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_objects_CHECKSUM_Counter_object_free(this.pointer, status)
+        }
+    }
+
+    internal fun lower(): Pointer = callWithPointer { it }
+
+    internal fun write(buf: RustBufferBuilder) {
+        // The Rust code always expects pointers written as 8 bytes,
+        // and will fail to compile if they don't fit.
+        buf.putLong(Pointer.nativeValue(this.lower()))
+    }
+
+    @Throws(CounterException::class)
+    override fun increment() {
+        callWithPointer {
+    rustCallWithError(CounterException) { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_Counter_increment(it,  status)
+}
+        }
+    }
+    
+    override fun value(): UInt =
+        callWithPointer {
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_Counter_value(it,  status)
+}
+        }.let {
+            UInt.lift(it)
+        }
+    
+    override fun parent(): Counter? =
+        callWithPointer {
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_Counter_parent(it,  status)
+}
+        }.let {
+            liftOptionalTypeCounter(it)
+        }
+    
+    
+
+    companion object {
+        internal fun lift(ptr: Pointer?): Counter {
+            // Rust should never hand us a null pointer, but if a bug means that it does, we'd
+            // rather throw than crash the whole JVM when the pointer is first used.
+            if (ptr == null || Pointer.nativeValue(ptr) == 0L) {
+                throw InternalException("Received a null pointer for a Counter from Rust")
+            }
+            return Counter(ptr)
+        }
+
+        internal fun read(buf: ByteBuffer): Counter {
+            return readChecked("Counter") {
+                // The Rust code always writes pointers as 8 bytes, and will
+                // fail to compile if they don't fit.
+                return Counter.lift(Pointer(buf.getLong()))
+            }
+        }
+
+        internal fun readBorrowed(buf: ByteBuffer): Counter {
+            // Rust only lends us the pointer for the duration of a callback, without
+            // adding a reference for us, so we must never free it.
+            val obj = Counter.read(buf)
+            obj.isBorrowed.set(true)
+            return obj
+        }
+
+        @Throws(CounterException::class)
+        
+        @JvmStatic
+        fun withLimit(initial: UInt, limit: UInt): Counter =
+            Counter(
+    rustCallWithError(CounterException) { status ->
+    _UniFFILib.INSTANCE.objects_CHECKSUM_Counter_with_limit(initial.lower(), limit.lower(),status)
+})
+        
+    }
+}
+
+
+// Callback Interfaces
+
+
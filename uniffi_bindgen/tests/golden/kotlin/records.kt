@@ -0,0 +1,1221 @@
+// This file was autogenerated by some hot garbage in the `uniffi` crate.
+// Trust me, you don't want to mess with it!
+
+@file:Suppress("NAME_SHADOWING")
+@file:OptIn(ExperimentalUnsignedTypes::class)
+
+package uniffi.records;
+
+import com.sun.jna.Library
+import com.sun.jna.Native
+import com.sun.jna.Pointer
+import com.sun.jna.Structure
+import java.nio.ByteBuffer
+import java.nio.ByteOrder
+import java.util.concurrent.atomic.AtomicLong
+import java.util.concurrent.atomic.AtomicBoolean
+import java.util.concurrent.atomic.AtomicReference
+import java.util.concurrent.locks.ReentrantLock
+import kotlin.concurrent.withLock
+import kotlin.coroutines.Continuation
+import kotlin.coroutines.resume
+import kotlin.coroutines.suspendCoroutine
+
+// Common helper code.
+//
+// Ideally this would live in a separate .kt file where it can be unittested etc
+// in isolation, and perhaps even published as a re-useable package.
+//
+// However, it's important that the detils of how this helper code works (e.g. the
+// way that different builtin types are passed across the FFI) exactly match what's
+// expected by the Rust code on the other side of the interface. In practice right
+// now that means coming from the exact some version of `uniffi` that was used to
+// compile the Rust component. The easiest way to ensure this is to bundle the Kotlin
+// helpers directly inline like we're doing here.
+
+// This is a helper for safely working with byte buffers returned from the Rust code.
+// A rust-owned buffer is represented by its capacity, its current length, and a
+// pointer to the underlying data.
+
+@Structure.FieldOrder("capacity", "len", "data")
+public open class RustBuffer : Structure() {
+    @JvmField var capacity: Int = 0
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : RustBuffer(), Structure.ByValue
+    class ByReference : RustBuffer(), Structure.ByReference
+
+    companion object {
+        internal fun alloc(size: Int = 0) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_records_CHECKSUM_rustbuffer_alloc(size, status)
+        }
+
+        internal fun free(buf: RustBuffer.ByValue) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_records_CHECKSUM_rustbuffer_free(buf, status)
+        }
+
+        internal fun reserve(buf: RustBuffer.ByValue, additional: Int) = rustCall() { status ->
+            _UniFFILib.INSTANCE.ffi_records_CHECKSUM_rustbuffer_reserve(buf, additional, status)
+        }
+    }
+
+    @Suppress("TooGenericExceptionThrown")
+    fun asByteBuffer() =
+        this.data?.getByteBuffer(0, this.len.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+        }
+}
+
+
+// This is a helper for safely passing byte references into the rust code.
+// We use it for `[ByRef] bytes` arguments, which the rust code reads in place
+// for the duration of the call. JNA can't give us a direct pointer to the contents of a
+// `ByteArray`, so we copy it into native memory first, but that still saves the cost of
+// writing it into a `RustBuffer` and then copying it out again on the rust side.
+
+@Structure.FieldOrder("len", "data")
+public open class ForeignBytes : Structure() {
+    @JvmField var len: Int = 0
+    @JvmField var data: Pointer? = null
+
+    class ByValue : ForeignBytes(), Structure.ByValue
+
+    companion object {
+        // The native memory is owned by the returned struct, which keeps it alive
+        // until the struct itself is garbage-collected after the call.
+        internal fun lower(v: ByteArray): ForeignBytes.ByValue {
+            val bytes = ForeignBytes.ByValue()
+            bytes.len = v.size
+            if (v.isNotEmpty()) {
+                val mem = com.sun.jna.Memory(v.size.toLong())
+                mem.write(0, v, 0, v.size)
+                bytes.data = mem
+            }
+            return bytes
+        }
+    }
+}
+
+
+
+// A helper for structured writing of data into a `RustBuffer`.
+// This is very similar to `java.nio.ByteBuffer` but it knows how to grow
+// the underlying `RustBuffer` on demand.
+//
+// TODO: we should benchmark writing things into a `RustBuffer` versus building
+// up a bytearray and then copying it across.
+
+public class RustBufferBuilder() {
+    var rbuf = RustBuffer.ByValue()
+    var bbuf: ByteBuffer? = null
+
+    init {
+        // This defaults to a totally arbitrary initial size, but can be set using the
+        // `rust_buffer_initial_capacity` config option.
+        val rbuf = RustBuffer.alloc(16)
+        rbuf.writeField("len", 0)
+        this.setRustBuffer(rbuf)
+    }
+
+    internal fun setRustBuffer(rbuf: RustBuffer.ByValue) {
+        this.rbuf = rbuf
+        this.bbuf = this.rbuf.data?.getByteBuffer(0, this.rbuf.capacity.toLong())?.also {
+            it.order(ByteOrder.BIG_ENDIAN)
+            it.position(rbuf.len)
+        }
+    }
+
+    fun finalize() : RustBuffer.ByValue {
+        val rbuf = this.rbuf
+        // Ensure that the JVM-level field is written through to native memory
+        // before turning the buffer, in case its recipient uses it in a context
+        // JNA doesn't apply its automatic synchronization logic.
+        rbuf.writeField("len", this.bbuf!!.position())
+        this.setRustBuffer(RustBuffer.ByValue())
+        return rbuf
+    }
+
+    fun discard() {
+        val rbuf = this.finalize()
+        RustBuffer.free(rbuf)
+    }
+
+    internal fun reserve(size: Int, write: (ByteBuffer) -> Unit) {
+        // TODO: this will perform two checks to ensure we're not overflowing the buffer:
+        // one here where we check if it needs to grow, and another when we call a write
+        // method on the ByteBuffer. It might be cheaper to use exception-driven control-flow
+        // here, trying the write and growing if it throws a `BufferOverflowException`.
+        // Benchmarking needed.
+        if (this.bbuf!!.position() + size > this.rbuf.capacity) {
+            rbuf.writeField("len", this.bbuf!!.position())
+            this.setRustBuffer(RustBuffer.reserve(this.rbuf, size))
+        }
+        write(this.bbuf!!)
+    }
+
+    fun putByte(v: Byte) {
+        this.reserve(1) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+
+    fun putShort(v: Short) {
+        this.reserve(2) { bbuf ->
+            bbuf.putShort(v)
+        }
+    }
+
+    fun putInt(v: Int) {
+        this.reserve(4) { bbuf ->
+            bbuf.putInt(v)
+        }
+    }
+
+    fun putLong(v: Long) {
+        this.reserve(8) { bbuf ->
+            bbuf.putLong(v)
+        }
+    }
+
+    fun putFloat(v: Float) {
+        this.reserve(4) { bbuf ->
+            bbuf.putFloat(v)
+        }
+    }
+
+    fun putDouble(v: Double) {
+        this.reserve(8) { bbuf ->
+            bbuf.putDouble(v)
+        }
+    }
+
+    fun put(v: ByteArray) {
+        this.reserve(v.size) { bbuf ->
+            bbuf.put(v)
+        }
+    }
+}
+
+// Helpers for reading primitive data types from a bytebuffer.
+
+internal fun<T> liftFromRustBuffer(rbuf: RustBuffer.ByValue, readItem: (ByteBuffer) -> T): T {
+    val buf = rbuf.asByteBuffer()!!
+    try {
+       val item = readItem(buf)
+       if (buf.hasRemaining()) {
+           throw RuntimeException("junk remaining in buffer after lifting, something is very wrong!!")
+       }
+       return item
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+
+// The `read` helpers for compound values wrap their bodies in this, so that if the buffer from
+// Rust is shorter than we expect (say because the Rust code was built from a different version
+// of the component), we report which type we were reading rather than a bare NIO exception.
+// Nested values are checked too, so the innermost type that ran off the end gets named.
+internal inline fun<T> readChecked(typeName: String, read: () -> T): T {
+    try {
+        return read()
+    } catch (e: java.nio.BufferUnderflowException) {
+        throw InternalException("buffer overrun reading $typeName")
+    }
+}
+
+
+// This is the only place that compound values allocate a `RustBuffer` when they're lowered.
+// Their `write` helpers write any nested values straight into the same builder, so lowering
+// a deeply nested record still allocates just the one buffer (growing it as needed).
+internal fun<T> lowerIntoRustBuffer(v: T, writeItem: (T, RustBufferBuilder) -> Unit): RustBuffer.ByValue {
+    // TODO: maybe we can calculate some sort of initial size hint?
+    val buf = RustBufferBuilder()
+    try {
+        writeItem(v, buf)
+        return buf.finalize()
+    } catch (e: Throwable) {
+        buf.discard()
+        throw e
+    }
+}
+
+
+// For every type used in the interface, we provide helper methods for conveniently
+// lifting and lowering that type from C-compatible data, and for reading and writing
+// values of that type in a buffer.
+
+
+
+
+// The unsigned types are passed over the FFI as the signed type of the same size, and the
+// conversions between them keep the same bits, so e.g. `u32::MAX` arrives as `-1` and is
+// lifted back into `UInt.MAX_VALUE`. Nothing is narrowed, so nothing can overflow.
+
+@ExperimentalUnsignedTypes
+internal fun UByte.Companion.lift(v: Byte): UByte {
+    return v.toUByte()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UByte.Companion.read(buf: ByteBuffer): UByte {
+    return UByte.lift(buf.get())
+}
+
+@ExperimentalUnsignedTypes
+internal fun UByte.lower(): Byte {
+    return this.toByte()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UByte.write(buf: RustBufferBuilder) {
+    buf.putByte(this.toByte())
+}
+
+
+
+
+
+@ExperimentalUnsignedTypes
+internal fun UInt.Companion.lift(v: Int): UInt {
+    return v.toUInt()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.Companion.read(buf: ByteBuffer): UInt {
+    return UInt.lift(buf.getInt())
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.lower(): Int {
+    return this.toInt()
+}
+
+@ExperimentalUnsignedTypes
+internal fun UInt.write(buf: RustBufferBuilder) {
+    buf.putInt(this.toInt())
+}
+
+
+
+
+
+internal fun Long.Companion.lift(v: Long): Long {
+    return v
+}
+
+internal fun Long.Companion.read(buf: ByteBuffer): Long {
+    return buf.getLong()
+}
+
+internal fun Long.lower(): Long {
+    return this
+}
+
+internal fun Long.write(buf: RustBufferBuilder) {
+    buf.putLong(this)
+}
+
+
+
+
+
+
+
+internal fun liftU128(rbuf: RustBuffer.ByValue): java.math.BigInteger {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readU128(buf)
+    }
+}
+
+internal fun readU128(buf: ByteBuffer): java.math.BigInteger {
+    return readChecked("java.math.BigInteger") {
+        // 128-bit integers are written as 16 bytes in big-endian order,
+        // which we always interpret as a positive magnitude.
+        val byteArr = ByteArray(16)
+        buf.get(byteArr)
+        return java.math.BigInteger(1, byteArr)
+    }
+}
+
+internal fun lowerU128(v: java.math.BigInteger): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeU128(v, buf)
+    }
+}
+
+internal fun writeU128(v: java.math.BigInteger, buf: RustBufferBuilder) {
+    if (v.signum() < 0 || v.bitLength() > 128) {
+        throw IllegalArgumentException("Value $v does not fit in a u128")
+    }
+    // `toByteArray()` may include an extra leading zero byte for the sign bit,
+    // which we need to skip in order to fit into exactly 16 bytes.
+    val byteArr = v.toByteArray()
+    for (i in byteArr.size until 16) {
+        buf.putByte(0.toByte())
+    }
+    buf.put(if (byteArr.size > 16) byteArr.copyOfRange(byteArr.size - 16, byteArr.size) else byteArr)
+}
+
+
+
+
+
+internal fun Double.Companion.lift(v: Double): Double {
+    return v
+}
+
+internal fun Double.Companion.read(buf: ByteBuffer): Double {
+    val v = buf.getDouble()
+    return v
+}
+
+internal fun Double.lower(): Double {
+    return this
+}
+
+internal fun Double.write(buf: RustBufferBuilder) {
+    buf.putDouble(this)
+}
+
+
+
+
+
+internal fun Boolean.Companion.lift(v: Byte): Boolean {
+    return v.toInt() != 0
+}
+
+internal fun Boolean.Companion.read(buf: ByteBuffer): Boolean {
+    return Boolean.lift(buf.get())
+}
+
+internal fun Boolean.lower(): Byte {
+    return if (this) 1.toByte() else 0.toByte()
+}
+
+internal fun Boolean.write(buf: RustBufferBuilder) {
+    buf.putByte(this.lower())
+}
+
+
+
+
+
+internal fun decodeUtf8(byteArr: ByteArray): String {
+    return byteArr.toString(Charsets.UTF_8)
+}
+
+internal fun String.Companion.lift(rbuf: RustBuffer.ByValue): String {
+    try {
+        val byteArr = ByteArray(rbuf.len)
+        rbuf.asByteBuffer()!!.get(byteArr)
+        return decodeUtf8(byteArr)
+    } finally {
+        RustBuffer.free(rbuf)
+    }
+}
+
+internal fun String.Companion.read(buf: ByteBuffer): String {
+    return readChecked("String") {
+        val len = buf.getInt()
+        val byteArr = ByteArray(len)
+        buf.get(byteArr)
+        return decodeUtf8(byteArr)
+    }
+}
+
+internal fun String.lower(): RustBuffer.ByValue {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    // Ideally we'd pass these bytes to `ffi_bytebuffer_from_bytes`, but doing so would require us
+    // to copy them into a JNA `Memory`. So we might as well directly copy them into a `RustBuffer`.
+    val rbuf = RustBuffer.alloc(byteArr.size)
+    rbuf.asByteBuffer()!!.put(byteArr)
+    return rbuf
+}
+
+internal fun String.write(buf: RustBufferBuilder) {
+    val byteArr = this.toByteArray(Charsets.UTF_8)
+    buf.putInt(byteArr.size)
+    buf.put(byteArr)
+}
+
+
+
+
+
+
+
+internal fun liftTimestamp(rbuf: RustBuffer.ByValue): java.time.Instant {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readTimestamp(buf)
+    }
+}
+
+internal fun readTimestamp(buf: ByteBuffer): java.time.Instant {
+    return readChecked("java.time.Instant") {
+        val seconds = buf.getLong()
+        // Type mismatch (should be u32) but we check for overflow/underflow below
+        val nanoseconds = buf.getInt().toLong()
+        if (nanoseconds < 0) {
+            throw java.time.DateTimeException("Instant nanoseconds exceed minimum or maximum supported by uniffi")
+        }
+        // The nanoseconds are relative to the epoch in the same direction as the seconds, and
+        // unlike negating `seconds` this can't overflow; it throws if the value is out of range.
+        if (seconds >= 0) {
+            return java.time.Instant.ofEpochSecond(seconds, nanoseconds)
+        } else {
+            return java.time.Instant.ofEpochSecond(seconds, -nanoseconds)
+        }
+    }
+}
+
+internal fun lowerTimestamp(v: java.time.Instant): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeTimestamp(v, buf)
+    }
+}
+
+internal fun writeTimestamp(v: java.time.Instant, buf: RustBufferBuilder) {
+    var epoch_offset = java.time.Duration.between(java.time.Instant.EPOCH, v)
+
+    var sign = 1
+    if (epoch_offset.isNegative()) {
+        sign = -1
+        epoch_offset = epoch_offset.negated()
+    }
+
+    if (epoch_offset.nano < 0) {
+        // Java docs provide guarantee that nano will always be positive, so this should be impossible
+        // See: https://docs.oracle.com/javase/8/docs/api/java/time/Instant.html
+        throw IllegalArgumentException("Invalid timestamp, nano value must be non-negative")
+    }
+
+    buf.putLong(sign * epoch_offset.seconds)
+    // Type mismatch (should be u32) but since values will always be between 0 and 999,999,999 it should be OK
+    buf.putInt(epoch_offset.nano)
+}
+
+
+
+
+
+
+
+internal fun liftDuration(rbuf: RustBuffer.ByValue): java.time.Duration {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readDuration(buf)
+    }
+}
+
+internal fun readDuration(buf: ByteBuffer): java.time.Duration {
+    return readChecked("java.time.Duration") {
+        // Type mismatch (should be u64) but we check for overflow/underflow below
+        val seconds = buf.getLong()
+        // Type mismatch (should be u32) but we check for overflow/underflow below
+        val nanoseconds = buf.getInt().toLong()
+        if (seconds < 0) {
+            throw java.time.DateTimeException("Duration exceeds minimum or maximum value supported by uniffi")
+        }
+        if (nanoseconds < 0) {
+            throw java.time.DateTimeException("Duration nanoseconds exceed minimum or maximum supported by uniffi")
+        }
+        return java.time.Duration.ofSeconds(seconds, nanoseconds)
+    }
+}
+
+internal fun lowerDuration(v: java.time.Duration): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeDuration(v, buf)
+    }
+}
+
+internal fun writeDuration(v: java.time.Duration, buf: RustBufferBuilder) {
+    if (v.seconds < 0) {
+        // Rust does not support negative Durations
+        throw IllegalArgumentException("Invalid duration, must be non-negative")
+    }
+
+    if (v.nano < 0) {
+        // Java docs provide guarantee that nano will always be positive, so this should be impossible
+        // See: https://docs.oracle.com/javase/8/docs/api/java/time/Duration.html
+        throw IllegalArgumentException("Invalid duration, nano value must be non-negative")
+    }
+
+    // Type mismatch (should be u64) but since Rust doesn't support negative durations we should be OK
+    buf.putLong(v.seconds)
+    // Type mismatch (should be u32) but since values will always be between 0 and 999,999,999 it should be OK
+    buf.putInt(v.nano)
+}
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type String?
+
+internal fun liftOptionalstring(rbuf: RustBuffer.ByValue): String? {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readOptionalstring(buf)
+    }
+}
+
+
+internal fun readOptionalstring(buf: ByteBuffer): String? {
+    return readChecked("String?") {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return String.read(buf)
+    }
+}
+
+
+internal fun lowerOptionalstring(v: String?): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeOptionalstring(v, buf)
+    }
+}
+
+
+internal fun writeOptionalstring(v: String?, buf: RustBufferBuilder) {
+    if (v == null) {
+        buf.putByte(0)
+    } else {
+        buf.putByte(1)
+        v.write(buf)
+    }
+}
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type java.time.Duration?
+
+internal fun liftOptionalDuration(rbuf: RustBuffer.ByValue): java.time.Duration? {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readOptionalDuration(buf)
+    }
+}
+
+
+internal fun readOptionalDuration(buf: ByteBuffer): java.time.Duration? {
+    return readChecked("java.time.Duration?") {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return readDuration(buf)
+    }
+}
+
+
+internal fun lowerOptionalDuration(v: java.time.Duration?): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeOptionalDuration(v, buf)
+    }
+}
+
+
+internal fun writeOptionalDuration(v: java.time.Duration?, buf: RustBufferBuilder) {
+    if (v == null) {
+        buf.putByte(0)
+    } else {
+        buf.putByte(1)
+        writeDuration(v, buf)
+    }
+}
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type Tuple4<String, UInt, Boolean, Long>?
+@ExperimentalUnsignedTypes
+internal fun liftOptionalTuple4stringu32booli64(rbuf: RustBuffer.ByValue): Tuple4<String, UInt, Boolean, Long>? {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readOptionalTuple4stringu32booli64(buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun readOptionalTuple4stringu32booli64(buf: ByteBuffer): Tuple4<String, UInt, Boolean, Long>? {
+    return readChecked("Tuple4<String, UInt, Boolean, Long>?") {
+        if (buf.get().toInt() == 0) {
+            return null
+        }
+        return readTuple4stringu32booli64(buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun lowerOptionalTuple4stringu32booli64(v: Tuple4<String, UInt, Boolean, Long>?): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeOptionalTuple4stringu32booli64(v, buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun writeOptionalTuple4stringu32booli64(v: Tuple4<String, UInt, Boolean, Long>?, buf: RustBufferBuilder) {
+    if (v == null) {
+        buf.putByte(0)
+    } else {
+        buf.putByte(1)
+        writeTuple4stringu32booli64(v, buf)
+    }
+}
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type List<String>
+
+
+internal fun liftSequencestring(rbuf: RustBuffer.ByValue): List<String> {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readSequencestring(buf)
+    }
+}
+
+
+internal fun readSequencestring(buf: ByteBuffer): List<String> {
+    return readChecked("List<String>") {
+        val len = buf.getInt()
+        return List<String>(len) {
+            String.read(buf)
+        }
+    }
+}
+
+
+internal fun lowerSequencestring(v: List<String>): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeSequencestring(v, buf)
+    }
+}
+
+
+internal fun writeSequencestring(v: List<String>, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    v.forEach {
+        it.write(buf)
+    }
+}
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type Map<UInt, Long>
+
+@ExperimentalUnsignedTypes
+internal fun liftMapu32i64(rbuf: RustBuffer.ByValue): Map<UInt, Long> {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readMapu32i64(buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun readMapu32i64(buf: ByteBuffer): Map<UInt, Long> {
+    return readChecked("Map<UInt, Long>") {
+        // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
+        val items : MutableMap<UInt, Long> = mutableMapOf()
+        val len = buf.getInt()
+        repeat(len) {
+            val k = UInt.read(buf)
+            val v = Long.read(buf)
+            items[k] = v
+        }
+        return items
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun lowerMapu32i64(m: Map<UInt, Long>): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(m) { m, buf ->
+        writeMapu32i64(m, buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun writeMapu32i64(v: Map<UInt, Long>, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    // The parens on `(k, v)` here ensure we're calling the right method,
+    // which is important for compatibility with older android devices.
+    // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+    v.forEach { (k, v) ->
+        k.write(buf)
+        v.write(buf)
+    }
+}
+
+
+
+
+
+
+
+
+// Helper functions for pasing values of type Map<String, Long>
+
+
+internal fun liftMapstringi64(rbuf: RustBuffer.ByValue): Map<String, Long> {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readMapstringi64(buf)
+    }
+}
+
+
+internal fun readMapstringi64(buf: ByteBuffer): Map<String, Long> {
+    return readChecked("Map<String, Long>") {
+        // TODO: Once Kotlin's `buildMap` API is stabilized we should use it here.
+        val items : MutableMap<String, Long> = mutableMapOf()
+        val len = buf.getInt()
+        repeat(len) {
+            val k = String.read(buf)
+            val v = Long.read(buf)
+            items[k] = v
+        }
+        return items
+    }
+}
+
+
+internal fun lowerMapstringi64(m: Map<String, Long>): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(m) { m, buf ->
+        writeMapstringi64(m, buf)
+    }
+}
+
+
+internal fun writeMapstringi64(v: Map<String, Long>, buf: RustBufferBuilder) {
+    buf.putInt(v.size)
+    // The parens on `(k, v)` here ensure we're calling the right method,
+    // which is important for compatibility with older android devices.
+    // Ref https://blog.danlew.net/2017/03/16/kotlin-puzzler-whose-line-is-it-anyways/
+    v.forEach { (k, v) ->
+        k.write(buf)
+        v.write(buf)
+    }
+}
+
+
+
+
+
+
+
+// Helper functions for pasing values of type Pair<Double, Double>
+
+
+internal fun liftTuple2f64f64(rbuf: RustBuffer.ByValue): Pair<Double, Double> {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readTuple2f64f64(buf)
+    }
+}
+
+
+internal fun readTuple2f64f64(buf: ByteBuffer): Pair<Double, Double> {
+    return readChecked("Pair<Double, Double>") {
+        val v1 = Double.read(buf)
+        val v2 = Double.read(buf)
+        return Pair(v1, v2)
+    }
+}
+
+
+internal fun lowerTuple2f64f64(v: Pair<Double, Double>): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeTuple2f64f64(v, buf)
+    }
+}
+
+
+internal fun writeTuple2f64f64(v: Pair<Double, Double>, buf: RustBufferBuilder) {
+    v.component1().write(buf)
+    v.component2().write(buf)
+}
+
+
+
+
+
+
+
+// Helper functions for pasing values of type Tuple4<String, UInt, Boolean, Long>
+
+@ExperimentalUnsignedTypes
+internal fun liftTuple4stringu32booli64(rbuf: RustBuffer.ByValue): Tuple4<String, UInt, Boolean, Long> {
+    return liftFromRustBuffer(rbuf) { buf ->
+        readTuple4stringu32booli64(buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun readTuple4stringu32booli64(buf: ByteBuffer): Tuple4<String, UInt, Boolean, Long> {
+    return readChecked("Tuple4<String, UInt, Boolean, Long>") {
+        val v1 = String.read(buf)
+        val v2 = UInt.read(buf)
+        val v3 = Boolean.read(buf)
+        val v4 = Long.read(buf)
+        return Tuple4(v1, v2, v3, v4)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun lowerTuple4stringu32booli64(v: Tuple4<String, UInt, Boolean, Long>): RustBuffer.ByValue {
+    return lowerIntoRustBuffer(v) { v, buf ->
+        writeTuple4stringu32booli64(v, buf)
+    }
+}
+
+@ExperimentalUnsignedTypes
+internal fun writeTuple4stringu32booli64(v: Tuple4<String, UInt, Boolean, Long>, buf: RustBufferBuilder) {
+    v.component1().write(buf)
+    v.component2().write(buf)
+    v.component3().write(buf)
+    v.component4().write(buf)
+}
+
+
+
+
+@Synchronized
+public fun findLibraryName(componentName: String): String {
+    val libOverride = System.getProperty("uniffi.component.${componentName}.libraryOverride")
+    if (libOverride != null) {
+        return libOverride
+    }
+    return "uniffi_records"
+}
+
+public inline fun <reified Lib : Library> loadIndirect(
+    componentName: String
+): Lib {
+    return Native.load<Lib>(findLibraryName(componentName), Lib::class.java)
+}
+
+// A JNA Library to expose the extern-C FFI definitions.
+// This is an implementation detail which will be called internally by the public API.
+
+internal interface _UniFFILib : Library {
+    companion object {
+        internal val INSTANCE: _UniFFILib by lazy { 
+            loadIndirect<_UniFFILib>(componentName = "records")
+            
+            
+        }
+    }
+
+    fun records_CHECKSUM_make_person(name: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_records_CHECKSUM_rustbuffer_alloc(size: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_records_CHECKSUM_rustbuffer_from_bytes(bytes: ForeignBytes.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    fun ffi_records_CHECKSUM_rustbuffer_free(buf: RustBuffer.ByValue,
+    uniffi_out_err: RustCallStatus
+    ): Unit
+
+    fun ffi_records_CHECKSUM_rustbuffer_reserve(buf: RustBuffer.ByValue,additional: Int,
+    uniffi_out_err: RustCallStatus
+    ): RustBuffer.ByValue
+
+    
+}
+
+// A handful of classes and functions to support the generated data structures.
+// This would be a good candidate for isolating in its own ffi-support lib.
+
+
+
+
+
+
+
+// Kotlin only has `Pair` and `Triple`, so we represent larger tuples with a class of the same
+// style. It's a data class, so it can be destructured in the same way as `Pair` and `Triple`.
+public data class Tuple4<out T1, out T2, out T3, out T4>(
+    val first: T1,
+    val second: T2,
+    val third: T3,
+    val fourth: T4
+) {
+    override fun toString(): String = "($first, $second, $third, $fourth)"
+}
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+
+// Public interface members begin here.
+// Custom types
+
+
+// Type aliases
+
+
+// Constants
+
+// Public facing enums
+
+
+// Error definitions
+@Structure.FieldOrder("code", "error_buf")
+internal open class RustCallStatus : Structure() {
+    @JvmField var code: Int = 0
+    @JvmField var error_buf: RustBuffer.ByValue = RustBuffer.ByValue()
+
+    fun isSuccess(): Boolean {
+        return code == 0
+    }
+
+    fun isError(): Boolean {
+        return code == 1
+    }
+
+    fun isPanic(): Boolean {
+        return code == 2
+    }
+}
+
+public class InternalException(message: String) : Exception(message)
+
+// Each top-level error class has a companion object that can lift the error from the call status's rust buffer
+public interface CallStatusErrorHandler<E> {
+    fun lift(error_buf: RustBuffer.ByValue): E;
+}
+
+// Helpers for calling Rust
+// In practice we usually need to be synchronized to call this safely, so it doesn't
+// synchronize itself
+
+// Call a rust function that returns a Result<>.  Pass in the Error class companion that corresponds to the Err
+internal inline fun <U, E: Exception> rustCallWithError(errorHandler: CallStatusErrorHandler<E>, callback: (RustCallStatus) -> U): U {
+    var status = RustCallStatus();
+    val return_value = callback(status)
+    if (status.isSuccess()) {
+        return return_value
+    } else if (status.isError()) {
+        throw errorHandler.lift(status.error_buf)
+    } else if (status.isPanic()) {
+        // when the rust code sees a panic, it tries to construct a rustbuffer
+        // with the message.  but if that code panics, then it just sends back
+        // an empty buffer.
+        if (status.error_buf.len > 0) {
+            throw InternalException(String.lift(status.error_buf))
+        } else {
+            throw InternalException("Rust panic")
+        }
+    } else {
+        throw InternalException("Unknown rust call status: $status.code")
+    }
+}
+
+// CallStatusErrorHandler implementation for times when we don't expect a CALL_ERROR
+public object NullCallStatusErrorHandler: CallStatusErrorHandler<InternalException> {
+    override fun lift(error_buf: RustBuffer.ByValue): InternalException {
+        RustBuffer.free(error_buf)
+        return InternalException("Unexpected CALL_ERROR")
+    }
+}
+
+// Call a rust function that returns a plain value
+internal inline fun <U> rustCall(callback: (RustCallStatus) -> U): U {
+    return rustCallWithError(NullCallStatusErrorHandler, callback);
+}
+// Public facing records
+
+
+/**
+ * Someone with a name.
+ */
+@ExperimentalUnsignedTypes
+public data class Person (
+    var name: String, 
+    var age: UByte = 0u, 
+    var nickname: String? = null, 
+    var tags: List<String> = listOf(), 
+    var scores: Map<String, Long>, 
+    var rounds: Map<UInt, Long>, 
+    var home: Pair<Double, Double>, 
+    var bestRecord: Tuple4<String, UInt, Boolean, Long>?, 
+    var id: java.math.BigInteger, 
+    var created: java.time.Instant, 
+    var sessionLength: java.time.Duration?, 
+    var `fun`: String?, 
+    var cachedSummary: String? 
+) {
+
+    companion object {
+        internal fun lift(rbuf: RustBuffer.ByValue): Person {
+            return liftFromRustBuffer(rbuf) { buf -> Person.read(buf) }
+        }
+
+        internal fun read(buf: ByteBuffer): Person {
+            return readChecked("Person") {
+                return Person(
+                String.read(buf),
+                UByte.read(buf),
+                readOptionalstring(buf),
+                readSequencestring(buf),
+                readMapstringi64(buf),
+                readMapu32i64(buf),
+                readTuple2f64f64(buf),
+                readOptionalTuple4stringu32booli64(buf),
+                readU128(buf),
+                readTimestamp(buf),
+                readOptionalDuration(buf),
+                readOptionalstring(buf),
+                readOptionalstring(buf)
+                )
+            }
+        }
+    }
+
+    internal fun lower(): RustBuffer.ByValue {
+        return lowerIntoRustBuffer(this, {v, buf -> v.write(buf)})
+    }
+
+    internal fun write(buf: RustBufferBuilder) {
+            (this.name).write(buf)
+        
+            (this.age).write(buf)
+        
+            writeOptionalstring((this.nickname), buf)
+        
+            writeSequencestring((this.tags), buf)
+        
+            writeMapstringi64((this.scores), buf)
+        
+            writeMapu32i64((this.rounds), buf)
+        
+            writeTuple2f64f64((this.home), buf)
+        
+            writeOptionalTuple4stringu32booli64((this.bestRecord), buf)
+        
+            writeU128((this.id), buf)
+        
+            writeTimestamp((this.created), buf)
+        
+            writeOptionalDuration((this.sessionLength), buf)
+        
+            writeOptionalstring((this.`fun`), buf)
+        
+            writeOptionalstring((this.cachedSummary), buf)
+        
+    }
+
+    
+
+    // These are defined explicitly so that fields marked `[ExcludeFromEquality]` aren't compared.
+    override fun equals(other: Any?): Boolean {
+        if (this === other) return true
+        if (other !is Person) return false
+        if (this.name != other.name) return false
+        if (this.age != other.age) return false
+        if (this.nickname != other.nickname) return false
+        if (this.tags != other.tags) return false
+        if (this.scores != other.scores) return false
+        if (this.rounds != other.rounds) return false
+        if (this.home != other.home) return false
+        if (this.bestRecord != other.bestRecord) return false
+        if (this.id != other.id) return false
+        if (this.created != other.created) return false
+        if (this.sessionLength != other.sessionLength) return false
+        if (this.`fun` != other.`fun`) return false
+        return true
+    }
+
+    override fun hashCode(): Int {
+        return java.util.Objects.hash(
+            this.name,
+            this.age,
+            this.nickname,
+            this.tags,
+            this.scores,
+            this.rounds,
+            this.home,
+            this.bestRecord,
+            this.id,
+            this.created,
+            this.sessionLength,
+            this.`fun`,
+        )
+    }
+
+    
+    
+}
+
+
+// Namespace functions
+
+
+
+@ExperimentalUnsignedTypes
+
+public fun makePerson(name: String): Person {
+    val _retval = 
+    rustCall() { status ->
+    _UniFFILib.INSTANCE.records_CHECKSUM_make_person(name.lower(),status)
+}
+    return Person.lift(_retval)
+}
+
+
+// Objects
+
+
+// Callback Interfaces
+
+
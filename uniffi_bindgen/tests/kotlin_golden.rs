@@ -0,0 +1,103 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Golden-file tests for the generated Kotlin bindings.
+//!
+//! Each test renders one of the `.udl` files in `tests/golden/kotlin/` and compares the output
+//! against the checked-in `.kt` file of the same name, to catch unintended changes to the
+//! generated code. If a change is intentional, regenerate the golden files by running:
+//!
+//! ```text
+//! UNIFFI_TESTS_UPDATE_GOLDEN=1 cargo test -p uniffi_bindgen --test kotlin_golden
+//! ```
+//!
+//! and check in the updated `.kt` files along with the change, so that reviewers can see
+//! exactly what it does to the generated code.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use uniffi_bindgen::bindings::kotlin::{generate_bindings, Config};
+use uniffi_bindgen::interface::ComponentInterface;
+
+const UPDATE_GOLDEN_ENV_VAR: &str = "UNIFFI_TESTS_UPDATE_GOLDEN";
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join("kotlin")
+}
+
+fn render(udl_file: &Path) -> Result<String> {
+    let udl = fs::read_to_string(udl_file)?;
+    let ci = ComponentInterface::from_webidl(&udl)?;
+    let (bindings, _warnings) = generate_bindings(&Config::from(&ci), &ci)?;
+    // The FFI function names include a checksum of the interface, which changes with every
+    // release of uniffi. Replace it with a placeholder so the golden files don't churn.
+    Ok(bindings.replace(&ci.ffi_namespace(), &format!("{}_CHECKSUM", ci.namespace())))
+}
+
+fn check_golden_file(name: &str) -> Result<()> {
+    let udl_file = golden_dir().join(format!("{}.udl", name));
+    let kt_file = udl_file.with_extension("kt");
+    let actual = render(&udl_file)
+        .with_context(|| format!("Failed to render bindings for {}", udl_file.display()))?;
+    if env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some() {
+        fs::write(&kt_file, actual)
+            .with_context(|| format!("Failed to write {}", kt_file.display()))?;
+        return Ok(());
+    }
+    let expected = match fs::read_to_string(&kt_file) {
+        Ok(expected) => expected,
+        Err(_) => bail!(
+            "Missing golden file {}; run the tests with {}=1 to generate it",
+            kt_file.display(),
+            UPDATE_GOLDEN_ENV_VAR
+        ),
+    };
+    if actual != expected {
+        // Report the first line that differs, since the full files are very long.
+        let mut expected_lines = expected.lines();
+        let mut actual_lines = actual.lines();
+        let mut line_number = 1;
+        loop {
+            match (expected_lines.next(), actual_lines.next()) {
+                (Some(e), Some(a)) if e == a => line_number += 1,
+                (e, a) => bail!(
+                    "Generated bindings don't match {} at line {}:\n  expected: {:?}\n    actual: {:?}\n\
+                     If this change is intentional, run the tests with {}=1 to update the golden file.",
+                    kt_file.display(),
+                    line_number,
+                    e.unwrap_or("<end of file>"),
+                    a.unwrap_or("<end of file>"),
+                    UPDATE_GOLDEN_ENV_VAR
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_objects() -> Result<()> {
+    check_golden_file("objects")
+}
+
+#[test]
+fn test_enums() -> Result<()> {
+    check_golden_file("enums")
+}
+
+#[test]
+fn test_callback_interfaces() -> Result<()> {
+    check_golden_file("callbacks")
+}
+
+#[test]
+fn test_records() -> Result<()> {
+    check_golden_file("records")
+}
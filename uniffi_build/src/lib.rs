@@ -26,7 +26,19 @@ use std::process::Command;
 /// command-line tool. This is mostly useful for developers who are working on uniffi
 /// itself and need to test out their changes to the bindings generator.
 pub fn generate_scaffolding(udl_file: &str) -> Result<()> {
-    println!("cargo:rerun-if-changed={}", udl_file);
+    generate_scaffolding_from_files(&[udl_file])
+}
+
+/// Generate the rust "scaffolding" for a uniffi component whose interface is split
+/// across several UDL files.
+///
+/// The files are merged into a single interface, and the generated scaffolding is
+/// named after the first of them, so given `example.udl` and `example_types.udl` it
+/// will be written into a file named `example.uniffi.rs` in the `$OUT_DIR` directory.
+pub fn generate_scaffolding_from_files(udl_files: &[&str]) -> Result<()> {
+    for udl_file in udl_files {
+        println!("cargo:rerun-if-changed={}", udl_file);
+    }
     // The UNIFFI_TESTS_DISABLE_EXTENSIONS variable disables some bindings, but it is evaluated
     // at *build* time, so we need to rebuild when it changes.
     println!("cargo:rerun-if-env-changed=UNIFFI_TESTS_DISABLE_EXTENSIONS");
@@ -34,13 +46,14 @@ pub fn generate_scaffolding(udl_file: &str) -> Result<()> {
     // Calling the command line helps making sure that the generated swift/Kotlin/whatever
     // bindings were generated with the same version of uniffi as the Rust scaffolding code.
     let out_dir = env::var("OUT_DIR").map_err(|_| anyhow::anyhow!("$OUT_DIR missing?!"))?;
-    run_uniffi_bindgen_scaffolding(&out_dir, udl_file)
+    run_uniffi_bindgen_scaffolding(&out_dir, udl_files)
 }
 
 #[cfg(not(feature = "builtin-bindgen"))]
-fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_file: &str) -> Result<()> {
+fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_files: &[&str]) -> Result<()> {
     let status = Command::new("uniffi-bindgen")
-        .args(&["scaffolding", "--out-dir", out_dir, udl_file])
+        .args(&["scaffolding", "--out-dir", out_dir])
+        .args(udl_files)
         .status()
         .context("failed to run `uniffi-bindgen` - have you installed it via `cargo install uniffi_bindgen`?")?;
     if !status.success() {
@@ -50,6 +63,12 @@ fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_file: &str) -> Result<()> {
 }
 
 #[cfg(feature = "builtin-bindgen")]
-fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_file: &str) -> Result<()> {
-    uniffi_bindgen::generate_component_scaffolding(udl_file, None, Some(out_dir), true)
+fn run_uniffi_bindgen_scaffolding(out_dir: &str, udl_files: &[&str]) -> Result<()> {
+    uniffi_bindgen::generate_component_scaffolding_from_files(
+        udl_files,
+        None,
+        Some(out_dir),
+        true,
+    )?;
+    Ok(())
 }